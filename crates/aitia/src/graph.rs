@@ -41,10 +41,9 @@ impl<'c, T: Fact> DepGraph<'c, T> {
             .collect()
     }
 
-    pub fn report(&self) -> std::io::Result<String> {
-        use std::fmt::Write;
-        let mut out = "".to_string();
-        let dot = format!(
+    /// Render the graph in the DOT language of Graphviz
+    pub fn dot(&self) -> String {
+        format!(
             "{:?}",
             petgraph::dot::Dot::with_attr_getters(
                 &**self,
@@ -52,7 +51,31 @@ impl<'c, T: Fact> DepGraph<'c, T> {
                 &|_g, _e| "".to_string(),
                 &|_g, _n| { "nojustify=true".to_string() },
             )
-        );
+        )
+    }
+
+    /// Render the graph as a Mermaid flowchart
+    pub fn mermaid(&self) -> String {
+        use std::fmt::Write;
+        let mut out = "flowchart TD\n".to_string();
+        for i in self.node_indices() {
+            let label = format!("{:?}", self[i])
+                .replace('"', "#quot;")
+                .replace('\n', "<br/>");
+            writeln!(&mut out, "    n{}[\"{}\"]", i.index(), label).unwrap();
+        }
+        for e in self.edge_indices() {
+            if let Some((a, b)) = self.edge_endpoints(e) {
+                writeln!(&mut out, "    n{} --> n{}", a.index(), b.index()).unwrap();
+            }
+        }
+        out
+    }
+
+    pub fn report(&self) -> std::io::Result<String> {
+        use std::fmt::Write;
+        let mut out = "".to_string();
+        let dot = self.dot();
 
         if let Ok(graph) = graph_easy(&dot) {
             writeln!(&mut out, "Original dot output:\n\n{}", dot).unwrap();
@@ -52,12 +52,12 @@ use std::collections::HashSet;
 
 pub use dep::{Dep, DepError, DepResult};
 pub use fact::{Fact, FactTraits};
+pub use graph::DepGraph;
+pub use traversal::{Traversal, TraversalError, TraversalInnerError, TraversalResult};
 
 #[cfg(test)]
 mod tests;
 
-use traversal::{Traversal, TraversalError, TraversalResult};
-
 #[macro_export]
 macro_rules! assert_fact {
     ($ctx: expr, $fact: expr) => {{
@@ -150,6 +150,26 @@ mod acyclic_single_path {
         assert_eq!(nodes.len(), edges + 1);
         nodes
     }
+
+    #[test]
+    fn render_graph() {
+        let truths = hashset![0];
+        let tr = Countdown(2).traverse(&truths).unwrap();
+        assert!(!tr.root_check_passed());
+        assert_eq!(tr.terminals(), &hashset![Countdown(0).into()]);
+        let leaf = Dep::from(Countdown(1));
+        assert_eq!(tr.graph().leaves(), hashset![&leaf]);
+
+        let mermaid = tr.graph().mermaid();
+        assert!(mermaid.starts_with("flowchart TD\n"));
+        assert_eq!(mermaid.matches("-->").count(), 1);
+        assert!(mermaid.contains("[\"Countdown(2)\"]"));
+        assert!(mermaid.contains("[\"Countdown(1)\"]"));
+
+        let dot = tr.graph().dot();
+        assert!(dot.starts_with("digraph {"));
+        assert_eq!(dot.matches("->").count(), 1);
+    }
 }
 
 /// Tests exploring a single loop with various starting and ending points
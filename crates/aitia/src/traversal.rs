@@ -26,7 +26,29 @@ pub struct Traversal<'c, T: Fact> {
     pub(crate) ctx: &'c T::Context,
 }
 
-impl<'c, T: Fact> Traversal<'c, T> {}
+impl<'c, T: Fact> Traversal<'c, T> {
+    /// Whether the target fact of the traversal is true
+    pub fn root_check_passed(&self) -> bool {
+        self.root_check_passed
+    }
+
+    /// The graph of the facts visited by the traversal
+    pub fn graph(&self) -> &DepGraph<'c, T> {
+        &self.graph
+    }
+
+    /// The facts whose check ended the traversal along their branch.
+    /// When the target fact is not true, these are the true facts which the
+    /// failing facts of the graph depend on.
+    pub fn terminals(&self) -> &HashSet<Dep<T>> {
+        &self.terminals
+    }
+
+    /// The context the traversal was run in
+    pub fn ctx(&self) -> &'c T::Context {
+        self.ctx
+    }
+}
 
 pub type TraversalResult<'c, F> = Result<Traversal<'c, F>, TraversalError<'c, F>>;
 
@@ -19,6 +19,7 @@ use holochain_conductor_api::InterfaceDriver;
 use holochain_conductor_api::{AdminInterfaceConfig, AppInfo};
 use holochain_types::prelude::DnaHash;
 use holochain_types::prelude::DnaModifiersOpt;
+use holochain_types::prelude::InstalledAppId;
 use holochain_types::prelude::RegisterDnaPayload;
 use holochain_types::prelude::Timestamp;
 use holochain_types::prelude::YamlProperties;
@@ -119,6 +120,12 @@ pub struct AddAppWs {
     /// If not provided, defaults to `*` which allows any origin.
     #[arg(long, default_value_t = AllowedOrigins::Any)]
     pub allowed_origins: AllowedOrigins,
+
+    /// Optional app to bind the interface to.
+    ///
+    /// If provided, only clients authenticated for this app can use the interface.
+    #[arg(long)]
+    pub installed_app_id: Option<InstalledAppId>,
 }
 
 /// Calls AdminRequest::RegisterDna
@@ -174,6 +181,10 @@ pub struct InstallApp {
 pub struct UninstallApp {
     /// The InstalledAppId to uninstall.
     pub app_id: String,
+
+    /// Also uninstall the apps which use cells created by this app.
+    #[arg(long)]
+    pub cascade: bool,
 }
 
 /// Calls AdminRequest::EnableApp
@@ -439,6 +450,8 @@ pub async fn add_admin_interface(cmd: &mut CmdRunner, args: AddAdminWs) -> anyho
                 driver: InterfaceDriver::Websocket {
                     port,
                     allowed_origins: AllowedOrigins::Any,
+                    bind_address: None,
+                    tls: None,
                 },
             },
         ]))
@@ -532,6 +545,7 @@ pub async fn uninstall_app(cmd: &mut CmdRunner, args: UninstallApp) -> anyhow::R
     let resp = cmd
         .command(AdminRequest::UninstallApp {
             installed_app_id: args.app_id,
+            cascade: args.cascade,
         })
         .await?;
 
@@ -610,6 +624,9 @@ pub async fn attach_app_interface(cmd: &mut CmdRunner, args: AddAppWs) -> anyhow
         .command(AdminRequest::AttachAppInterface {
             port: args.port,
             allowed_origins: args.allowed_origins,
+            installed_app_id: args.installed_app_id,
+            bind_address: None,
+            tls: None,
         })
         .await?;
     tracing::debug!(?resp);
@@ -81,12 +81,19 @@ pub(crate) fn random_admin_port(config: &mut ConductorConfig) {
                 *port = 0;
             }
         }
+        // The sandbox can only talk to conductors over a websocket port,
+        // so an admin interface on a unix socket is left as it is.
+        Some(AdminInterfaceConfig {
+            driver: InterfaceDriver::UnixSocket { .. },
+        }) => {}
         None => {
             let port = 0;
             config.admin_interfaces = Some(vec![AdminInterfaceConfig {
                 driver: InterfaceDriver::Websocket {
                     port,
                     allowed_origins: AllowedOrigins::Any,
+                    bind_address: None,
+                    tls: None,
                 },
             }]);
         }
@@ -99,6 +106,8 @@ pub(crate) fn set_admin_port(config: &mut ConductorConfig, port: u16) {
         driver: InterfaceDriver::Websocket {
             port,
             allowed_origins: AllowedOrigins::Any,
+            bind_address: None,
+            tls: None,
         },
     };
     match config
@@ -1,7 +1,12 @@
 use assert_cmd::prelude::*;
 use holochain_cli_sandbox::cli::LaunchInfo;
+use holochain_conductor_api::AdminRequest;
+use holochain_conductor_api::AdminResponse;
+use holochain_conductor_api::AppAuthenticationRequest;
+use holochain_conductor_api::AppAuthenticationResponse;
 use holochain_conductor_api::AppRequest;
 use holochain_conductor_api::AppResponse;
+use holochain_conductor_api::IssueAppAuthenticationTokenPayload;
 use holochain_websocket::{
     self as ws, ConnectRequest, WebsocketConfig, WebsocketReceiver, WebsocketSender,
 };
@@ -73,26 +78,55 @@ async fn new_websocket_client_for_port(
     .await?)
 }
 
-async fn get_app_info(port: u16) {
+struct D(tokio::task::JoinHandle<()>);
+impl Drop for D {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+async fn get_app_info(admin_port: u16, port: u16) {
+    let (admin_tx, mut admin_rx) = new_websocket_client_for_port(admin_port)
+        .await
+        .unwrap_or_else(|_| panic!("Failed to connect to conductor on port [{}]", admin_port));
+    let _admin_d = D(tokio::task::spawn(async move {
+        while admin_rx.recv::<AdminResponse>().await.is_ok() {}
+    }));
+
+    // Issue a token for the installed app
+    let response = admin_tx.request(AdminRequest::ListApps {
+        status_filter: None,
+    });
+    let r: AdminResponse = check_timeout(response).await;
+    let installed_app_id = match r {
+        AdminResponse::AppsListed(apps) => apps[0].installed_app_id.clone(),
+        other => panic!("Failed to list apps, got: {:?}", other),
+    };
+    let response = admin_tx.request(AdminRequest::IssueAppAuthenticationToken(
+        IssueAppAuthenticationTokenPayload::for_installed_app_id(installed_app_id.clone()),
+    ));
+    let r: AdminResponse = check_timeout(response).await;
+    let token = match r {
+        AdminResponse::AppAuthenticationTokenIssued(issued) => issued.token,
+        other => panic!("Failed to issue app authentication token, got: {:?}", other),
+    };
+
     tracing::debug!(calling_app_interface = ?port);
     let (app_tx, mut rx) = new_websocket_client_for_port(port)
         .await
         .unwrap_or_else(|_| panic!("Failed to connect to conductor on port [{}]", port));
-    struct D(tokio::task::JoinHandle<()>);
-    impl Drop for D {
-        fn drop(&mut self) {
-            self.0.abort();
-        }
-    }
     let _d = D(tokio::task::spawn(async move {
         while rx.recv::<AppResponse>().await.is_ok() {}
     }));
-    let request = AppRequest::AppInfo {
-        installed_app_id: "Stub".to_string(),
-    };
+    let response: AppAuthenticationResponse = app_tx
+        .authenticate(AppAuthenticationRequest { token })
+        .await
+        .unwrap();
+    assert_eq!(response, AppAuthenticationResponse::Authenticated);
+    let request = AppRequest::AppInfo { installed_app_id };
     let response = app_tx.request(request);
     let r: AppResponse = check_timeout(response).await;
-    assert_matches!(r, AppResponse::AppInfo(None));
+    assert_matches!(r, AppResponse::AppInfo(Some(_)));
 }
 
 async fn check_timeout<T>(response: impl Future<Output = std::io::Result<T>>) -> T {
@@ -179,7 +213,11 @@ async fn generate_sandbox_and_connect() {
     let launch_info = get_launch_info(&mut stdout).await;
 
     // - Make a call to list app info to the port
-    get_app_info(*launch_info.app_ports.first().expect("No app ports found")).await;
+    get_app_info(
+        launch_info.admin_port,
+        *launch_info.app_ports.first().expect("No app ports found"),
+    )
+    .await;
 }
 
 /// Generates a new sandbox with a single app deployed and tries to list DNA
@@ -51,17 +51,31 @@ pub struct Context {
     pub op_info: HashMap<OpRef, OpInfo>,
 }
 
+/// Matches the facts written to the logs by `aitia::trace!`
+static FACT_REGEX: once_cell::sync::Lazy<regex::Regex> =
+    once_cell::sync::Lazy::new(|| regex::Regex::new("<AITIA>(.*?)</AITIA>").unwrap());
+
 impl Context {
-    pub fn from_file(mut r: impl BufRead) -> Self {
+    /// Build a context from the facts in a log.
+    pub fn from_file(r: impl BufRead) -> std::io::Result<Self> {
+        let mut ctx = Self::default();
+        ctx.apply_log(r)?;
+        Ok(ctx)
+    }
+
+    /// Apply all the facts in a log, which may have been written by either the
+    /// plain or the JSON formatter of `holochain_trace`.
+    ///
+    /// The logs of several nodes can be applied to the same context, to trace
+    /// causes across nodes.
+    pub fn apply_log(&mut self, r: impl BufRead) -> std::io::Result<()> {
         use aitia::logging::Log;
-        let mut la = Self::default();
-        let mut line = String::new();
-        while let Ok(_bytes) = r.read_line(&mut line) {
-            if let Some(fact) = Self::parse(&line) {
-                la.apply(fact);
+        for line in r.lines() {
+            if let Some(fact) = Self::parse(&line?) {
+                self.apply(fact);
             }
         }
-        la
+        Ok(())
     }
 
     pub fn check(&self, fact: &Event) -> bool {
@@ -139,6 +153,26 @@ impl Context {
 impl aitia::logging::Log for Context {
     type Fact = Event;
 
+    fn parse(line: &str) -> Option<Event> {
+        // The JSON formatter writes the fact escaped inside the message field
+        let message = line
+            .trim_start()
+            .starts_with('{')
+            .then(|| serde_json::from_str::<serde_json::Value>(line).ok())
+            .flatten()
+            .and_then(|json| json["fields"]["message"].as_str().map(ToString::to_string));
+        let message = message.as_deref().unwrap_or(line);
+
+        let encoded = FACT_REGEX.captures(message)?.get(1)?.as_str();
+        match serde_json::from_str(encoded) {
+            Ok(fact) => Some(fact),
+            Err(err) => {
+                tracing::warn!("Skipping a fact which could not be decoded: {err}: {encoded}");
+                None
+            }
+        }
+    }
+
     fn apply(&mut self, fact: Event) {
         match fact.clone() {
             Event::Integrated { .. } => {}
@@ -179,9 +213,53 @@ impl aitia::logging::Log for Context {
                 }
             }
         }
-        let duplicate = self.facts.insert(fact.clone());
-        if duplicate {
+        let new = self.facts.insert(fact.clone());
+        if !new {
             tracing::warn!("Duplicate fact {:?}", fact);
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use aitia::logging::LogLine;
+
+    use super::*;
+
+    #[test]
+    fn parse_plain_and_json_logs() {
+        let joined = Event::AgentJoined {
+            node: "alice".into(),
+            agent: AgentPubKey::from_raw_32(vec![1; 32]),
+        };
+        let integrated = Event::Integrated {
+            by: "bob".into(),
+            op: DhtOpHash::from_raw_32(vec![2; 32]),
+        };
+        let plain = |fact: &Event| {
+            format!(
+                "2024-01-01T00:00:00Z hc_sleuth: fact={fact:?} <AITIA>{}</AITIA>",
+                fact.encode()
+            )
+        };
+        let json = |fact: &Event| {
+            serde_json::json!({
+                "level": "TRACE",
+                "fields": { "message": plain(fact), "aitia": "json" },
+            })
+            .to_string()
+        };
+
+        let log = [
+            "an unrelated line".to_string(),
+            plain(&joined),
+            json(&integrated),
+            "<AITIA>not a fact</AITIA>".to_string(),
+        ]
+        .join("\n");
+        let ctx = Context::from_file(log.as_bytes()).unwrap();
+
+        assert_eq!(ctx.facts, [joined, integrated].into_iter().collect());
+        assert!(ctx.map_node_to_agents.contains_key("alice"));
+    }
+}
@@ -151,9 +151,12 @@ impl aitia::Fact for Event {
                 format!("[{by}] ReceivedHash({method}): {op:?}")
             }
             Event::Authored { by, op } => {
-                let node = ctx.agent_node(by).expect("I got lazy");
                 let op_hash = op.as_hash();
-                format!("[{node}] Authored: {op_hash}")
+                match ctx.agent_node(by) {
+                    Ok(node) => format!("[{node}] Authored: {op_hash}"),
+                    // The agent may not have joined in any of the logs
+                    Err(_) => format!("[{by}] Authored: {op_hash}"),
+                }
             }
             Event::AgentJoined { node, agent } => {
                 format!("[{node}] AgentJoined: {agent}")
@@ -1,13 +1,20 @@
-//! This is just the beginnings of a CLI tool for generating hc_sleuth reports.
-//! It's nowhere near useful, so it's not even built yet.
+//! A CLI tool for generating hc_sleuth reports from the logs of conductors.
+//!
+//! The conductors must have been run with the `tracing_scope` of their network
+//! config set, so that each of them can be told apart in the logs, and with a
+//! log filter which lets through the level set by `AITIA_LOG` (`trace` by
+//! default), so that the facts are included in the logs.
 
-use std::{path::PathBuf, str::FromStr};
+use std::{io::BufReader, path::PathBuf, str::FromStr};
 
-use hc_sleuth::{report, Event};
+use hc_sleuth::{
+    query::{why_not_integrated, GraphFormat},
+    report, Context, Event,
+};
 use holochain_types::prelude::*;
 use structopt::StructOpt;
 
-fn main() {
+fn main() -> anyhow::Result<()> {
     let opt = HcSleuth::from_args();
 
     match opt {
@@ -21,13 +28,24 @@ fn main() {
             );
         }
         HcSleuth::Query {
-            op_hash: _,
-            node: _,
-            log_paths: _,
+            op_hash,
+            node,
+            graph,
+            log_paths,
         } => {
-            unimplemented!("command-line query not yet implemented")
+            if log_paths.is_empty() {
+                anyhow::bail!("At least one log file is needed to query");
+            }
+            let mut ctx = Context::default();
+            for path in log_paths {
+                let file = std::fs::File::open(&path)
+                    .map_err(|e| anyhow::anyhow!("Could not open {}: {e}", path.display()))?;
+                ctx.apply_log(BufReader::new(file))?;
+            }
+            print!("{}", why_not_integrated(&ctx, node, op_hash.0, graph));
         }
     }
+    Ok(())
 }
 
 #[derive(Debug, StructOpt)]
@@ -39,17 +57,26 @@ pub enum HcSleuth {
     ShowGraph,
     Query {
         #[structopt(
-            short = "h",
-            long,
-            help = "The action or entry hash to check for integration"
+            short,
+            long = "op",
+            help = "The hash of the op to check for integration"
         )]
         op_hash: TargetHash,
         #[structopt(
             short,
             long,
-            help = "The node ID which integrated (check the `tracing_scope` setting of your conductor config for this value)"
+            help = "The node ID which should have integrated the op (check the `tracing_scope` setting of your conductor config for this value)"
         )]
         node: String,
+        #[structopt(
+            short,
+            long,
+            help = "Also print the graph of failing facts, as 'dot' or 'mermaid'"
+        )]
+        graph: Option<GraphFormat>,
+        #[structopt(
+            help = "The log files of all the conductors involved, written as plain text or JSON"
+        )]
         log_paths: Vec<PathBuf>,
     },
 }
@@ -1 +1,144 @@
+//! Answer questions about the events recorded in a [`Context`].
 
+use std::{fmt::Write, str::FromStr};
+
+use aitia::{DepGraph, Fact, TraversalError, TraversalInnerError};
+
+use super::*;
+
+/// A format to render the graph of a traversal in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GraphFormat {
+    /// The DOT language of Graphviz
+    Dot,
+    /// A Mermaid flowchart
+    Mermaid,
+}
+
+impl FromStr for GraphFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "dot" => Ok(Self::Dot),
+            "mermaid" => Ok(Self::Mermaid),
+            _ => Err(format!(
+                "Unknown graph format '{s}', expected 'dot' or 'mermaid'"
+            )),
+        }
+    }
+}
+
+/// Explain why a node has not integrated an op.
+///
+/// If the node has not integrated the op, this lists the failing facts which
+/// the integration depends on and which don't depend on any other failing
+/// fact. These are the first places to look for the cause of the problem.
+pub fn why_not_integrated(
+    ctx: &Context,
+    node: SleuthId,
+    op: OpRef,
+    graph_format: Option<GraphFormat>,
+) -> String {
+    let mut out = String::new();
+    if !ctx.map_node_to_agents.contains_key(&node) {
+        writeln!(
+            &mut out,
+            "WARNING: no agent was seen joining node '{node}'. Check the node ID and that the logs of the node were included."
+        )
+        .unwrap();
+    }
+
+    let target = Event::Integrated {
+        by: node.clone(),
+        op: op.clone(),
+    };
+    let (graph, leaves) = match target.traverse(ctx) {
+        Ok(tr) => {
+            if tr.root_check_passed() {
+                writeln!(&mut out, "[{node}] has integrated {op}").unwrap();
+                return out;
+            }
+            let leaves = tr
+                .graph()
+                .leaves()
+                .into_iter()
+                .map(|dep| dep.explain(ctx))
+                .collect::<Vec<_>>();
+            (render_graph(tr.graph(), graph_format), leaves)
+        }
+        Err(TraversalError { inner, graph }) => {
+            let TraversalInnerError::Dep(err) = inner;
+            writeln!(
+                &mut out,
+                "The causes could not be fully traced, because the logs are missing information: {}",
+                err.info
+            )
+            .unwrap();
+            let leaves = err.fact.iter().map(|f| f.explain(ctx)).collect();
+            (render_graph(&graph, graph_format), leaves)
+        }
+    };
+
+    writeln!(&mut out, "[{node}] has NOT integrated {op}").unwrap();
+    writeln!(
+        &mut out,
+        "It depends on these failing facts, which no other failing fact explains:"
+    )
+    .unwrap();
+    let mut leaves = leaves;
+    leaves.sort();
+    for leaf in leaves {
+        writeln!(&mut out, "- {leaf}").unwrap();
+    }
+    if let Some(graph) = graph {
+        writeln!(&mut out, "\n{graph}").unwrap();
+    }
+    out
+}
+
+fn render_graph(graph: &DepGraph<Event>, format: Option<GraphFormat>) -> Option<String> {
+    format.map(|format| match format {
+        GraphFormat::Dot => graph.dot(),
+        GraphFormat::Mermaid => graph.mermaid(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use aitia::logging::Log;
+
+    use super::*;
+
+    #[test]
+    fn query_integration() {
+        let op = DhtOpHash::from_raw_32(vec![2; 32]);
+        let mut ctx = Context::default();
+        ctx.apply(Event::AgentJoined {
+            node: "bob".into(),
+            agent: AgentPubKey::from_raw_32(vec![1; 32]),
+        });
+
+        // Nothing is known about the op, so its causes can't be traced past
+        // sys validation, which needs to know the dependencies of the op.
+        let report = why_not_integrated(&ctx, "bob".into(), op.clone(), Some(GraphFormat::Mermaid));
+        assert!(report.contains(&format!("[bob] has NOT integrated {op}")));
+        assert!(report.contains("could not be fully traced"));
+        assert!(report.contains(&format!("- [bob] SysValidated: {op}")));
+        assert!(report.contains("flowchart TD"));
+        assert!(!report.contains("WARNING"));
+
+        let report = why_not_integrated(&ctx, "carol".into(), op.clone(), None);
+        assert!(report.starts_with("WARNING: no agent was seen joining node 'carol'"));
+        assert!(!report.contains("flowchart TD"));
+
+        ctx.apply(Event::Integrated {
+            by: "bob".into(),
+            op: op.clone(),
+        });
+        assert_eq!(
+            why_not_integrated(&ctx, "bob".into(), op.clone(), Some(GraphFormat::Dot)),
+            format!("[bob] has integrated {op}\n")
+        );
+    }
+}
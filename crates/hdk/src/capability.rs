@@ -198,3 +198,41 @@ pub fn update_cap_grant(
         chain_top_ordering: ChainTopOrdering::default(),
     })
 }
+
+/// List the capability grants on the local source chain which have neither been updated nor deleted.
+///
+/// The [`CapGrantQuery`] narrows the grants down by tag and assigned grantee.
+/// The default query returns every live grant.
+///
+/// Each [`LiveCapGrant`] carries the action hash needed to [`update_cap_grant`] or [`delete_cap_grant`].
+///
+/// ```ignore
+/// let grants = capability_grants(CapGrantQuery::default().tag("my_tag"))?;
+/// ```
+pub fn capability_grants(query: CapGrantQuery) -> ExternResult<Vec<LiveCapGrant>> {
+    HDK.with(|h| h.borrow().capability_grants(query))
+}
+
+/// List the capability claims on the local source chain which have not been deleted.
+///
+/// The [`CapClaimQuery`] narrows the claims down by tag and grantor.
+/// There is no guarantee that a listed claim is still accepted by its grantor.
+///
+/// ```ignore
+/// let claims = capability_claims(CapClaimQuery::default().grantor(agent))?;
+/// ```
+pub fn capability_claims(query: CapClaimQuery) -> ExternResult<Vec<LiveCapClaim>> {
+    HDK.with(|h| h.borrow().capability_claims(query))
+}
+
+/// Get the capability which authorized the current zome call.
+///
+/// Returns the calling agent, the secret they provided if any, and the grant which the secret
+/// matched. Calls made by the chain author are authorized by [`CapGrant::ChainAuthor`].
+///
+/// ```ignore
+/// let capability_info = capability_info()?;
+/// ```
+pub fn capability_info() -> ExternResult<CapabilityInfo> {
+    HDK.with(|h| h.borrow().capability_info(()))
+}
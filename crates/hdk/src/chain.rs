@@ -31,3 +31,12 @@ pub fn get_agent_activity(
 pub fn query(filter: ChainQueryFilter) -> ExternResult<Vec<Record>> {
     HDK.with(|h| h.borrow().query(filter))
 }
+
+/// Walks the source chain which this agent migrated from, like [ `query` ] walks the current one.
+///
+/// The previous chain is the one on the DNA referenced by the [ `OpenChain` ] action of
+/// the current chain, which is committed when the conductor migrates an agent to a new DNA.
+/// Returns an error if the current chain was not opened by a migration.
+pub fn query_previous_chain(filter: ChainQueryFilter) -> ExternResult<Vec<Record>> {
+    HDK.with(|h| h.borrow().query_previous_chain(filter))
+}
@@ -294,6 +294,26 @@ pub fn get_details<H: Into<AnyDhtHash>>(
         .unwrap())
 }
 
+/// Get what the authorities for a hash know about it, without fetching any records.
+///
+/// Every authority that responds returns a [`MetadataSet`] holding the actions which created
+/// the entry or action, the updates and deletes on it, the number of live links on the hash as a
+/// base and, for an action hash, the validation status of its record. The [`MetadataRequest`]
+/// selects which actions, updates and deletes are returned, and whether the [`EntryDhtStatus`]
+/// of an entry is worked out.
+///
+/// Each set also says whether the agent which sent it holds the hash in its storage arc, in
+/// [`MetadataSet::is_authority`]. Sets from agents which do not may be incomplete. The part of the
+/// DHT covered by that agent's storage arc is in [`MetadataSet::storage_arc`], when it is known.
+///
+/// If the calling agent is an authority for the hash, only its own metadata is returned.
+pub fn get_meta<H: Into<AnyDhtHash>>(
+    hash: H,
+    request: MetadataRequest,
+) -> ExternResult<Vec<MetadataSet>> {
+    HDK.with(|h| h.borrow().get_meta(GetMetaInput::new(hash.into(), request)))
+}
+
 /// Implements a whole lot of sane defaults for a struct or enum that should behave as an entry.
 /// All the entry def fields are available as dedicated methods on the type and matching From impls
 /// are provided for each. This allows for both Foo::entry_types() and EntryDef::from(Foo::new())
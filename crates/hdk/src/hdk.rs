@@ -27,6 +27,7 @@ pub trait HdkT: HdiT {
         get_agent_activity_input: GetAgentActivityInput,
     ) -> ExternResult<AgentActivity>;
     fn query(&self, filter: ChainQueryFilter) -> ExternResult<Vec<Record>>;
+    fn query_previous_chain(&self, filter: ChainQueryFilter) -> ExternResult<Vec<Record>>;
     // Ed25519
     fn sign(&self, sign: Sign) -> ExternResult<Signature>;
     fn sign_ephemeral(&self, sign_ephemeral: SignEphemeral) -> ExternResult<EphemeralSignatures>;
@@ -36,6 +37,7 @@ pub trait HdkT: HdiT {
     fn delete(&self, delete_input: DeleteInput) -> ExternResult<ActionHash>;
     fn get(&self, get_input: Vec<GetInput>) -> ExternResult<Vec<Option<Record>>>;
     fn get_details(&self, get_input: Vec<GetInput>) -> ExternResult<Vec<Option<Details>>>;
+    fn get_meta(&self, get_meta_input: GetMetaInput) -> ExternResult<Vec<MetadataSet>>;
     // CounterSigning
     fn accept_countersigning_preflight_request(
         &self,
@@ -44,6 +46,10 @@ pub trait HdkT: HdiT {
     // Info
     fn agent_info(&self, agent_info_input: ()) -> ExternResult<AgentInfo>;
     fn call_info(&self, call_info_input: ()) -> ExternResult<CallInfo>;
+    // Capability
+    fn capability_claims(&self, query: CapClaimQuery) -> ExternResult<Vec<LiveCapClaim>>;
+    fn capability_grants(&self, query: CapGrantQuery) -> ExternResult<Vec<LiveCapGrant>>;
+    fn capability_info(&self, capability_info_input: ()) -> ExternResult<CapabilityInfo>;
     // Link
     fn create_link(&self, create_link_input: CreateLinkInput) -> ExternResult<ActionHash>;
     fn delete_link(&self, delete_link_input: DeleteLinkInput) -> ExternResult<ActionHash>;
@@ -109,6 +115,7 @@ mockall::mock! {
             get_agent_activity_input: GetAgentActivityInput,
         ) -> ExternResult<AgentActivity>;
         fn query(&self, filter: ChainQueryFilter) -> ExternResult<Vec<Record>>;
+        fn query_previous_chain(&self, filter: ChainQueryFilter) -> ExternResult<Vec<Record>>;
         // Ed25519
         fn sign(&self, sign: Sign) -> ExternResult<Signature>;
         fn sign_ephemeral(&self, sign_ephemeral: SignEphemeral) -> ExternResult<EphemeralSignatures>;
@@ -118,6 +125,7 @@ mockall::mock! {
         fn delete(&self, delete_input: DeleteInput) -> ExternResult<ActionHash>;
         fn get(&self, get_input: Vec<GetInput>) -> ExternResult<Vec<Option<Record>>>;
         fn get_details(&self, get_input: Vec<GetInput>) -> ExternResult<Vec<Option<Details>>>;
+        fn get_meta(&self, get_meta_input: GetMetaInput) -> ExternResult<Vec<MetadataSet>>;
         // CounterSigning
         fn accept_countersigning_preflight_request(
             &self,
@@ -126,6 +134,10 @@ mockall::mock! {
         // Info
         fn agent_info(&self, agent_info_input: ()) -> ExternResult<AgentInfo>;
         fn call_info(&self, call_info_input: ()) -> ExternResult<CallInfo>;
+        // Capability
+        fn capability_claims(&self, query: CapClaimQuery) -> ExternResult<Vec<LiveCapClaim>>;
+        fn capability_grants(&self, query: CapGrantQuery) -> ExternResult<Vec<LiveCapGrant>>;
+        fn capability_info(&self, capability_info_input: ()) -> ExternResult<CapabilityInfo>;
         // Link
         fn create_link(&self, create_link_input: CreateLinkInput) -> ExternResult<ActionHash>;
         fn delete_link(&self, delete_link_input: DeleteLinkInput) -> ExternResult<ActionHash>;
@@ -309,6 +321,9 @@ impl HdkT for ErrHdk {
     fn query(&self, _: ChainQueryFilter) -> ExternResult<Vec<Record>> {
         Self::err()
     }
+    fn query_previous_chain(&self, _: ChainQueryFilter) -> ExternResult<Vec<Record>> {
+        Self::err()
+    }
     fn sign(&self, _: Sign) -> ExternResult<Signature> {
         Self::err()
     }
@@ -330,6 +345,9 @@ impl HdkT for ErrHdk {
     fn get_details(&self, _: Vec<GetInput>) -> ExternResult<Vec<Option<Details>>> {
         Self::err()
     }
+    fn get_meta(&self, _: GetMetaInput) -> ExternResult<Vec<MetadataSet>> {
+        Self::err()
+    }
     // CounterSigning
     fn accept_countersigning_preflight_request(
         &self,
@@ -343,6 +361,16 @@ impl HdkT for ErrHdk {
     fn call_info(&self, _: ()) -> ExternResult<CallInfo> {
         Self::err()
     }
+    // Capability
+    fn capability_claims(&self, _: CapClaimQuery) -> ExternResult<Vec<LiveCapClaim>> {
+        Self::err()
+    }
+    fn capability_grants(&self, _: CapGrantQuery) -> ExternResult<Vec<LiveCapGrant>> {
+        Self::err()
+    }
+    fn capability_info(&self, _: ()) -> ExternResult<CapabilityInfo> {
+        Self::err()
+    }
     // Link
     fn create_link(&self, _: CreateLinkInput) -> ExternResult<ActionHash> {
         Self::err()
@@ -539,6 +567,9 @@ impl HdkT for HostHdk {
     fn query(&self, filter: ChainQueryFilter) -> ExternResult<Vec<Record>> {
         host_call::<ChainQueryFilter, Vec<Record>>(__hc__query_1, filter)
     }
+    fn query_previous_chain(&self, filter: ChainQueryFilter) -> ExternResult<Vec<Record>> {
+        host_call::<ChainQueryFilter, Vec<Record>>(__hc__query_previous_chain_1, filter)
+    }
     fn sign(&self, sign: Sign) -> ExternResult<Signature> {
         host_call::<Sign, Signature>(__hc__sign_1, sign)
     }
@@ -560,6 +591,9 @@ impl HdkT for HostHdk {
     fn get_details(&self, get_inputs: Vec<GetInput>) -> ExternResult<Vec<Option<Details>>> {
         host_call::<Vec<GetInput>, Vec<Option<Details>>>(__hc__get_details_1, get_inputs)
     }
+    fn get_meta(&self, get_meta_input: GetMetaInput) -> ExternResult<Vec<MetadataSet>> {
+        host_call::<GetMetaInput, Vec<MetadataSet>>(__hc__get_meta_1, get_meta_input)
+    }
     // CounterSigning
     fn accept_countersigning_preflight_request(
         &self,
@@ -576,6 +610,15 @@ impl HdkT for HostHdk {
     fn call_info(&self, _: ()) -> ExternResult<CallInfo> {
         host_call::<(), CallInfo>(__hc__call_info_1, ())
     }
+    fn capability_claims(&self, query: CapClaimQuery) -> ExternResult<Vec<LiveCapClaim>> {
+        host_call::<CapClaimQuery, Vec<LiveCapClaim>>(__hc__capability_claims_1, query)
+    }
+    fn capability_grants(&self, query: CapGrantQuery) -> ExternResult<Vec<LiveCapGrant>> {
+        host_call::<CapGrantQuery, Vec<LiveCapGrant>>(__hc__capability_grants_1, query)
+    }
+    fn capability_info(&self, _: ()) -> ExternResult<CapabilityInfo> {
+        host_call::<(), CapabilityInfo>(__hc__capability_info_1, ())
+    }
     fn create_link(&self, create_link_input: CreateLinkInput) -> ExternResult<ActionHash> {
         host_call::<CreateLinkInput, ActionHash>(__hc__create_link_1, create_link_input)
     }
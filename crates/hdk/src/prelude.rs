@@ -1,3 +1,6 @@
+pub use crate::capability::capability_claims;
+pub use crate::capability::capability_grants;
+pub use crate::capability::capability_info;
 pub use crate::capability::create_cap_claim;
 pub use crate::capability::create_cap_grant;
 pub use crate::capability::delete_cap_grant;
@@ -6,6 +9,7 @@ pub use crate::capability::update_cap_grant;
 pub use crate::chain::get_agent_activity;
 pub use crate::chain::must_get_agent_activity;
 pub use crate::chain::query;
+pub use crate::chain::query_previous_chain;
 pub use crate::countersigning::accept_countersigning_preflight_request;
 pub use crate::countersigning::session_times_from_millis;
 pub use crate::ed25519::sign;
@@ -20,6 +24,7 @@ pub use crate::entry::delete;
 pub use crate::entry::delete_entry;
 pub use crate::entry::get;
 pub use crate::entry::get_details;
+pub use crate::entry::get_meta;
 pub use crate::entry::must_get_action;
 pub use crate::entry::must_get_entry;
 pub use crate::entry::must_get_valid_record;
@@ -137,6 +142,7 @@ macro_rules! holochain_externs {
             capability_info:1,
             get:1,
             get_details:1,
+            get_meta:1,
             get_links:1,
             get_link_details:1,
             count_links:1,
@@ -146,6 +152,7 @@ macro_rules! holochain_externs {
             must_get_action:1,
             accept_countersigning_preflight_request:1,
             query:1,
+            query_previous_chain:1,
             call_remote:1,
             block_agent:1,
             unblock_agent:1,
@@ -136,7 +136,27 @@ pub fn schedule(scheduled_fn: &str) -> ExternResult<()> {
     HDK.with(|h| h.borrow().schedule(String::from(scheduled_fn)))
 }
 
-/// @todo Not implemented
+/// Pause the current zome call for the given duration.
+///
+/// The zome call is not suspended while it sleeps: the thread running it waits
+/// for the duration. Other zome calls and networking carry on on other threads,
+/// but every sleeping call keeps a thread busy. This is suitable for short
+/// pauses when polling or backing off between attempts, e.g. around
+/// [`call_remote`] when the remote agent may not be reachable yet.
+///
+/// ```ignore
+/// sleep(std::time::Duration::from_millis(500))?;
+/// ```
+///
+/// The conductor caps the duration of a single sleep with its
+/// `max_zome_sleep_duration` tuning param (30 seconds by default), in zome
+/// calls, `init` and `post_commit` alike. Asking to sleep for longer returns an
+/// error without sleeping at all. It also caps how many calls may sleep at
+/// once with its `max_concurrent_zome_sleeps` tuning param (16 by default), and
+/// sleeping while that many calls are already asleep returns an error too.
+///
+/// Sleeping holds the zome call open, including any source chain writes
+/// made so far, and counts towards the caller's timeout for the call.
 pub fn sleep(wake_after: std::time::Duration) -> ExternResult<()> {
     HDK.with(|h| h.borrow().sleep(wake_after))
 }
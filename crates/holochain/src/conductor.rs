@@ -14,6 +14,7 @@
 // TODO: clean up allow(missing_docs) once parent is fully documented
 
 pub mod api;
+pub mod app_auth_token_store;
 mod cell;
 #[cfg(feature = "chc")]
 pub mod chc;
@@ -33,6 +34,7 @@ pub mod p2p_agent_store;
 pub mod paths;
 #[allow(missing_docs)]
 pub mod ribosome_store;
+pub mod signal_log;
 pub mod space;
 pub mod state;
 
@@ -13,6 +13,7 @@ use crate::core::ribosome::real_ribosome::RealRibosome;
 use crate::core::workflow::ZomeCallResult;
 use async_trait::async_trait;
 use holo_hash::DnaHash;
+use holochain_conductor_api::conductor::ConductorTuningParams;
 use holochain_conductor_api::ZomeCall;
 use holochain_keystore::MetaLairClient;
 use holochain_state::host_fn_workspace::SourceChainWorkspace;
@@ -241,6 +242,16 @@ pub trait CellConductorReadHandleT: Send + Sync {
 
     /// Expose delete_clone_cell functionality to zomes.
     async fn delete_clone_cell(&self, payload: DeleteCloneCellPayload) -> ConductorResult<()>;
+
+    /// Get the tuning params of the conductor this cell is running in.
+    fn conductor_tuning_params(&self) -> ConductorTuningParams;
+
+    /// Query the source chain of this cell's agent on the DNA it migrated from.
+    async fn query_previous_chain(
+        &self,
+        prev_dna_hash: DnaHash,
+        filter: ChainQueryFilter,
+    ) -> ConductorResult<Vec<Record>>;
 }
 
 #[async_trait]
@@ -347,4 +358,19 @@ impl CellConductorReadHandleT for CellConductorApi {
             .delete_clone_cell(&payload)
             .await
     }
+
+    fn conductor_tuning_params(&self) -> ConductorTuningParams {
+        self.conductor_handle.get_config().conductor_tuning_params()
+    }
+
+    async fn query_previous_chain(
+        &self,
+        prev_dna_hash: DnaHash,
+        filter: ChainQueryFilter,
+    ) -> ConductorResult<Vec<Record>> {
+        let cell_id = CellId::new(prev_dna_hash, self.cell_id.agent_pubkey().clone());
+        self.conductor_handle
+            .query_source_chain(&cell_id, filter)
+            .await
+    }
 }
@@ -152,25 +152,60 @@ impl AdminInterfaceApi for RealAdminInterfaceApi {
                     &dna_definitions,
                 )))
             }
-            UninstallApp { installed_app_id } => {
+            InstallAppFromStore(payload) => {
+                let app: InstalledApp = self
+                    .conductor_handle
+                    .clone()
+                    .install_app_from_store(*payload)
+                    .await?
+                    .into();
+                let dna_definitions = self.conductor_handle.get_dna_definitions(&app)?;
+                Ok(AdminResponse::AppInstalled(AppInfo::from_installed_app(
+                    &app,
+                    &dna_definitions,
+                )))
+            }
+            UninstallApp {
+                installed_app_id,
+                cascade,
+            } => {
                 self.conductor_handle
                     .clone()
-                    .uninstall_app(&installed_app_id)
+                    .uninstall_app(&installed_app_id, cascade)
                     .await?;
                 Ok(AdminResponse::AppUninstalled)
             }
+            MigrateAgent {
+                old_cell_id,
+                new_dna_hash,
+            } => {
+                self.conductor_handle
+                    .migrate_agent(old_cell_id, new_dna_hash)
+                    .await?;
+                Ok(AdminResponse::AgentMigrated)
+            }
             ListDnas => {
                 let dna_list = self.conductor_handle.list_dnas();
                 Ok(AdminResponse::DnasListed(dna_list))
             }
             GenerateAgentPubKey => {
-                let agent_pub_key = self
+                let agent_pub_key = self.conductor_handle.generate_agent_pub_key().await?;
+                Ok(AdminResponse::AgentPubKeyGenerated(agent_pub_key))
+            }
+            RotateAgentKey { agent_key } => {
+                let new_key = self
                     .conductor_handle
-                    .keystore()
                     .clone()
-                    .new_sign_keypair_random()
+                    .rotate_agent_key(agent_key)
                     .await?;
-                Ok(AdminResponse::AgentPubKeyGenerated(agent_pub_key))
+                Ok(AdminResponse::AgentKeyRotated(new_key))
+            }
+            RevokeAgentKey { agent_key } => {
+                self.conductor_handle
+                    .clone()
+                    .revoke_agent_key(agent_key)
+                    .await?;
+                Ok(AdminResponse::AgentKeyRevoked)
             }
             ListCellIds => {
                 let cell_ids = self
@@ -222,15 +257,49 @@ impl AdminInterfaceApi for RealAdminInterfaceApi {
             AttachAppInterface {
                 port,
                 allowed_origins,
+                installed_app_id,
+                bind_address,
+                tls,
             } => {
-                let port = port.unwrap_or(0);
-                let port = self
+                let driver = InterfaceDriver::Websocket {
+                    port: port.unwrap_or(0),
+                    allowed_origins,
+                    bind_address,
+                    tls,
+                };
+                let driver = self
                     .conductor_handle
                     .clone()
-                    .add_app_interface(either::Either::Left(port), allowed_origins)
+                    .add_app_interface_with_driver(driver, installed_app_id)
                     .await?;
+                let port = driver
+                    .port()
+                    .expect("A websocket interface always has a port");
                 Ok(AdminResponse::AppInterfaceAttached { port })
             }
+            AttachAppUnixSocketInterface {
+                path,
+                mode,
+                installed_app_id,
+            } => {
+                self.conductor_handle
+                    .clone()
+                    .add_unix_socket_app_interface(path, mode, installed_app_id)
+                    .await?;
+                Ok(AdminResponse::AppUnixSocketInterfaceAttached)
+            }
+            IssueAppAuthenticationToken(payload) => {
+                let issued = self
+                    .conductor_handle
+                    .issue_app_authentication_token(payload)
+                    .await?;
+                Ok(AdminResponse::AppAuthenticationTokenIssued(issued))
+            }
+            RevokeAppAuthenticationToken(token) => {
+                self.conductor_handle
+                    .revoke_app_authentication_token(&token);
+                Ok(AdminResponse::AppAuthenticationTokenRevoked)
+            }
             ListAppInterfaces => {
                 let interfaces = self.conductor_handle.list_app_interfaces().await?;
                 Ok(AdminResponse::AppInterfacesListed(interfaces))
@@ -280,6 +349,17 @@ impl AdminInterfaceApi for RealAdminInterfaceApi {
                     .await?;
                 Ok(AdminResponse::RecordsGrafted)
             }
+            ExportChain { cell_id } => {
+                let archive = self.conductor_handle.export_chain(&cell_id).await?;
+                Ok(AdminResponse::ChainExported(Box::new(archive)))
+            }
+            ImportChain { archive, validate } => {
+                self.conductor_handle
+                    .clone()
+                    .import_chain(*archive, validate)
+                    .await?;
+                Ok(AdminResponse::ChainImported)
+            }
             GrantZomeCallCapability(payload) => {
                 self.conductor_handle
                     .clone()
@@ -297,6 +377,35 @@ impl AdminInterfaceApi for RealAdminInterfaceApi {
             StorageInfo => Ok(AdminResponse::StorageInfo(
                 self.conductor_handle.storage_info().await?,
             )),
+            RotateDatabaseKey => {
+                self.conductor_handle.rotate_database_key().await?;
+                Ok(AdminResponse::DatabaseKeyRotated)
+            }
+            Block(span) => {
+                let block = span.try_into_block().map_err(ConductorApiError::other)?;
+                // Neither tx5 nor a proxied connection reveals the address of
+                // the peer, so an IP block would never take effect.
+                if let BlockTarget::Ip(ip, _) = block.target() {
+                    if !self
+                        .conductor_handle
+                        .get_config()
+                        .network
+                        .reveals_remote_ip()
+                    {
+                        return Err(ConductorError::IpBlockNotEnforceable(*ip).into());
+                    }
+                }
+                self.conductor_handle.block(block).await?;
+                Ok(AdminResponse::Blocked)
+            }
+            Unblock(span) => {
+                let block = span.try_into_block().map_err(ConductorApiError::other)?;
+                self.conductor_handle.unblock(block).await?;
+                Ok(AdminResponse::Unblocked)
+            }
+            ListBlocks => Ok(AdminResponse::BlocksListed(
+                self.conductor_handle.list_blocks().await?,
+            )),
         }
     }
 }
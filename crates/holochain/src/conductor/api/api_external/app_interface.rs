@@ -1,9 +1,12 @@
 use super::InterfaceApi;
+use crate::conductor::api::error::ConductorApiError;
 use crate::conductor::api::error::ConductorApiResult;
 use crate::conductor::api::error::ExternalApiWireError;
 use crate::conductor::api::error::SerializationError;
 use crate::conductor::interface::error::InterfaceError;
 use crate::conductor::interface::error::InterfaceResult;
+use crate::conductor::signal_log::SignalCursor;
+use crate::conductor::state::AppInterfaceId;
 use crate::conductor::ConductorHandle;
 
 use holochain_serialized_bytes::prelude::*;
@@ -41,12 +44,109 @@ pub trait AppInterfaceApi: 'static + Send + Sync + Clone {
 #[derive(Clone)]
 pub struct RealAppInterfaceApi {
     conductor_handle: ConductorHandle,
+    /// The app interface this api serves, if any.
+    /// Requests which configure the interface, like signal subscriptions,
+    /// apply to all app interfaces if this is not set.
+    interface_id: Option<AppInterfaceId>,
+    /// The app which the client of this api authenticated for.
+    /// If this is set, requests concerning any other app are rejected.
+    installed_app_id: Option<InstalledAppId>,
+    /// The position of the client in the signals of the authenticated app.
+    signal_cursor: Option<SignalCursor>,
 }
 
 impl RealAppInterfaceApi {
     /// Create a new instance from a shared Conductor reference
     pub fn new(conductor_handle: ConductorHandle) -> Self {
-        Self { conductor_handle }
+        Self {
+            conductor_handle,
+            interface_id: None,
+            installed_app_id: None,
+            signal_cursor: None,
+        }
+    }
+
+    /// Create a new instance from a shared Conductor reference,
+    /// serving the app interface with the given id
+    pub fn new_for_interface(
+        conductor_handle: ConductorHandle,
+        interface_id: AppInterfaceId,
+    ) -> Self {
+        Self {
+            conductor_handle,
+            interface_id: Some(interface_id),
+            installed_app_id: None,
+            signal_cursor: None,
+        }
+    }
+
+    /// Authenticate a client with an app authentication token, returning an api
+    /// which only serves requests for the app the token was issued for.
+    ///
+    /// If the interface is bound to an app, `interface_app` must be that app.
+    pub fn authenticate(
+        &self,
+        token: &AppAuthenticationToken,
+        interface_app: Option<&InstalledAppId>,
+    ) -> ConductorApiResult<Self> {
+        let installed_app_id = self
+            .conductor_handle
+            .authenticate_app_token(token, interface_app)?;
+        let signal_cursor = self.conductor_handle.signal_log().cursor(&installed_app_id);
+        Ok(Self {
+            installed_app_id: Some(installed_app_id),
+            signal_cursor,
+            ..self.clone()
+        })
+    }
+
+    /// The app which the client of this api authenticated for, if any.
+    pub fn installed_app_id(&self) -> Option<&InstalledAppId> {
+        self.installed_app_id.as_ref()
+    }
+
+    /// The position of the client in the signals of the authenticated app,
+    /// if the client authenticated.
+    pub fn signal_cursor(&self) -> Option<&SignalCursor> {
+        self.signal_cursor.as_ref()
+    }
+
+    /// Check whether a cell belongs to the authenticated app.
+    /// Every cell is allowed if the client did not authenticate.
+    pub async fn allows_cell(&self, cell_id: &CellId) -> ConductorApiResult<bool> {
+        match &self.installed_app_id {
+            Some(installed_app_id) => Ok(self
+                .conductor_handle
+                .get_state()
+                .await?
+                .get_app(installed_app_id)?
+                .all_cells()
+                .any(|id| id == cell_id)),
+            None => Ok(true),
+        }
+    }
+
+    fn check_app(&self, installed_app_id: &InstalledAppId) -> ConductorApiResult<()> {
+        match &self.installed_app_id {
+            Some(authenticated) if authenticated != installed_app_id => {
+                Err(ConductorApiError::NotAuthorizedForApp(
+                    installed_app_id.clone(),
+                    authenticated.clone(),
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    async fn check_cell(&self, cell_id: &CellId) -> ConductorApiResult<()> {
+        if self.allows_cell(cell_id).await? {
+            Ok(())
+        } else {
+            Err(ConductorApiError::NotAuthorizedForCell(
+                cell_id.clone(),
+                self.installed_app_id.clone().unwrap_or_default(),
+            ))
+        }
     }
 }
 
@@ -58,12 +158,16 @@ impl AppInterfaceApi for RealAppInterfaceApi {
         request: AppRequest,
     ) -> ConductorApiResult<AppResponse> {
         match request {
-            AppRequest::AppInfo { installed_app_id } => Ok(AppResponse::AppInfo(
-                self.conductor_handle
-                    .get_app_info(&installed_app_id)
-                    .await?,
-            )),
+            AppRequest::AppInfo { installed_app_id } => {
+                self.check_app(&installed_app_id)?;
+                Ok(AppResponse::AppInfo(
+                    self.conductor_handle
+                        .get_app_info(&installed_app_id)
+                        .await?,
+                ))
+            }
             AppRequest::CallZome(call) => {
+                self.check_cell(&call.cell_id).await?;
                 match self.conductor_handle.call_zome(*call.clone()).await? {
                     Ok(ZomeCallResponse::Ok(output)) => Ok(AppResponse::ZomeCalled(Box::new(output))),
                     Ok(ZomeCallResponse::Unauthorized(zome_call_authorization, _, zome_name, fn_name, _)) => Ok(AppResponse::Error(
@@ -86,6 +190,7 @@ impl AppInterfaceApi for RealAppInterfaceApi {
                 }
             }
             AppRequest::CreateCloneCell(payload) => {
+                self.check_app(&payload.app_id)?;
                 let clone_cell = self
                     .conductor_handle
                     .clone()
@@ -94,6 +199,7 @@ impl AppInterfaceApi for RealAppInterfaceApi {
                 Ok(AppResponse::CloneCellCreated(clone_cell))
             }
             AppRequest::DisableCloneCell(payload) => {
+                self.check_app(&payload.app_id)?;
                 self.conductor_handle
                     .clone()
                     .disable_clone_cell(&payload)
@@ -101,6 +207,7 @@ impl AppInterfaceApi for RealAppInterfaceApi {
                 Ok(AppResponse::CloneCellDisabled)
             }
             AppRequest::EnableCloneCell(payload) => {
+                self.check_app(&payload.app_id)?;
                 let enabled_cell = self
                     .conductor_handle
                     .clone()
@@ -108,13 +215,87 @@ impl AppInterfaceApi for RealAppInterfaceApi {
                     .await?;
                 Ok(AppResponse::CloneCellEnabled(enabled_cell))
             }
+            AppRequest::ProvisionDeferredCell(payload) => {
+                self.check_app(&payload.app_id)?;
+                let provisioned_cell = self
+                    .conductor_handle
+                    .clone()
+                    .provision_deferred_cell(*payload)
+                    .await?;
+                Ok(AppResponse::DeferredCellProvisioned(provisioned_cell))
+            }
             AppRequest::NetworkInfo(payload) => {
+                for dna_hash in payload.dnas.iter() {
+                    self.check_cell(&CellId::new(
+                        dna_hash.clone(),
+                        payload.agent_pub_key.clone(),
+                    ))
+                    .await?;
+                }
                 let info = self.conductor_handle.network_info(&payload).await?;
                 Ok(AppResponse::NetworkInfo(info))
             }
             AppRequest::ListWasmHostFunctions => Ok(AppResponse::ListWasmHostFunctions(
                 self.conductor_handle.list_wasm_host_functions().await?,
             )),
+            AppRequest::SetSignalSubscription(subscription) => {
+                self.check_app(subscription.installed_app_id())?;
+                self.conductor_handle
+                    .set_signal_subscription(self.interface_id.clone(), *subscription)
+                    .await?;
+                Ok(AppResponse::SignalSubscriptionSet)
+            }
+            AppRequest::ResumeSignals { from_seq } => {
+                let cursor = self.signal_cursor.as_ref().ok_or_else(|| {
+                    ConductorApiError::other(
+                        "Signals can only be resumed on an authenticated app interface connection",
+                    )
+                })?;
+                let (oldest_seq, next_seq) = self
+                    .conductor_handle
+                    .signal_log()
+                    .resume(cursor, from_seq)
+                    .ok_or_else(|| {
+                        ConductorApiError::other(format!(
+                            "App {} is not installed",
+                            cursor.installed_app_id()
+                        ))
+                    })?;
+                Ok(AppResponse::SignalsResumed {
+                    oldest_seq,
+                    next_seq,
+                })
+            }
+            AppRequest::GetCountersigningSessionState(cell_id) => {
+                self.check_cell(&cell_id).await?;
+                let state = self
+                    .conductor_handle
+                    .countersigning_session_state(&cell_id)
+                    .await?;
+                Ok(AppResponse::CountersigningSessionState(Box::new(state)))
+            }
+            AppRequest::AbandonCountersigningSession(cell_id) => {
+                self.check_cell(&cell_id).await?;
+                self.conductor_handle
+                    .abandon_countersigning_session(&cell_id)
+                    .await?;
+                Ok(AppResponse::CountersigningSessionAbandoned)
+            }
+            AppRequest::PublishCountersigningSession(cell_id) => {
+                self.check_cell(&cell_id).await?;
+                self.conductor_handle
+                    .publish_countersigning_session(&cell_id)
+                    .await?;
+                Ok(AppResponse::PublishCountersigningSessionTriggered)
+            }
+            AppRequest::ListZomeExports { installed_app_id } => {
+                self.check_app(&installed_app_id)?;
+                Ok(AppResponse::ZomeExportsListed(
+                    self.conductor_handle
+                        .list_zome_exports(&installed_app_id)
+                        .await?,
+                ))
+            }
         }
     }
 }
@@ -32,6 +32,16 @@ pub enum ConductorApiError {
         call_cell_id: CellId,
     },
 
+    /// The client authenticated for one app made a request concerning another.
+    #[error(
+        "Not authorized to make requests for app {0}, the connection is authenticated for app {1}"
+    )]
+    NotAuthorizedForApp(InstalledAppId, InstalledAppId),
+
+    /// The client authenticated for an app made a request to a cell outside of it.
+    #[error("Not authorized to make requests to cell {0:?}, which is not part of app {1}")]
+    NotAuthorizedForCell(CellId, InstalledAppId),
+
     /// Conductor threw an error during API call.
     #[error("Conductor returned an error while using a ConductorApi: {0:?}")]
     ConductorError(#[from] ConductorError),
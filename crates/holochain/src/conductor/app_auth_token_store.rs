@@ -0,0 +1,219 @@
+//! The store of tokens which authenticate connections to app interfaces.
+//!
+//! Tokens are issued over the admin interface with
+//! [`AdminRequest::IssueAppAuthenticationToken`](holochain_conductor_api::AdminRequest::IssueAppAuthenticationToken)
+//! and are scoped to a single app. They are kept in memory only, so all tokens
+//! are invalidated when the conductor restarts.
+
+use holochain_conductor_api::AppAuthenticationToken;
+use holochain_conductor_api::AppAuthenticationTokenIssued;
+use holochain_conductor_api::IssueAppAuthenticationTokenPayload;
+use holochain_types::prelude::*;
+use rand::RngCore;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// The number of random bytes in a token.
+const TOKEN_SIZE: usize = 16;
+
+/// Why a token could not be used to authenticate.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum AppAuthenticationError {
+    /// The token was never issued, or it was revoked or already used.
+    #[error("The app authentication token is not valid")]
+    InvalidToken,
+
+    /// The token has expired.
+    #[error("The app authentication token has expired")]
+    ExpiredToken,
+
+    /// The token is for a different app than the one the interface is bound to.
+    #[error("The app authentication token is for app {token_app}, but the interface is bound to app {interface_app}")]
+    AppMismatch {
+        /// The app the token was issued for.
+        token_app: InstalledAppId,
+        /// The app the interface is bound to.
+        interface_app: InstalledAppId,
+    },
+}
+
+#[derive(Debug, Clone)]
+struct TokenEntry {
+    installed_app_id: InstalledAppId,
+    expires_at: Option<Timestamp>,
+    single_use: bool,
+}
+
+/// Issued tokens, which are valid until they expire, are revoked, or,
+/// for single use tokens, are used.
+#[derive(Debug, Default)]
+pub struct AppAuthTokenStore {
+    tokens: HashMap<AppAuthenticationToken, TokenEntry>,
+}
+
+impl AppAuthTokenStore {
+    /// Issue a new token for the app in the payload.
+    pub fn issue_token(
+        &mut self,
+        payload: IssueAppAuthenticationTokenPayload,
+    ) -> AppAuthenticationTokenIssued {
+        self.prune_expired();
+
+        let mut token = vec![0; TOKEN_SIZE];
+        rand::thread_rng().fill_bytes(&mut token);
+
+        let expires_at = if payload.expiry_seconds == 0 {
+            None
+        } else {
+            (Timestamp::now() + Duration::from_secs(payload.expiry_seconds)).ok()
+        };
+
+        self.tokens.insert(
+            token.clone(),
+            TokenEntry {
+                installed_app_id: payload.installed_app_id,
+                expires_at,
+                single_use: payload.single_use,
+            },
+        );
+
+        AppAuthenticationTokenIssued { token, expires_at }
+    }
+
+    /// Revoke a token. Revoking a token which doesn't exist is not an error.
+    pub fn revoke_token(&mut self, token: &AppAuthenticationToken) {
+        self.tokens.remove(token);
+    }
+
+    /// Revoke all tokens for an app, e.g. when it is uninstalled.
+    pub fn revoke_tokens_for_app(&mut self, installed_app_id: &InstalledAppId) {
+        self.tokens
+            .retain(|_, entry| entry.installed_app_id != *installed_app_id);
+    }
+
+    /// Check a token and return the app it was issued for.
+    ///
+    /// If the interface is bound to an app, the token must have been issued
+    /// for that app. Single use tokens are removed once they authenticated
+    /// a connection.
+    pub fn authenticate_token(
+        &mut self,
+        token: &AppAuthenticationToken,
+        interface_app: Option<&InstalledAppId>,
+    ) -> Result<InstalledAppId, AppAuthenticationError> {
+        let entry = self
+            .tokens
+            .get(token)
+            .cloned()
+            .ok_or(AppAuthenticationError::InvalidToken)?;
+
+        if entry
+            .expires_at
+            .map(|expires_at| expires_at < Timestamp::now())
+            .unwrap_or(false)
+        {
+            self.tokens.remove(token);
+            return Err(AppAuthenticationError::ExpiredToken);
+        }
+
+        if let Some(interface_app) = interface_app {
+            if *interface_app != entry.installed_app_id {
+                return Err(AppAuthenticationError::AppMismatch {
+                    token_app: entry.installed_app_id,
+                    interface_app: interface_app.clone(),
+                });
+            }
+        }
+
+        if entry.single_use {
+            self.tokens.remove(token);
+        }
+
+        Ok(entry.installed_app_id)
+    }
+
+    fn prune_expired(&mut self) {
+        let now = Timestamp::now();
+        self.tokens.retain(|_, entry| {
+            entry
+                .expires_at
+                .map(|expires_at| expires_at >= now)
+                .unwrap_or(true)
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn payload(installed_app_id: &str) -> IssueAppAuthenticationTokenPayload {
+        IssueAppAuthenticationTokenPayload::for_installed_app_id(installed_app_id.to_string())
+    }
+
+    #[test]
+    fn single_use_token_can_only_be_used_once() {
+        let mut store = AppAuthTokenStore::default();
+        let issued = store.issue_token(payload("app"));
+        assert!(issued.expires_at.is_some());
+
+        assert_eq!(
+            Ok("app".to_string()),
+            store.authenticate_token(&issued.token, None)
+        );
+        assert_eq!(
+            Err(AppAuthenticationError::InvalidToken),
+            store.authenticate_token(&issued.token, None)
+        );
+    }
+
+    #[test]
+    fn multi_use_token_can_be_revoked() {
+        let mut store = AppAuthTokenStore::default();
+        let issued = store.issue_token(payload("app").single_use(false).expiry_seconds(0));
+        assert_eq!(None, issued.expires_at);
+
+        for _ in 0..2 {
+            assert_eq!(
+                Ok("app".to_string()),
+                store.authenticate_token(&issued.token, None)
+            );
+        }
+
+        store.revoke_token(&issued.token);
+        assert_eq!(
+            Err(AppAuthenticationError::InvalidToken),
+            store.authenticate_token(&issued.token, None)
+        );
+    }
+
+    #[test]
+    fn token_must_match_the_app_of_the_interface() {
+        let mut store = AppAuthTokenStore::default();
+        let issued = store.issue_token(payload("app").single_use(false));
+
+        assert_eq!(
+            Err(AppAuthenticationError::AppMismatch {
+                token_app: "app".to_string(),
+                interface_app: "other app".to_string(),
+            }),
+            store.authenticate_token(&issued.token, Some(&"other app".to_string()))
+        );
+        assert_eq!(
+            Ok("app".to_string()),
+            store.authenticate_token(&issued.token, Some(&"app".to_string()))
+        );
+    }
+
+    #[test]
+    fn expired_token_is_rejected() {
+        let mut store = AppAuthTokenStore::default();
+        let issued = store.issue_token(payload("app"));
+        store.tokens.get_mut(&issued.token).unwrap().expires_at = Some(Timestamp::from_micros(0));
+
+        assert_eq!(
+            Err(AppAuthenticationError::ExpiredToken),
+            store.authenticate_token(&issued.token, None)
+        );
+    }
+}
@@ -35,6 +35,7 @@ use crate::core::queue_consumer::spawn_queue_consumer_tasks;
 use crate::core::queue_consumer::InitialQueueTriggers;
 use crate::core::queue_consumer::QueueTriggers;
 use crate::core::ribosome::guest_callback::init::InitResult;
+use crate::core::ribosome::guest_callback::migrate_agent::MigrateAgentResult;
 use crate::core::ribosome::real_ribosome::RealRibosome;
 use crate::core::ribosome::ZomeCallInvocation;
 use crate::core::workflow::call_zome_workflow;
@@ -42,6 +43,9 @@ use crate::core::workflow::countersigning_workflow::countersigning_success;
 use crate::core::workflow::countersigning_workflow::incoming_countersigning;
 use crate::core::workflow::genesis_workflow::genesis_workflow;
 use crate::core::workflow::initialize_zomes_workflow;
+use crate::core::workflow::migrate_agent_workflow::migrate_agent_callback;
+use crate::core::workflow::migrate_agent_workflow::migrate_agent_workflow;
+use crate::core::workflow::migrate_agent_workflow::MigrateAgentWorkflowArgs;
 use crate::core::workflow::CallZomeWorkflowArgs;
 use crate::core::workflow::GenesisWorkflowArgs;
 use crate::core::workflow::GenesisWorkspace;
@@ -395,6 +399,7 @@ impl Cell {
             | QueryAgentInfoSignedNearBasis { .. }
             | QueryPeerDensity { .. }
             | Publish { .. }
+            | PublishWarrants { .. }
             | FetchOpData { .. } => {
                 // These events are aggregated over a set of cells, so need to be handled at the conductor level.
                 unreachable!()
@@ -608,6 +613,7 @@ impl Cell {
                     &self.space.countersigning_workspace,
                     self.queue_triggers.countersigning.clone(),
                 )
+                .await
                 .map_err(Box::new)?;
                 Ok(())
             }
@@ -678,14 +684,30 @@ impl Cell {
             .map_err(Into::into)
     }
 
-    #[instrument(skip(self, _dht_hash, _options))]
+    #[instrument(skip(self, options))]
     /// a remote node is asking us for metadata
     async fn handle_get_meta(
         &self,
-        _dht_hash: holo_hash::AnyDhtHash,
-        _options: holochain_p2p::event::GetMetaOptions,
+        dht_hash: holo_hash::AnyDhtHash,
+        options: holochain_p2p::event::GetMetaOptions,
     ) -> CellResult<MetadataSet> {
-        unimplemented!()
+        use holochain_p2p::HolochainP2pDnaT;
+        debug!(id = ?self.id());
+        let db = self.space.dht_db.clone();
+        let is_authority = self
+            .holochain_p2p_dna()
+            .authority_for_hash(dht_hash.clone().into())
+            .await?;
+        let agent_info = crate::conductor::p2p_agent_store::get_single_agent_info(
+            self.space.p2p_agents_db.clone().into(),
+            self.dna_hash().clone(),
+            self.id.agent_pubkey().clone(),
+        )
+        .await?;
+        let mut meta = authority::handle_get_meta(db.into(), dht_hash, options).await?;
+        meta.is_authority = is_authority;
+        meta.storage_arc = agent_info.map(|info| info.storage_arc.inner());
+        Ok(meta)
     }
 
     #[instrument(skip(self, options))]
@@ -1012,6 +1034,60 @@ impl Cell {
         Ok(())
     }
 
+    async fn migrate_agent_workspace(
+        &self,
+        ribosome: &RealRibosome,
+    ) -> CellResult<SourceChainWorkspace> {
+        Ok(SourceChainWorkspace::new(
+            self.get_or_create_authored_db()?,
+            self.dht_db().clone(),
+            self.space.dht_query_cache.clone(),
+            self.cache().clone(),
+            self.conductor_api.keystore().clone(),
+            self.id.agent_pubkey().clone(),
+            Arc::new(ribosome.dna_def().as_content().clone()),
+        )
+        .await?)
+    }
+
+    /// Run the `migrate_agent` callback of this DNA for one half of migrating
+    /// its agent, without committing anything.
+    /// Fails if the callback fails.
+    pub(crate) async fn check_migrate_agent(&self, migrate_agent: MigrateAgent) -> CellResult<()> {
+        let ribosome = self.get_ribosome()?;
+        let workspace = self.migrate_agent_workspace(&ribosome).await?;
+        let result =
+            migrate_agent_callback(workspace, &ribosome, migrate_agent).map_err(Box::new)?;
+        trace!(?result);
+        match result {
+            MigrateAgentResult::Pass => Ok(()),
+            r => Err(CellError::MigrateAgentFailed(r)),
+        }
+    }
+
+    /// Close or open this cell's chain, as one half of migrating its agent
+    /// between this DNA and another one.
+    /// The `migrate_agent` callbacks must have been run with
+    /// [`Cell::check_migrate_agent`] first.
+    pub(crate) async fn migrate_agent(
+        &self,
+        migrate_agent: MigrateAgent,
+        other_dna_hash: DnaHash,
+    ) -> CellResult<()> {
+        let ribosome = self.get_ribosome()?;
+        let workspace = self.migrate_agent_workspace(&ribosome).await?;
+        let args = MigrateAgentWorkflowArgs::new(
+            ribosome,
+            self.conductor_handle.clone(),
+            migrate_agent,
+            other_dna_hash,
+        );
+        migrate_agent_workflow(workspace, self.holochain_p2p_cell.clone(), args)
+            .await
+            .map_err(Box::new)?;
+        Ok(())
+    }
+
     /// Clean up long-running managed tasks.
     pub async fn cleanup(&self) -> CellResult<()> {
         use holochain_p2p::HolochainP2pDnaT;
@@ -54,10 +54,12 @@ use holo_hash::DnaHash;
 use holochain_conductor_api::conductor::KeystoreConfig;
 use holochain_conductor_api::AppInfo;
 use holochain_conductor_api::AppStatusFilter;
+use holochain_conductor_api::CountersigningSessionState;
 use holochain_conductor_api::FullIntegrationStateDump;
 use holochain_conductor_api::FullStateDump;
 use holochain_conductor_api::IntegrationStateDump;
 use holochain_conductor_api::JsonDump;
+use holochain_conductor_api::ProvisionedCell;
 pub use holochain_conductor_services::*;
 use holochain_keystore::lair_keystore::spawn_lair_keystore;
 use holochain_keystore::lair_keystore::spawn_lair_keystore_in_proc;
@@ -98,6 +100,7 @@ use crate::{
 
 use super::api::RealAppInterfaceApi;
 use super::api::ZomeCall;
+use super::app_auth_token_store::AppAuthTokenStore;
 use super::config::AdminInterfaceConfig;
 use super::config::InterfaceDriver;
 use super::entry_def_store::get_entry_defs;
@@ -105,15 +108,17 @@ use super::error::ConductorError;
 use super::interface::error::InterfaceResult;
 use super::interface::websocket::spawn_admin_interface_tasks;
 use super::interface::websocket::spawn_app_interface_task;
-use super::interface::websocket::spawn_websocket_listener;
+use super::interface::websocket::spawn_listener;
 use super::interface::websocket::SIGNAL_BUFFER_SIZE;
 use super::interface::AppInterfaceRuntime;
 use super::interface::SignalBroadcaster;
+use super::interface::SignalSubscriptions;
 use super::manager::TaskManagerResult;
 use super::p2p_agent_store;
 use super::p2p_agent_store::P2pBatch;
 use super::p2p_agent_store::*;
 use super::ribosome_store::RibosomeStore;
+use super::signal_log::SignalLog;
 use super::space::Space;
 use super::space::Spaces;
 use super::state::AppInterfaceConfig;
@@ -126,10 +131,18 @@ pub use self::share::RwShare;
 
 mod builder;
 
+mod chain_archive;
+
 mod chc;
 
+mod countersigning;
+
+mod db_key;
+
 mod graft_records_onto_source_chain;
 
+mod zome_exports;
+
 /// How long we should attempt to achieve a "network join" when first activating a cell,
 /// before moving on and letting the network health activity go on in the background.
 ///
@@ -217,6 +230,12 @@ pub struct Conductor {
     /// Collection app interface data, keyed by id
     app_interfaces: RwShare<HashMap<AppInterfaceId, AppInterfaceRuntime>>,
 
+    /// The tokens which clients can use to authenticate to app interfaces
+    app_auth_token_store: RwShare<AppAuthTokenStore>,
+
+    /// The signals of every app, which app interface connections read from
+    signal_log: SignalLog,
+
     /// The interface to the task manager
     task_manager: TaskManagerClient,
 
@@ -256,8 +275,6 @@ impl Conductor {
 mod startup_shutdown_impls {
     use std::ops::Deref;
 
-    use kitsune_p2p_types::box_fut_plain;
-
     use crate::conductor::manager::{spawn_task_outcome_handler, OutcomeReceiver, OutcomeSender};
 
     use super::*;
@@ -281,6 +298,8 @@ mod startup_shutdown_impls {
                 .data_root_path
                 .clone()
                 .map(|path| PathBuf::from(path.deref()));
+            let signal_log =
+                SignalLog::new(config.conductor_tuning_params().signal_replay_buffer_size());
 
             Self {
                 spaces,
@@ -288,6 +307,8 @@ mod startup_shutdown_impls {
                 config,
                 shutting_down: Arc::new(AtomicBool::new(false)),
                 app_interfaces: RwShare::new(HashMap::new()),
+                app_auth_token_store: RwShare::new(AppAuthTokenStore::default()),
+                signal_log,
                 task_manager: TaskManagerClient::new(outcome_sender, tracing_scope),
                 // Must be initialized later, since it requires an Arc<Conductor>
                 outcomes_task: RwShare::new(None),
@@ -356,20 +377,7 @@ mod startup_shutdown_impls {
                 *lock = Some(task);
             });
 
-            self.services.share_mut(|services| {
-                let mut dpki = MockDpkiService::new();
-                dpki.expect_is_key_valid()
-                    .returning(|_, _| box_fut_plain(Ok(true)));
-                dpki.expect_key_mutation()
-                    .returning(|_, _| box_fut_plain(Ok(())));
-
-                let app_store = MockAppStoreService::new();
-
-                *services = Some(ConductorServices {
-                    dpki: Arc::new(dpki),
-                    app_store: Arc::new(app_store),
-                });
-            });
+            self.clone().initialize_services().await?;
 
             self.clone().add_admin_interfaces(admin_configs).await?;
 
@@ -388,17 +396,85 @@ mod startup_shutdown_impls {
 
             res
         }
+
+        /// Set up the conductor services.
+        ///
+        /// The built-in DPKI and app store services run in the cells of the apps
+        /// named in the config, once those apps are installed.
+        pub(crate) async fn initialize_services(self: Arc<Self>) -> ConductorResult<()> {
+            let dpki_cell = match &self.config.dpki {
+                Some(config) => self.service_cell_id(&config.instance_id).await?,
+                None => None,
+            };
+            let app_store_cell = match &self.config.app_store {
+                Some(config) => self.service_cell_id(&config.installed_app_id).await?,
+                None => None,
+            };
+            let services = ConductorServices::builtin(
+                Arc::new(ServiceCellRunner(Arc::downgrade(&self))),
+                self.keystore().clone(),
+                ConductorServiceCells {
+                    dpki: dpki_cell,
+                    app_store: app_store_cell,
+                },
+            );
+            self.services.share_mut(|s| *s = Some(services));
+            Ok(())
+        }
+
+        /// The cell which runs a conductor service, if its app is installed.
+        ///
+        /// A service app must have exactly one provisioned cell.
+        async fn service_cell_id(
+            &self,
+            installed_app_id: &InstalledAppId,
+        ) -> ConductorResult<Option<CellId>> {
+            let state = self.get_state().await?;
+            let app = match state.installed_apps().get(installed_app_id) {
+                Some(app) => app,
+                None => {
+                    warn!(%installed_app_id, "The app of a conductor service is not installed");
+                    return Ok(None);
+                }
+            };
+            let mut cells = app.provisioned_cells().map(|(_, cell_id)| cell_id.clone());
+            match (cells.next(), cells.next()) {
+                (Some(cell_id), None) => Ok(Some(cell_id)),
+                _ => Err(ConductorError::other(format!(
+                    "The app {installed_app_id} of a conductor service must have exactly one provisioned cell"
+                ))),
+            }
+        }
+
+        /// Whether the config names this app as the app of a conductor service
+        pub(crate) fn is_service_app(&self, installed_app_id: &InstalledAppId) -> bool {
+            self.config
+                .dpki
+                .as_ref()
+                .map_or(false, |config| config.instance_id == *installed_app_id)
+                || self
+                    .config
+                    .app_store
+                    .as_ref()
+                    .map_or(false, |config| config.installed_app_id == *installed_app_id)
+        }
     }
 }
 
 /// Methods related to conductor interfaces
 mod interface_impls {
     use super::*;
+    use holochain_conductor_api::signal_subscription::SignalSubscription;
+    use holochain_conductor_api::AppAuthenticationToken;
+    use holochain_conductor_api::AppAuthenticationTokenIssued;
+    use holochain_conductor_api::IssueAppAuthenticationTokenPayload;
     use holochain_types::websocket::AllowedOrigins;
 
     impl Conductor {
         /// Spawn all admin interface tasks, register them with the TaskManager,
-        /// and modify the conductor accordingly, based on the config passed in
+        /// and modify the conductor accordingly, based on the config passed in.
+        /// Returns the ports of the websocket interfaces. Unix socket interfaces
+        /// have no port.
         pub async fn add_admin_interfaces(
             self: Arc<Self>,
             configs: Vec<AdminInterfaceConfig>,
@@ -411,23 +487,14 @@ mod interface_impls {
                 let admin_api = admin_api.clone();
                 let tm = tm.clone();
                 async move {
-                    match driver {
-                        InterfaceDriver::Websocket {
-                            port,
-                            allowed_origins,
-                        } => {
-                            let listener = spawn_websocket_listener(port, allowed_origins).await?;
-                            let port = listener.local_addr()?.port();
-                            spawn_admin_interface_tasks(
-                                tm.clone(),
-                                listener,
-                                admin_api.clone(),
-                                port,
-                            );
+                    let listener = spawn_listener(&driver).await?;
+                    let port = match driver {
+                        InterfaceDriver::Websocket { .. } => Some(listener.local_addr()?.port()),
+                        InterfaceDriver::UnixSocket { .. } => None,
+                    };
+                    spawn_admin_interface_tasks(tm.clone(), listener, admin_api.clone());
 
-                            InterfaceResult::Ok(port)
-                        }
-                    }
+                    InterfaceResult::Ok(port)
                 }
             };
 
@@ -439,7 +506,7 @@ mod interface_impls {
                     .into_iter()
                     .collect();
             // Exit if the admin interfaces fail to be created
-            let ports = ports.map_err(Box::new)?;
+            let ports: Vec<u16> = ports.map_err(Box::new)?.into_iter().flatten().collect();
 
             for p in &ports {
                 self.add_admin_port(*p);
@@ -451,36 +518,114 @@ mod interface_impls {
         /// Spawn a new app interface task, register it with the TaskManager,
         /// and modify the conductor accordingly, based on the config passed in
         /// which is just a networking port number (or 0 to auto-select one).
+        /// If an `installed_app_id` is given, the interface only accepts clients
+        /// authenticated for that app.
         /// Returns the given or auto-chosen port number if giving an Ok Result
         pub async fn add_app_interface(
             self: Arc<Self>,
             port: either::Either<u16, AppInterfaceId>,
             allowed_origins: AllowedOrigins,
+            installed_app_id: Option<InstalledAppId>,
         ) -> ConductorResult<u16> {
             let interface_id = match port {
                 either::Either::Left(port) => AppInterfaceId::new(port),
                 either::Either::Right(id) => id,
             };
-            let port = interface_id.port();
-            tracing::debug!("Attaching interface {}", port);
-            let app_api = RealAppInterfaceApi::new(self.clone());
+            let driver = InterfaceDriver::Websocket {
+                port: interface_id.port(),
+                allowed_origins,
+                bind_address: None,
+                tls: None,
+            };
+            let driver = self
+                .spawn_app_interface(interface_id, driver, installed_app_id)
+                .await?;
+            Ok(driver
+                .port()
+                .expect("A websocket interface always has a port"))
+        }
+
+        /// Spawn a new app interface task with the given driver, register it with
+        /// the TaskManager, and modify the conductor accordingly.
+        /// If an `installed_app_id` is given, the interface only accepts clients
+        /// authenticated for that app.
+        /// Returns the driver, with the port filled in if it was chosen by the OS.
+        pub async fn add_app_interface_with_driver(
+            self: Arc<Self>,
+            driver: InterfaceDriver,
+            installed_app_id: Option<InstalledAppId>,
+        ) -> ConductorResult<InterfaceDriver> {
+            let interface_id = AppInterfaceId::new(driver.port().unwrap_or(0));
+            self.spawn_app_interface(interface_id, driver, installed_app_id)
+                .await
+        }
+
+        /// Spawn a new app interface task on a unix domain socket at the given path,
+        /// register it with the TaskManager, and modify the conductor accordingly.
+        /// If a `mode` is given, the permissions of the socket file are set to it.
+        /// If an `installed_app_id` is given, the interface only accepts clients
+        /// authenticated for that app.
+        pub async fn add_unix_socket_app_interface(
+            self: Arc<Self>,
+            path: PathBuf,
+            mode: Option<u32>,
+            installed_app_id: Option<InstalledAppId>,
+        ) -> ConductorResult<()> {
+            let driver = InterfaceDriver::UnixSocket { path, mode };
+            self.spawn_app_interface(AppInterfaceId::default(), driver, installed_app_id)
+                .await?;
+            Ok(())
+        }
+
+        /// Spawn an app interface with the given driver and persist its config.
+        /// Returns the driver, with the port filled in if it was chosen by the OS.
+        async fn spawn_app_interface(
+            self: Arc<Self>,
+            interface_id: AppInterfaceId,
+            driver: InterfaceDriver,
+            installed_app_id: Option<InstalledAppId>,
+        ) -> ConductorResult<InterfaceDriver> {
+            tracing::debug!("Attaching interface {:?}", driver);
+            let app_api =
+                RealAppInterfaceApi::new_for_interface(self.clone(), interface_id.clone());
             // This receiver is thrown away because we can produce infinite new
             // receivers from the Sender
             let (signal_tx, _r) = tokio::sync::broadcast::channel(SIGNAL_BUFFER_SIZE);
 
+            // Restore the signal subscriptions of a previously configured interface
+            let state = self.get_state().await?;
+            let persisted_subscriptions = state
+                .interface_by_id(&interface_id)
+                .map(|config| config.signal_subscriptions)
+                .unwrap_or_default();
+            let signal_subscriptions = SignalSubscriptions::default();
+            for (installed_app_id, subscription) in persisted_subscriptions.iter() {
+                match state.get_app(installed_app_id) {
+                    Ok(_) => signal_subscriptions
+                        .set(installed_app_id.clone(), subscription.filters().clone()),
+                    Err(err) => {
+                        tracing::warn!(?err, "Ignoring signal subscription of a missing app")
+                    }
+                }
+            }
+
             let tm = self.task_manager();
 
             // TODO: RELIABILITY: Handle this task by restarting it if it fails and log the error
-            let port = spawn_app_interface_task(
+            let driver = spawn_app_interface_task(
                 tm.clone(),
-                port,
-                allowed_origins.clone(),
+                driver,
+                installed_app_id.clone(),
                 app_api,
-                signal_tx.clone(),
+                self.signal_log.clone(),
+                signal_subscriptions.clone(),
             )
             .await
             .map_err(Box::new)?;
-            let interface = AppInterfaceRuntime::Websocket { signal_tx };
+            let interface = AppInterfaceRuntime::Websocket {
+                signal_tx,
+                signal_subscriptions,
+            };
 
             self.app_interfaces.share_mut(|app_interfaces| {
                 if app_interfaces.contains_key(&interface_id) {
@@ -492,14 +637,18 @@ mod interface_impls {
                 app_interfaces.insert(interface_id.clone(), interface);
                 Ok(())
             })?;
-            let config = AppInterfaceConfig::websocket(port, allowed_origins);
+            let config = AppInterfaceConfig {
+                signal_subscriptions: persisted_subscriptions,
+                driver: driver.clone(),
+                installed_app_id,
+            };
             self.update_state(|mut state| {
                 state.app_interfaces.insert(interface_id, config);
                 Ok(state)
             })
             .await?;
-            tracing::debug!("App interface added at port: {}", port);
-            Ok(port)
+            tracing::debug!("App interface added: {:?}", driver);
+            Ok(driver)
         }
 
         /// Returns a port which is guaranteed to have a websocket listener with an Admin interface
@@ -508,29 +657,136 @@ mod interface_impls {
             self.admin_websocket_ports.share_ref(|p| p.first().copied())
         }
 
-        /// Give a list of networking ports taken up as running app interface tasks
+        /// Give a list of networking ports taken up as running app interface tasks.
+        /// App interfaces on unix domain sockets are not included.
         pub async fn list_app_interfaces(&self) -> ConductorResult<Vec<u16>> {
             Ok(self
                 .get_state()
                 .await?
                 .app_interfaces
                 .values()
-                .map(|config| config.driver.port())
+                .filter_map(|config| config.driver.port())
                 .collect())
         }
 
+        /// Issue a token which a client can use to authenticate to an app interface
+        /// on behalf of the app in the payload.
+        pub async fn issue_app_authentication_token(
+            &self,
+            payload: IssueAppAuthenticationTokenPayload,
+        ) -> ConductorResult<AppAuthenticationTokenIssued> {
+            // Tokens can only be issued for installed apps
+            self.get_state().await?.get_app(&payload.installed_app_id)?;
+            Ok(self
+                .app_auth_token_store
+                .share_mut(|store| store.issue_token(payload)))
+        }
+
+        /// Revoke an app authentication token, so that it can no longer be used.
+        pub fn revoke_app_authentication_token(&self, token: &AppAuthenticationToken) {
+            self.app_auth_token_store
+                .share_mut(|store| store.revoke_token(token));
+        }
+
+        /// Check an app authentication token, returning the app it grants access to.
+        ///
+        /// If the app interface is bound to an app, `interface_app` must be that app.
+        pub fn authenticate_app_token(
+            &self,
+            token: &AppAuthenticationToken,
+            interface_app: Option<&InstalledAppId>,
+        ) -> ConductorResult<InstalledAppId> {
+            Ok(self
+                .app_auth_token_store
+                .share_mut(|store| store.authenticate_token(token, interface_app))?)
+        }
+
+        /// Set the signal subscription of an app on the given app interface,
+        /// or on all app interfaces if none is given.
+        pub async fn set_signal_subscription(
+            &self,
+            interface_id: Option<AppInterfaceId>,
+            subscription: SignalSubscription,
+        ) -> ConductorResult<()> {
+            self.get_state()
+                .await?
+                .get_app(subscription.installed_app_id())?;
+            let interface_ids: Vec<AppInterfaceId> = match interface_id {
+                Some(id) => vec![id],
+                None => self
+                    .app_interfaces
+                    .share_ref(|app_interfaces| app_interfaces.keys().cloned().collect()),
+            };
+
+            self.update_state({
+                let interface_ids = interface_ids.clone();
+                let subscription = subscription.clone();
+                move |mut state| {
+                    for id in interface_ids.iter() {
+                        if let Some(config) = state.app_interfaces.get_mut(id) {
+                            config.signal_subscriptions.insert(
+                                subscription.installed_app_id().clone(),
+                                subscription.clone(),
+                            );
+                        }
+                    }
+                    Ok(state)
+                }
+            })
+            .await?;
+
+            let (installed_app_id, filters) = subscription.into_inner();
+            self.app_interfaces.share_ref(|app_interfaces| {
+                for id in interface_ids.iter() {
+                    if let Some(interface) = app_interfaces.get(id) {
+                        interface
+                            .signal_subscriptions()
+                            .set(installed_app_id.clone(), filters.clone());
+                    }
+                }
+            });
+            Ok(())
+        }
+
+        /// Remove the signal subscriptions of an app from all app interfaces.
+        pub(crate) async fn remove_signal_subscriptions(
+            &self,
+            installed_app_id: &InstalledAppId,
+        ) -> ConductorResult<()> {
+            self.update_state({
+                let installed_app_id = installed_app_id.clone();
+                move |mut state| {
+                    for config in state.app_interfaces.values_mut() {
+                        config.signal_subscriptions.remove(&installed_app_id);
+                    }
+                    Ok(state)
+                }
+            })
+            .await?;
+            self.app_interfaces.share_ref(|app_interfaces| {
+                for interface in app_interfaces.values() {
+                    interface.signal_subscriptions().remove(installed_app_id);
+                }
+            });
+            Ok(())
+        }
+
         /// Start all app interfaces currently in state.
         /// This should only be run at conductor initialization.
-        #[allow(irrefutable_let_patterns)]
         pub(crate) async fn startup_app_interfaces(self: Arc<Self>) -> ConductorResult<()> {
-            for (id, config) in &self.get_state().await?.app_interfaces {
+            let state = self.get_state().await?;
+            self.signal_log.refresh_apps(&state);
+            for (id, config) in &state.app_interfaces {
                 debug!("Starting up app interface: {:?}", id);
+                let mut driver = config.driver.clone();
+                // Bind to the port that was originally asked for,
+                // so an OS assigned port is assigned anew.
+                if let InterfaceDriver::Websocket { port, .. } = &mut driver {
+                    *port = id.port();
+                }
                 let _ = self
                     .clone()
-                    .add_app_interface(
-                        either::Right(id.clone()),
-                        config.driver.allowed_origins().clone(),
-                    )
+                    .spawn_app_interface(id.clone(), driver, config.installed_app_id.clone())
                     .await?;
             }
             Ok(())
@@ -570,7 +826,10 @@ mod dna_impls {
             app: &InstalledApp,
         ) -> ConductorResult<HashMap<CellId, DnaDefHashed>> {
             let mut dna_defs = HashMap::new();
-            for cell_id in app.all_cells() {
+            for cell_id in app
+                .all_cells()
+                .chain(app.deferred_cells().map(|(_, cell_id)| cell_id))
+            {
                 let ribosome = self.get_ribosome(cell_id.dna_hash())?;
                 let dna_def = ribosome.dna_def();
                 dna_defs.insert(cell_id.to_owned(), dna_def.to_owned());
@@ -805,11 +1064,16 @@ mod network_impls {
     use holochain_conductor_api::{DnaStorageInfo, NetworkInfo, StorageBlob, StorageInfo};
     use holochain_p2p::HolochainP2pSender;
     use holochain_sqlite::stats::{get_size_on_disk, get_used_size};
+    use holochain_types::warrant::SignedWarrantExt;
     use holochain_zome_types::block::Block;
+    use holochain_zome_types::block::BlockTarget;
     use holochain_zome_types::block::BlockTargetId;
+    use holochain_zome_types::block::CellBlockReason;
     use kitsune_p2p::KitsuneAgent;
     use kitsune_p2p::KitsuneBinType;
 
+    use crate::core::workflow::app_validation_workflow::AppValidationWorkspace;
+
     use crate::conductor::api::error::{
         zome_call_response_to_conductor_api_result, ConductorApiError,
     };
@@ -869,6 +1133,11 @@ mod network_impls {
             self.spaces.unblock(input).await
         }
 
+        /// List all blocks which have not ended yet.
+        pub async fn list_blocks(&self) -> DatabaseResult<Vec<BlockSpan>> {
+            self.spaces.list_blocks(Timestamp::now()).await
+        }
+
         /// Check if some target is blocked.
         pub async fn is_blocked(
             &self,
@@ -878,6 +1147,162 @@ mod network_impls {
             self.spaces.is_blocked(input, timestamp).await
         }
 
+        /// Store warrants which were published to us and block their warrantees.
+        ///
+        /// Warrants with invalid signatures, or whose proof does not hold when
+        /// checked against the warranted actions, are dropped. Cells running on
+        /// this conductor are never blocked, even if they have been warranted.
+        pub(crate) async fn handle_publish_warrants(
+            &self,
+            dna_hash: &DnaHash,
+            warrants: Vec<SignedWarrant>,
+        ) -> ConductorResult<()> {
+            let mut valid = Vec::with_capacity(warrants.len());
+            for warrant in warrants {
+                if let Err(e) = warrant.validate().await {
+                    warn!(?e, "Dropping warrant with an invalid signature");
+                    continue;
+                }
+                match self
+                    .verify_warrant_proof(dna_hash, &warrant.warrant.proof)
+                    .await
+                {
+                    Ok(true) => valid.push(warrant),
+                    Ok(false) => warn!(?warrant, "Dropping warrant whose proof does not hold"),
+                    Err(e) => warn!(?e, "Dropping warrant whose proof could not be checked"),
+                }
+            }
+            self.store_warrants_and_block(dna_hash, valid).await
+        }
+
+        /// Check the proof of a warrant against the actions it refers to,
+        /// using a running cell of the DNA to fetch them.
+        ///
+        /// A proof can't hold if no cell of the DNA is running.
+        async fn verify_warrant_proof(
+            &self,
+            dna_hash: &DnaHash,
+            proof: &WarrantProof,
+        ) -> ConductorResult<bool> {
+            let cell_id = match self
+                .running_cell_ids()
+                .into_iter()
+                .find(|cell_id| cell_id.dna_hash() == dna_hash)
+            {
+                Some(cell_id) => cell_id,
+                None => return Ok(false),
+            };
+            let cell = self.cell_by_id(&cell_id).await?;
+            let dna_def = self
+                .get_dna_def(dna_hash)
+                .ok_or_else(|| DnaError::DnaMissing(dna_hash.clone()))?;
+            let workspace = AppValidationWorkspace::new(
+                self.get_or_create_authored_db(dna_hash, cell_id.agent_pubkey().clone())?
+                    .into(),
+                self.get_or_create_dht_db(dna_hash)?,
+                self.get_or_create_space(dna_hash)?.dht_query_cache,
+                cell.cache().clone(),
+                self.keystore().clone(),
+                Arc::new(dna_def),
+            );
+            let ribosome = self.get_ribosome(dna_hash)?;
+            Ok(crate::core::warrant::verify_warrant_proof(
+                proof,
+                &workspace,
+                cell.holochain_p2p_dna(),
+                &ribosome,
+                self,
+            )
+            .await?)
+        }
+
+        /// Store warrants and block their warrantees, unless the warrantee is a local cell.
+        async fn store_warrants_and_block(
+            &self,
+            dna_hash: &DnaHash,
+            valid: Vec<SignedWarrant>,
+        ) -> ConductorResult<()> {
+            if valid.is_empty() {
+                return Ok(());
+            }
+
+            let db = self.spaces.dht_db(dna_hash)?;
+            db.write_async({
+                let valid = valid.clone();
+                move |txn| -> StateMutationResult<()> {
+                    for warrant in valid {
+                        add_warrant_if_unique(txn, warrant)?;
+                    }
+                    Ok(())
+                }
+            })
+            .await?;
+
+            let interval = InclusiveTimestampInterval::try_new(Timestamp::MIN, Timestamp::MAX)
+                .map_err(ConductorError::other)?;
+            let local_cells = self.running_cell_ids();
+            for warrant in valid {
+                let cell_id = CellId::new(dna_hash.clone(), warrant.warrantee().clone());
+                if local_cells.contains(&cell_id) {
+                    continue;
+                }
+                self.block(Block::new(
+                    BlockTarget::Cell(cell_id, CellBlockReason::Warrant(warrant.warrant.proof)),
+                    interval.clone(),
+                ))
+                .await?;
+            }
+            Ok(())
+        }
+
+        /// Sign warrants for the given proofs on behalf of a cell of this DNA,
+        /// store them locally and publish them to the authorities of each warrantee.
+        ///
+        /// Returns the issued warrants, which is empty if no cell of this DNA is running.
+        pub(crate) async fn issue_warrants(
+            &self,
+            dna_hash: &DnaHash,
+            proofs: Vec<WarrantProof>,
+        ) -> ConductorResult<Vec<SignedWarrant>> {
+            if proofs.is_empty() {
+                return Ok(Vec::new());
+            }
+            let author = match self
+                .running_cell_ids()
+                .into_iter()
+                .find(|cell_id| cell_id.dna_hash() == dna_hash)
+            {
+                Some(cell_id) => cell_id.agent_pubkey().clone(),
+                None => return Ok(Vec::new()),
+            };
+
+            let mut warrants = Vec::with_capacity(proofs.len());
+            for proof in proofs {
+                let warrant = Warrant::new(proof, author.clone(), Timestamp::now());
+                warrants.push(SignedWarrant::sign(self.keystore(), warrant).await?);
+            }
+
+            // Our own validation produced these proofs, so they aren't checked again.
+            self.store_warrants_and_block(dna_hash, warrants.clone())
+                .await?;
+
+            let network = self.holochain_p2p.to_dna(dna_hash.clone(), None);
+            let mut by_warrantee: HashMap<AgentPubKey, Vec<SignedWarrant>> = HashMap::new();
+            for warrant in warrants.iter() {
+                by_warrantee
+                    .entry(warrant.warrantee().clone())
+                    .or_default()
+                    .push(warrant.clone());
+            }
+            for (warrantee, warrants) in by_warrantee {
+                if let Err(e) = network.publish_warrants(warrantee.into(), warrants).await {
+                    warn!(?e, "Failed to publish warrants");
+                }
+            }
+
+            Ok(warrants)
+        }
+
         pub(crate) async fn prune_p2p_agents_db(&self) -> ConductorResult<()> {
             use holochain_p2p::AgentPubKeyExt;
 
@@ -1264,6 +1689,22 @@ mod network_impls {
                     .instrument(debug_span!("handle_publish"))
                     .await;
                 }
+                PublishWarrants {
+                    dna_hash,
+                    respond,
+                    warrants,
+                    ..
+                } => {
+                    async {
+                        let res = self
+                            .handle_publish_warrants(&dna_hash, warrants)
+                            .await
+                            .map_err(holochain_p2p::HolochainP2pError::other);
+                        respond.respond(Ok(async move { res }.boxed().into()));
+                    }
+                    .instrument(debug_span!("handle_publish_warrants"))
+                    .await;
+                }
                 FetchOpData {
                     respond,
                     query,
@@ -1365,6 +1806,101 @@ mod network_impls {
     }
 }
 
+/// Methods related to agent keys and DPKI
+mod dpki_impls {
+    use super::*;
+
+    impl Conductor {
+        /// Generate a new agent key in the keystore and register it with DPKI, if there is one
+        pub async fn generate_agent_pub_key(&self) -> ConductorResult<AgentPubKey> {
+            let agent_key = self.keystore.new_sign_keypair_random().await?;
+            if let Some(dpki) = self.conductor_services()?.dpki {
+                dpki.register_key(agent_key.clone()).await?;
+            }
+            Ok(agent_key)
+        }
+
+        /// Replace an agent key with a newly generated one in DPKI, and return the new key.
+        ///
+        /// Actions authored with the old key after the rotation will fail validation,
+        /// so all apps installed with the old key are disabled.
+        pub async fn rotate_agent_key(
+            self: Arc<Self>,
+            agent_key: AgentPubKey,
+        ) -> ConductorResult<AgentPubKey> {
+            let dpki = self
+                .conductor_services()?
+                .dpki
+                .ok_or(ConductorError::NoDpki)?;
+            let new_key = self.keystore.new_sign_keypair_random().await?;
+            dpki.update_key(agent_key.clone(), new_key.clone()).await?;
+            self.disable_apps_of_invalid_key(agent_key, "rotated")
+                .await?;
+            Ok(new_key)
+        }
+
+        /// Revoke an agent key in DPKI, without replacing it.
+        ///
+        /// Actions authored with the key after the revocation will fail validation,
+        /// so all apps installed with the key are disabled.
+        pub async fn revoke_agent_key(
+            self: Arc<Self>,
+            agent_key: AgentPubKey,
+        ) -> ConductorResult<()> {
+            self.conductor_services()?
+                .dpki
+                .ok_or(ConductorError::NoDpki)?
+                .remove_key(agent_key.clone())
+                .await?;
+            self.disable_apps_of_invalid_key(agent_key, "revoked").await
+        }
+
+        /// Register a key with DPKI, if there is one, unless DPKI already knows it.
+        ///
+        /// A key which was replaced or revoked can't be used again.
+        pub(crate) async fn register_agent_key_if_unknown(
+            &self,
+            agent_key: &AgentPubKey,
+        ) -> ConductorResult<()> {
+            let dpki = match self.conductor_services()?.dpki {
+                Some(dpki) => dpki,
+                None => return Ok(()),
+            };
+            match dpki.key_state(agent_key.clone(), Timestamp::now()).await? {
+                KeyState::Valid => Ok(()),
+                KeyState::NotFound => Ok(dpki.register_key(agent_key.clone()).await?),
+                KeyState::Invalidated => {
+                    Err(ConductorError::AgentKeyInvalidated(agent_key.clone()))
+                }
+            }
+        }
+
+        async fn disable_apps_of_invalid_key(
+            self: Arc<Self>,
+            agent_key: AgentPubKey,
+            what: &str,
+        ) -> ConductorResult<()> {
+            let app_ids: Vec<InstalledAppId> = self
+                .get_state()
+                .await?
+                .installed_apps()
+                .iter()
+                .filter(|(_, app)| *app.agent_key() == agent_key && app.status().is_enabled())
+                .map(|(id, _)| id.clone())
+                .collect();
+            for app_id in app_ids {
+                self.clone()
+                    .disable_app(
+                        app_id,
+                        DisabledAppReason::Error(format!("Agent key {agent_key} was {what}")),
+                    )
+                    .await?;
+            }
+            Ok(())
+        }
+    }
+}
+
 /// Methods related to app installation and management
 mod app_impls {
     use super::*;
@@ -1392,6 +1928,22 @@ mod app_impls {
             Ok(())
         }
 
+        /// Fetch an app bundle from the app store service and install it
+        pub async fn install_app_from_store(
+            self: Arc<Self>,
+            payload: InstallAppFromStorePayload,
+        ) -> ConductorResult<StoppedApp> {
+            let bundle = self
+                .conductor_services()?
+                .app_store
+                .ok_or(ConductorError::NoAppStore)?
+                .get_app_bundle(payload.app_hash.clone())
+                .await?
+                .ok_or_else(|| ConductorError::AppNotInStore(payload.app_hash.clone()))?;
+            self.install_app_bundle(payload.into_install_app_payload(bundle))
+                .await
+        }
+
         /// Install DNAs and set up Cells as specified by an AppBundle
         pub async fn install_app_bundle(
             self: Arc<Self>,
@@ -1411,6 +1963,8 @@ mod app_impls {
                 ..
             } = payload;
 
+            self.register_agent_key_if_unknown(&agent_key).await?;
+
             let bundle = {
                 let original_bundle = source.resolve().await?;
                 if let Some(network_seed) = network_seed {
@@ -1430,14 +1984,24 @@ mod app_impls {
             let local_dnas = self
                 .ribosome_store()
                 .share_ref(|store| bundle.get_all_dnas_from_store(store));
+            let state = self.get_state().await?;
+            let existing_cells: HashSet<CellId> = state
+                .installed_apps()
+                .values()
+                .flat_map(|app| app.provisioned_cells().map(|(_, cell_id)| cell_id.clone()))
+                .collect();
             let ops = bundle
-                .resolve_cells(&local_dnas, agent_key.clone(), membrane_proofs)
+                .resolve_cells(
+                    &local_dnas,
+                    agent_key.clone(),
+                    membrane_proofs,
+                    &existing_cells,
+                )
                 .await?;
 
             let cells_to_create = ops.cells_to_create();
 
             // check if cells_to_create contains a cell identical to an existing one
-            let state = self.get_state().await?;
             let all_cells: HashSet<_> = state
                 .installed_apps()
                 .values()
@@ -1470,11 +2034,26 @@ mod app_impls {
 
                 // Update the db
                 let stopped_app = self.add_disabled_app_to_db(app).await?;
+                if self.is_service_app(stopped_app.id()) {
+                    self.clone().initialize_services().await?;
+                }
 
                 // Return the result, which be may an error if no_rollback was specified
                 genesis_result.map(|()| stopped_app)
             } else if let Err(err) = genesis_result {
-                // Rollback created cells on error
+                // Rollback created cells on error. The app was never added, so only
+                // it is undone: a cell which another app has been installed with in
+                // the meantime belongs to that app now and is left alone.
+                let state = self.get_state().await?;
+                let cells_in_use: HashSet<&CellId> = state
+                    .installed_apps()
+                    .values()
+                    .flat_map(|app| app.all_cells())
+                    .collect();
+                let cell_ids: Vec<_> = cell_ids
+                    .into_iter()
+                    .filter(|cell_id| !cells_in_use.contains(cell_id))
+                    .collect();
                 self.remove_cells(&cell_ids).await;
                 Err(err)
             } else {
@@ -1482,15 +2061,130 @@ mod app_impls {
             }
         }
 
+        /// Instantiate the cell of a role whose provisioning was deferred at installation.
+        ///
+        /// Genesis is run for the cell with the given membrane proof. If the app
+        /// is running, the new cell is started too.
+        pub async fn provision_deferred_cell(
+            self: Arc<Self>,
+            payload: ProvisionDeferredCellPayload,
+        ) -> ConductorResult<ProvisionedCell> {
+            let ProvisionDeferredCellPayload {
+                app_id,
+                role_name,
+                membrane_proof,
+            } = payload;
+
+            // Checking the role and claiming it happen in one state update,
+            // so that concurrent calls can't both run genesis for the cell.
+            let (_, cell_id) = self
+                .update_state_prime({
+                    let app_id = app_id.clone();
+                    let role_name = role_name.clone();
+                    move |mut state| {
+                        let role = state.get_app(&app_id)?.role(&role_name)?;
+                        if !role.is_deferred() {
+                            return Err(AppError::RoleNotDeferred(role_name).into());
+                        }
+                        let cell_id = role.cell_id().clone();
+
+                        // the cell may have been created by another app since this one was installed
+                        if state
+                            .installed_apps()
+                            .values()
+                            .flat_map(|app| app.all_cells())
+                            .any(|c| *c == cell_id)
+                        {
+                            return Err(ConductorError::CellAlreadyExists(cell_id));
+                        }
+
+                        state
+                            .get_app_mut(&app_id)?
+                            .claim_deferred_cell(&role_name)?;
+                        Ok((state, cell_id))
+                    }
+                })
+                .await?;
+
+            if let Err(e) = crate::conductor::conductor::genesis_cells(
+                self.clone(),
+                vec![(cell_id.clone(), membrane_proof)],
+            )
+            .await
+            {
+                self.update_state({
+                    let app_id = app_id.clone();
+                    move |mut state| {
+                        state
+                            .get_app_mut(&app_id)?
+                            .release_deferred_cell(&role_name)?;
+                        Ok(state)
+                    }
+                })
+                .await?;
+                return Err(e);
+            }
+
+            self.update_state({
+                let app_id = app_id.clone();
+                move |mut state| {
+                    state
+                        .get_app_mut(&app_id)?
+                        .provision_deferred_cell(&role_name)?;
+                    Ok(state)
+                }
+            })
+            .await?;
+
+            self.create_and_add_initialized_cells_for_running_apps(Some(&app_id))
+                .await?;
+
+            let dna_def = self.get_ribosome(cell_id.dna_hash())?.dna_def().clone();
+            Ok(ProvisionedCell {
+                cell_id,
+                dna_modifiers: dna_def.modifiers.clone(),
+                name: dna_def.name.clone(),
+            })
+        }
+
         /// Uninstall an app
+        ///
+        /// If other apps use cells created by this app, the uninstallation is refused,
+        /// unless `cascade` is set, in which case those apps are uninstalled too.
         #[tracing::instrument(skip(self))]
         pub async fn uninstall_app(
             self: Arc<Self>,
             installed_app_id: &InstalledAppId,
+            cascade: bool,
         ) -> ConductorResult<()> {
             let self_clone = self.clone();
-            let app = self.remove_app_from_db(installed_app_id).await?;
-            tracing::debug!(msg = "Removed app from db.", app = ?app);
+            let state = self.get_state().await?;
+            let dependents = state.dependent_apps(installed_app_id)?;
+            if !dependents.is_empty() && !cascade {
+                return Err(ConductorError::AppHasDependents(
+                    installed_app_id.clone(),
+                    dependents,
+                ));
+            }
+
+            let mut to_uninstall = vec![installed_app_id.clone()];
+            to_uninstall.extend(state.all_dependent_apps(installed_app_id)?);
+
+            // Dependents are removed first, so no app is ever left using a removed app's cells.
+            for app_id in to_uninstall.iter().rev() {
+                let app = self.remove_app_from_db(app_id).await?;
+                tracing::debug!(msg = "Removed app from db.", app = ?app);
+                self.remove_signal_subscriptions(app_id).await?;
+                self.app_auth_token_store
+                    .share_mut(|store| store.revoke_tokens_for_app(app_id));
+            }
+
+            if to_uninstall
+                .iter()
+                .any(|app_id| self.is_service_app(app_id))
+            {
+                self.clone().initialize_services().await?;
+            }
 
             // Remove cells which may now be dangling due to the removed app
             self_clone
@@ -1533,7 +2227,8 @@ mod app_impls {
             Ok(app_infos)
         }
 
-        /// Get the IDs of all active installed Apps which use this Cell
+        /// Get the IDs of all active installed Apps which use this Cell,
+        /// including the apps which use it as an existing cell of another app
         pub async fn list_running_apps_for_dependent_cell_id(
             &self,
             cell_id: &CellId,
@@ -1649,6 +2344,105 @@ mod cell_impls {
     }
 }
 
+/// Methods related to migrating agents between DNAs
+mod migration_impls {
+    use super::*;
+
+    impl Conductor {
+        /// Migrate the agent of a cell to a new DNA, on which the agent must
+        /// already have a running cell.
+        ///
+        /// The chain of the old cell is closed with a `CloseChain` action pointing
+        /// to the new DNA, and then the chain of the new cell is opened with an
+        /// `OpenChain` action pointing to the old DNA, after which the new cell can
+        /// read the records of the old chain with `query_previous_chain`.
+        /// The `migrate_agent` callbacks of both DNAs are run before either
+        /// action is committed, so either of them can abort the migration and
+        /// leave the old chain open.
+        ///
+        /// If the migration was interrupted after closing the old chain, calling
+        /// this again completes it.
+        pub async fn migrate_agent(
+            &self,
+            old_cell_id: CellId,
+            new_dna_hash: DnaHash,
+        ) -> ConductorResult<()> {
+            let old_dna_hash = old_cell_id.dna_hash().clone();
+            if old_dna_hash == new_dna_hash {
+                return Err(ConductorError::other(
+                    "Can't migrate an agent to the DNA it is already on",
+                ));
+            }
+            let new_cell_id = CellId::new(new_dna_hash.clone(), old_cell_id.agent_pubkey().clone());
+            let old_cell = self.cell_by_id(&old_cell_id).await?;
+            let new_cell = self.cell_by_id(&new_cell_id).await?;
+
+            let close = self
+                .query_source_chain(
+                    &old_cell_id,
+                    ChainQueryFilter::new().action_type(ActionType::CloseChain),
+                )
+                .await?;
+            let close_old = match close.first().map(|r| r.action()) {
+                None => true,
+                Some(Action::CloseChain(c)) if c.new_dna_hash == new_dna_hash => false,
+                Some(_) => return Err(ConductorError::ChainAlreadyMigrated(old_cell_id)),
+            };
+
+            let open = self
+                .query_source_chain(
+                    &new_cell_id,
+                    ChainQueryFilter::new().action_type(ActionType::OpenChain),
+                )
+                .await?;
+            let open_new = match open.first().map(|r| r.action()) {
+                None => true,
+                Some(Action::OpenChain(o)) if o.prev_dna_hash == old_dna_hash => false,
+                Some(_) => return Err(ConductorError::ChainAlreadyMigrated(new_cell_id)),
+            };
+
+            if close_old {
+                old_cell.check_migrate_agent(MigrateAgent::Close).await?;
+            }
+            if open_new {
+                new_cell.check_migrate_agent(MigrateAgent::Open).await?;
+            }
+
+            if close_old {
+                old_cell
+                    .migrate_agent(MigrateAgent::Close, new_dna_hash)
+                    .await?;
+            }
+            if open_new {
+                new_cell
+                    .migrate_agent(MigrateAgent::Open, old_dna_hash)
+                    .await?;
+            }
+
+            Ok(())
+        }
+
+        /// Query the source chain of a cell, whether or not it is running
+        pub(crate) async fn query_source_chain(
+            &self,
+            cell_id: &CellId,
+            filter: ChainQueryFilter,
+        ) -> ConductorResult<Vec<Record>> {
+            let dna_hash = cell_id.dna_hash();
+            let chain = SourceChainRead::new(
+                self.get_or_create_authored_db(dna_hash, cell_id.agent_pubkey().clone())?
+                    .into(),
+                self.get_or_create_dht_db(dna_hash)?.into(),
+                self.get_or_create_space(dna_hash)?.dht_query_cache,
+                self.keystore.clone(),
+                cell_id.agent_pubkey().clone(),
+            )
+            .await?;
+            Ok(chain.query(filter).await?)
+        }
+    }
+}
+
 /// Methods related to clone cell management
 mod clone_cell_impls {
     use holochain_zome_types::prelude::ClonedCell;
@@ -1824,17 +2618,34 @@ mod app_status_impls {
         }
 
         /// Disable an app
+        ///
+        /// Apps which use cells created by this app can't run without them,
+        /// so they are disabled too, for the same reason.
         #[tracing::instrument(skip(self))]
         pub async fn disable_app(
             self: Arc<Self>,
             app_id: InstalledAppId,
             reason: DisabledAppReason,
         ) -> ConductorResult<InstalledApp> {
-            let (app, delta) = self
-                .transition_app_status(app_id.clone(), AppStatusTransition::Disable(reason))
-                .await?;
-            self.process_app_status_fx(delta, Some(vec![app_id.to_owned()].into_iter().collect()))
+            let (_, (app, delta, disabled)) = self
+                .update_state_prime(move |mut state| {
+                    let dependents = state.all_dependent_apps(&app_id)?;
+                    let mut delta = AppStatusFx::default();
+                    for dependent in dependents.iter() {
+                        let (_, dependent_delta) = state.transition_app_status(
+                            dependent,
+                            AppStatusTransition::Disable(reason.clone()),
+                        )?;
+                        delta = delta.combine(dependent_delta);
+                    }
+                    let (app, app_delta) = state
+                        .transition_app_status(&app_id, AppStatusTransition::Disable(reason))?;
+                    let app = app.clone();
+                    let disabled: HashSet<_> = std::iter::once(app_id).chain(dependents).collect();
+                    Ok((state, (app, delta.combine(app_delta), disabled)))
+                })
                 .await?;
+            self.process_app_status_fx(delta, Some(disabled)).await?;
             Ok(app)
         }
 
@@ -2072,7 +2883,9 @@ mod state_impls {
         where
             F: FnOnce(ConductorState) -> ConductorResult<ConductorState> + 'static,
         {
-            self.spaces.update_state(f).await
+            let state = self.spaces.update_state(f).await?;
+            self.signal_log.refresh_apps(&state);
+            Ok(state)
         }
 
         /// Update the internal state with a pure function mapping old state to new,
@@ -2087,7 +2900,9 @@ mod state_impls {
             O: Send + 'static,
         {
             self.check_running()?;
-            self.spaces.update_state_prime(f).await
+            let (state, output) = self.spaces.update_state_prime(f).await?;
+            self.signal_log.refresh_apps(&state);
+            Ok((state, output))
         }
     }
 }
@@ -2370,6 +3185,74 @@ mod misc_impls {
             .await
         }
 
+        /// Export the source chain of a cell, with its private entries and the
+        /// locks of countersigning sessions in progress, into an archive signed
+        /// by the agent of the cell.
+        pub async fn export_chain(
+            &self,
+            cell_id: &CellId,
+        ) -> ConductorApiResult<SignedChainArchive> {
+            chain_archive::export_chain(self, cell_id).await
+        }
+
+        /// Restore the source chain of a cell from an archive, before the cell
+        /// is installed.
+        ///
+        /// The signature and the structure of the archived chain are checked
+        /// before the records are written, and they are validated against the
+        /// DNA if `validate` is true. Installing the cell afterwards skips
+        /// genesis, since the restored chain already has it.
+        pub async fn import_chain(
+            self: Arc<Self>,
+            archive: SignedChainArchive,
+            validate: bool,
+        ) -> ConductorApiResult<()> {
+            chain_archive::import_chain(self, archive, validate).await
+        }
+
+        /// Replace the master key the keys of the databases are derived from,
+        /// and rekey every open database with it.
+        pub async fn rotate_database_key(&self) -> ConductorResult<()> {
+            db_key::rotate_db_master_key(self).await
+        }
+
+        /// Get the state of the countersigning session the cell is taking part in.
+        pub async fn countersigning_session_state(
+            &self,
+            cell_id: &CellId,
+        ) -> ConductorApiResult<Option<CountersigningSessionState>> {
+            countersigning::countersigning_session_state(self, cell_id).await
+        }
+
+        /// Unlock the chain of the cell, abandoning the countersigning
+        /// session it is taking part in before the session expires. A session
+        /// entry which was committed stays at the head of the chain.
+        pub async fn abandon_countersigning_session(
+            &self,
+            cell_id: &CellId,
+        ) -> ConductorApiResult<()> {
+            countersigning::abandon_countersigning_session(self, cell_id).await
+        }
+
+        /// Publish the committed session entry of the cell to the
+        /// authorities of the countersigning session again.
+        pub async fn publish_countersigning_session(
+            &self,
+            cell_id: &CellId,
+        ) -> ConductorApiResult<()> {
+            countersigning::publish_countersigning_session(self, cell_id).await
+        }
+
+        /// List the zome functions and callbacks exported by the zomes of the
+        /// provisioned cells of the app.
+        pub async fn list_zome_exports(
+            &self,
+            installed_app_id: &InstalledAppId,
+        ) -> ConductorApiResult<HashMap<RoleName, Vec<holochain_conductor_api::ZomeExports>>>
+        {
+            zome_exports::list_zome_exports(self, installed_app_id).await
+        }
+
         /// Update coordinator zomes on an existing dna.
         pub async fn update_coordinators(
             &self,
@@ -2414,6 +3297,14 @@ mod accessor_impls {
     use super::*;
 
     impl Conductor {
+        /// The services available to the conductor, once they have been initialized
+        pub fn conductor_services(&self) -> ConductorResult<ConductorServices> {
+            self.services.share_ref(|s| {
+                s.clone()
+                    .ok_or_else(|| ConductorError::other("Conductor services not yet initialized"))
+            })
+        }
+
         pub(crate) fn ribosome_store(&self) -> &RwShare<RibosomeStore> {
             &self.ribosome_store
         }
@@ -2428,7 +3319,12 @@ mod accessor_impls {
             let senders = self
                 .app_interfaces
                 .share_ref(|ai| ai.values().map(|i| i.signal_tx()).cloned().collect());
-            SignalBroadcaster::new(senders)
+            SignalBroadcaster::new(senders).with_signal_log(self.signal_log.clone())
+        }
+
+        /// The log of signals which app interface connections read from
+        pub fn signal_log(&self) -> &SignalLog {
+            &self.signal_log
         }
 
         /// Instantiate a Ribosome for use with a DNA
@@ -2886,7 +3782,13 @@ mod test_utils_impls {
                 if app_interfaces.contains_key(&id) {
                     return Err(ConductorError::AppInterfaceIdCollision(id));
                 }
-                let _ = app_interfaces.insert(id, AppInterfaceRuntime::Test { signal_tx });
+                let _ = app_interfaces.insert(
+                    id,
+                    AppInterfaceRuntime::Test {
+                        signal_tx,
+                        signal_subscriptions: SignalSubscriptions::default(),
+                    },
+                );
                 Ok(())
             })
         }
@@ -3161,3 +4063,46 @@ async fn p2p_event_task(
 
 #[cfg(test)]
 pub mod tests;
+
+/// Runs the zome calls of the built-in conductor services in this conductor's cells.
+///
+/// The conductor is held weakly, since it holds the services.
+struct ServiceCellRunner(std::sync::Weak<Conductor>);
+
+#[async_trait::async_trait]
+impl CellRunner for ServiceCellRunner {
+    async fn call_zome(
+        &self,
+        provenance: &AgentPubKey,
+        cap_secret: Option<CapSecret>,
+        cell_id: CellId,
+        zome_name: ZomeName,
+        fn_name: FunctionName,
+        payload: ExternIO,
+    ) -> anyhow::Result<ExternIO> {
+        let conductor = self
+            .0
+            .upgrade()
+            .ok_or_else(|| anyhow::anyhow!("The conductor has shut down"))?;
+        let (nonce, expires_at) =
+            holochain_nonce::fresh_nonce(Timestamp::now()).map_err(|e| anyhow::anyhow!(e))?;
+        let call = ZomeCall::try_from_unsigned_zome_call(
+            conductor.keystore(),
+            ZomeCallUnsigned {
+                provenance: provenance.clone(),
+                cell_id,
+                zome_name,
+                fn_name,
+                cap_secret,
+                payload,
+                nonce,
+                expires_at,
+            },
+        )
+        .await?;
+        match conductor.call_zome(call).await?? {
+            ZomeCallResponse::Ok(response) => Ok(response),
+            other => Err(anyhow::anyhow!("Service zome call failed: {other:?}")),
+        }
+    }
+}
@@ -5,7 +5,7 @@ use crate::conductor::metrics::{create_post_commit_duration_metric, PostCommitDu
 use crate::conductor::paths::DataRootPath;
 use crate::conductor::ribosome_store::RibosomeStore;
 use crate::conductor::ConductorHandle;
-use holochain_conductor_api::conductor::paths::KeystorePath;
+use holochain_conductor_api::conductor::paths::{DatabasesRootPath, KeystorePath};
 
 /// A configurable Builder for Conductor and sometimes ConductorHandle
 #[derive(Default)]
@@ -137,7 +137,17 @@ impl ConductorBuilder {
 
         let ribosome_store = RwShare::new(ribosome_store);
 
-        let spaces = Spaces::new(config.clone())?;
+        let db_dir: DatabasesRootPath = config
+            .data_root_path
+            .clone()
+            .ok_or(ConductorError::NoDataRootPath)?
+            .try_into()?;
+        let db_master_key =
+            super::db_key::load_or_create_db_master_key(&keystore, db_dir.as_path()).await?;
+
+        info!("Conductor startup: database key loaded.");
+
+        let spaces = Spaces::new(config.clone(), db_master_key)?;
         let tag = spaces.get_state().await?.tag().clone();
 
         let tag_ed: Arc<str> = format!("{}_ed", tag.0).into_boxed_str().into();
@@ -379,7 +389,14 @@ impl ConductorBuilder {
             .unwrap_or_else(holochain_keystore::test_keystore);
 
         let config = Arc::new(self.config);
-        let spaces = Spaces::new(config.clone())?;
+        let db_dir: DatabasesRootPath = config
+            .data_root_path
+            .clone()
+            .ok_or(ConductorError::NoDataRootPath)?
+            .try_into()?;
+        let db_master_key =
+            super::db_key::load_or_create_db_master_key(&keystore, db_dir.as_path()).await?;
+        let spaces = Spaces::new(config.clone(), db_master_key)?;
         let tag = spaces.get_state().await?.tag().clone();
 
         let tag_ed: Arc<str> = format!("{}_ed", tag.0).into_boxed_str().into();
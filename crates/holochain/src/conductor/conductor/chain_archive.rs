@@ -0,0 +1,117 @@
+use holochain_state::chain_lock::live_chain_locks;
+
+use super::graft_records_onto_source_chain::validate_records;
+use crate::conductor::api::error::ConductorApiError;
+
+use super::*;
+
+pub(crate) async fn export_chain(
+    conductor: &Conductor,
+    cell_id: &CellId,
+) -> ConductorApiResult<SignedChainArchive> {
+    let cell = conductor.cell_by_id(cell_id).await?;
+    let authored_db = cell.get_or_create_authored_db()?;
+    let author = cell_id.agent_pubkey().clone();
+
+    let records = source_chain::export_records(authored_db.clone().into(), author.clone()).await?;
+    let chain_locks = authored_db
+        .read_async(move |txn| live_chain_locks(&txn, &author))
+        .await?
+        .into_iter()
+        .map(|(lock, expires_at)| ArchivedChainLock { lock, expires_at })
+        .collect();
+
+    let archive = ChainArchive {
+        version: CHAIN_ARCHIVE_VERSION,
+        cell_id: cell_id.clone(),
+        exported_at: Timestamp::now(),
+        records,
+        chain_locks,
+    };
+    SignedChainArchive::sign(conductor.keystore(), archive)
+        .await
+        .map_err(ConductorApiError::other)
+}
+
+pub(crate) async fn import_chain(
+    conductor: ConductorHandle,
+    archive: SignedChainArchive,
+    validate: bool,
+) -> ConductorApiResult<()> {
+    archive.validate().await?;
+    let ChainArchive {
+        version,
+        cell_id,
+        records,
+        chain_locks,
+        ..
+    } = archive.archive;
+    if version != CHAIN_ARCHIVE_VERSION {
+        return Err(ConductorApiError::other(format!(
+            "Chain archive version {} is not supported, expected version {}",
+            version, CHAIN_ARCHIVE_VERSION
+        )));
+    }
+    source_chain::check_chain_integrity(cell_id.agent_pubkey(), &records).await?;
+
+    // The chain is restored before the cell is installed, so that installing
+    // it finds the restored genesis rather than writing and publishing a new
+    // one, which would fork the chain.
+    let state = conductor.get_state().await?;
+    if state
+        .installed_apps()
+        .values()
+        .flat_map(|app| app.all_cells())
+        .any(|installed| *installed == cell_id)
+    {
+        return Err(ConductorError::CellAlreadyExists(cell_id).into());
+    }
+    let author = cell_id.agent_pubkey().clone();
+    let authored_db = conductor.get_or_create_authored_db(cell_id.dna_hash(), author.clone())?;
+    let chain_exists = authored_db
+        .read_async({
+            let author = author.clone();
+            move |txn| -> DatabaseResult<bool> {
+                Ok(txn.query_row(
+                    "SELECT EXISTS(SELECT 1 FROM Action WHERE author = ?)",
+                    [&author],
+                    |row| row.get(0),
+                )?)
+            }
+        })
+        .await?;
+    if chain_exists {
+        return Err(ConductorError::ChainAlreadyExists(cell_id).into());
+    }
+
+    if validate {
+        validate_records(conductor.clone(), &cell_id, &None, &records).await?;
+    }
+
+    let data = records
+        .into_iter()
+        .map(|record| {
+            let ops = produce_op_lites_from_records(vec![&record])?;
+            let (sah, entry) = record.into_inner();
+            Ok((sah, ops, entry.into_option()))
+        })
+        .collect::<StateMutationResult<Vec<_>>>()?;
+
+    // The locks on the chain are those of the archive, restoring the
+    // countersigning sessions which are still in progress.
+    let now = Timestamp::now();
+    authored_db
+        .write_async(move |txn| {
+            for (sah, ops, entry) in data {
+                source_chain::put_raw(txn, sah, ops, entry)?;
+            }
+            for ArchivedChainLock { lock, expires_at } in chain_locks {
+                if expires_at >= now {
+                    lock_chain(txn, &lock, &author, &expires_at)?;
+                }
+            }
+            SourceChainResult::Ok(())
+        })
+        .await?;
+    Ok(())
+}
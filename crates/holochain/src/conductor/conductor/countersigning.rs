@@ -0,0 +1,148 @@
+use holochain_conductor_api::CountersigningSessionState;
+use holochain_state::chain_lock::live_chain_locks;
+
+use crate::conductor::api::error::ConductorApiError;
+use crate::core::workflow::countersigning_workflow::countersigning_publish;
+
+use super::*;
+
+/// The countersigning session an author is taking part in.
+struct AuthorSession {
+    /// When the lock on the chain expires.
+    expires_at: Timestamp,
+    /// The session entry, if the author has committed it.
+    committed: Option<CommittedSession>,
+}
+
+struct CommittedSession {
+    entry_hash: EntryHash,
+    session_data: CounterSigningSessionData,
+    /// The StoreEntry op to send to the entry authorities.
+    op: DhtOp,
+}
+
+/// Get the session the author is taking part in from its authored database.
+fn author_session(
+    txn: &Transaction,
+    author: Arc<AgentPubKey>,
+) -> SourceChainResult<Option<AuthorSession>> {
+    let locks = live_chain_locks(txn, &author)?;
+    let expires_at = match locks.iter().map(|(_, expires_at)| *expires_at).max() {
+        Some(expires_at) => expires_at,
+        None => return Ok(None),
+    };
+    let head = match chain_head_db(txn, author)? {
+        Some(HeadInfo { action, .. }) => Txn::from(txn).get_record(&action.into())?,
+        None => None,
+    };
+    let mut committed = None;
+    if let Some(record) = head {
+        let (action, record_entry) = record.into_inner();
+        if let (Some(entry_hash), Some(entry @ Entry::CounterSign(session_data, _))) =
+            (action.action().entry_hash(), record_entry.as_option())
+        {
+            // The entry at the head of the chain belongs to the session in
+            // progress only if the chain is locked for its preflight request.
+            let lock = holo_hash::encode::blake2b_256(&holochain_serialized_bytes::encode(
+                session_data.preflight_request(),
+            )?);
+            if locks.iter().any(|(l, _)| *l == lock) {
+                if let Ok(new_entry_action) = action.action().clone().try_into() {
+                    committed = Some(CommittedSession {
+                        entry_hash: entry_hash.clone(),
+                        session_data: (**session_data).clone(),
+                        op: DhtOp::StoreEntry(
+                            action.signature().clone(),
+                            new_entry_action,
+                            entry.clone(),
+                        ),
+                    });
+                }
+            }
+        }
+    }
+    Ok(Some(AuthorSession {
+        expires_at,
+        committed,
+    }))
+}
+
+async fn get_author_session(
+    conductor: &Conductor,
+    cell_id: &CellId,
+) -> ConductorApiResult<Option<AuthorSession>> {
+    let cell = conductor.cell_by_id(cell_id).await?;
+    let author = Arc::new(cell_id.agent_pubkey().clone());
+    Ok(cell
+        .get_or_create_authored_db()?
+        .read_async(move |txn| author_session(&txn, author))
+        .await?)
+}
+
+fn no_session(cell_id: &CellId) -> ConductorApiError {
+    ConductorApiError::other(format!(
+        "Cell {:?} is not taking part in a countersigning session",
+        cell_id
+    ))
+}
+
+pub(crate) async fn countersigning_session_state(
+    conductor: &Conductor,
+    cell_id: &CellId,
+) -> ConductorApiResult<Option<CountersigningSessionState>> {
+    Ok(get_author_session(conductor, cell_id).await?.map(
+        |AuthorSession {
+             expires_at,
+             committed,
+         }| match committed {
+            Some(CommittedSession {
+                entry_hash,
+                session_data,
+                ..
+            }) => CountersigningSessionState::SignaturesCollecting {
+                entry_hash,
+                session_data: Box::new(session_data),
+                expires_at,
+            },
+            None => CountersigningSessionState::Accepted { expires_at },
+        },
+    ))
+}
+
+pub(crate) async fn abandon_countersigning_session(
+    conductor: &Conductor,
+    cell_id: &CellId,
+) -> ConductorApiResult<()> {
+    let cell = conductor.cell_by_id(cell_id).await?;
+    let author = cell_id.agent_pubkey().clone();
+    let abandoned = cell
+        .get_or_create_authored_db()?
+        .write_async(move |txn| {
+            let locked = !live_chain_locks(txn, &author)?.is_empty();
+            // Removing the lock is what happens when the session times out.
+            unlock_chain(txn, &author)?;
+            StateMutationResult::Ok(locked)
+        })
+        .await?;
+    if !abandoned {
+        return Err(no_session(cell_id));
+    }
+    Ok(())
+}
+
+pub(crate) async fn publish_countersigning_session(
+    conductor: &Conductor,
+    cell_id: &CellId,
+) -> ConductorApiResult<()> {
+    let op = match get_author_session(conductor, cell_id)
+        .await?
+        .and_then(|session| session.committed)
+    {
+        Some(CommittedSession { op, .. }) => op,
+        None => return Err(no_session(cell_id)),
+    };
+    let cell = conductor.cell_by_id(cell_id).await?;
+    countersigning_publish(cell.holochain_p2p_dna(), op, cell_id.agent_pubkey().clone())
+        .await
+        .map_err(|e| ConductorApiError::other(format!("{:?}", e)))
+}
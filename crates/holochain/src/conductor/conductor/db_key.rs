@@ -0,0 +1,133 @@
+//! The master key the at-rest encryption keys of the databases are derived from.
+//!
+//! The master key is stored next to the databases, encrypted with a shared
+//! secret which never leaves the keystore, so that the databases can only be
+//! decrypted by a conductor which can unlock the keystore.
+
+use std::path::Path;
+
+use holochain_sqlite::db::DbMasterKey;
+
+use super::*;
+
+/// The tag of the keystore secret the master key is encrypted with.
+const DB_KEY_TAG: &str = "holochain_db_key";
+
+/// The file in the databases directory the encrypted master key is stored in.
+const DB_KEY_FILE: &str = "db_master_key";
+
+const NONCE_LEN: usize = 24;
+const KEY_LEN: usize = 32;
+
+/// Load the master key from the databases directory, or create and store a
+/// new one if there is none yet.
+///
+/// Databases which were encrypted before there was a master key are rekeyed
+/// when they are first opened with the new master key.
+pub(crate) async fn load_or_create_db_master_key(
+    keystore: &MetaLairClient,
+    db_dir: &Path,
+) -> ConductorResult<DbMasterKey> {
+    let path = db_dir.join(DB_KEY_FILE);
+    match tokio::fs::read(&path).await {
+        Ok(stored) => decrypt_db_master_key(keystore, &stored).await,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            // The secret may already exist if the key file was removed,
+            // in which case it is reused.
+            let _ = keystore.new_shared_secret(DB_KEY_TAG.into()).await;
+            let master_key = DbMasterKey::generate()?;
+            store_db_master_key(keystore, db_dir, &master_key).await?;
+            Ok(master_key)
+        }
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Replace the master key with a new one and rekey every database with it.
+///
+/// The new master key is stored before any database is rekeyed, and it keeps
+/// the keys it replaced, so that databases which were not rekeyed because
+/// rekeying was interrupted are rekeyed when they are next opened. Once every
+/// database is rekeyed, the replaced keys are dropped.
+pub(crate) async fn rotate_db_master_key(conductor: &Conductor) -> ConductorResult<()> {
+    let _lock = conductor.spaces.db_key_rotation_lock.lock().await;
+    let master_key = conductor.spaces.db_master_key.read().rotate()?;
+    store_db_master_key(
+        &conductor.keystore,
+        conductor.spaces.db_dir.as_path(),
+        &master_key,
+    )
+    .await?;
+    *conductor.spaces.db_master_key.write() = master_key.clone();
+    conductor.spaces.rekey_databases(&master_key).await?;
+
+    let master_key = master_key.without_retired();
+    store_db_master_key(
+        &conductor.keystore,
+        conductor.spaces.db_dir.as_path(),
+        &master_key,
+    )
+    .await?;
+    *conductor.spaces.db_master_key.write() = master_key;
+    tracing::info!("Rotated the database encryption key");
+    Ok(())
+}
+
+async fn store_db_master_key(
+    keystore: &MetaLairClient,
+    db_dir: &Path,
+    master_key: &DbMasterKey,
+) -> ConductorResult<()> {
+    let mut keys = Vec::new();
+    keys.extend_from_slice(
+        master_key
+            .current()
+            .ok_or_else(|| ConductorError::other("There is no database master key to store"))?,
+    );
+    for retired in master_key.retired() {
+        keys.extend_from_slice(retired);
+    }
+    let (nonce, cipher) = keystore
+        .shared_secret_encrypt(DB_KEY_TAG.into(), keys.into())
+        .await?;
+
+    let mut stored = nonce.to_vec();
+    stored.extend_from_slice(&cipher);
+
+    // Write to a temporary file first, so that the key can't be lost if
+    // the conductor stops while it is being written.
+    let path = db_dir.join(DB_KEY_FILE);
+    let tmp_path = path.with_extension("tmp");
+    tokio::fs::write(&tmp_path, stored).await?;
+    tokio::fs::rename(&tmp_path, &path).await?;
+    Ok(())
+}
+
+async fn decrypt_db_master_key(
+    keystore: &MetaLairClient,
+    stored: &[u8],
+) -> ConductorResult<DbMasterKey> {
+    if stored.len() < NONCE_LEN {
+        return Err(ConductorError::other(
+            "The database master key file is corrupt",
+        ));
+    }
+    let (nonce, cipher) = stored.split_at(NONCE_LEN);
+    let keys = keystore
+        .shared_secret_decrypt(
+            DB_KEY_TAG.into(),
+            nonce.try_into().expect("nonce has the right length"),
+            cipher.into(),
+        )
+        .await?;
+
+    let mut keys = keys.chunks_exact(KEY_LEN).map(|key| {
+        let mut k = [0; KEY_LEN];
+        k.copy_from_slice(key);
+        k
+    });
+    let current = keys
+        .next()
+        .ok_or_else(|| ConductorError::other("The database master key file is corrupt"))?;
+    Ok(DbMasterKey::new(current, keys.collect()))
+}
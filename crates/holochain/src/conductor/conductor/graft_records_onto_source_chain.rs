@@ -125,7 +125,7 @@ pub(crate) async fn graft_records_onto_source_chain(
     Ok(())
 }
 
-async fn validate_records(
+pub(super) async fn validate_records(
     handle: ConductorHandle,
     cell_id: &CellId,
     chain_top: &Option<(ActionHash, u32)>,
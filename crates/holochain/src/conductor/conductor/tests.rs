@@ -14,6 +14,7 @@ use crate::{
 use ::fixt::prelude::*;
 use holochain_conductor_api::AppInfoStatus;
 use holochain_conductor_api::CellInfo;
+use holochain_conductor_api::ZomeExports;
 use holochain_keystore::crude_mock_keystore::*;
 use holochain_keystore::test_keystore;
 use holochain_types::inline_zome::InlineZomeSet;
@@ -39,6 +40,7 @@ async fn can_update_state() {
             ..Default::default()
         }
         .into(),
+        Default::default(),
     )
     .unwrap();
     let conductor = Conductor::new(
@@ -93,6 +95,7 @@ async fn app_ids_are_unique() {
             ..Default::default()
         }
         .into(),
+        Default::default(),
     )
     .unwrap();
     let conductor = Conductor::new(
@@ -231,18 +234,6 @@ async fn common_genesis_test_app(
 ) -> ConductorApiResult<SweetApp> {
     let hardcoded_zome = InlineIntegrityZome::new_unique(Vec::new(), 0);
 
-    // Just a strong reminder that we need to be careful once we start using existing Cells:
-    // When a Cell panics or fails validation in general, we want to disable all Apps touching that Cell.
-    // However, if the panic/failure happens during Genesis, we want to completely
-    // destroy the app which is attempting to Create that Cell, but *NOT* any other apps
-    // which might be touching that Cell.
-    //
-    // It probably works out to be the same either way, since if we are creating a Cell,
-    // no other app could be possibly referencing it, but just in case we have some kind of complex
-    // behavior like installing two apps which reference each others' Cells at the same time,
-    // we need to be aware of this distinction.
-    holochain_types::app::we_must_remember_to_rework_cell_panic_handling_after_implementing_use_existing_cell_resolution();
-
     // Create one DNA which always works, and another from a zome that gets passed in
     let (dna_hardcoded, _, _) = mk_dna(("hardcoded", hardcoded_zome)).await;
     let (dna_custom, _, _) = mk_dna(custom_zomes).await;
@@ -300,7 +291,7 @@ async fn test_uninstall_app() {
     // - Uninstall the first app
     conductor
         .raw_handle()
-        .uninstall_app(&"app1".to_string())
+        .uninstall_app(&"app1".to_string(), false)
         .await
         .unwrap();
 
@@ -317,7 +308,7 @@ async fn test_uninstall_app() {
     // - Uninstall the remaining app
     conductor
         .raw_handle()
-        .uninstall_app(&"app2".to_string())
+        .uninstall_app(&"app2".to_string(), false)
         .await
         .unwrap();
 
@@ -1165,3 +1156,196 @@ async fn test_init_concurrency() {
     assert_eq!(num_calls_clone.fetch_add(0, Ordering::SeqCst), 100);
     assert_eq!(num_inits_clone.fetch_add(0, Ordering::SeqCst), 1);
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn list_zome_exports() {
+    holochain_trace::test_run().ok();
+    let zome = InlineZomeSet::new_unique_single("integrity", "zome", vec![], 0)
+        .function("integrity", "validate", |_, _: Op| {
+            Ok(ValidateResult::Valid)
+        })
+        .function("zome", "init", |_, ()| Ok(InitCallbackResult::Pass))
+        .function(
+            "zome",
+            "post_commit",
+            |_, _: Vec<SignedActionHashed>| Ok(()),
+        )
+        .function("zome", "foo", |_, ()| Ok(()))
+        .function("zome", "bar", |_, ()| Ok(()));
+    let dnas = [mk_dna(zome).await.0];
+    let mut conductor = SweetConductor::from_standard_config().await;
+    conductor.setup_app("app", &dnas).await.unwrap();
+
+    let exports = conductor
+        .list_zome_exports(&"app".to_string())
+        .await
+        .unwrap();
+    assert_eq!(exports.len(), 1);
+    let zomes = exports.into_values().next().unwrap();
+    assert_eq!(
+        zomes,
+        vec![
+            ZomeExports {
+                zome_name: "integrity".into(),
+                zome_fns: vec![],
+                callbacks: vec!["entry_defs".into(), "validate".into()],
+                scheduled_fns: vec![],
+            },
+            ZomeExports {
+                zome_name: "zome".into(),
+                zome_fns: vec!["bar".into(), "foo".into()],
+                callbacks: vec!["init".into(), "post_commit".into()],
+                scheduled_fns: vec![],
+            },
+        ]
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn rotate_database_key() {
+    holochain_trace::test_run().ok();
+    let (dna, _, _) = mk_dna(simple_crud_zome()).await;
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let app = conductor.setup_app("app", [&dna]).await.unwrap();
+    let zome = app.cells()[0].zome("coordinator");
+
+    let hash: ActionHash = conductor
+        .call(&zome, "create_string", "1".to_string())
+        .await;
+
+    let spaces = conductor.raw_handle().spaces.clone();
+    let key_path = spaces.db_dir.join("db_master_key");
+    let stored_key = std::fs::read(&key_path).unwrap();
+    let master_key = spaces.db_master_key.read().clone();
+
+    conductor.rotate_database_key().await.unwrap();
+
+    // The new master key replaced the old one, which was dropped once every
+    // database was rekeyed.
+    assert_ne!(std::fs::read(&key_path).unwrap(), stored_key);
+    let rotated = spaces.db_master_key.read().clone();
+    assert_ne!(rotated.current(), master_key.current());
+    assert_eq!(None, rotated.retired().next());
+
+    // The rekeyed databases can still be used.
+    let record: Option<Record> = conductor.call(&zome, "read", hash.clone()).await;
+    assert!(record.is_some());
+    let _: ActionHash = conductor
+        .call(&zome, "create_string", "2".to_string())
+        .await;
+
+    // And the stored master key opens them after a restart.
+    conductor.shutdown().await;
+    conductor.startup().await;
+
+    let record: Option<Record> = conductor.call(&zome, "read", hash).await;
+    assert!(record.is_some());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn published_warrants_block_only_with_a_verified_proof() {
+    use holochain_keystore::AgentPubKeyExt;
+    use holochain_state::mutations::insert_op;
+    use holochain_types::warrant::SignedWarrantExt;
+    use holochain_zome_types::block::BlockTargetId;
+
+    holochain_trace::test_run().ok();
+    let (dna, _, _) = mk_dna(simple_crud_zome()).await;
+    let conductor = SweetConductor::from_standard_config().await;
+    let app = conductor.setup_app("app", [&dna]).await.unwrap();
+    let dna_hash = app.cells()[0].cell_id().dna_hash().clone();
+    let keystore = conductor.keystore();
+    let warrantee = SweetAgents::one(keystore.clone()).await;
+    let issuer = SweetAgents::one(keystore.clone()).await;
+    let warrantee_cell = CellId::new(dna_hash.clone(), warrantee.clone());
+
+    // The warrantee authored two different actions at seq 3, and one at seq 4.
+    let mut create = fixt!(Create);
+    create.author = warrantee.clone();
+    create.action_seq = 3;
+    let first = Action::Create(create.clone());
+    create.timestamp = Timestamp::from_micros(create.timestamp.as_micros() + 1);
+    let second = Action::Create(create.clone());
+    create.action_seq = 4;
+    let later = Action::Create(create);
+
+    let mut signed = Vec::new();
+    for action in [first, second, later] {
+        let signature = warrantee.sign(&keystore, &action).await.unwrap();
+        signed.push((ActionHash::with_data_sync(&action), signature, action));
+    }
+    let ops: Vec<_> = signed
+        .iter()
+        .map(|(_, signature, action)| {
+            DhtOpHashed::from_content_sync(DhtOp::RegisterAgentActivity(
+                signature.clone(),
+                action.clone(),
+            ))
+        })
+        .collect();
+    conductor
+        .get_dht_db(&dna_hash)
+        .unwrap()
+        .write_async(move |txn| -> StateMutationResult<()> {
+            for op in ops {
+                insert_op(txn, &op)?;
+            }
+            Ok(())
+        })
+        .await
+        .unwrap();
+
+    let and_sig = |i: usize| (signed[i].0.clone(), signed[i].1.clone());
+    let fork_warrant = |pair| {
+        Warrant::new(
+            WarrantProof::ChainIntegrity(ChainIntegrityWarrant::ChainFork {
+                chain_author: warrantee.clone(),
+                action_pair: pair,
+            }),
+            issuer.clone(),
+            Timestamp::now(),
+        )
+    };
+    let is_blocked = || {
+        conductor.raw_handle().is_blocked(
+            BlockTargetId::Cell(warrantee_cell.clone()),
+            Timestamp::now(),
+        )
+    };
+
+    // A validly signed warrant whose actions are at different seqs is forged.
+    let forged = SignedWarrant::sign(&keystore, fork_warrant((and_sig(0), and_sig(2))))
+        .await
+        .unwrap();
+    conductor
+        .raw_handle()
+        .handle_publish_warrants(&dna_hash, vec![forged])
+        .await
+        .unwrap();
+    assert!(!is_blocked().await.unwrap());
+
+    // As is one whose signature doesn't belong to the claimed action.
+    let forged = SignedWarrant::sign(
+        &keystore,
+        fork_warrant((and_sig(0), (signed[1].0.clone(), signed[0].1.clone()))),
+    )
+    .await
+    .unwrap();
+    conductor
+        .raw_handle()
+        .handle_publish_warrants(&dna_hash, vec![forged])
+        .await
+        .unwrap();
+    assert!(!is_blocked().await.unwrap());
+
+    // A real fork blocks the warrantee.
+    let real = SignedWarrant::sign(&keystore, fork_warrant((and_sig(0), and_sig(1))))
+        .await
+        .unwrap();
+    conductor
+        .raw_handle()
+        .handle_publish_warrants(&dna_hash, vec![real])
+        .await
+        .unwrap();
+    assert!(is_blocked().await.unwrap());
+}
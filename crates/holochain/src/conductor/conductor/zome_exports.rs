@@ -0,0 +1,61 @@
+use holochain_conductor_api::ZomeExports;
+use holochain_state::schedule::scheduled_fns;
+
+use crate::core::ribosome::error::RibosomeResult;
+
+use super::*;
+
+pub(crate) async fn list_zome_exports(
+    conductor: &Conductor,
+    installed_app_id: &InstalledAppId,
+) -> ConductorApiResult<HashMap<RoleName, Vec<ZomeExports>>> {
+    let state = conductor.get_state().await?;
+    let app = state.get_app(installed_app_id)?;
+    let mut exports = HashMap::new();
+    for (role_name, role_assignment) in app.roles() {
+        // Deferred roles have no cell yet, so there is nothing to list.
+        if let Some(cell_id) = role_assignment.provisioned_cell() {
+            exports.insert(
+                role_name.clone(),
+                list_cell_zome_exports(conductor, cell_id).await?,
+            );
+        }
+    }
+    Ok(exports)
+}
+
+async fn list_cell_zome_exports(
+    conductor: &Conductor,
+    cell_id: &CellId,
+) -> ConductorApiResult<Vec<ZomeExports>> {
+    let ribosome = conductor.get_ribosome(cell_id.dna_hash())?;
+    let author = cell_id.agent_pubkey().clone();
+    let scheduled = conductor
+        .get_or_create_authored_db(cell_id.dna_hash(), author.clone())?
+        .read_async(move |txn| scheduled_fns(&txn, &author))
+        .await?;
+
+    // Listing the exports may compile the wasm of the zomes, if it is not cached yet.
+    let exports = tokio::task::spawn_blocking(move || {
+        ribosome
+            .dna_def()
+            .all_zomes()
+            .map(|(zome_name, zome_def)| {
+                let zome = Zome::new(zome_name.clone(), zome_def.clone());
+                Ok(ZomeExports {
+                    zome_name: zome_name.clone(),
+                    zome_fns: ribosome.list_zome_fns(&zome)?,
+                    callbacks: ribosome.list_callbacks(&zome)?,
+                    scheduled_fns: scheduled
+                        .iter()
+                        .filter(|scheduled_fn| scheduled_fn.zome_name() == zome_name)
+                        .map(|scheduled_fn| scheduled_fn.fn_name().clone())
+                        .collect(),
+                })
+            })
+            .collect::<RibosomeResult<Vec<_>>>()
+    })
+    .await
+    .map_err(ConductorError::from)??;
+    Ok(exports)
+}
@@ -1,3 +1,4 @@
+use super::app_auth_token_store::AppAuthenticationError;
 use super::interface::error::InterfaceError;
 use super::{entry_def_store::error::EntryDefStoreError, state::AppInterfaceId};
 use crate::conductor::cell::error::CellError;
@@ -70,6 +71,9 @@ pub enum ConductorError {
     #[error("Attempted to add two app interfaces with the same id: {0:?}")]
     AppInterfaceIdCollision(AppInterfaceId),
 
+    #[error(transparent)]
+    AppAuthenticationError(#[from] AppAuthenticationError),
+
     // Box is to avoid cycle in error definition
     #[error(transparent)]
     InterfaceError(#[from] Box<InterfaceError>),
@@ -92,6 +96,36 @@ pub enum ConductorError {
     #[error("Tried to perform an operation on an app that was not running: {0}")]
     AppNotRunning(InstalledAppId),
 
+    #[error("App '{0}' can't be uninstalled because these apps use its cells: {1:?}")]
+    AppHasDependents(InstalledAppId, Vec<InstalledAppId>),
+
+    #[error("The chain of cell {0:?} was already migrated to or from another DNA")]
+    ChainAlreadyMigrated(CellId),
+
+    #[error("The cell {0:?} already has a source chain to restore the archive over")]
+    ChainAlreadyExists(CellId),
+
+    #[error("The app store has no app bundle with hash {0}")]
+    AppNotInStore(AppHash),
+
+    #[error("No app store is configured, or its app is not installed")]
+    NoAppStore,
+
+    #[error("No DPKI service is configured, or its app is not installed")]
+    NoDpki,
+
+    #[error("The agent key {0} was replaced or revoked in DPKI")]
+    AgentKeyInvalidated(AgentPubKey),
+
+    #[error("Can't block the IP address {0}, because the configured network transport can't tell which address a peer connects from")]
+    IpBlockNotEnforceable(std::net::IpAddr),
+
+    #[error(transparent)]
+    AppStoreServiceError(#[from] holochain_conductor_services::AppStoreServiceError),
+
+    #[error(transparent)]
+    DpkiServiceError(#[from] holochain_conductor_services::DpkiServiceError),
+
     #[error(transparent)]
     HolochainP2pError(#[from] holochain_p2p::HolochainP2pError),
 
@@ -2,13 +2,18 @@
 //! and dispatch them to the appropriate handlers within Holochain.
 //! They also allow emitting responses and one-way Signals.
 //!
-//! Currently the only InterfaceDriver is a Websocket-based one, whose
-//! implementation can be found in the `websocket` module here.
+//! All InterfaceDrivers are Websocket-based, either over TCP or over a
+//! Unix domain socket. The implementation can be found in the `websocket` module here.
 
 use crate::conductor::api::*;
+use crate::conductor::signal_log::SignalLog;
 use error::InterfaceError;
 use error::InterfaceResult;
+use holochain_conductor_api::signal_subscription::SignalFilterSet;
+use holochain_types::prelude::*;
+use holochain_types::share::RwShare;
 use holochain_types::signal::Signal;
+use std::collections::HashMap;
 use std::convert::TryInto;
 use tokio::sync::broadcast;
 
@@ -24,6 +29,8 @@ pub enum AppInterfaceRuntime {
     Websocket {
         /// The channel for this interface to send Signals across
         signal_tx: broadcast::Sender<Signal>,
+        /// The signal filters applied before signals are sent to clients
+        signal_subscriptions: SignalSubscriptions,
     },
 
     #[cfg(any(test, feature = "test_utils"))]
@@ -31,6 +38,8 @@ pub enum AppInterfaceRuntime {
     Test {
         /// The channel for this interface to send Signals across
         signal_tx: broadcast::Sender<Signal>,
+        /// The signal filters applied before signals are sent to clients
+        signal_subscriptions: SignalSubscriptions,
     },
 }
 
@@ -43,6 +52,61 @@ impl AppInterfaceRuntime {
             Self::Test { signal_tx, .. } => signal_tx,
         }
     }
+
+    /// Get the signal subscriptions of the interface
+    pub fn signal_subscriptions(&self) -> &SignalSubscriptions {
+        match self {
+            Self::Websocket {
+                signal_subscriptions,
+                ..
+            } => signal_subscriptions,
+            #[cfg(any(test, feature = "test_utils"))]
+            Self::Test {
+                signal_subscriptions,
+                ..
+            } => signal_subscriptions,
+        }
+    }
+}
+
+/// The signal subscriptions of an app interface, as they are enforced at runtime.
+///
+/// The persisted [`SignalSubscription`](holochain_conductor_api::signal_subscription::SignalSubscription)s
+/// are stored in the interface's `AppInterfaceConfig`. Here they are kept in
+/// memory, so that every signal passing through the interface can be checked
+/// without consulting the conductor state.
+#[derive(Clone, Debug, Default)]
+pub struct SignalSubscriptions(RwShare<HashMap<InstalledAppId, SignalFilterSet>>);
+
+impl SignalSubscriptions {
+    /// Set the filters for an app, replacing any previous ones.
+    pub fn set(&self, installed_app_id: InstalledAppId, filters: SignalFilterSet) {
+        self.0.share_mut(|subs| {
+            subs.insert(installed_app_id, filters);
+        });
+    }
+
+    /// Remove the filters for an app, so that all of its signals are let through.
+    pub fn remove(&self, installed_app_id: &InstalledAppId) {
+        self.0.share_mut(|subs| {
+            subs.remove(installed_app_id);
+        });
+    }
+
+    /// Check whether a signal may be sent to the clients of this interface
+    /// which are connected on behalf of the given app.
+    ///
+    /// Only the subscription of that app is consulted, for signals from the
+    /// app's cells and system signals alike, so the subscription of one app
+    /// never affects which signals the clients of another app receive.
+    /// Signals are let through if the app has no subscription.
+    pub fn allows(&self, installed_app_id: &InstalledAppId, signal: &Signal) -> bool {
+        self.0.share_ref(|subs| {
+            subs.get(installed_app_id)
+                .map(|filters| filters.allows(signal))
+                .unwrap_or(true)
+        })
+    }
 }
 
 /// A collection of Senders to be used for emitting Signals from a Cell.
@@ -50,16 +114,21 @@ impl AppInterfaceRuntime {
 #[derive(Clone, Debug)]
 pub struct SignalBroadcaster {
     senders: Vec<broadcast::Sender<Signal>>,
+    /// The log which app interface connections read signals from
+    signal_log: Option<SignalLog>,
 }
 
 impl SignalBroadcaster {
     /// Send the signal to the connected client. Send error is logged but not
     /// returned to the caller.
     pub(crate) fn send(&mut self, sig: Signal) -> InterfaceResult<()> {
+        if let Some(signal_log) = &self.signal_log {
+            signal_log.record(&sig);
+        }
         self.senders.iter_mut().for_each(|tx| {
             if let Err(err) = tx.send(sig.clone()) {
-                tracing::info!(
-                    "{:?}: no app websocket connected",
+                tracing::trace!(
+                    "{:?}: no signal subscribers",
                     InterfaceError::SignalSend(err)
                 )
             }
@@ -69,7 +138,16 @@ impl SignalBroadcaster {
 
     /// internal constructor
     pub fn new(senders: Vec<broadcast::Sender<Signal>>) -> Self {
-        Self { senders }
+        Self {
+            senders,
+            signal_log: None,
+        }
+    }
+
+    /// Also record every signal in the given log
+    pub fn with_signal_log(mut self, signal_log: SignalLog) -> Self {
+        self.signal_log = Some(signal_log);
+        self
     }
 
     #[cfg(test)]
@@ -77,6 +155,7 @@ impl SignalBroadcaster {
     pub fn noop() -> Self {
         Self {
             senders: Vec::new(),
+            signal_log: None,
         }
     }
 
@@ -103,3 +182,40 @@ impl SignalBroadcaster {
 }
 
 pub use holochain_conductor_api::config::InterfaceDriver;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use holochain_conductor_api::signal_subscription::{SignalFilter, SignalKind};
+    use holochain_types::signal::SystemSignal;
+
+    #[test]
+    fn signal_subscriptions_are_scoped_to_app() {
+        let subs = SignalSubscriptions::default();
+        let app_a: InstalledAppId = "a".into();
+        let app_b: InstalledAppId = "b".into();
+        let system = Signal::System(SystemSignal::Test("test".into()));
+        let cell_id = CellId::new(
+            DnaHash::from_raw_36(vec![1; 36]),
+            AgentPubKey::from_raw_36(vec![1; 36]),
+        );
+
+        subs.set(app_a.clone(), SignalFilterSet::block_all());
+        assert!(!subs.allows(&app_a, &system));
+        assert!(subs.allows(&app_b, &system));
+
+        // Excluding the system signals of app b does not affect app a
+        subs.set(app_a.clone(), SignalFilterSet::allow_all());
+        subs.set(
+            app_b.clone(),
+            SignalFilterSet::Exclude(
+                [(cell_id, SignalFilter::empty().with_kind(SignalKind::System))].into(),
+            ),
+        );
+        assert!(subs.allows(&app_a, &system));
+        assert!(!subs.allows(&app_b, &system));
+
+        subs.remove(&app_b);
+        assert!(subs.allows(&app_b, &system));
+    }
+}
@@ -15,6 +15,8 @@ pub enum InterfaceError {
     SignalReceive(tokio::sync::broadcast::error::TryRecvError),
     #[error(transparent)]
     RequestHandler(Box<ConductorError>),
+    #[error("Failed to authenticate the connection: {0}")]
+    AuthenticationFailed(String),
     #[error("Got an unexpected message: {0}")]
     UnexpectedMessage(String),
     #[error("Failed to send across interface")]
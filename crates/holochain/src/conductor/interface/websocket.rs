@@ -1,20 +1,26 @@
 //! Module for establishing Websocket-based Interfaces,
-//! i.e. those configured with `InterfaceDriver::Websocket`
+//! i.e. those configured with `InterfaceDriver::Websocket`,
+//! or with `InterfaceDriver::UnixSocket`, which speaks the same
+//! protocol over a unix domain socket.
 
 use super::error::InterfaceResult;
 use crate::conductor::interface::*;
 use crate::conductor::manager::TaskManagerClient;
+use crate::conductor::signal_log::SignalCursor;
+use crate::conductor::signal_log::SignalLog;
+use holochain_conductor_api::config::InterfaceTlsConfig;
+use holochain_conductor_api::config::DEFAULT_INTERFACE_BIND_ADDRESS;
 use holochain_serialized_bytes::SerializedBytes;
-use holochain_types::signal::Signal;
 use holochain_websocket::ReceiveMessage;
+use holochain_websocket::TlsServerConfig;
 use holochain_websocket::WebsocketConfig;
 use holochain_websocket::WebsocketListener;
 use holochain_websocket::WebsocketReceiver;
 use holochain_websocket::WebsocketSender;
 
 use holochain_types::websocket::AllowedOrigins;
+use std::net::IpAddr;
 use std::sync::Arc;
-use tokio::sync::broadcast;
 use tokio::task::JoinHandle;
 use tracing::*;
 
@@ -24,31 +30,91 @@ use tracing::*;
 /// to accomodate interdependent operations.
 const CONCURRENCY_COUNT: usize = 128;
 
-// TODO: This is arbitrary, choose reasonable size.
-// ERROR TODO XXX (david.b): There is no such thing as backpressure in
-//                           broadcast queues! It'll just start deleting
-//                           items, and giving "Lagged" errors on receivers.
-/// Number of signals in buffer before applying
-/// back pressure.
+/// Number of signals in the buffer of the broadcast channel of an interface.
+/// Only in-process subscribers, like tests, receive signals from this channel.
+/// App interface connections read signals from the [`SignalLog`] instead,
+/// at their own pace.
 pub(crate) const SIGNAL_BUFFER_SIZE: usize = 50;
 /// The maximum number of connections allowed to the admin interface
 pub const MAX_CONNECTIONS: usize = 400;
+/// How long a new app interface connection has to authenticate before it is closed
+const APP_AUTHENTICATION_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
 
-/// Create a WebsocketListener to be used in interfaces
+/// Create a WebsocketListener to be used in interfaces.
+/// If `tls` is given, the listener serves `wss://` with its certificate.
 pub async fn spawn_websocket_listener(
+    bind_address: IpAddr,
     port: u16,
     allowed_origins: AllowedOrigins,
+    tls: Option<&InterfaceTlsConfig>,
 ) -> InterfaceResult<WebsocketListener> {
     trace!("Initializing Admin interface");
 
     let mut config = WebsocketConfig::LISTENER_DEFAULT;
     config.allowed_origins = Some(allowed_origins);
+    if let Some(tls) = tls {
+        config.tls = Some(TlsServerConfig::from_pem_files(
+            &tls.cert_path,
+            &tls.key_path,
+        )?);
+    }
 
-    let listener = WebsocketListener::bind(Arc::new(config), format!("127.0.0.1:{}", port)).await?;
+    let listener = WebsocketListener::bind(Arc::new(config), (bind_address, port)).await?;
     trace!("LISTENING AT: {}", listener.local_addr()?);
     Ok(listener)
 }
 
+/// Create a WebsocketListener on a unix domain socket to be used in interfaces
+#[cfg(unix)]
+pub async fn spawn_unix_socket_listener(
+    path: &std::path::Path,
+    mode: Option<u32>,
+) -> InterfaceResult<WebsocketListener> {
+    let listener = WebsocketListener::bind_unix_socket(
+        Arc::new(WebsocketConfig::LISTENER_DEFAULT),
+        path,
+        mode,
+    )
+    .await?;
+    trace!("LISTENING AT: {}", path.display());
+    Ok(listener)
+}
+
+/// Create a WebsocketListener for the given interface driver
+pub async fn spawn_listener(driver: &InterfaceDriver) -> InterfaceResult<WebsocketListener> {
+    match driver {
+        InterfaceDriver::Websocket {
+            port,
+            allowed_origins,
+            bind_address,
+            tls,
+        } => {
+            spawn_websocket_listener(
+                bind_address.unwrap_or(DEFAULT_INTERFACE_BIND_ADDRESS),
+                *port,
+                allowed_origins.clone(),
+                tls.as_ref(),
+            )
+            .await
+        }
+        #[cfg(unix)]
+        InterfaceDriver::UnixSocket { path, mode } => spawn_unix_socket_listener(path, *mode).await,
+        #[cfg(not(unix))]
+        InterfaceDriver::UnixSocket { .. } => Err(InterfaceError::Other(
+            "Unix domain socket interfaces are not supported on this platform".to_string(),
+        )),
+    }
+}
+
+/// Where a listener accepts connections, for logging
+fn listener_location(listener: &WebsocketListener) -> String {
+    match (listener.local_path(), listener.local_addr()) {
+        (Some(path), _) => format!("socket {}", path.display()),
+        (None, Ok(addr)) => format!("port {}", addr.port()),
+        (None, Err(_)) => "unknown address".to_string(),
+    }
+}
+
 /// Abort tokio tasks on Drop.
 #[derive(Default)]
 struct TaskList(pub Vec<JoinHandle<()>>);
@@ -74,9 +140,9 @@ pub fn spawn_admin_interface_tasks<A: InterfaceApi>(
     tm: TaskManagerClient,
     listener: WebsocketListener,
     api: A,
-    port: u16,
 ) {
-    tm.add_conductor_task_ignored(&format!("admin interface, port {}", port), move || {
+    let location = listener_location(&listener);
+    tm.add_conductor_task_ignored(&format!("admin interface, {}", location), move || {
         async move {
             let mut task_list = TaskList::default();
             // establish a new connection to a client
@@ -107,23 +173,29 @@ pub fn spawn_admin_interface_tasks<A: InterfaceApi>(
 }
 
 /// Create an App Interface, which includes the ability to receive signals
-/// from Cells via a broadcast channel
-pub async fn spawn_app_interface_task<A: InterfaceApi>(
+/// from Cells, read from the signal log.
+///
+/// Each client must authenticate with an app authentication token before
+/// any of its requests are handled. If `installed_app_id` is set, only tokens
+/// for that app are accepted.
+///
+/// Returns the driver of the interface, with the port filled in if it
+/// was chosen by the OS.
+pub async fn spawn_app_interface_task(
     tm: TaskManagerClient,
-    port: u16,
-    allowed_origins: AllowedOrigins,
-    api: A,
-    signal_broadcaster: broadcast::Sender<Signal>,
-) -> InterfaceResult<u16> {
+    mut driver: InterfaceDriver,
+    installed_app_id: Option<InstalledAppId>,
+    api: RealAppInterfaceApi,
+    signal_log: SignalLog,
+    signal_subscriptions: SignalSubscriptions,
+) -> InterfaceResult<InterfaceDriver> {
     trace!("Initializing App interface");
 
-    let mut config = WebsocketConfig::LISTENER_DEFAULT;
-    config.allowed_origins = Some(allowed_origins);
-
-    let listener = WebsocketListener::bind(Arc::new(config), format!("127.0.0.1:{}", port)).await?;
-    let addr = listener.local_addr()?;
-    trace!("LISTENING AT: {}", addr);
-    let port = addr.port();
+    let listener = spawn_listener(&driver).await?;
+    if let InterfaceDriver::Websocket { port, .. } = &mut driver {
+        *port = listener.local_addr()?.port();
+    }
+    let location = listener_location(&listener);
 
     tm.add_conductor_task_ignored("app interface new connection handler", move || {
         async move {
@@ -132,15 +204,44 @@ pub async fn spawn_app_interface_task<A: InterfaceApi>(
             loop {
                 match listener.accept().await {
                     Ok((tx_to_iface, rx_from_iface)) => {
-                        let rx_from_cell = signal_broadcaster.subscribe();
-                        spawn_recv_incoming_msgs_and_outgoing_signals(
-                            &mut task_list,
-                            api.clone(),
-                            rx_from_iface,
-                            rx_from_cell,
-                            tx_to_iface,
-                            port,
-                        );
+                        task_list.prune();
+                        // Signals emitted while the client authenticates are sent too
+                        let next_seqs = signal_log.next_seqs();
+                        let signal_log = signal_log.clone();
+                        let api = api.clone();
+                        let installed_app_id = installed_app_id.clone();
+                        let signal_subscriptions = signal_subscriptions.clone();
+                        let location = location.clone();
+                        task_list.0.push(tokio::task::spawn(async move {
+                            let mut rx_from_iface = rx_from_iface;
+                            let api = match authenticate_app_connection(
+                                &api,
+                                installed_app_id.as_ref(),
+                                &mut rx_from_iface,
+                            )
+                            .await
+                            {
+                                Ok(api) => api,
+                                Err(err) => {
+                                    warn!(?err, "App interface connection failed to authenticate");
+                                    return;
+                                }
+                            };
+                            if let Some(cursor) = api.signal_cursor() {
+                                if let Some(seq) = next_seqs.get(cursor.installed_app_id()) {
+                                    signal_log.resume(cursor, *seq);
+                                }
+                            }
+                            recv_incoming_msgs_and_outgoing_signals(
+                                api,
+                                rx_from_iface,
+                                signal_log,
+                                signal_subscriptions,
+                                tx_to_iface,
+                                location,
+                            )
+                            .await;
+                        }));
                     }
                     Err(err) => {
                         warn!("App socket connection failed: {}", err);
@@ -149,7 +250,7 @@ pub async fn spawn_app_interface_task<A: InterfaceApi>(
             }
         }
     });
-    Ok(port)
+    Ok(driver)
 }
 
 /// Polls for messages coming in from the external client.
@@ -181,58 +282,67 @@ async fn recv_incoming_admin_msgs<A: InterfaceApi>(api: A, rx_from_iface: Websoc
         .await;
 }
 
+/// Waits for the first message of a new app interface connection, which must
+/// authenticate it. Returns the api scoped to the app the client authenticated for.
+async fn authenticate_app_connection(
+    api: &RealAppInterfaceApi,
+    interface_app: Option<&InstalledAppId>,
+    rx_from_iface: &mut WebsocketReceiver,
+) -> InterfaceResult<RealAppInterfaceApi> {
+    let msg = tokio::time::timeout(
+        APP_AUTHENTICATION_TIMEOUT,
+        rx_from_iface.recv::<AppRequest>(),
+    )
+    .await
+    .map_err(|_| InterfaceError::AuthenticationFailed("Timed out".to_string()))??;
+    match msg {
+        ReceiveMessage::Authenticate(data, respond) => {
+            let result = holochain_serialized_bytes::decode(&data)
+                .map_err(|e| e.to_string())
+                .and_then(|request: AppAuthenticationRequest| {
+                    api.authenticate(&request.token, interface_app)
+                        .map_err(|e| e.to_string())
+                });
+            let response = match &result {
+                Ok(_) => AppAuthenticationResponse::Authenticated,
+                Err(reason) => AppAuthenticationResponse::AuthenticationFailed(reason.clone()),
+            };
+            respond.respond(response).await?;
+            result.map_err(InterfaceError::AuthenticationFailed)
+        }
+        other => Err(InterfaceError::UnexpectedMessage(format!(
+            "Expected the connection to authenticate first, got {:?}",
+            other
+        ))),
+    }
+}
+
 /// Polls for messages coming in from the external client while simultaneously
-/// polling for signals being broadcast from the Cells associated with this
-/// App interface.
-fn spawn_recv_incoming_msgs_and_outgoing_signals<A: InterfaceApi>(
-    task_list: &mut TaskList,
-    api: A,
+/// sending the signals of the app the client authenticated for.
+///
+/// Signals are read from the signal log at the position of the connection's
+/// [`SignalCursor`], and each signal is sent only once the previous one was,
+/// so a slow client falls behind rather than losing signals, until the log
+/// drops them.
+async fn recv_incoming_msgs_and_outgoing_signals(
+    api: RealAppInterfaceApi,
     rx_from_iface: WebsocketReceiver,
-    rx_from_cell: broadcast::Receiver<Signal>,
+    signal_log: SignalLog,
+    signal_subscriptions: SignalSubscriptions,
     tx_to_iface: WebsocketSender,
-    port: u16,
+    location: String,
 ) {
     use futures::stream::StreamExt;
 
-    trace!("CONNECTION: {}", rx_from_iface.peer_addr());
-
-    let rx_from_cell = futures::stream::unfold(rx_from_cell, move |mut rx_from_cell| async move {
-        loop {
-            match rx_from_cell.recv().await {
-                // We missed some signals, but the channel is still open
-                Err(tokio::sync::broadcast::error::RecvError::Lagged(dropped)) => {
-                    warn!("Holochain app port {port} dropped {dropped} signals. The app is emitting signals too fast.");
-                    continue;
-                }
-                Ok(item) => return Some((item, rx_from_cell)),
-                _ => {
-                    debug!("SignalChannelClosed");
-                    return None;
-                }
-            }
-        }
-    });
+    trace!("CONNECTION: {:?}", rx_from_iface.peer_addr());
 
-    // TODO - metrics to indicate if we're getting overloaded here.
-    task_list
-        .0
-        .push(tokio::task::spawn(rx_from_cell.for_each_concurrent(
-            CONCURRENCY_COUNT,
-            move |signal| {
-                let tx_to_iface = tx_to_iface.clone();
-                async move {
-                    trace!(msg = "Sending signal!", ?signal);
-                    if let Err(err) = async move {
-                        tx_to_iface.signal(signal).await?;
-                        InterfaceResult::Ok(())
-                    }
-                    .await
-                    {
-                        error!(?err, "error emitting signal");
-                    }
-                }
-            },
-        )));
+    let signals = send_signals(
+        signal_log,
+        api.signal_cursor().cloned(),
+        signal_subscriptions,
+        tx_to_iface,
+        location,
+    );
 
     let rx_from_iface =
         futures::stream::unfold(rx_from_iface, move |mut rx_from_iface| async move {
@@ -246,19 +356,54 @@ fn spawn_recv_incoming_msgs_and_outgoing_signals<A: InterfaceApi>(
         });
 
     // TODO - metrics to indicate if we're getting overloaded here.
-    task_list
-        .0
-        .push(tokio::task::spawn(rx_from_iface.for_each_concurrent(
-            CONCURRENCY_COUNT,
-            move |msg| {
-                let api = api.clone();
-                async move {
-                    if let Err(err) = handle_incoming_message(msg, api).await {
-                        error!(?err, "error handling websocket message");
-                    }
-                }
-            },
-        )));
+    let requests = rx_from_iface.for_each_concurrent(CONCURRENCY_COUNT, move |msg| {
+        let api = api.clone();
+        async move {
+            if let Err(err) = handle_incoming_message(msg, api).await {
+                error!(?err, "error handling websocket message");
+            }
+        }
+    });
+
+    // The connection is done once the client disconnects
+    futures::pin_mut!(signals, requests);
+    futures::future::select(signals, requests).await;
+}
+
+/// Sends the signals at the cursor to the client, in order, for as long as
+/// the app is installed and the client is connected.
+async fn send_signals(
+    signal_log: SignalLog,
+    cursor: Option<SignalCursor>,
+    signal_subscriptions: SignalSubscriptions,
+    tx_to_iface: WebsocketSender,
+    location: String,
+) {
+    // Only authenticated connections have a cursor. Others never get signals,
+    // but stay connected until the client disconnects.
+    let cursor = match cursor {
+        Some(cursor) => cursor,
+        None => return futures::future::pending().await,
+    };
+    while let Some(signals) = signal_log.next_signals(&cursor).await {
+        for signal in signals {
+            // The client resumed from elsewhere, so read again from there
+            if !cursor.advance(signal.seq) {
+                break;
+            }
+            // The clients of this interface are not subscribed to this signal
+            if !signal_subscriptions.allows(cursor.installed_app_id(), &signal.signal) {
+                trace!(msg = "Signal filtered out by subscription", ?signal);
+                continue;
+            }
+            trace!(msg = "Sending signal!", ?signal);
+            if let Err(err) = tx_to_iface.signal(signal).await {
+                error!(?err, "error emitting signal at {location}");
+            }
+        }
+    }
+    debug!("Signal log of app {} closed", cursor.installed_app_id());
+    futures::future::pending().await
 }
 
 /// Handles messages on all interfaces
@@ -274,6 +419,15 @@ where
             warn!("Unexpected Signal From Client!");
             Ok(())
         }
+        ReceiveMessage::Authenticate(_, respond) => {
+            warn!("Unexpected Authenticate From Client!");
+            respond
+                .respond(AppAuthenticationResponse::AuthenticationFailed(
+                    "The connection is already authenticated, or doesn't need to be".into(),
+                ))
+                .await?;
+            Ok(())
+        }
         ReceiveMessage::Request(data, respond) => {
             use holochain_serialized_bytes::SerializedBytesError;
             let result: A::ApiResponse = api.handle_request(Ok(data)).await?;
@@ -386,6 +540,8 @@ pub mod test {
                 driver: InterfaceDriver::Websocket {
                     port: admin_port,
                     allowed_origins: AllowedOrigins::Any,
+                    bind_address: None,
+                    tls: None,
                 },
             }])
             .await
@@ -437,6 +593,9 @@ pub mod test {
         let request = AdminRequest::AttachAppInterface {
             port: None,
             allowed_origins: AllowedOrigins::Any,
+            installed_app_id: None,
+            bind_address: None,
+            tls: None,
         };
         let response: AdminResponse = admin_tx.request(request).await.unwrap();
         let app_port = match response {
@@ -445,6 +604,24 @@ pub mod test {
         };
 
         let (app_tx, mut rx) = websocket_client_by_port(app_port).await.unwrap();
+
+        // Authenticate
+        let request = AdminRequest::IssueAppAuthenticationToken(
+            IssueAppAuthenticationTokenPayload::for_installed_app_id(
+                app_info.installed_app_id.clone(),
+            ),
+        );
+        let response: AdminResponse = admin_tx.request(request).await.unwrap();
+        let token = match response {
+            AdminResponse::AppAuthenticationTokenIssued(issued) => issued.token,
+            _ => panic!("app authentication token couldn't be issued"),
+        };
+        let response: AppAuthenticationResponse = app_tx
+            .authenticate(AppAuthenticationRequest { token })
+            .await
+            .unwrap();
+        assert_eq!(response, AppAuthenticationResponse::Authenticated);
+
         let (s_send, mut s_recv) = tokio::sync::mpsc::unbounded_channel();
         let app_rx_task = tokio::task::spawn(async move {
             while let Ok(ReceiveMessage::Signal(s)) = rx.recv::<AppResponse>().await {
@@ -480,7 +657,9 @@ pub mod test {
         }
 
         // ensure that the signal is received and is decodable
-        match Signal::try_from_vec(s_recv.recv().await.unwrap()).unwrap() {
+        let signal = SequencedSignal::try_from_vec(s_recv.recv().await.unwrap()).unwrap();
+        assert_eq!(1, signal.seq);
+        match signal.signal {
             Signal::App { signal, .. } => {
                 let expected = AppSignal::new(ExternIO::encode(TestSignal::Tested).unwrap());
                 assert_eq!(expected, signal);
@@ -983,6 +1162,9 @@ pub mod test {
         let msg = AdminRequest::AttachAppInterface {
             port: None,
             allowed_origins: AllowedOrigins::Any,
+            installed_app_id: None,
+            bind_address: None,
+            tls: None,
         };
         let msg = msg.try_into().unwrap();
         let respond = |response: AdminResponse| {
@@ -1051,6 +1233,7 @@ pub mod test {
                     .map(TestZomes::from)
                     .map(|z| z.coordinator.into_inner())
                     .collect(),
+                rate_limits: Default::default(),
             },
             zomes.into_iter().flat_map(|t| Vec::<DnaWasm>::from(t)),
         )
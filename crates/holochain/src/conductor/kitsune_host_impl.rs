@@ -6,7 +6,7 @@ mod query_size_limited_regions;
 use holochain_conductor_api::conductor::ConductorConfig;
 use kitsune_p2p_bin_data::KitsuneSpace;
 pub use query_region_op_hashes::query_region_op_hashes;
-pub use query_region_set::query_region_set;
+pub use query_region_set::{query_region_set, SpaceRegionTree};
 pub use query_size_limited_regions::query_size_limited_regions;
 
 use std::sync::Arc;
@@ -170,8 +170,10 @@ impl KitsuneHost for KitsuneHostImpl {
         async move {
             let topology = self.get_topology(space.clone()).await?;
             let db = self.spaces.dht_db(&dna_hash)?;
+            let region_tree = self.spaces.region_tree(&dna_hash)?;
             let region_set =
-                query_region_set(db, topology.clone(), &self.strat, dht_arc_set).await?;
+                query_region_set(db, region_tree, topology.clone(), &self.strat, dht_arc_set)
+                    .await?;
             Ok(region_set)
         }
         .boxed()
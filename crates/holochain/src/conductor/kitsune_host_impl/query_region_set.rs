@@ -1,6 +1,9 @@
-use std::sync::{
-    atomic::{AtomicI64, Ordering},
-    Arc,
+use std::{
+    sync::{
+        atomic::{AtomicI64, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
 };
 
 use holochain_p2p::{dht::prelude::*, dht_arc::DhtArcSet};
@@ -12,6 +15,10 @@ use crate::conductor::error::ConductorResult;
 static LAST_LOG_MS: AtomicI64 = AtomicI64::new(0);
 const LOG_RATE_MS: i64 = 1000;
 
+/// How often the region tree of a space is rebuilt from the database, to drop
+/// the data of any ops which were removed from the database.
+pub const REGION_TREE_RECONCILE_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
 /// The network module needs info about various groupings ("regions") of ops.
 ///
 /// Note that this always includes all ops regardless of integration status.
@@ -20,8 +27,12 @@ const LOG_RATE_MS: i64 = 1000;
 /// If we calculate our region hash only by integrated ops, we will experience
 /// mismatches for a large number of ops repeatedly until we have integrated
 /// those ops. Note that when *sending* ops we filter out ops in limbo.
+///
+/// The regions are answered from the in-memory region tree of the space, which
+/// is first updated with any ops added to the database since the last query.
 pub async fn query_region_set(
     db: DbWrite<DbKindDht>,
+    region_tree: SpaceRegionTree,
     topology: Topology,
     strat: &ArqStrat,
     dht_arc_set: Arc<DhtArcSet>,
@@ -67,18 +78,118 @@ pub async fn query_region_set(
     let coords = RegionCoordSetLtcs::new(times, arq_set);
 
     let region_set = db
-        .read_async(move |txn| {
-            let sql = holochain_sqlite::sql::sql_cell::FETCH_OP_REGION;
-            let mut stmt = txn.prepare_cached(sql).map_err(DatabaseError::from)?;
-            let regions = coords
-                .into_region_set(|(_, coords)| query_region_data(&mut stmt, &topology, coords))?;
-            DatabaseResult::Ok(regions)
-        })
+        .read_async(move |txn| region_tree.region_set(&txn, topology, coords))
         .await?;
 
     Ok(region_set)
 }
 
+/// The in-memory [`RegionTree`] of the ops in the DHT database of a space.
+///
+/// Rather than recomputing every region from the database on each gossip round,
+/// the tree is updated with the ops which were added to the database since it
+/// was last queried, and is periodically rebuilt from the whole database.
+#[derive(Clone, Default)]
+pub struct SpaceRegionTree(Arc<parking_lot::Mutex<Option<RegionTreeState>>>);
+
+struct RegionTreeState {
+    tree: RegionTree,
+    /// The rowid of the last op in the database which was added to the tree
+    last_rowid: i64,
+    /// When the tree was last built from the whole database
+    reconciled_at: Instant,
+}
+
+impl SpaceRegionTree {
+    fn region_set(
+        &self,
+        txn: &Transaction,
+        topology: Topology,
+        coords: RegionCoordSetLtcs,
+    ) -> DatabaseResult<RegionSetLtcs> {
+        let mut guard = self.0.lock();
+        // The state is taken out while it is being updated, so that if the
+        // update fails, the tree is rebuilt on the next query rather than being
+        // left partially updated.
+        let state = match guard.take() {
+            Some(mut state) if state.tree.topo() == &topology => {
+                if state.reconciled_at.elapsed() < REGION_TREE_RECONCILE_INTERVAL {
+                    state.catch_up(txn)?;
+                } else {
+                    state.reconcile(txn)?;
+                }
+                state
+            }
+            _ => RegionTreeState::load(txn, topology)?,
+        };
+        Ok(guard.insert(state).tree.region_set(coords))
+    }
+}
+
+impl RegionTreeState {
+    /// Build the tree from the whole database
+    fn load(txn: &Transaction, topology: Topology) -> DatabaseResult<Self> {
+        let mut state = Self {
+            tree: RegionTree::new(topology),
+            last_rowid: 0,
+            reconciled_at: Instant::now(),
+        };
+        state.catch_up(txn)?;
+        Ok(state)
+    }
+
+    /// Add the ops which were added to the database since the last update.
+    ///
+    /// Ops authored before the time origin of the topology are in no region,
+    /// so they are skipped, as the region queries on the database do.
+    fn catch_up(&mut self, txn: &Transaction) -> DatabaseResult<()> {
+        let sql = holochain_sqlite::sql::sql_cell::FETCH_OP_REGION_DATA;
+        let mut stmt = txn.prepare_cached(sql)?;
+        let mut rows = stmt.query(named_params! {
+            ":rowid": self.last_rowid,
+        })?;
+        while let Some(row) = rows.next()? {
+            self.last_rowid = row.get("rowid")?;
+            let timestamp: Timestamp = row.get("authored_timestamp")?;
+            if timestamp < self.tree.topo().time_origin {
+                continue;
+            }
+            let loc: u32 = row.get("storage_center_loc")?;
+            let action_size: i64 = row.get("action_size")?;
+            let entry_size: i64 = row.get("entry_size")?;
+            let topology = self.tree.topo();
+            let coords = SpacetimeQuantumCoords {
+                space: topology.space_quantum(loc.into()),
+                time: topology.time_quantum(timestamp),
+            };
+            let data = RegionData {
+                hash: RegionHash::from_vec(row.get("hash")?).expect("op hash must be 39 bytes"),
+                size: (action_size + entry_size).min(u32::MAX as i64) as u32,
+                count: 1,
+            };
+            self.tree.update(&coords, data);
+        }
+        Ok(())
+    }
+
+    /// Rebuild the tree from the whole database, to drop the data of any ops
+    /// which have been removed from the database.
+    fn reconcile(&mut self, txn: &Transaction) -> DatabaseResult<()> {
+        self.catch_up(txn)?;
+        let fresh = Self::load(txn, self.tree.topo().clone())?;
+        let mismatched = self.tree.reconcile(fresh.tree);
+        if mismatched > 0 {
+            tracing::debug!(
+                mismatched,
+                "Rebuilt a region tree which differed from the database"
+            );
+        }
+        self.last_rowid = fresh.last_rowid;
+        self.reconciled_at = fresh.reconciled_at;
+        Ok(())
+    }
+}
+
 pub(super) fn query_region_data(
     stmt: &mut rusqlite::CachedStatement,
     topology: &Topology,
@@ -117,6 +228,75 @@ mod tests {
     use holochain_serialized_bytes::UnsafeBytes;
     use holochain_state::{prelude::*, test_utils::test_dht_db};
 
+    /// The regions answered from the region tree must match the regions
+    /// computed by querying the database for each region, including when the
+    /// database has ops from before the time origin.
+    #[tokio::test(flavor = "multi_thread")]
+    async fn region_tree_matches_region_queries() {
+        let db = test_dht_db();
+        let now = Timestamp::now();
+        let hour = Duration::from_secs(60 * 60);
+        let topo = Topology::standard((now - hour * 24).unwrap(), Duration::ZERO);
+        let strat = ArqStrat::default();
+        let arcset = Arc::new(DhtArcSet::Full);
+
+        // Ops spread over the last day, with every fourth one authored before
+        // the time origin
+        let mk_ops = |range: std::ops::Range<u32>| {
+            range
+                .map(|i| {
+                    let hours_ago = if i % 4 == 0 { 25 + i % 3 } else { 1 + i % 23 };
+                    let entry = Entry::App(AppEntryBytes(
+                        UnsafeBytes::from(vec![i as u8; 100]).try_into().unwrap(),
+                    ));
+                    let mut create = ::fixt::fixt!(Create);
+                    create.timestamp = (now - hour * hours_ago).unwrap();
+                    let action = NewEntryAction::Create(create);
+                    DhtOpHashed::from_content_sync(DhtOp::StoreEntry(
+                        ::fixt::fixt!(Signature),
+                        action,
+                        entry,
+                    ))
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let region_tree = SpaceRegionTree::default();
+        for ops in [mk_ops(0..40), mk_ops(40..60)] {
+            db.test_write(move |txn| {
+                for op in ops.iter() {
+                    insert_op(txn, op).unwrap()
+                }
+            });
+
+            let regions = query_region_set(
+                db.to_db(),
+                region_tree.clone(),
+                topo.clone(),
+                &strat,
+                arcset.clone(),
+            )
+            .await
+            .unwrap();
+
+            let expected = db.test_read({
+                let topo = topo.clone();
+                let coords = regions.coords.clone();
+                move |txn| {
+                    let sql = holochain_sqlite::sql::sql_cell::FETCH_OP_REGION;
+                    let mut stmt = txn.prepare_cached(sql).unwrap();
+                    coords
+                        .into_region_set(|(_, coords)| query_region_data(&mut stmt, &topo, coords))
+                        .unwrap()
+                }
+            });
+            assert_eq!(regions, expected);
+
+            let sum: RegionData = regions.regions().map(|r| r.data).sum();
+            assert!(sum.count > 0);
+        }
+    }
+
     /// Ensure that the size reported by RegionData is "close enough" to the actual size of
     /// ops that get transferred over the wire.
     #[tokio::test(flavor = "multi_thread")]
@@ -127,9 +307,16 @@ mod tests {
         let strat = ArqStrat::default();
         let arcset = Arc::new(DhtArcSet::Full);
 
-        let regions_empty = query_region_set(db.to_db(), topo.clone(), &strat, arcset.clone())
-            .await
-            .unwrap();
+        let region_tree = SpaceRegionTree::default();
+        let regions_empty = query_region_set(
+            db.to_db(),
+            region_tree.clone(),
+            topo.clone(),
+            &strat,
+            arcset.clone(),
+        )
+        .await
+        .unwrap();
         {
             let sum: RegionData = regions_empty.regions().map(|r| r.data).sum();
             assert_eq!(sum.count, 0);
@@ -170,7 +357,7 @@ mod tests {
             }
         });
 
-        let regions = query_region_set(db.to_db(), topo, &strat, arcset)
+        let regions = query_region_set(db.to_db(), region_tree, topo, &strat, arcset)
             .await
             .unwrap();
 
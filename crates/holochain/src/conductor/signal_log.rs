@@ -0,0 +1,355 @@
+//! The log of signals which app interface connections read from.
+//!
+//! Every signal is given a sequence number which increases by one for every
+//! signal of an app, and is kept in a bounded in-memory buffer for that app.
+//! Each app interface connection reads from the buffer at its own pace, so
+//! slow clients don't lose signals unless they fall behind by more than the
+//! size of the buffer, and clients which reconnect can resume from the last
+//! signal they received with
+//! [`AppRequest::ResumeSignals`](holochain_conductor_api::AppRequest::ResumeSignals).
+//!
+//! The buffer is kept in memory only, and sequence numbers start from 1 again
+//! when the conductor restarts. A signal which belongs to several apps, such
+//! as a system signal, is stored once and shared by the buffers of those apps.
+
+use crate::conductor::state::ConductorState;
+use holochain_types::prelude::*;
+use holochain_types::share::RwShare;
+use holochain_types::signal::SequencedSignal;
+use holochain_types::signal::Signal;
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use tokio::sync::watch;
+use tokio::sync::Notify;
+
+/// The maximum number of signals handed out by one read.
+const READ_BATCH_SIZE: usize = 64;
+
+/// The signals of every installed app, shared by all app interfaces.
+#[derive(Clone, Debug)]
+pub struct SignalLog(RwShare<SignalLogInner>);
+
+#[derive(Debug)]
+struct SignalLogInner {
+    capacity: usize,
+    /// The apps of every cell, so that signals can be attributed to apps.
+    /// A cell can be part of several apps.
+    cell_apps: HashMap<CellId, Vec<InstalledAppId>>,
+    apps: HashMap<InstalledAppId, AppSignalLog>,
+}
+
+#[derive(Debug)]
+struct AppSignalLog {
+    next_seq: u64,
+    /// The signals of the app along with their sequence numbers.
+    buffer: VecDeque<(u64, Arc<Signal>)>,
+    /// Carries the next sequence number, to wake up readers on new signals.
+    /// Dropped when the app is removed, which ends all reads of its signals.
+    notify: watch::Sender<u64>,
+}
+
+impl AppSignalLog {
+    fn new() -> Self {
+        Self {
+            next_seq: 1,
+            buffer: VecDeque::new(),
+            notify: watch::channel(1).0,
+        }
+    }
+
+    fn oldest_seq(&self) -> u64 {
+        self.buffer
+            .front()
+            .map(|(seq, _)| *seq)
+            .unwrap_or(self.next_seq)
+    }
+}
+
+/// The position of an app interface connection in the signals of its app,
+/// i.e. the sequence number of the next signal to send to the client.
+#[derive(Clone, Debug)]
+pub struct SignalCursor {
+    installed_app_id: InstalledAppId,
+    next_seq: Arc<AtomicU64>,
+    moved: Arc<Notify>,
+}
+
+impl SignalCursor {
+    /// The app whose signals this cursor points into.
+    pub fn installed_app_id(&self) -> &InstalledAppId {
+        &self.installed_app_id
+    }
+
+    /// The sequence number of the next signal to send to the client.
+    pub fn next_seq(&self) -> u64 {
+        self.next_seq.load(Ordering::SeqCst)
+    }
+
+    /// Move past the signal with the given sequence number.
+    ///
+    /// Returns false if the cursor was moved elsewhere in the meantime,
+    /// in which case the signal must not be sent.
+    pub(crate) fn advance(&self, seq: u64) -> bool {
+        self.next_seq
+            .compare_exchange(seq, seq + 1, Ordering::SeqCst, Ordering::SeqCst)
+            .is_ok()
+    }
+
+    fn move_to(&self, seq: u64) {
+        self.next_seq.store(seq, Ordering::SeqCst);
+        self.moved.notify_one();
+    }
+}
+
+impl SignalLog {
+    /// Create a log which keeps up to `capacity` signals per app.
+    pub fn new(capacity: usize) -> Self {
+        Self(RwShare::new(SignalLogInner {
+            capacity: capacity.max(1),
+            cell_apps: HashMap::new(),
+            apps: HashMap::new(),
+        }))
+    }
+
+    /// Bring the apps in the log in line with the apps in the conductor state.
+    ///
+    /// Newly installed apps get an empty log, and the logs of removed apps
+    /// are dropped, which ends all reads of their signals.
+    pub fn refresh_apps(&self, state: &ConductorState) {
+        self.0.share_mut(|inner| {
+            let mut cell_apps: HashMap<CellId, Vec<InstalledAppId>> = HashMap::new();
+            for (installed_app_id, app) in state.installed_apps().iter() {
+                for cell_id in app.all_cells() {
+                    cell_apps
+                        .entry(cell_id.clone())
+                        .or_default()
+                        .push(installed_app_id.clone());
+                }
+                inner
+                    .apps
+                    .entry(installed_app_id.clone())
+                    .or_insert_with(AppSignalLog::new);
+            }
+            inner.apps.retain(|installed_app_id, _| {
+                state.installed_apps().contains_key(installed_app_id)
+            });
+            inner.cell_apps = cell_apps;
+        });
+    }
+
+    /// Add a signal to the log of every app it belongs to.
+    ///
+    /// Signals from a cell belong to the apps of that cell. Signals which are
+    /// not attributable to a cell belong to every app. The signal is stored
+    /// once, however many apps it belongs to.
+    pub fn record(&self, signal: &Signal) {
+        let signal = Arc::new(signal.clone());
+        self.0.share_mut(|inner| {
+            let capacity = inner.capacity;
+            let installed_app_ids: Vec<InstalledAppId> = match signal.cell_id() {
+                Some(cell_id) => inner.cell_apps.get(cell_id).cloned().unwrap_or_default(),
+                None => inner.apps.keys().cloned().collect(),
+            };
+            for installed_app_id in installed_app_ids {
+                if let Some(log) = inner.apps.get_mut(&installed_app_id) {
+                    log.buffer.push_back((log.next_seq, signal.clone()));
+                    log.next_seq += 1;
+                    while log.buffer.len() > capacity {
+                        log.buffer.pop_front();
+                    }
+                    log.notify.send_replace(log.next_seq);
+                }
+            }
+        });
+    }
+
+    /// Create a cursor into the signals of an app, pointing past the last signal
+    /// emitted so far, or `None` if the app is not installed.
+    pub fn cursor(&self, installed_app_id: &InstalledAppId) -> Option<SignalCursor> {
+        self.0.share_ref(|inner| {
+            inner.apps.get(installed_app_id).map(|log| SignalCursor {
+                installed_app_id: installed_app_id.clone(),
+                next_seq: Arc::new(AtomicU64::new(log.next_seq)),
+                moved: Arc::new(Notify::new()),
+            })
+        })
+    }
+
+    /// The sequence number of the next signal of every app.
+    pub fn next_seqs(&self) -> HashMap<InstalledAppId, u64> {
+        self.0.share_ref(|inner| {
+            inner
+                .apps
+                .iter()
+                .map(|(installed_app_id, log)| (installed_app_id.clone(), log.next_seq))
+                .collect()
+        })
+    }
+
+    /// Move a cursor back to the signal with the sequence number `from_seq`,
+    /// or to the oldest signal in the log if that signal was already dropped.
+    /// A `from_seq` past the last signal emitted so far moves the cursor to
+    /// the next signal to be emitted.
+    ///
+    /// Returns the sequence numbers of the oldest signal in the log and of
+    /// the next signal to be emitted, or `None` if the app is not installed.
+    pub fn resume(&self, cursor: &SignalCursor, from_seq: u64) -> Option<(u64, u64)> {
+        self.0.share_ref(|inner| {
+            let log = inner.apps.get(&cursor.installed_app_id)?;
+            let oldest_seq = log.oldest_seq();
+            cursor.move_to(from_seq.clamp(oldest_seq, log.next_seq));
+            Some((oldest_seq, log.next_seq))
+        })
+    }
+
+    /// Wait for signals at the position of the cursor and return them.
+    ///
+    /// The cursor is not advanced, that is up to the caller as it sends the
+    /// signals. If the cursor has fallen behind the oldest signal in the log,
+    /// it is moved to that signal. Returns `None` once the app is removed.
+    pub async fn next_signals(&self, cursor: &SignalCursor) -> Option<Vec<SequencedSignal>> {
+        loop {
+            let (signals, mut changed) = self.0.share_ref(|inner| {
+                let log = inner.apps.get(&cursor.installed_app_id)?;
+                let oldest_seq = log.oldest_seq();
+                let mut next_seq = cursor.next_seq();
+                if next_seq < oldest_seq {
+                    tracing::warn!(
+                        installed_app_id = %cursor.installed_app_id,
+                        dropped = oldest_seq - next_seq,
+                        "An app interface client fell behind and missed signals"
+                    );
+                    cursor.move_to(oldest_seq);
+                    next_seq = oldest_seq;
+                }
+                let skip = (next_seq - oldest_seq) as usize;
+                let signals: Vec<_> = log
+                    .buffer
+                    .iter()
+                    .skip(skip)
+                    .take(READ_BATCH_SIZE)
+                    .map(|(seq, signal)| SequencedSignal {
+                        seq: *seq,
+                        signal: signal.as_ref().clone(),
+                    })
+                    .collect();
+                Some((signals, log.notify.subscribe()))
+            })?;
+            if !signals.is_empty() {
+                return Some(signals);
+            }
+            tokio::select! {
+                changed = changed.changed() => {
+                    if changed.is_err() {
+                        return None;
+                    }
+                }
+                _ = cursor.moved.notified() => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use holochain_types::signal::test_signal;
+
+    fn log_with_app(capacity: usize) -> SignalLog {
+        let log = SignalLog::new(capacity);
+        log.0.share_mut(|inner| {
+            inner.apps.insert("app".to_string(), AppSignalLog::new());
+        });
+        log
+    }
+
+    fn seqs(signals: &[SequencedSignal]) -> Vec<u64> {
+        signals.iter().map(|signal| signal.seq).collect()
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn signals_are_sequenced_and_can_be_resumed() {
+        let log = log_with_app(10);
+        let cursor = log.cursor(&"app".to_string()).unwrap();
+        assert_eq!(1, cursor.next_seq());
+
+        for i in 0..3 {
+            log.record(&test_signal(&i.to_string()));
+        }
+        let signals = log.next_signals(&cursor).await.unwrap();
+        assert_eq!(vec![1, 2, 3], seqs(&signals));
+        for signal in &signals {
+            assert!(cursor.advance(signal.seq));
+        }
+        assert_eq!(4, cursor.next_seq());
+
+        assert_eq!(Some((1, 4)), log.resume(&cursor, 2));
+        let signals = log.next_signals(&cursor).await.unwrap();
+        assert_eq!(vec![2, 3], seqs(&signals));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn cursors_skip_dropped_signals() {
+        let log = log_with_app(2);
+        let cursor = log.cursor(&"app".to_string()).unwrap();
+
+        for i in 0..5 {
+            log.record(&test_signal(&i.to_string()));
+        }
+        let signals = log.next_signals(&cursor).await.unwrap();
+        assert_eq!(vec![4, 5], seqs(&signals));
+
+        assert_eq!(Some((4, 6)), log.resume(&cursor, 1));
+        assert_eq!(4, cursor.next_seq());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn resuming_past_the_last_signal_waits_for_the_next_one() {
+        let log = log_with_app(10);
+        let cursor = log.cursor(&"app".to_string()).unwrap();
+
+        log.record(&test_signal("0"));
+        assert_eq!(Some((1, 2)), log.resume(&cursor, 100));
+        assert_eq!(2, cursor.next_seq());
+
+        log.record(&test_signal("1"));
+        let signals = log.next_signals(&cursor).await.unwrap();
+        assert_eq!(vec![2], seqs(&signals));
+    }
+
+    #[test]
+    fn signals_of_several_apps_are_stored_once() {
+        let log = log_with_app(10);
+        log.0.share_mut(|inner| {
+            inner.apps.insert("other".to_string(), AppSignalLog::new());
+        });
+
+        log.record(&test_signal("system"));
+        log.0.share_ref(|inner| {
+            let app = &inner.apps[&"app".to_string()].buffer[0];
+            let other = &inner.apps[&"other".to_string()].buffer[0];
+            assert_eq!((1, 1), (app.0, other.0));
+            assert!(Arc::ptr_eq(&app.1, &other.1));
+        });
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn reads_wait_for_new_signals() {
+        let log = log_with_app(10);
+        let cursor = log.cursor(&"app".to_string()).unwrap();
+
+        let read = tokio::spawn({
+            let log = log.clone();
+            let cursor = cursor.clone();
+            async move { log.next_signals(&cursor).await }
+        });
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        log.record(&test_signal("late"));
+
+        let signals = read.await.unwrap().unwrap();
+        assert_eq!(vec![1], seqs(&signals));
+    }
+}
@@ -10,6 +10,7 @@ use std::{
 use super::{
     conductor::RwShare,
     error::ConductorResult,
+    kitsune_host_impl::SpaceRegionTree,
     p2p_agent_store::{self, P2pBatch},
 };
 use crate::conductor::{error::ConductorError, state::ConductorState};
@@ -35,7 +36,7 @@ use holochain_p2p::{
 };
 use holochain_sqlite::prelude::{
     DatabaseResult, DbKindAuthored, DbKindCache, DbKindConductor, DbKindDht, DbKindP2pAgents,
-    DbKindP2pMetrics, DbKindWasm, DbSyncLevel, DbSyncStrategy, DbWrite, ReadAccess,
+    DbKindP2pMetrics, DbKindWasm, DbMasterKey, DbSyncLevel, DbSyncStrategy, DbWrite, ReadAccess,
 };
 use holochain_state::{
     host_fn_workspace::SourceChainWorkspace,
@@ -66,6 +67,10 @@ pub struct Spaces {
     pub(crate) queue_consumer_map: QueueConsumerMap,
     pub(crate) conductor_db: DbWrite<DbKindConductor>,
     pub(crate) wasm_db: DbWrite<DbKindWasm>,
+    /// The key the keys of all databases are derived from.
+    pub(crate) db_master_key: Arc<parking_lot::RwLock<DbMasterKey>>,
+    /// Serializes rotations of the master key.
+    pub(crate) db_key_rotation_lock: Arc<tokio::sync::Mutex<()>>,
 }
 
 #[derive(Clone)]
@@ -104,6 +109,9 @@ pub struct Space {
     /// A cache for slow database queries.
     pub dht_query_cache: DhtDbQueryCache,
 
+    /// The in-memory region data of the dht database, used for gossip.
+    pub region_tree: SpaceRegionTree,
+
     /// Countersigning workspace that is shared across this cell.
     pub countersigning_workspace: CountersigningWorkspace,
 
@@ -114,6 +122,8 @@ pub struct Space {
     pub incoming_ops_batch: IncomingOpsBatch,
 
     root_db_dir: Arc<PathBuf>,
+
+    db_master_key: Arc<parking_lot::RwLock<DbMasterKey>>,
 }
 
 #[cfg(test)]
@@ -130,7 +140,7 @@ pub struct TestSpace {
 
 impl Spaces {
     /// Create a new empty set of [`DnaHash`] spaces.
-    pub fn new(config: Arc<ConductorConfig>) -> ConductorResult<Self> {
+    pub fn new(config: Arc<ConductorConfig>, db_master_key: DbMasterKey) -> ConductorResult<Self> {
         let root_db_dir: DatabasesRootPath = config
             .data_root_path
             .clone()
@@ -141,10 +151,18 @@ impl Spaces {
             DbSyncStrategy::Fast => DbSyncLevel::Off,
             DbSyncStrategy::Resilient => DbSyncLevel::Normal,
         };
-        let conductor_db =
-            DbWrite::open_with_sync_level(root_db_dir.as_ref(), DbKindConductor, db_sync_level)?;
-        let wasm_db =
-            DbWrite::open_with_sync_level(root_db_dir.as_ref(), DbKindWasm, db_sync_level)?;
+        let conductor_db = DbWrite::open_with_key(
+            root_db_dir.as_ref(),
+            DbKindConductor,
+            db_sync_level,
+            &db_master_key,
+        )?;
+        let wasm_db = DbWrite::open_with_key(
+            root_db_dir.as_ref(),
+            DbKindWasm,
+            db_sync_level,
+            &db_master_key,
+        )?;
         Ok(Spaces {
             map: RwShare::new(HashMap::new()),
             db_dir: Arc::new(root_db_dir),
@@ -152,9 +170,36 @@ impl Spaces {
             queue_consumer_map: QueueConsumerMap::new(),
             conductor_db,
             wasm_db,
+            db_master_key: Arc::new(parking_lot::RwLock::new(db_master_key)),
+            db_key_rotation_lock: Arc::new(tokio::sync::Mutex::new(())),
         })
     }
 
+    /// Rekey every database, open or not, with the keys derived from this
+    /// master key.
+    pub(crate) async fn rekey_databases(&self, master_key: &DbMasterKey) -> DatabaseResult<()> {
+        self.conductor_db.rekey(master_key).await?;
+        self.wasm_db.rekey(master_key).await?;
+        let spaces: Vec<Space> = self
+            .map
+            .share_ref(|spaces| spaces.values().cloned().collect());
+        for space in spaces {
+            space.cache_db.rekey(master_key).await?;
+            space.dht_db.rekey(master_key).await?;
+            space.p2p_agents_db.rekey(master_key).await?;
+            space.p2p_metrics_db.rekey(master_key).await?;
+            for authored_db in space.get_all_authored_dbs() {
+                authored_db.rekey(master_key).await?;
+            }
+        }
+        let db_dir = self.db_dir.clone();
+        let master_key = master_key.clone();
+        tokio::task::spawn_blocking(move || {
+            holochain_sqlite::db::rekey_closed_databases(db_dir.as_path(), &master_key)
+        })
+        .await?
+    }
+
     /// Block some target.
     pub async fn block(&self, input: Block) -> DatabaseResult<()> {
         holochain_state::block::block(&self.conductor_db, input).await
@@ -216,43 +261,49 @@ impl Spaces {
                 )
                 .await?
             }
-            // @todo
+            // IPs are only ever blocked directly
             BlockTargetId::Ip(_) => {
                 vec![]
             }
         };
 
-        // If node_agents_in_spaces is not yet initialized, we can't know anything about
-        // which cells are blocked, so avoid the race condition by returning false
-        // TODO: actually fix the preflight, because this could be a loophole for someone
-        //       to evade a block in some circumstances
-        if cell_ids.is_empty() {
-            return Ok(false);
-        }
-
         self.conductor_db
             .read_async(move |txn| {
-                Ok(
-                    // If the target_id is directly blocked then we always return true.
-                    holochain_state::block::query_is_blocked(&txn, target_id, timestamp)?
-            // If there are zero unblocked cells then return true.
-            || {
-                let mut all_blocked_cell_ids = true;
+                // If the target_id is directly blocked then we always return true.
+                if holochain_state::block::query_is_blocked(&txn, target_id, timestamp)? {
+                    return Ok(true);
+                }
+
+                // If node_agents_in_spaces is not yet initialized, we can't know anything about
+                // which cells are blocked, so avoid the race condition by returning false
+                // TODO: actually fix the preflight, because this could be a loophole for someone
+                //       to evade a block in some circumstances
+                if cell_ids.is_empty() {
+                    return Ok(false);
+                }
+
+                // If there are zero unblocked cells then return true.
                 for cell_id in cell_ids {
                     if !holochain_state::block::query_is_blocked(
                         &txn,
-                        BlockTargetId::Cell(cell_id), timestamp)? {
-                            all_blocked_cell_ids = false;
-                            break;
-                        }
+                        BlockTargetId::Cell(cell_id),
+                        timestamp,
+                    )? {
+                        return Ok(false);
+                    }
                 }
-                all_blocked_cell_ids
-            },
-                )
+                Ok(true)
             })
             .await
     }
 
+    /// List all blocks which have not ended by the given timestamp.
+    pub async fn list_blocks(&self, timestamp: Timestamp) -> DatabaseResult<Vec<BlockSpan>> {
+        self.conductor_db
+            .read_async(move |txn| holochain_state::block::query_block_spans(&txn, timestamp))
+            .await
+    }
+
     /// Get the holochain conductor state
     pub async fn get_state(&self) -> ConductorResult<ConductorState> {
         match query_conductor_state(&self.conductor_db).await? {
@@ -327,6 +378,7 @@ impl Spaces {
                             Arc::new(dna_hash.clone()),
                             self.db_dir.to_path_buf(),
                             self.config.db_sync_strategy,
+                            self.db_master_key.clone(),
                         )?;
 
                         let r = f(&space);
@@ -366,6 +418,11 @@ impl Spaces {
         self.get_or_create_space_ref(dna_hash, |space| space.dht_db.clone())
     }
 
+    /// Get the region tree of the dht database (this will create the space if it doesn't already exist).
+    pub fn region_tree(&self, dna_hash: &DnaHash) -> DatabaseResult<SpaceRegionTree> {
+        self.get_or_create_space_ref(dna_hash, |space| space.region_tree.clone())
+    }
+
     /// Get the peer database (this will create the space if it doesn't already exist).
     pub fn p2p_agents_db(&self, dna_hash: &DnaHash) -> DatabaseResult<DbWrite<DbKindP2pAgents>> {
         self.get_or_create_space_ref(dna_hash, |space| space.p2p_agents_db.clone())
@@ -646,7 +703,7 @@ impl Spaces {
                 // If the workflow has not been spawned yet we can't handle incoming messages.
                 None => return Ok(()),
             };
-            incoming_countersigning(ops, &workspace, trigger)?;
+            incoming_countersigning(ops, &workspace, trigger).await?;
         } else {
             let space = self.get_or_create_space(dna_hash)?;
             let trigger = match self
@@ -680,34 +737,44 @@ impl Space {
         dna_hash: Arc<DnaHash>,
         root_db_dir: PathBuf,
         db_sync_strategy: DbSyncStrategy,
+        db_master_key: Arc<parking_lot::RwLock<DbMasterKey>>,
     ) -> DatabaseResult<Self> {
+        let master_key = db_master_key.read().clone();
         let space = dna_hash.to_kitsune();
         let db_sync_level = match db_sync_strategy {
             DbSyncStrategy::Fast => DbSyncLevel::Off,
             DbSyncStrategy::Resilient => DbSyncLevel::Normal,
         };
-        let cache = DbWrite::open_with_sync_level(
+        let cache = DbWrite::open_with_key(
             root_db_dir.as_ref(),
             DbKindCache(dna_hash.clone()),
             db_sync_level,
+            &master_key,
         )?;
-        let dht_db = DbWrite::open_with_sync_level(
+        let dht_db = DbWrite::open_with_key(
             root_db_dir.as_ref(),
             DbKindDht(dna_hash.clone()),
             db_sync_level,
+            &master_key,
         )?;
-        let p2p_agents_db = DbWrite::open_with_sync_level(
+        let p2p_agents_db = DbWrite::open_with_key(
             root_db_dir.as_ref(),
             DbKindP2pAgents(space.clone()),
             db_sync_level,
+            &master_key,
         )?;
-        let p2p_metrics_db = DbWrite::open_with_sync_level(
+        let p2p_metrics_db = DbWrite::open_with_key(
             root_db_dir.as_ref(),
             DbKindP2pMetrics(space),
             db_sync_level,
+            &master_key,
+        )?;
+        let conductor_db: DbWrite<DbKindConductor> = DbWrite::open_with_key(
+            root_db_dir.as_ref(),
+            DbKindConductor,
+            db_sync_level,
+            &master_key,
         )?;
-        let conductor_db: DbWrite<DbKindConductor> =
-            DbWrite::open_with_sync_level(root_db_dir.as_ref(), DbKindConductor, db_sync_level)?;
 
         let (tx, rx) = tokio::sync::mpsc::channel(100);
         tokio::spawn(p2p_agent_store::p2p_put_all_batch(
@@ -716,7 +783,7 @@ impl Space {
         ));
         let p2p_batch_sender = tx;
 
-        let countersigning_workspace = CountersigningWorkspace::new();
+        let countersigning_workspace = CountersigningWorkspace::new(dht_db.clone());
         let incoming_op_hashes = IncomingOpHashes::default();
         let incoming_ops_batch = IncomingOpsBatch::default();
         let dht_query_cache = DhtDbQueryCache::new(dht_db.clone().into());
@@ -732,8 +799,10 @@ impl Space {
             incoming_op_hashes,
             incoming_ops_batch,
             dht_query_cache,
+            region_tree: SpaceRegionTree::default(),
             conductor_db,
             root_db_dir: Arc::new(root_db_dir),
+            db_master_key,
         };
         Ok(r)
     }
@@ -781,10 +850,11 @@ impl Space {
         match self.authored_dbs.lock().entry(author.clone()) {
             hash_map::Entry::Occupied(entry) => Ok(entry.get().clone()),
             hash_map::Entry::Vacant(entry) => {
-                let db = DbWrite::open_with_sync_level(
+                let db = DbWrite::open_with_key(
                     self.root_db_dir.as_ref(),
                     DbKindAuthored(Arc::new(CellId::new((*self.dna_hash).clone(), author))),
                     DbSyncLevel::Normal,
+                    &self.db_master_key.read(),
                 )?;
 
                 entry.insert(db.clone());
@@ -842,6 +912,7 @@ impl TestSpaces {
                 ..Default::default()
             }
             .into(),
+            Default::default(),
         )
         .unwrap();
         spaces.map.share_mut(|map| {
@@ -872,6 +943,7 @@ impl TestSpace {
                 Arc::new(dna_hash),
                 temp_dir.path().to_path_buf(),
                 Default::default(),
+                Default::default(),
             )
             .unwrap(),
             _temp_dir: temp_dir,
@@ -44,6 +44,7 @@ async fn test_region_queries() {
             ..Default::default()
         }
         .into(),
+        Default::default(),
     )
     .unwrap();
     let keystore = test_keystore();
@@ -79,10 +80,18 @@ async fn test_region_queries() {
     let db = spaces.dht_db(dna_def.as_hash()).unwrap();
     let mut ops = vec![];
 
+    let region_tree = spaces.region_tree(dna_def.as_hash()).unwrap();
+
     // - Check that we have no ops to begin with
-    let region_set = query_region_set(db.clone(), topo.clone(), &strat, Arc::new(DhtArcSet::Full))
-        .await
-        .unwrap();
+    let region_set = query_region_set(
+        db.clone(),
+        region_tree.clone(),
+        topo.clone(),
+        &strat,
+        Arc::new(DhtArcSet::Full),
+    )
+    .await
+    .unwrap();
     let region_sum: RegionData = region_set.regions().map(|r| r.data).sum();
     assert_eq!(region_sum.count as usize, 0);
 
@@ -107,9 +116,28 @@ async fn test_region_queries() {
         let op2 = DhtOpHashed::from_content_sync(op2);
         fill_db(&db, op2).await;
     }
-    let region_set = query_region_set(db.clone(), topo.clone(), &strat, Arc::new(DhtArcSet::Full))
-        .await
-        .unwrap();
+    // The space's region tree only adds the ops inserted since the last query
+    let region_set = query_region_set(
+        db.clone(),
+        region_tree,
+        topo.clone(),
+        &strat,
+        Arc::new(DhtArcSet::Full),
+    )
+    .await
+    .unwrap();
+
+    // - Check that the updated tree matches a tree built from scratch
+    let fresh_region_set = query_region_set(
+        db.clone(),
+        Default::default(),
+        topo.clone(),
+        &strat,
+        Arc::new(DhtArcSet::Full),
+    )
+    .await
+    .unwrap();
+    assert_eq!(region_set, fresh_region_set);
 
     // - Check that the aggregate of all region data matches expectations
     let region_sum: RegionData = region_set.regions().map(|r| r.data).sum();
@@ -9,6 +9,7 @@ use holochain_types::websocket::AllowedOrigins;
 use serde::Deserialize;
 use serde::Serialize;
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::sync::Arc;
 
 use super::error::{ConductorError, ConductorResult};
@@ -191,6 +192,43 @@ impl ConductorState {
         Ok((app, delta))
     }
 
+    /// Find the other apps which use existing cells created by the given app.
+    pub fn dependent_apps(&self, id: &InstalledAppId) -> ConductorResult<Vec<InstalledAppId>> {
+        let provided: HashSet<&CellId> = self
+            .get_app(id)?
+            .own_provisioned_cells()
+            .map(|(_, cell_id)| cell_id)
+            .collect();
+        Ok(self
+            .installed_apps
+            .iter()
+            .filter(|(other_id, app)| {
+                *other_id != id
+                    && app
+                        .existing_cells()
+                        .any(|(_, cell_id)| provided.contains(cell_id))
+            })
+            .map(|(other_id, _)| other_id.clone())
+            .collect())
+    }
+
+    /// Find the apps which depend on the given app, directly or through other
+    /// dependents, in the order they were found.
+    pub fn all_dependent_apps(&self, id: &InstalledAppId) -> ConductorResult<Vec<InstalledAppId>> {
+        let mut found = vec![id.clone()];
+        let mut i = 0;
+        while i < found.len() {
+            for dependent in self.dependent_apps(&found[i])? {
+                if !found.contains(&dependent) {
+                    found.push(dependent);
+                }
+            }
+            i += 1;
+        }
+        found.remove(0);
+        Ok(found)
+    }
+
     /// Returns the interface configuration with the given ID if present
     pub fn interface_by_id(&self, id: &AppInterfaceId) -> Option<AppInterfaceConfig> {
         self.app_interfaces.get(id).cloned()
@@ -220,9 +258,7 @@ impl ConductorState {
 /// GUIs, browser based web UIs, local native UIs, other local applications and scripts.
 /// We currently have:
 /// * websockets
-///
-/// We will also soon develop
-/// * Unix domain sockets
+/// * websockets over Unix domain sockets
 ///
 /// The cells (referenced by ID) that are to be made available via that interface should be listed.
 #[derive(Clone, Deserialize, Serialize, Debug)]
@@ -233,17 +269,29 @@ pub struct AppInterfaceConfig {
 
     /// The driver for the interface, e.g. Websocket
     pub driver: InterfaceDriver,
+
+    /// The app this interface is bound to, if any.
+    /// A bound interface only accepts clients authenticated for that app.
+    #[serde(default)]
+    pub installed_app_id: Option<InstalledAppId>,
 }
 
 impl AppInterfaceConfig {
     /// Create config for a websocket interface
-    pub fn websocket(port: u16, allowed_origins: AllowedOrigins) -> Self {
+    pub fn websocket(
+        port: u16,
+        allowed_origins: AllowedOrigins,
+        installed_app_id: Option<InstalledAppId>,
+    ) -> Self {
         Self {
             signal_subscriptions: HashMap::new(),
             driver: InterfaceDriver::Websocket {
                 port,
                 allowed_origins,
+                bind_address: None,
+                tls: None,
             },
+            installed_app_id,
         }
     }
 }
@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use holochain_conductor_api::config::conductor::DpkiConfig;
+use holochain_types::inline_zome::InlineZomeSet;
+use holochain_types::prelude::*;
+use holochain_wasm_test_utils::TestWasm;
+use matches::assert_matches;
+use parking_lot::Mutex;
+
+use crate::conductor::api::error::ConductorApiError;
+use crate::conductor::conductor::{KeyMutationInput, KeyState, KeyStateInput, DPKI_ZOME_NAME};
+use crate::conductor::error::ConductorError;
+use crate::sweettest::{
+    SweetAgents, SweetConductor, SweetConductorBatch, SweetConductorConfig, SweetDnaFile,
+};
+use crate::wait_for_1m;
+
+/// A DPKI DNA which keeps its key registry in memory
+fn dpki_zomes(registry: Arc<Mutex<HashMap<AgentPubKey, KeyState>>>) -> InlineZomeSet {
+    InlineZomeSet::new_unique_single("integrity", DPKI_ZOME_NAME, vec![], 0)
+        .function(DPKI_ZOME_NAME, "key_state", {
+            let registry = registry.clone();
+            move |_, input: KeyStateInput| {
+                Ok(registry
+                    .lock()
+                    .get(&input.key)
+                    .cloned()
+                    .unwrap_or(KeyState::NotFound))
+            }
+        })
+        .function(DPKI_ZOME_NAME, "key_mutation", {
+            move |_, input: KeyMutationInput| {
+                let mut registry = registry.lock();
+                if let Some(old_key) = input.old_key {
+                    registry.insert(old_key, KeyState::Invalidated);
+                }
+                if let Some((new_key, _)) = input.new_key {
+                    registry.insert(new_key, KeyState::Valid);
+                }
+                Ok(())
+            }
+        })
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn revoking_agent_key_disables_its_apps() {
+    holochain_trace::test_run().unwrap();
+
+    let mut config = SweetConductorConfig::standard();
+    config.dpki = Some(DpkiConfig {
+        instance_id: "dpki".into(),
+        init_params: "".into(),
+    });
+    let mut conductor = SweetConductor::from_config(config).await;
+
+    let registry = Arc::new(Mutex::new(HashMap::new()));
+    let (dpki_dna, _, _) =
+        SweetDnaFile::unique_from_inline_zomes(dpki_zomes(registry.clone())).await;
+    conductor.setup_app("dpki", [&dpki_dna]).await.unwrap();
+
+    let (dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Create]).await;
+    let (revoked, kept) = SweetAgents::two(conductor.keystore()).await;
+
+    // Installing an app registers its agent key with DPKI
+    conductor
+        .setup_app_for_agent("revoked", revoked.clone(), [&dna])
+        .await
+        .unwrap();
+    conductor
+        .setup_app_for_agent("kept", kept.clone(), [&dna])
+        .await
+        .unwrap();
+    assert_eq!(registry.lock().get(&revoked), Some(&KeyState::Valid));
+    assert_eq!(registry.lock().get(&kept), Some(&KeyState::Valid));
+
+    conductor
+        .raw_handle()
+        .revoke_agent_key(revoked.clone())
+        .await
+        .unwrap();
+    assert_eq!(registry.lock().get(&revoked), Some(&KeyState::Invalidated));
+
+    let state = conductor.get_state_from_handle().await.unwrap();
+    let status = |id: &str| state.get_app(&id.to_string()).unwrap().status().clone();
+    assert!(matches!(
+        status("revoked"),
+        AppStatus::Disabled(DisabledAppReason::Error(_))
+    ));
+    assert_eq!(status("kept"), AppStatus::Running);
+
+    // A revoked key is not registered again, and can't be used for new apps
+    let (other_dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Create]).await;
+    assert_matches!(
+        conductor
+            .setup_app_for_agent("revoked_again", revoked.clone(), [&other_dna])
+            .await,
+        Err(ConductorApiError::ConductorError(
+            ConductorError::AgentKeyInvalidated(key)
+        )) if key == revoked
+    );
+    assert_eq!(registry.lock().get(&revoked), Some(&KeyState::Invalidated));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn dpki_installed_after_the_app_checks_authors() {
+    holochain_trace::test_run().unwrap();
+
+    let mut config = SweetConductorConfig::standard();
+    config.dpki = Some(DpkiConfig {
+        instance_id: "dpki".into(),
+        init_params: "".into(),
+    });
+    let mut conductors =
+        SweetConductorBatch::from_configs([config, SweetConductorConfig::standard()]).await;
+
+    let (dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Create]).await;
+    let apps = conductors.setup_app("app", [&dna]).await.unwrap();
+    let ((alice,), (bob,)) = apps.into_tuples();
+    conductors.exchange_peer_info().await;
+
+    // DPKI is only installed once the app is running, and doesn't know bob's key.
+    let registry = Arc::new(Mutex::new(HashMap::new()));
+    let (dpki_dna, _, _) =
+        SweetDnaFile::unique_from_inline_zomes(dpki_zomes(registry.clone())).await;
+    conductors[0].setup_app("dpki", [&dpki_dna]).await.unwrap();
+
+    let _: ActionHash = conductors[1]
+        .call(&bob.zome(TestWasm::Create), "create_entry", ())
+        .await;
+
+    // Alice's conductor checks bob's key with DPKI, and rejects his new ops.
+    let bob_key = bob.agent_pubkey().clone();
+    let rejected_ops_by_bob = || {
+        let bob_key = bob_key.clone();
+        alice.dht_db().test_read(move |txn| {
+            txn.query_row(
+                "
+                SELECT COUNT(DhtOp.hash) FROM DhtOp
+                JOIN Action ON DhtOp.action_hash = Action.hash
+                WHERE Action.author = :author AND DhtOp.validation_status = :rejected
+                ",
+                rusqlite::named_params! {
+                    ":author": bob_key,
+                    ":rejected": ValidationStatus::Rejected,
+                },
+                |row| row.get::<_, usize>(0),
+            )
+            .unwrap()
+        })
+    };
+    wait_for_1m!(rejected_ops_by_bob(), |count: &usize| *count > 0, |count| {
+        assert!(count > 0, "no ops by bob were rejected")
+    });
+}
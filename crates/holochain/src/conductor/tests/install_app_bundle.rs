@@ -2,7 +2,7 @@
 use std::{collections::HashMap, path::PathBuf};
 
 use crate::conductor::api::error::ConductorApiError;
-use crate::{conductor::error::ConductorError, sweettest::*};
+use crate::{assert_eq_retry_10s, conductor::error::ConductorError, sweettest::*};
 use ::fixt::prelude::strum_macros;
 use holo_hash::{AgentPubKey, DnaHash};
 use holochain_types::prelude::*;
@@ -32,6 +32,7 @@ async fn clone_only_provisioning_creates_no_cell_and_allows_cloning() {
             name: "name".into(),
             dna: AppRoleDnaManifest {
                 location: Some(DnaLocation::Bundled(path.clone())),
+                sha256: None,
                 modifiers: modifiers.clone(),
                 installed_hash: Some(installed_dna_hash.into()),
                 clone_limit,
@@ -135,6 +136,423 @@ async fn clone_only_provisioning_creates_no_cell_and_allows_cloning() {
     // TODO: test that the cell can't be provisioned later
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn deferred_provisioning_creates_cell_on_request() {
+    holochain_trace::test_run().ok();
+
+    let conductor = SweetConductor::from_standard_config().await;
+    let alice = SweetAgents::one(conductor.keystore()).await;
+
+    let (dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Create]).await;
+    let path = PathBuf::from(format!("{}", dna.dna_hash()));
+    let installed_dna_hash = DnaHash::with_data_sync(dna.dna_def());
+    let cell_id = CellId::new(dna.dna_hash().to_owned(), alice.clone());
+
+    let roles = vec![AppRoleManifest {
+        name: "name".into(),
+        dna: AppRoleDnaManifest {
+            location: Some(DnaLocation::Bundled(path.clone())),
+            sha256: None,
+            modifiers: DnaModifiersOpt::none(),
+            installed_hash: Some(installed_dna_hash.into()),
+            clone_limit: 0,
+        },
+        provisioning: Some(CellProvisioning::Create { deferred: true }),
+    }];
+    let manifest = AppManifestCurrentBuilder::default()
+        .name("test_app".into())
+        .description(None)
+        .roles(roles)
+        .build()
+        .unwrap();
+    let resources = vec![(path, DnaBundle::from_dna_file(dna.clone()).unwrap())];
+    let bundle = AppBundle::new(manifest.into(), resources, PathBuf::from("."))
+        .await
+        .unwrap();
+
+    conductor
+        .clone()
+        .install_app_bundle(InstallAppPayload {
+            agent_key: alice.clone(),
+            source: AppBundleSource::Bundle(bundle),
+            installed_app_id: Some("app_1".into()),
+            network_seed: None,
+            membrane_proofs: HashMap::new(),
+            #[cfg(feature = "chc")]
+            ignore_genesis_failure: false,
+        })
+        .await
+        .unwrap();
+    conductor.enable_app("app_1".into()).await.unwrap();
+
+    // The deferred role has no cell yet and is reported as a stem cell.
+    assert!(!conductor.running_cell_ids().contains(&cell_id));
+    let app_info = conductor
+        .get_app_info(&"app_1".to_string())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_matches!(
+        app_info.cell_info["name"].as_slice(),
+        [holochain_conductor_api::CellInfo::Stem(stem)] if stem.original_dna_hash == *dna.dna_hash()
+    );
+
+    // Of two concurrent requests, only one provisions the cell.
+    let provision = || {
+        conductor
+            .raw_handle()
+            .provision_deferred_cell(ProvisionDeferredCellPayload {
+                app_id: "app_1".into(),
+                role_name: "name".into(),
+                membrane_proof: None,
+            })
+    };
+    let (provisioned_cell, refused) = match futures::future::join(provision(), provision()).await {
+        (Ok(cell), Err(e)) | (Err(e), Ok(cell)) => (cell, e),
+        results => panic!("Expected exactly one provisioning to succeed, got {results:?}"),
+    };
+    assert_matches!(
+        refused,
+        ConductorError::AppError(AppError::RoleNotDeferred(_))
+    );
+    assert_eq!(provisioned_cell.cell_id, cell_id);
+    assert!(conductor.running_cell_ids().contains(&cell_id));
+    let app_info = conductor
+        .get_app_info(&"app_1".to_string())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_matches!(
+        app_info.cell_info["name"].as_slice(),
+        [holochain_conductor_api::CellInfo::Provisioned(cell)] if cell.cell_id == cell_id
+    );
+
+    // The cell can only be provisioned once.
+    let result = conductor
+        .raw_handle()
+        .provision_deferred_cell(ProvisionDeferredCellPayload {
+            app_id: "app_1".into(),
+            role_name: "name".into(),
+            membrane_proof: None,
+        })
+        .await;
+    assert_matches!(
+        result,
+        Err(ConductorError::AppError(AppError::RoleNotDeferred(_)))
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn use_existing_cell_and_refuse_to_uninstall_its_provider() {
+    holochain_trace::test_run().ok();
+
+    let conductor = SweetConductor::from_standard_config().await;
+    let alice = SweetAgents::one(conductor.keystore()).await;
+
+    let (dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Create]).await;
+    let cell_id = CellId::new(dna.dna_hash().to_owned(), alice.clone());
+
+    let bundle = |provisioning: CellProvisioning| {
+        let dna = dna.clone();
+        async move {
+            let path = PathBuf::from(format!("{}", dna.dna_hash()));
+            let roles = vec![AppRoleManifest {
+                name: "profiles".into(),
+                dna: AppRoleDnaManifest {
+                    location: Some(DnaLocation::Bundled(path.clone())),
+                    sha256: None,
+                    modifiers: DnaModifiersOpt::none(),
+                    installed_hash: Some(DnaHash::with_data_sync(dna.dna_def()).into()),
+                    clone_limit: 0,
+                },
+                provisioning: Some(provisioning),
+            }];
+            let manifest = AppManifestCurrentBuilder::default()
+                .name("test_app".into())
+                .description(None)
+                .roles(roles)
+                .build()
+                .unwrap();
+            let resources = vec![(path, DnaBundle::from_dna_file(dna).unwrap())];
+            AppBundle::new(manifest.into(), resources, PathBuf::from("."))
+                .await
+                .unwrap()
+        }
+    };
+    let install = |app_id: &'static str, bundle: AppBundle| {
+        conductor
+            .raw_handle()
+            .install_app_bundle(InstallAppPayload {
+                agent_key: alice.clone(),
+                source: AppBundleSource::Bundle(bundle),
+                installed_app_id: Some(app_id.into()),
+                network_seed: None,
+                membrane_proofs: HashMap::new(),
+                #[cfg(feature = "chc")]
+                ignore_genesis_failure: false,
+            })
+    };
+
+    // There is no cell to use yet.
+    let result = install(
+        "consumer",
+        bundle(CellProvisioning::UseExisting { deferred: false }).await,
+    )
+    .await;
+    assert!(result.is_err());
+
+    install(
+        "provider",
+        bundle(CellProvisioning::Create { deferred: false }).await,
+    )
+    .await
+    .unwrap();
+    install(
+        "consumer",
+        bundle(CellProvisioning::UseExisting { deferred: false }).await,
+    )
+    .await
+    .unwrap();
+    install(
+        "other_consumer",
+        bundle(CellProvisioning::CreateIfNotExists { deferred: false }).await,
+    )
+    .await
+    .unwrap();
+
+    let state = conductor.get_state().await.unwrap();
+    for app_id in ["provider", "consumer", "other_consumer"] {
+        let app = state.get_app(&app_id.to_string()).unwrap();
+        assert_eq!(app.all_cells().collect::<Vec<_>>(), vec![&cell_id]);
+    }
+
+    // The provider can't be uninstalled while other apps use its cell.
+    let result = conductor
+        .raw_handle()
+        .uninstall_app(&"provider".to_string(), false)
+        .await;
+    match result {
+        Err(ConductorError::AppHasDependents(id, mut dependents)) => {
+            dependents.sort();
+            assert_eq!(id, "provider");
+            assert_eq!(dependents, vec!["consumer", "other_consumer"]);
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    for app_id in ["provider", "consumer", "other_consumer"] {
+        conductor
+            .raw_handle()
+            .enable_app(app_id.into())
+            .await
+            .unwrap();
+    }
+    assert!(conductor.running_cell_ids().contains(&cell_id));
+
+    // Disabling the provider disables the apps using its cell, which then stops.
+    conductor
+        .raw_handle()
+        .disable_app("provider".into(), DisabledAppReason::User)
+        .await
+        .unwrap();
+    let state = conductor.get_state().await.unwrap();
+    for app_id in ["provider", "consumer", "other_consumer"] {
+        assert_eq!(
+            state.get_app(&app_id.to_string()).unwrap().status(),
+            &AppStatus::Disabled(DisabledAppReason::User)
+        );
+    }
+    assert!(!conductor.running_cell_ids().contains(&cell_id));
+
+    // A consumer can be uninstalled without affecting the provider.
+    conductor
+        .raw_handle()
+        .uninstall_app(&"other_consumer".to_string(), false)
+        .await
+        .unwrap();
+
+    // Cascading uninstalls the remaining consumer along with the provider.
+    conductor
+        .raw_handle()
+        .uninstall_app(&"provider".to_string(), true)
+        .await
+        .unwrap();
+    assert_eq!(
+        conductor.get_state().await.unwrap().installed_apps().len(),
+        0
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn failures_of_a_shared_cell_only_affect_the_apps_using_it() {
+    use crate::conductor::manager::ManagedTaskError;
+
+    holochain_trace::test_run().ok();
+
+    let conductor = SweetConductor::from_standard_config().await;
+    let alice = SweetAgents::one(conductor.keystore()).await;
+
+    let (shared_dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Create]).await;
+    let (own_dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Create]).await;
+    // Genesis fails for this DNA, because its properties are not set.
+    let (failing_dna, _, _) =
+        SweetDnaFile::unique_from_test_wasms(vec![TestWasm::GenesisSelfCheckRequiresProperties])
+            .await;
+    let shared_cell_id = CellId::new(shared_dna.dna_hash().to_owned(), alice.clone());
+    let own_cell_id = CellId::new(own_dna.dna_hash().to_owned(), alice.clone());
+
+    let bundle = |roles: Vec<(&'static str, DnaFile, CellProvisioning)>| async move {
+        let mut resources = Vec::new();
+        let roles = roles
+            .into_iter()
+            .map(|(name, dna, provisioning)| {
+                let path = PathBuf::from(format!("{}", dna.dna_hash()));
+                let installed_hash = DnaHash::with_data_sync(dna.dna_def());
+                resources.push((path.clone(), DnaBundle::from_dna_file(dna).unwrap()));
+                AppRoleManifest {
+                    name: name.into(),
+                    dna: AppRoleDnaManifest {
+                        location: Some(DnaLocation::Bundled(path)),
+                        sha256: None,
+                        modifiers: DnaModifiersOpt::none(),
+                        installed_hash: Some(installed_hash.into()),
+                        clone_limit: 0,
+                    },
+                    provisioning: Some(provisioning),
+                }
+            })
+            .collect();
+        let manifest = AppManifestCurrentBuilder::default()
+            .name("test_app".into())
+            .description(None)
+            .roles(roles)
+            .build()
+            .unwrap();
+        AppBundle::new(manifest.into(), resources, PathBuf::from("."))
+            .await
+            .unwrap()
+    };
+    let install = |app_id: &'static str, bundle: AppBundle| {
+        conductor
+            .raw_handle()
+            .install_app_bundle(InstallAppPayload {
+                agent_key: alice.clone(),
+                source: AppBundleSource::Bundle(bundle),
+                installed_app_id: Some(app_id.into()),
+                network_seed: None,
+                membrane_proofs: HashMap::new(),
+                #[cfg(feature = "chc")]
+                ignore_genesis_failure: false,
+            })
+    };
+    let fail_cell = |cell_id: CellId| {
+        conductor
+            .raw_handle()
+            .task_manager()
+            .add_cell_task_critical("failing task", cell_id, |_| async {
+                Err(ManagedTaskError::Io(std::io::Error::new(
+                    std::io::ErrorKind::Other,
+                    "intentional failure for testing",
+                )))
+            })
+    };
+    let is_disabled = |app_id: &'static str| {
+        let conductor = conductor.raw_handle();
+        async move {
+            matches!(
+                conductor
+                    .get_state()
+                    .await
+                    .unwrap()
+                    .get_app(&app_id.to_string())
+                    .unwrap()
+                    .status(),
+                AppStatus::Disabled(DisabledAppReason::Error(_))
+            )
+        }
+    };
+
+    install(
+        "provider",
+        bundle(vec![(
+            "shared",
+            shared_dna.clone(),
+            CellProvisioning::Create { deferred: false },
+        )])
+        .await,
+    )
+    .await
+    .unwrap();
+    install(
+        "consumer",
+        bundle(vec![
+            (
+                "shared",
+                shared_dna.clone(),
+                CellProvisioning::UseExisting { deferred: false },
+            ),
+            (
+                "own",
+                own_dna.clone(),
+                CellProvisioning::Create { deferred: false },
+            ),
+        ])
+        .await,
+    )
+    .await
+    .unwrap();
+    for app_id in ["provider", "consumer"] {
+        conductor
+            .raw_handle()
+            .enable_app(app_id.into())
+            .await
+            .unwrap();
+    }
+
+    // Genesis failing while installing an app which uses the shared cell
+    // undoes that app only.
+    let result = install(
+        "failing_consumer",
+        bundle(vec![
+            (
+                "shared",
+                shared_dna.clone(),
+                CellProvisioning::UseExisting { deferred: false },
+            ),
+            (
+                "failing",
+                failing_dna,
+                CellProvisioning::Create { deferred: false },
+            ),
+        ])
+        .await,
+    )
+    .await;
+    assert_matches!(result, Err(ConductorError::GenesisFailed { .. }));
+    let state = conductor.get_state().await.unwrap();
+    assert!(state.get_app(&"failing_consumer".to_string()).is_err());
+    assert_eq!(state.running_apps().count(), 2);
+    assert!(conductor.running_cell_ids().contains(&shared_cell_id));
+
+    // A failure of a cell which only the consumer uses disables only the consumer.
+    fail_cell(own_cell_id.clone());
+    assert_eq_retry_10s!(is_disabled("consumer").await, true);
+    assert!(!is_disabled("provider").await);
+    assert!(conductor.running_cell_ids().contains(&shared_cell_id));
+    assert!(!conductor.running_cell_ids().contains(&own_cell_id));
+
+    // A failure of the shared cell disables every app using it.
+    conductor
+        .raw_handle()
+        .enable_app("consumer".into())
+        .await
+        .unwrap();
+    fail_cell(shared_cell_id.clone());
+    assert_eq_retry_10s!(is_disabled("provider").await, true);
+    assert_eq_retry_10s!(is_disabled("consumer").await, true);
+    assert!(!conductor.running_cell_ids().contains(&shared_cell_id));
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn reject_duplicate_app_for_same_agent() {
     let conductor = SweetConductor::from_standard_config().await;
@@ -150,6 +568,7 @@ async fn reject_duplicate_app_for_same_agent() {
         name: "name".into(),
         dna: AppRoleDnaManifest {
             location: Some(DnaLocation::Bundled(path.clone())),
+            sha256: None,
             modifiers: modifiers.clone(),
             installed_hash: Some(installed_dna_hash.into()),
             clone_limit: 0,
@@ -261,6 +680,7 @@ async fn can_install_app_a_second_time_using_nothing_but_the_manifest_from_app_i
         name: "name".into(),
         dna: AppRoleDnaManifest {
             location: Some(DnaLocation::Bundled(path.clone())),
+            sha256: None,
             modifiers: modifiers.clone(),
             // Note that there is no installed hash provided. We'll check that this changes later.
             installed_hash: None,
@@ -365,6 +785,7 @@ async fn network_seed_regression() {
             name: "rolename".into(),
             dna: AppRoleDnaManifest {
                 location: Some(DnaLocation::Path(dna_path)),
+                sha256: None,
                 modifiers: DnaModifiersOpt::default(),
                 installed_hash: None,
                 clone_limit: 0,
@@ -596,6 +1017,7 @@ impl TestCase {
                     name: "rolename".into(),
                     dna: AppRoleDnaManifest {
                         location: Some(DnaLocation::Bundled(hashpath.clone())),
+                        sha256: None,
                         modifiers: dna_modifiers.clone(),
                         installed_hash: None,
                         clone_limit: 10,
@@ -620,6 +1042,7 @@ impl TestCase {
                     name: "rolename".into(),
                     dna: AppRoleDnaManifest {
                         location: Some(DnaLocation::Path(dna_path.clone())),
+                        sha256: None,
                         modifiers: dna_modifiers.clone(),
                         installed_hash: Some(dna_hash.clone().into()),
                         clone_limit: 0,
@@ -674,3 +1097,151 @@ impl TestCase {
         (installed_hash, case)
     }
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn install_app_from_store() {
+    use crate::conductor::conductor::{AppStoreService, AppStoreServiceError, APP_STORE_ZOME_NAME};
+    use holochain_conductor_api::config::conductor::AppStoreConfig;
+    use holochain_types::inline_zome::InlineZomeSet;
+
+    holochain_trace::test_run().unwrap();
+
+    let mut config = SweetConductorConfig::standard();
+    config.app_store = Some(AppStoreConfig {
+        installed_app_id: "store".into(),
+    });
+    let mut conductor = SweetConductor::from_config(config).await;
+    let agent = SweetAgents::one(conductor.keystore()).await;
+    let (dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Foo]).await;
+    let path = PathBuf::from(format!("{}", dna.dna_hash()));
+
+    let roles = vec![AppRoleManifest {
+        name: "foo".into(),
+        dna: AppRoleDnaManifest {
+            location: Some(DnaLocation::Bundled(path.clone())),
+            sha256: None,
+            modifiers: DnaModifiersOpt::none(),
+            installed_hash: None,
+            clone_limit: 0,
+        },
+        provisioning: None,
+    }];
+    let manifest = AppManifestCurrentBuilder::default()
+        .name("store_app".into())
+        .description(None)
+        .roles(roles)
+        .build()
+        .unwrap();
+    let dna_bundle_bytes = DnaBundle::from_dna_file(dna.clone())
+        .unwrap()
+        .encode()
+        .unwrap();
+    let forged_dna_hash = DnaHash::from_raw_32(vec![2; 32]);
+    let resources = vec![(path, DnaBundle::from_dna_file(dna.clone()).unwrap())];
+    let bundle_bytes = AppBundle::new(manifest.into(), resources, PathBuf::from("."))
+        .await
+        .unwrap()
+        .encode()
+        .unwrap();
+    let app_hash = AppHash::from_bundle_bytes(&bundle_bytes);
+    let forged_hash = AppHash::from(EntryHash::from_raw_32(vec![2; 32]));
+
+    let payload = |app_hash| InstallAppFromStorePayload {
+        app_hash,
+        agent_key: agent.clone(),
+        installed_app_id: None,
+        membrane_proofs: HashMap::new(),
+        network_seed: None,
+    };
+
+    // There is no app store until its app is installed
+    assert_matches!(
+        conductor
+            .raw_handle()
+            .install_app_from_store(payload(app_hash.clone()))
+            .await,
+        Err(ConductorError::NoAppStore)
+    );
+
+    // The store serves the bundles under their own hashes, but also under forged ones
+    let store_zomes = InlineZomeSet::new_unique_single("integrity", APP_STORE_ZOME_NAME, vec![], 0)
+        .function(APP_STORE_ZOME_NAME, "get_app_bundle", {
+            let app_hash = app_hash.clone();
+            let forged_hash = forged_hash.clone();
+            move |_, hash: AppHash| {
+                Ok((hash == app_hash || hash == forged_hash).then(|| bundle_bytes.clone()))
+            }
+        })
+        .function(APP_STORE_ZOME_NAME, "get_dna_bundle", {
+            let dna_hash = dna.dna_hash().clone();
+            let forged_dna_hash = forged_dna_hash.clone();
+            move |_, hash: DnaHash| {
+                Ok((hash == dna_hash || hash == forged_dna_hash).then(|| dna_bundle_bytes.clone()))
+            }
+        });
+    let (store_dna, _, _) = SweetDnaFile::unique_from_inline_zomes(store_zomes).await;
+    conductor.setup_app("store", [&store_dna]).await.unwrap();
+
+    // A DNA bundle is only returned for the hash of its DNA
+    let app_store = conductor
+        .raw_handle()
+        .conductor_services()
+        .unwrap()
+        .app_store
+        .unwrap();
+    let dna_bundle = app_store
+        .get_dna_bundle(dna.dna_hash().clone())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        dna_bundle
+            .into_dna_file(DnaModifiersOpt::none())
+            .await
+            .unwrap()
+            .0
+            .dna_hash(),
+        dna.dna_hash()
+    );
+    assert_matches!(
+        app_store
+            .get_dna_bundle(DnaHash::from_raw_32(vec![3; 32]))
+            .await,
+        Ok(None)
+    );
+    assert_matches!(
+        app_store.get_dna_bundle(forged_dna_hash).await,
+        Err(AppStoreServiceError::DnaHashMismatch { .. })
+    );
+
+    // An app which is not in the store can't be installed
+    assert_matches!(
+        conductor
+            .raw_handle()
+            .install_app_from_store(payload(AppHash::from(EntryHash::from_raw_32(vec![3; 32]))))
+            .await,
+        Err(ConductorError::AppNotInStore(_))
+    );
+
+    // Nor can a bundle which doesn't match the requested hash
+    assert_matches!(
+        conductor
+            .raw_handle()
+            .install_app_from_store(payload(forged_hash))
+            .await,
+        Err(ConductorError::AppStoreServiceError(
+            AppStoreServiceError::AppHashMismatch { .. }
+        ))
+    );
+
+    let app = conductor
+        .raw_handle()
+        .install_app_from_store(payload(app_hash))
+        .await
+        .unwrap();
+    assert_eq!(app.id(), "store_app");
+    assert_eq!(
+        app.all_cells().cloned().collect::<Vec<_>>(),
+        vec![CellId::new(dna.dna_hash().clone(), agent)]
+    );
+}
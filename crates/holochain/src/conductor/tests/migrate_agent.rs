@@ -0,0 +1,249 @@
+use holochain_types::prelude::*;
+use holochain_wasm_test_utils::TestWasm;
+use matches::assert_matches;
+
+use crate::conductor::error::ConductorError;
+use crate::sweettest::{SweetAgents, SweetConductor, SweetDnaFile};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn migrate_agent_closes_old_chain_and_opens_new_chain() {
+    holochain_trace::test_run().unwrap();
+
+    let (old_dna, _, _) =
+        SweetDnaFile::unique_from_test_wasms(vec![TestWasm::MigrateAgentPass]).await;
+    let (new_dna, _, _) =
+        SweetDnaFile::unique_from_test_wasms(vec![TestWasm::MigrateAgentPass]).await;
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let agent = SweetAgents::one(conductor.keystore()).await;
+
+    conductor
+        .setup_app_for_agent("old", agent.clone(), [&old_dna])
+        .await
+        .unwrap();
+    conductor
+        .setup_app_for_agent("new", agent.clone(), [&new_dna])
+        .await
+        .unwrap();
+    let old_cell_id = CellId::new(old_dna.dna_hash().clone(), agent.clone());
+    let new_cell_id = CellId::new(new_dna.dna_hash().clone(), agent.clone());
+
+    conductor
+        .migrate_agent(old_cell_id.clone(), new_dna.dna_hash().clone())
+        .await
+        .unwrap();
+
+    let last_action = |records: Vec<Record>| records.last().unwrap().action().clone();
+    assert_matches!(
+        last_action(
+            conductor
+                .query_source_chain(&old_cell_id, ChainQueryFilter::new())
+                .await
+                .unwrap()
+        ),
+        Action::CloseChain(CloseChain { new_dna_hash, .. }) if &new_dna_hash == new_dna.dna_hash()
+    );
+    assert_matches!(
+        last_action(
+            conductor
+                .query_source_chain(&new_cell_id, ChainQueryFilter::new())
+                .await
+                .unwrap()
+        ),
+        Action::OpenChain(OpenChain { prev_dna_hash, .. }) if &prev_dna_hash == old_dna.dna_hash()
+    );
+
+    // Migrating again to the same DNA changes nothing
+    conductor
+        .migrate_agent(old_cell_id.clone(), new_dna.dna_hash().clone())
+        .await
+        .unwrap();
+    assert_eq!(
+        conductor
+            .query_source_chain(
+                &new_cell_id,
+                ChainQueryFilter::new().action_type(ActionType::OpenChain)
+            )
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+
+    // But the closed chain can't be migrated anywhere else
+    let (other_dna, _, _) =
+        SweetDnaFile::unique_from_test_wasms(vec![TestWasm::MigrateAgentPass]).await;
+    conductor
+        .setup_app_for_agent("other", agent.clone(), [&other_dna])
+        .await
+        .unwrap();
+    assert_matches!(
+        conductor
+            .migrate_agent(old_cell_id.clone(), other_dna.dna_hash().clone())
+            .await,
+        Err(ConductorError::ChainAlreadyMigrated(id)) if id == old_cell_id
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn migrate_agent_is_aborted_by_failing_callback() {
+    holochain_trace::test_run().unwrap();
+
+    let (old_dna, _, _) =
+        SweetDnaFile::unique_from_test_wasms(vec![TestWasm::MigrateAgentFail]).await;
+    let (new_dna, _, _) =
+        SweetDnaFile::unique_from_test_wasms(vec![TestWasm::MigrateAgentPass]).await;
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let agent = SweetAgents::one(conductor.keystore()).await;
+
+    conductor
+        .setup_app_for_agent("old", agent.clone(), [&old_dna])
+        .await
+        .unwrap();
+    conductor
+        .setup_app_for_agent("new", agent.clone(), [&new_dna])
+        .await
+        .unwrap();
+    let old_cell_id = CellId::new(old_dna.dna_hash().clone(), agent.clone());
+
+    assert_matches!(
+        conductor
+            .migrate_agent(old_cell_id.clone(), new_dna.dna_hash().clone())
+            .await,
+        Err(ConductorError::InternalCellError(_))
+    );
+    assert!(conductor
+        .query_source_chain(
+            &old_cell_id,
+            ChainQueryFilter::new().action_type(ActionType::CloseChain)
+        )
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn migrate_agent_is_aborted_by_failing_callback_of_new_dna() {
+    holochain_trace::test_run().unwrap();
+
+    let (old_dna, _, _) =
+        SweetDnaFile::unique_from_test_wasms(vec![TestWasm::MigrateAgentPass, TestWasm::Create])
+            .await;
+    let (new_dna, _, _) =
+        SweetDnaFile::unique_from_test_wasms(vec![TestWasm::MigrateAgentFail]).await;
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let agent = SweetAgents::one(conductor.keystore()).await;
+
+    let (old_cell,) = conductor
+        .setup_app_for_agent("old", agent.clone(), [&old_dna])
+        .await
+        .unwrap()
+        .into_tuple();
+    conductor
+        .setup_app_for_agent("new", agent.clone(), [&new_dna])
+        .await
+        .unwrap();
+    let old_cell_id = old_cell.cell_id().clone();
+
+    assert_matches!(
+        conductor
+            .migrate_agent(old_cell_id.clone(), new_dna.dna_hash().clone())
+            .await,
+        Err(ConductorError::InternalCellError(_))
+    );
+    assert!(conductor
+        .query_source_chain(
+            &old_cell_id,
+            ChainQueryFilter::new().action_type(ActionType::CloseChain)
+        )
+        .await
+        .unwrap()
+        .is_empty());
+
+    // The old chain is still open for commits
+    let action_hash: ActionHash = conductor
+        .call(&old_cell.zome(TestWasm::Create), "create_entry", ())
+        .await;
+    assert_eq!(
+        conductor
+            .query_source_chain(&old_cell_id, ChainQueryFilter::new())
+            .await
+            .unwrap()
+            .last()
+            .unwrap()
+            .action_address(),
+        &action_hash
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn migrated_cell_reads_previous_chain() {
+    holochain_trace::test_run().unwrap();
+
+    let (old_dna, _, _) =
+        SweetDnaFile::unique_from_test_wasms(vec![TestWasm::MigrateAgentPass]).await;
+    let (new_dna, _, _) =
+        SweetDnaFile::unique_from_test_wasms(vec![TestWasm::MigrateAgentPass]).await;
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let agent = SweetAgents::one(conductor.keystore()).await;
+
+    let (old_cell,) = conductor
+        .setup_app_for_agent("old", agent.clone(), [&old_dna])
+        .await
+        .unwrap()
+        .into_tuple();
+    let (new_cell,) = conductor
+        .setup_app_for_agent("new", agent.clone(), [&new_dna])
+        .await
+        .unwrap()
+        .into_tuple();
+    let old_zome = old_cell.zome(TestWasm::MigrateAgentPass);
+    let new_zome = new_cell.zome(TestWasm::MigrateAgentPass);
+
+    // Neither chain was opened by a migration yet
+    assert!(conductor
+        .call_fallible::<_, Vec<Record>>(&new_zome, "query_previous_chain", ChainQueryFilter::new())
+        .await
+        .is_err());
+
+    conductor
+        .migrate_agent(old_cell.cell_id().clone(), new_dna.dna_hash().clone())
+        .await
+        .unwrap();
+
+    let old_chain = conductor
+        .query_source_chain(old_cell.cell_id(), ChainQueryFilter::new())
+        .await
+        .unwrap();
+    let previous_chain: Vec<Record> = conductor
+        .call(&new_zome, "query_previous_chain", ChainQueryFilter::new())
+        .await;
+    assert_eq!(
+        previous_chain
+            .iter()
+            .map(|r| r.action_address().clone())
+            .collect::<Vec<_>>(),
+        old_chain
+            .iter()
+            .map(|r| r.action_address().clone())
+            .collect::<Vec<_>>(),
+    );
+
+    // The filter is applied to the previous chain
+    let close_chain: Vec<Record> = conductor
+        .call(
+            &new_zome,
+            "query_previous_chain",
+            ChainQueryFilter::new().action_type(ActionType::CloseChain),
+        )
+        .await;
+    assert_matches!(
+        close_chain.as_slice(),
+        [record] if matches!(record.action(), Action::CloseChain(_))
+    );
+
+    // The old chain was closed, not opened, by the migration
+    assert!(conductor
+        .call_fallible::<_, Vec<Record>>(&old_zome, "query_previous_chain", ChainQueryFilter::new())
+        .await
+        .is_err());
+}
@@ -11,5 +11,6 @@ pub mod workflow;
 
 mod metrics;
 mod sys_validate;
+pub(crate) mod warrant;
 
 pub use sys_validate::*;
@@ -157,18 +157,19 @@ pub async fn spawn_queue_consumer_tasks(
     // Sys validation
     // One per space.
     let tx_sys = queue_consumer_map.spawn_once_sys_validation(dna_hash.clone(), || {
+        let workspace = SysValidationWorkspace::new(
+            authored_db.clone().into(),
+            dht_db.clone(),
+            dht_query_cache.clone(),
+            cache.clone(),
+            Arc::new(dna_def),
+            conductor
+                .get_config()
+                .conductor_tuning_params()
+                .sys_validation_retry_delay(),
+        );
         spawn_sys_validation_consumer(
-            SysValidationWorkspace::new(
-                authored_db.clone().into(),
-                dht_db.clone(),
-                dht_query_cache.clone(),
-                cache.clone(),
-                Arc::new(dna_def),
-                conductor
-                    .get_config()
-                    .conductor_tuning_params()
-                    .sys_validation_retry_delay(),
-            ),
+            workspace,
             space.clone(),
             conductor.clone(),
             tx_app.clone(),
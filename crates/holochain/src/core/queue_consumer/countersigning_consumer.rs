@@ -23,5 +23,8 @@ pub(crate) fn spawn_countersigning_consumer(
         move || countersigning_workflow(space.clone(), dna_network.clone(), trigger_sys.clone()),
     );
 
+    // Run once on startup to recover the sessions held before a restart.
+    tx.trigger(&"recover_countersigning_sessions");
+
     tx
 }
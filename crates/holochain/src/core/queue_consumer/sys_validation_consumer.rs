@@ -1,9 +1,11 @@
 //! The workflow and queue consumer for sys validation
 
 use super::*;
+use crate::conductor::conductor::DpkiService;
 use crate::core::workflow::sys_validation_workflow::sys_validation_workflow;
 use crate::core::workflow::sys_validation_workflow::validation_deps::ValidationDependencies;
 use crate::core::workflow::sys_validation_workflow::SysValidationWorkspace;
+use futures::FutureExt;
 use parking_lot::Mutex;
 use tracing::*;
 
@@ -21,6 +23,7 @@ pub fn spawn_sys_validation_consumer(
     let workspace = Arc::new(workspace);
     let space = Arc::new(space);
     let config = conductor.config.clone();
+    let dna_hash = space.dna_hash.clone();
 
     let current_validation_dependencies = Arc::new(Mutex::new(ValidationDependencies::new()));
 
@@ -37,9 +40,37 @@ pub fn spawn_sys_validation_consumer(
                 trigger_self.clone(),
                 network.clone(),
                 config.clone(),
+                dpki_for_dna(&conductor, &dna_hash),
+                {
+                    let conductor = conductor.clone();
+                    let dna_hash = dna_hash.clone();
+                    move |proofs| {
+                        let conductor = conductor.clone();
+                        let dna_hash = dna_hash.clone();
+                        async move { conductor.issue_warrants(&dna_hash, proofs).await }.boxed()
+                    }
+                },
             )
         },
     );
 
     tx
 }
+
+/// The DPKI service to check the keys of the DNA's authors against, if any.
+///
+/// This is looked up for every run of the workflow, since DPKI may be
+/// installed after the DNA's cells are already running.
+fn dpki_for_dna(conductor: &ConductorHandle, dna_hash: &DnaHash) -> Option<Arc<dyn DpkiService>> {
+    let services = conductor.conductor_services().ok()?;
+    // The DNAs which run the services can't have their authors checked by them.
+    let is_service_dna = services
+        .protected_cell_ids()
+        .iter()
+        .any(|cell_id| cell_id.dna_hash() == dna_hash);
+    if is_service_dna {
+        None
+    } else {
+        services.dpki
+    }
+}
@@ -78,7 +78,10 @@ use guest_callback::init::InitHostAccess;
 use guest_callback::migrate_agent::MigrateAgentHostAccess;
 use guest_callback::post_commit::PostCommitHostAccess;
 use guest_callback::validate::ValidateHostAccess;
+use guest_callback::weigh::WeighHostAccess;
+use guest_callback::weigh::WeighInvocation;
 use holo_hash::AgentPubKey;
+use holochain_conductor_api::conductor::ConductorTuningParams;
 use holochain_keystore::MetaLairClient;
 use holochain_nonce::*;
 use holochain_p2p::HolochainP2pDna;
@@ -150,6 +153,7 @@ pub enum HostContext {
     MigrateAgent(MigrateAgentHostAccess),
     PostCommit(PostCommitHostAccess), // MAYBE: add emit_signal access here?
     Validate(ValidateHostAccess),
+    Weigh(WeighHostAccess),
     ZomeCall(ZomeCallHostAccess),
 }
 
@@ -164,6 +168,7 @@ impl From<&HostContext> for HostFnAccess {
             HostContext::EntryDefs(access) => access.into(),
             HostContext::MigrateAgent(access) => access.into(),
             HostContext::PostCommit(access) => access.into(),
+            HostContext::Weigh(access) => access.into(),
         }
     }
 }
@@ -247,6 +252,37 @@ impl HostContext {
             ),
         }
     }
+
+    /// Get the call zome handle, if one was provided
+    pub fn maybe_call_zome_handle(&self) -> Option<&CellConductorReadHandle> {
+        match self {
+            Self::ZomeCall(ZomeCallHostAccess {
+                call_zome_handle, ..
+            })
+            | Self::Init(InitHostAccess {
+                call_zome_handle, ..
+            }) => Some(call_zome_handle),
+            _ => None,
+        }
+    }
+
+    /// Get the tuning params of the conductor running the callback.
+    /// Contexts which are not given them get the default tuning params.
+    pub fn conductor_tuning_params(&self) -> ConductorTuningParams {
+        match self {
+            Self::ZomeCall(ZomeCallHostAccess {
+                call_zome_handle, ..
+            })
+            | Self::Init(InitHostAccess {
+                call_zome_handle, ..
+            }) => call_zome_handle.conductor_tuning_params(),
+            Self::PostCommit(PostCommitHostAccess {
+                conductor_tuning_params,
+                ..
+            }) => conductor_tuning_params.clone(),
+            _ => ConductorTuningParams::default(),
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -286,6 +322,28 @@ impl FnComponents {
     }
 }
 
+/// The names of the functions a zome can export which are called by the
+/// conductor as callbacks rather than as zome functions.
+pub const CALLBACK_FN_NAMES: &[&str] = &[
+    "entry_defs",
+    "genesis_self_check",
+    "genesis_self_check_1",
+    "genesis_self_check_2",
+    "init",
+    "migrate_agent",
+    "migrate_agent_open",
+    "migrate_agent_close",
+    "post_commit",
+    "recv_remote_signal",
+    "validate",
+    "weigh",
+];
+
+/// Whether the function is called by the conductor as a callback.
+pub fn is_callback(fn_name: &FunctionName) -> bool {
+    CALLBACK_FN_NAMES.contains(&fn_name.0.as_str())
+}
+
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(test, derive(arbitrary::Arbitrary))]
 pub enum ZomesToInvoke {
@@ -680,19 +738,13 @@ pub trait RibosomeT: Sized + std::fmt::Debug + Send + Sync {
     /// values without needing to make holochain a dependency.
     fn get_const_fn(&self, zome: &Zome, name: &str) -> Result<Option<i32>, RibosomeError>;
 
-    /// @todo list out all the available callbacks and maybe cache them somewhere
-    fn list_callbacks(&self) {
-        unimplemented!()
-        // pseudocode
-        // self.instance().exports().filter(|e| e.is_callback())
-    }
+    /// List the callbacks the zome implements, i.e. the exported functions
+    /// which are called by the conductor, such as `init` or `validate`.
+    fn list_callbacks(&self, zome: &Zome) -> RibosomeResult<Vec<FunctionName>>;
 
-    /// @todo list out all the available zome functions and maybe cache them somewhere
-    fn list_zome_fns(&self) {
-        unimplemented!()
-        // pseudocode
-        // self.instance().exports().filter(|e| !e.is_callback())
-    }
+    /// List the zome functions of the zome, i.e. the exported functions
+    /// which are not callbacks and can be called by clients and other zomes.
+    fn list_zome_fns(&self, zome: &Zome) -> RibosomeResult<Vec<FunctionName>>;
 
     fn run_genesis_self_check(
         &self,
@@ -732,6 +784,14 @@ pub trait RibosomeT: Sized + std::fmt::Debug + Send + Sync {
         invocation: ValidateInvocation,
     ) -> RibosomeResult<ValidateResult>;
 
+    /// Weigh an action for rate limiting with the `weigh` callback of a
+    /// single integrity zome. Actions are weightless if there is no callback.
+    fn run_weigh(
+        &self,
+        access: WeighHostAccess,
+        invocation: WeighInvocation,
+    ) -> RibosomeResult<RateWeight>;
+
     /// Runs the specified zome fn. Returns the cursor used by HDK,
     /// so that it can be passed on to source chain manager for transactional writes
     fn call_zome_function(
@@ -743,9 +803,37 @@ pub trait RibosomeT: Sized + std::fmt::Debug + Send + Sync {
     fn zome_types(&self) -> &Arc<GlobalZomeTypes>;
 }
 
-/// Placeholder for weighing. Currently produces zero weight.
-pub fn weigh_placeholder() -> EntryRateWeight {
-    EntryRateWeight::default()
+/// Weigh an action for rate limiting, using the `weigh` callback of the
+/// integrity zome which defines the entry or link type being written.
+///
+/// Actions which no integrity zome defines, such as writes of system entries,
+/// are weightless.
+pub fn weigh_action<R: RibosomeT>(
+    ribosome: &R,
+    zome_index: Option<ZomeIndex>,
+    input: WeighInput,
+) -> RibosomeResult<RateWeight> {
+    match zome_index.and_then(|zome_index| ribosome.get_integrity_zome(&zome_index)) {
+        Some(zome) => ribosome.run_weigh(WeighHostAccess, WeighInvocation::new(zome, input)),
+        None => Ok(RateWeight::default()),
+    }
+}
+
+/// Combine the weight of a new entry action with the size of its entry,
+/// normalized so that an entry of [`ENTRY_SIZE_LIMIT`] bytes has the maximum
+/// [`RateBytes`].
+pub fn entry_rate_weight(weight: RateWeight, entry: &Entry) -> EntryRateWeight {
+    let size = match entry {
+        Entry::App(bytes) | Entry::CounterSign(_, bytes) => bytes.bytes().len(),
+        _ => 0,
+    };
+    let rate_bytes =
+        (size.saturating_mul(RateBytes::MAX as usize) + ENTRY_SIZE_LIMIT - 1) / ENTRY_SIZE_LIMIT;
+    EntryRateWeight {
+        bucket_id: weight.bucket_id,
+        units: weight.units,
+        rate_bytes: rate_bytes.min(RateBytes::MAX as usize) as RateBytes,
+    }
 }
 
 #[cfg(test)]
@@ -94,6 +94,21 @@ pub enum RibosomeError {
     #[error("Host function {2} cannot be called from zome function {1} in zome {0}")]
     HostFnPermissions(ZomeName, FunctionName, String),
 
+    /// Zome function tried to sleep for longer than the conductor allows.
+    #[error("Zome function {1} in zome {0} tried to sleep for {2:?} but the maximum is {3:?}")]
+    SleepTooLong(
+        ZomeName,
+        FunctionName,
+        std::time::Duration,
+        std::time::Duration,
+    ),
+
+    /// Zome function tried to sleep while the most sleeps the conductor allows were already in progress.
+    #[error(
+        "Zome function {1} in zome {0} tried to sleep but {2} zome calls are already sleeping"
+    )]
+    TooManySleeps(ZomeName, FunctionName, usize),
+
     /// An attempt to was made to perform a clone operation on a cell that is not provisioned or belongs to another app.
     #[error("Invalid request to modify a cell which belongs to another app")]
     InvalidCloneTarget,
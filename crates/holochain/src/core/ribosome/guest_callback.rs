@@ -4,6 +4,7 @@ pub mod init;
 pub mod migrate_agent;
 pub mod post_commit;
 pub mod validate;
+pub mod weigh;
 use super::HostContext;
 use crate::core::ribosome::error::RibosomeError;
 use crate::core::ribosome::FnComponents;
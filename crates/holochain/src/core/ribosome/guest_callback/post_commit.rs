@@ -8,6 +8,7 @@ use crate::core::ribosome::Invocation;
 use crate::core::ribosome::InvocationAuth;
 use crate::core::ribosome::ZomesToInvoke;
 use derive_more::Constructor;
+use holochain_conductor_api::conductor::ConductorTuningParams;
 use holochain_keystore::MetaLairClient;
 use holochain_p2p::HolochainP2pDna;
 use holochain_serialized_bytes::prelude::*;
@@ -36,6 +37,7 @@ pub struct PostCommitHostAccess {
     pub keystore: MetaLairClient,
     pub network: HolochainP2pDna,
     pub signal_tx: SignalBroadcaster,
+    pub conductor_tuning_params: ConductorTuningParams,
 }
 
 impl std::fmt::Debug for PostCommitHostAccess {
@@ -104,6 +106,9 @@ pub async fn send_post_commit(
                     keystore: keystore.clone(),
                     network: network.clone(),
                     signal_tx: conductor_handle.signal_broadcaster(),
+                    conductor_tuning_params: conductor_handle
+                        .get_config()
+                        .conductor_tuning_params(),
                 },
                 invocation: PostCommitInvocation::new(zome, actions.clone()),
                 cell_id: cell_id.clone(),
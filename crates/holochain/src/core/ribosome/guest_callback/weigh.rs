@@ -0,0 +1,114 @@
+use crate::core::ribosome::FnComponents;
+use crate::core::ribosome::HostContext;
+use crate::core::ribosome::Invocation;
+use crate::core::ribosome::InvocationAuth;
+use crate::core::ribosome::ZomesToInvoke;
+use derive_more::Constructor;
+use holochain_serialized_bytes::prelude::*;
+use holochain_types::prelude::*;
+
+#[derive(Clone, Constructor, Debug)]
+pub struct WeighHostAccess;
+
+impl From<WeighHostAccess> for HostContext {
+    fn from(weigh_host_access: WeighHostAccess) -> Self {
+        Self::Weigh(weigh_host_access)
+    }
+}
+
+impl From<&WeighHostAccess> for HostFnAccess {
+    fn from(_: &WeighHostAccess) -> Self {
+        let mut access = Self::none();
+        access.bindings_deterministic = Permission::Allow;
+        access
+    }
+}
+
+/// Weighs a single action for rate limiting, in the integrity zome which
+/// defines the entry or link type being written.
+#[derive(Clone, Constructor, Debug)]
+pub struct WeighInvocation {
+    zome: IntegrityZome,
+    input: WeighInput,
+}
+
+impl Invocation for WeighInvocation {
+    fn zomes(&self) -> ZomesToInvoke {
+        ZomesToInvoke::OneIntegrity(self.zome.clone())
+    }
+    fn fn_components(&self) -> FnComponents {
+        vec!["weigh".into()].into()
+    }
+    fn host_input(self) -> Result<ExternIO, SerializedBytesError> {
+        ExternIO::encode(self.input)
+    }
+    fn auth(&self) -> InvocationAuth {
+        InvocationAuth::LocalCallback
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "slow_tests")]
+mod slow_tests {
+    use super::WeighHostAccess;
+    use super::WeighInvocation;
+    use crate::core::ribosome::RibosomeT;
+    use crate::fixt::curve::Zomes;
+    use crate::fixt::RealRibosomeFixturator;
+    use ::fixt::prelude::*;
+    use holochain_types::prelude::*;
+    use holochain_wasm_test_utils::TestWasm;
+
+    fn weigh_link_input() -> WeighInput {
+        WeighInput::Link(CreateLink {
+            author: fixt!(AgentPubKey),
+            timestamp: Timestamp::now(),
+            action_seq: 5,
+            prev_action: fixt!(ActionHash),
+            base_address: fixt!(EntryHash).into(),
+            target_address: fixt!(EntryHash).into(),
+            zome_index: 0.into(),
+            link_type: 0.into(),
+            tag: ().into(),
+            weight: (),
+        })
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_weigh_unimplemented() {
+        let ribosome = RealRibosomeFixturator::new(Zomes(vec![TestWasm::Foo]))
+            .next()
+            .unwrap();
+        let zome = ribosome.get_integrity_zome(&0.into()).unwrap();
+
+        let result = ribosome
+            .run_weigh(
+                WeighHostAccess,
+                WeighInvocation::new(zome, weigh_link_input()),
+            )
+            .unwrap();
+        assert_eq!(result, RateWeight::default());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_weigh_implemented() {
+        let ribosome = RealRibosomeFixturator::new(Zomes(vec![TestWasm::RateLimits]))
+            .next()
+            .unwrap();
+        let zome = ribosome.get_integrity_zome(&0.into()).unwrap();
+
+        let result = ribosome
+            .run_weigh(
+                WeighHostAccess,
+                WeighInvocation::new(zome, weigh_link_input()),
+            )
+            .unwrap();
+        assert_eq!(
+            result,
+            RateWeight {
+                bucket_id: 1,
+                units: 10,
+            }
+        );
+    }
+}
@@ -81,14 +81,14 @@ host_fn_api_impls! {
 
     fn call (Vec<zt::call::Call>) -> Vec<zt::prelude::ZomeCallResponse>;
 
-    // @todo List all the local capability claims.
-    fn capability_claims (()) -> ();
+    // List the live capability claims on the local chain.
+    fn capability_claims (zt::capability::CapClaimQuery) -> Vec<zt::capability::LiveCapClaim>;
 
-    // @todo List all the local capability grants.
-    fn capability_grants (()) -> ();
+    // List the live capability grants on the local chain.
+    fn capability_grants (zt::capability::CapGrantQuery) -> Vec<zt::capability::LiveCapGrant>;
 
-    // @todo Get the capability for the current zome call.
-    fn capability_info (()) -> ();
+    // Get the capability which authorized the current zome call.
+    fn capability_info (()) -> zt::capability::CapabilityInfo;
 
     // The EntryDefId determines how a create is handled on the host side.
     // CapGrant and CapClaim are handled natively.
@@ -154,6 +154,9 @@ host_fn_api_impls! {
 
     fn get_details (Vec<zt::entry::GetInput>) -> Vec<Option<zt::metadata::Details>>;
 
+    // Get the metadata the authorities for a hash hold about it.
+    fn get_meta (zt::metadata::GetMetaInput) -> Vec<zt::metadata::MetadataSet>;
+
     // Get links by entry hash from the cascade.
     fn get_links (Vec<zt::link::GetLinksInput>) -> Vec<Vec<zt::link::Link>>;
 
@@ -178,6 +181,9 @@ host_fn_api_impls! {
     // Query the source chain for data.
     fn query (zt::query::ChainQueryFilter) -> Vec<Record>;
 
+    // Query the source chain which the agent migrated from.
+    fn query_previous_chain (zt::query::ChainQueryFilter) -> Vec<Record>;
+
     // the length of random bytes to create
     fn random_bytes (u32) -> zt::bytes::Bytes;
 
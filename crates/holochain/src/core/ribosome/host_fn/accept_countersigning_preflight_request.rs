@@ -389,6 +389,149 @@ pub mod wasm_test {
             .await;
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    #[cfg(feature = "slow_tests")]
+    async fn inspect_and_abandon_session() {
+        use holochain_conductor_api::CountersigningSessionState;
+        holochain_trace::test_run().ok();
+        let RibosomeTestFixture {
+            conductor,
+            alice,
+            alice_cell,
+            alice_pubkey,
+            bob,
+            bob_pubkey,
+            ..
+        } = RibosomeTestFixture::new(TestWasm::CounterSigning).await;
+        let alice_cell_id = alice_cell.cell_id();
+
+        let _: ActionHash = conductor.call(&alice, "create_a_thing", ()).await;
+        let _: ActionHash = conductor.call(&bob, "create_a_thing", ()).await;
+        assert_eq!(
+            None,
+            conductor
+                .raw_handle()
+                .countersigning_session_state(alice_cell_id)
+                .await
+                .unwrap()
+        );
+
+        let preflight_request: PreflightRequest = conductor
+            .call(
+                &alice,
+                "generate_countersigning_preflight_request",
+                vec![
+                    (alice_pubkey.clone(), vec![Role(0)]),
+                    (bob_pubkey.clone(), vec![]),
+                ],
+            )
+            .await;
+        let alice_acceptance: PreflightRequestAcceptance = conductor
+            .call(
+                &alice,
+                "accept_countersigning_preflight_request",
+                preflight_request.clone(),
+            )
+            .await;
+        let alice_response =
+            if let PreflightRequestAcceptance::Accepted(ref response) = alice_acceptance {
+                response
+            } else {
+                unreachable!();
+            };
+        let bob_acceptance: PreflightRequestAcceptance = conductor
+            .call(
+                &bob,
+                "accept_countersigning_preflight_request",
+                preflight_request.clone(),
+            )
+            .await;
+        let bob_response =
+            if let PreflightRequestAcceptance::Accepted(ref response) = bob_acceptance {
+                response
+            } else {
+                unreachable!();
+            };
+
+        // Alice has accepted the session, so her chain is locked.
+        assert!(matches!(
+            conductor
+                .raw_handle()
+                .countersigning_session_state(alice_cell_id)
+                .await
+                .unwrap(),
+            Some(CountersigningSessionState::Accepted { .. })
+        ));
+
+        // Alice commits the session entry but bob never does.
+        let (session_action_hash, entry_hash): (ActionHash, EntryHash) = conductor
+            .call(
+                &alice,
+                "create_a_countersigned_thing_with_entry_hash",
+                vec![alice_response.clone(), bob_response.clone()],
+            )
+            .await;
+        match conductor
+            .raw_handle()
+            .countersigning_session_state(alice_cell_id)
+            .await
+            .unwrap()
+        {
+            Some(CountersigningSessionState::SignaturesCollecting {
+                entry_hash: session_entry_hash,
+                session_data,
+                ..
+            }) => {
+                assert_eq!(entry_hash, session_entry_hash);
+                assert_eq!(&preflight_request, session_data.preflight_request());
+            }
+            something_else => panic!("{:?}", something_else),
+        }
+
+        // The session entry can be published again while the session is stuck.
+        conductor
+            .raw_handle()
+            .publish_countersigning_session(alice_cell_id)
+            .await
+            .unwrap();
+
+        // Abandoning the session unlocks the chain, leaving the session entry
+        // at its head.
+        conductor
+            .raw_handle()
+            .abandon_countersigning_session(alice_cell_id)
+            .await
+            .unwrap();
+        assert_eq!(
+            None,
+            conductor
+                .raw_handle()
+                .countersigning_session_state(alice_cell_id)
+                .await
+                .unwrap()
+        );
+        let next_action_hash: ActionHash = conductor.call(&alice, "create_a_thing", ()).await;
+        let next_action: SignedActionHashed = conductor
+            .call(&alice, "must_get_action", next_action_hash)
+            .await;
+        assert_eq!(
+            Some(&session_action_hash),
+            next_action.action().prev_action()
+        );
+
+        // There is no longer a session to abandon or publish.
+        assert!(conductor
+            .raw_handle()
+            .abandon_countersigning_session(alice_cell_id)
+            .await
+            .is_err());
+        assert!(conductor
+            .raw_handle()
+            .publish_countersigning_session(alice_cell_id)
+            .await
+            .is_err());
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     #[cfg(feature = "slow_tests")]
     #[cfg_attr(target_os = "macos", ignore = "flaky")]
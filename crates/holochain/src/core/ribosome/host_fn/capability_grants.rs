@@ -1,16 +1,46 @@
 use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::RibosomeError;
 use crate::core::ribosome::RibosomeT;
+use holochain_types::prelude::*;
+use holochain_wasmer_host::prelude::*;
 use std::sync::Arc;
 use wasmer::RuntimeError;
 
-/// list all the grants stored locally in the chain filtered by tag
+/// list all the grants stored locally in the chain filtered by the query
 /// this is only the current grants as per local CRUD
 pub fn capability_grants(
     _ribosome: Arc<impl RibosomeT>,
-    _call_context: Arc<CallContext>,
-    _input: (),
-) -> Result<(), RuntimeError> {
-    unimplemented!();
+    call_context: Arc<CallContext>,
+    input: CapGrantQuery,
+) -> Result<Vec<LiveCapGrant>, RuntimeError> {
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            read_workspace: Permission::Allow,
+            ..
+        } => tokio_helper::block_forever_on(async move {
+            call_context
+                .host_context
+                .workspace()
+                .source_chain()
+                .as_ref()
+                .expect("Must have source chain if read_workspace access is given")
+                .live_cap_grants(input)
+                .await
+                .map_err(|source_chain_error| -> RuntimeError {
+                    wasm_error!(WasmErrorInner::Host(source_chain_error.to_string())).into()
+                })
+        }),
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "capability_grants".into(),
+            )
+            .to_string(),
+        ))
+        .into()),
+    }
 }
 
 #[cfg(test)]
@@ -58,6 +88,80 @@ pub mod wasm_test {
         assert_eq!(entry_secret, secret);
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn ribosome_capability_grants_and_claims() {
+        holochain_trace::test_run().ok();
+        let RibosomeTestFixture {
+            conductor,
+            alice,
+            alice_pubkey,
+            bob,
+            bob_pubkey,
+            ..
+        } = RibosomeTestFixture::new(TestWasm::Capability).await;
+
+        // init grants unrestricted access to accept_cap_claim
+        let grants: Vec<LiveCapGrant> = conductor
+            .call(&alice, "list_cap_grants", CapGrantQuery::default())
+            .await;
+        assert_eq!(grants.len(), 1);
+        assert_matches!(grants[0].grant.access, CapAccess::Unrestricted);
+
+        let secret: CapSecret = conductor.call(&alice, "cap_secret", ()).await;
+        let action_hash: ActionHash = conductor
+            .call(&alice, "transferable_cap_grant", secret)
+            .await;
+        let rolled_hash: ActionHash = conductor
+            .call(&alice, "roll_cap_grant", action_hash.clone())
+            .await;
+
+        // the rolled grant replaces the original one
+        let grants: Vec<LiveCapGrant> = conductor
+            .call(&alice, "list_cap_grants", CapGrantQuery::default())
+            .await;
+        assert_eq!(grants.len(), 2);
+        assert!(grants.iter().all(|g| g.action_hash != action_hash));
+        assert!(grants.iter().any(|g| g.action_hash == rolled_hash));
+
+        let _: ActionHash = conductor
+            .call(&alice, "delete_cap_grant", rolled_hash)
+            .await;
+        let grants: Vec<LiveCapGrant> = conductor
+            .call(&alice, "list_cap_grants", CapGrantQuery::default())
+            .await;
+        assert_eq!(grants.len(), 1);
+
+        // an assigned grant for bob shows up as a claim on bob's chain
+        let _: () = conductor
+            .call(&alice, "send_assigned_cap_claim", bob_pubkey.clone())
+            .await;
+        let grants: Vec<LiveCapGrant> = conductor
+            .call(
+                &alice,
+                "list_cap_grants",
+                CapGrantQuery::default().grantee(bob_pubkey),
+            )
+            .await;
+        assert_eq!(grants.len(), 1);
+        assert_eq!(grants[0].grant.tag, "has_cap_claim");
+
+        let claims: Vec<LiveCapClaim> = conductor
+            .call(
+                &bob,
+                "list_cap_claims",
+                CapClaimQuery::default().grantor(alice_pubkey.clone()),
+            )
+            .await;
+        assert_eq!(claims.len(), 1);
+        assert_eq!(claims[0].claim.tag, "has_cap_claim");
+
+        // calls made by the chain author are authorized as such
+        let info: CapabilityInfo = conductor.call(&alice, "get_capability_info", ()).await;
+        assert_eq!(info.provenance, alice_pubkey);
+        assert_eq!(info.cap_secret, None);
+        assert_eq!(info.cap_grant, CapGrant::ChainAuthor(alice_pubkey));
+    }
+
     // MAYBE: [ B-03669 ] can move this to an integration test (may need to switch to using a RibosomeStore)
     #[tokio::test(flavor = "multi_thread")]
     async fn ribosome_authorized_call() -> anyhow::Result<()> {
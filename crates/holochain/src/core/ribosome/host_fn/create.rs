@@ -1,8 +1,10 @@
-use crate::core::ribosome::weigh_placeholder;
+use crate::core::ribosome::entry_rate_weight;
+use crate::core::ribosome::weigh_action;
 use crate::core::ribosome::CallContext;
 use crate::core::ribosome::HostFnAccess;
 use crate::core::ribosome::RibosomeError;
 use crate::core::ribosome::RibosomeT;
+use holochain_state::source_chain::SourceChainError;
 use holochain_types::prelude::*;
 use holochain_wasmer_host::prelude::*;
 use std::sync::Arc;
@@ -11,7 +13,7 @@ use wasmer::RuntimeError;
 /// create record
 #[allow(clippy::extra_unused_lifetimes)]
 pub fn create<'a>(
-    _ribosome: Arc<impl RibosomeT>,
+    ribosome: Arc<impl RibosomeT>,
     call_context: Arc<CallContext>,
     input: CreateInput,
 ) -> Result<ActionHash, RuntimeError> {
@@ -27,10 +29,10 @@ pub fn create<'a>(
                 chain_top_ordering,
             } = input;
 
-            let weight = weigh_placeholder();
-
             // Countersigned entries have different action handling.
             match entry {
+                // Every party to a countersigning session must agree on the
+                // weight of the entry, so countersigned entries are not weighed.
                 Entry::CounterSign(_, _) => tokio_helper::block_forever_on(async move {
                     call_context
                         .host_context
@@ -38,7 +40,7 @@ pub fn create<'a>(
                         .source_chain()
                         .as_ref()
                         .expect("Must have source chain if write_workspace access is given")
-                        .put_countersigned(entry, chain_top_ordering, weight)
+                        .put_countersigned(entry, chain_top_ordering, EntryRateWeight::default())
                         .await
                         .map_err(|source_chain_error| -> RuntimeError {
                             wasm_error!(WasmErrorInner::Host(source_chain_error.to_string())).into()
@@ -49,17 +51,17 @@ pub fn create<'a>(
                     let entry_hash = EntryHash::with_data_sync(&entry);
 
                     // extract the entry defs for a zome
-                    let entry_type = match entry_location {
+                    let (entry_type, zome_index) = match entry_location {
                         EntryDefLocation::App(AppEntryDefLocation {
                             zome_index,
                             entry_def_index,
                         }) => {
                             let app_entry_def =
                                 AppEntryDef::new(entry_def_index, zome_index, entry_visibility);
-                            EntryType::App(app_entry_def)
+                            (EntryType::App(app_entry_def), Some(zome_index))
                         }
-                        EntryDefLocation::CapGrant => EntryType::CapGrant,
-                        EntryDefLocation::CapClaim => EntryType::CapClaim,
+                        EntryDefLocation::CapGrant => (EntryType::CapGrant, None),
+                        EntryDefLocation::CapClaim => (EntryType::CapClaim, None),
                     };
 
                     // build an action for the entry being committed
@@ -80,7 +82,21 @@ pub fn create<'a>(
                             .source_chain()
                             .as_ref()
                             .expect("Must have source chain if write_workspace access is given")
-                            .put_weightless(action_builder, Some(entry), chain_top_ordering)
+                            .put_weighed_with(
+                                action_builder,
+                                Some(entry),
+                                chain_top_ordering,
+                                |action, entry| {
+                                    let entry = entry.expect("The entry was just put");
+                                    let weight = weigh_action(
+                                        ribosome.as_ref(),
+                                        zome_index,
+                                        WeighInput::Create(action.clone(), entry.clone()),
+                                    )
+                                    .map_err(SourceChainError::other)?;
+                                    Ok(entry_rate_weight(weight, entry))
+                                },
+                            )
                             .await
                             .map_err(|source_chain_error| -> RuntimeError {
                                 wasm_error!(WasmErrorInner::Host(source_chain_error.to_string()))
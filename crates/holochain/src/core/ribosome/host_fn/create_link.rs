@@ -1,7 +1,9 @@
+use crate::core::ribosome::weigh_action;
 use crate::core::ribosome::CallContext;
 use crate::core::ribosome::HostFnAccess;
 use crate::core::ribosome::RibosomeError;
 use crate::core::ribosome::RibosomeT;
+use holochain_state::source_chain::SourceChainError;
 use holochain_wasmer_host::prelude::*;
 
 use holochain_types::prelude::*;
@@ -10,7 +12,7 @@ use wasmer::RuntimeError;
 
 #[allow(clippy::extra_unused_lifetimes)]
 pub fn create_link<'a>(
-    _ribosome: Arc<impl RibosomeT>,
+    ribosome: Arc<impl RibosomeT>,
     call_context: Arc<CallContext>,
     input: CreateLinkInput,
 ) -> Result<ActionHash, RuntimeError> {
@@ -32,23 +34,26 @@ pub fn create_link<'a>(
             let action_builder =
                 builder::CreateLink::new(base_address, target_address, zome_index, link_type, tag);
 
-            let action_hash = tokio_helper::block_forever_on(tokio::task::spawn(async move {
+            let action_hash = tokio_helper::block_forever_on(async move {
                 // push the action into the source chain
-                let action_hash = call_context
+                call_context
                     .host_context
                     .workspace_write()
                     .source_chain()
                     .as_ref()
                     .expect("Must have source chain if write_workspace access is given")
-                    .put_weightless(action_builder, None, chain_top_ordering)
-                    .await?;
-                Ok::<ActionHash, RibosomeError>(action_hash)
-            }))
-            .map_err(|join_error| -> RuntimeError {
-                wasm_error!(WasmErrorInner::Host(join_error.to_string())).into()
-            })?
-            .map_err(|ribosome_error| -> RuntimeError {
-                wasm_error!(WasmErrorInner::Host(ribosome_error.to_string())).into()
+                    .put_weighed_with(action_builder, None, chain_top_ordering, |action, _| {
+                        weigh_action(
+                            ribosome.as_ref(),
+                            Some(zome_index),
+                            WeighInput::Link(action.clone()),
+                        )
+                        .map_err(SourceChainError::other)
+                    })
+                    .await
+            })
+            .map_err(|source_chain_error| -> RuntimeError {
+                wasm_error!(WasmErrorInner::Host(source_chain_error.to_string())).into()
             })?;
 
             // return the hash of the committed link
@@ -1,8 +1,10 @@
 use crate::core::ribosome::error::RibosomeError;
+use crate::core::ribosome::weigh_action;
 use crate::core::ribosome::CallContext;
 use crate::core::ribosome::RibosomeT;
 use holochain_cascade::error::CascadeError;
 use holochain_cascade::CascadeImpl;
+use holochain_state::source_chain::SourceChainError;
 use holochain_wasmer_host::prelude::*;
 
 use crate::core::ribosome::HostFnAccess;
@@ -14,7 +16,7 @@ use wasmer::RuntimeError;
 
 #[allow(clippy::extra_unused_lifetimes)]
 pub fn delete<'a>(
-    _ribosome: Arc<impl RibosomeT>,
+    ribosome: Arc<impl RibosomeT>,
     call_context: Arc<CallContext>,
     input: DeleteInput,
 ) -> Result<ActionHash, RuntimeError> {
@@ -27,8 +29,12 @@ pub fn delete<'a>(
                 deletes_action_hash,
                 chain_top_ordering,
             } = input;
-            let (deletes_entry_address, _) =
+            let (deletes_entry_address, entry_type) =
                 get_original_entry_data(call_context.clone(), deletes_action_hash.clone())?;
+            let zome_index = match entry_type {
+                EntryType::App(app_entry_def) => Some(app_entry_def.zome_index),
+                _ => None,
+            };
 
             let host_access = call_context.host_context();
 
@@ -44,7 +50,14 @@ pub fn delete<'a>(
                     deletes_entry_address,
                 };
                 let action_hash = source_chain
-                    .put_weightless(action_builder, None, chain_top_ordering)
+                    .put_weighed_with(action_builder, None, chain_top_ordering, |action, _| {
+                        weigh_action(
+                            ribosome.as_ref(),
+                            zome_index,
+                            WeighInput::Delete(action.clone()),
+                        )
+                        .map_err(SourceChainError::other)
+                    })
                     .await
                     .map_err(|source_chain_error| {
                         wasm_error!(WasmErrorInner::Host(source_chain_error.to_string()))
@@ -0,0 +1,125 @@
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::RibosomeError;
+use crate::core::ribosome::RibosomeT;
+use holochain_cascade::CascadeImpl;
+use holochain_p2p::actor::GetMetaOptions;
+use holochain_types::prelude::*;
+use holochain_wasmer_host::prelude::*;
+use std::sync::Arc;
+use wasmer::RuntimeError;
+
+/// Get the metadata the authorities for a hash hold about it
+#[allow(clippy::extra_unused_lifetimes)]
+#[tracing::instrument(skip(_ribosome, call_context), fields(? call_context.zome, function = ? call_context.function_name))]
+pub fn get_meta<'a>(
+    _ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    input: GetMetaInput,
+) -> Result<Vec<MetadataSet>, RuntimeError> {
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            read_workspace: Permission::Allow,
+            ..
+        } => tokio_helper::block_forever_on(async move {
+            let GetMetaInput {
+                any_dht_hash,
+                metadata_request,
+            } = input;
+            let options = GetMetaOptions {
+                metadata_request,
+                ..Default::default()
+            };
+
+            CascadeImpl::from_workspace_and_network(
+                &call_context.host_context.workspace(),
+                call_context.host_context.network().to_owned(),
+            )
+            .dht_get_meta(any_dht_hash, options)
+            .await
+            .map_err(|cascade_error| {
+                wasm_error!(WasmErrorInner::Host(cascade_error.to_string())).into()
+            })
+        }),
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "get_meta".into(),
+            )
+            .to_string(),
+        ))
+        .into()),
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "slow_tests")]
+mod tests {
+    use crate::core::ribosome::wasm_test::RibosomeTestFixture;
+    use crate::sweettest::await_consistency;
+    use hdk::prelude::*;
+    use holochain_p2p::actor::HolochainP2pRefToDna;
+    use holochain_p2p::HolochainP2pDnaT;
+    use holochain_wasm_test_utils::TestWasm;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn get_meta() {
+        holochain_trace::test_run().ok();
+        let RibosomeTestFixture {
+            conductor,
+            alice,
+            alice_cell,
+            bob,
+            bob_cell,
+            ..
+        } = RibosomeTestFixture::new(TestWasm::Link).await;
+
+        // Alice and Bob both link from the same base
+        let create_link_hash: ActionHash = conductor.call(&alice, "create_link", ()).await;
+        let _: ActionHash = conductor.call(&bob, "create_link", ()).await;
+        let base: AnyLinkableHash = conductor.call(&alice, "get_base_hash", ()).await;
+
+        await_consistency(10, [&alice_cell, &bob_cell])
+            .await
+            .unwrap();
+
+        // The authorities for the base count both links
+        let meta: Vec<MetadataSet> = conductor
+            .call(&alice, "get_meta_of", base.into_any_dht_hash().unwrap())
+            .await;
+        assert!(!meta.is_empty());
+        assert!(meta.iter().all(|m| m.link_count == 2));
+
+        // The authorities for the link action know it is valid
+        let meta: Vec<MetadataSet> = conductor
+            .call(
+                &bob,
+                "get_meta_of",
+                AnyDhtHash::from(create_link_hash.clone()),
+            )
+            .await;
+        assert!(!meta.is_empty());
+        for m in meta {
+            assert_eq!(Some(ValidationStatus::Valid), m.validation_status);
+            assert_eq!(
+                vec![create_link_hash.clone()],
+                m.actions
+                    .into_iter()
+                    .map(|a| a.action_hash)
+                    .collect::<Vec<_>>()
+            );
+        }
+
+        // Authorities which answer over the network report their storage arc
+        let network = conductor
+            .holochain_p2p()
+            .to_dna(alice_cell.cell_id().dna_hash().clone(), None);
+        let meta = network
+            .get_meta(create_link_hash.into(), Default::default())
+            .await
+            .unwrap();
+        assert!(!meta.is_empty());
+        assert!(meta.iter().all(|m| m.storage_arc.is_some()));
+    }
+}
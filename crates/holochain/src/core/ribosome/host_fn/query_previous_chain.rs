@@ -0,0 +1,60 @@
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::RibosomeError;
+use crate::core::ribosome::RibosomeT;
+use holochain_types::prelude::*;
+use holochain_wasmer_host::prelude::*;
+use std::sync::Arc;
+use wasmer::RuntimeError;
+
+pub fn query_previous_chain(
+    _ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    input: ChainQueryFilter,
+) -> Result<Vec<Record>, RuntimeError> {
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            read_workspace: Permission::Allow,
+            ..
+        } => tokio_helper::block_forever_on(async move {
+            let host_error =
+                |e: String| -> RuntimeError { wasm_error!(WasmErrorInner::Host(e)).into() };
+
+            // The previous chain is the one referenced by the OpenChain action of this chain.
+            let open_chain = call_context
+                .host_context
+                .workspace()
+                .source_chain()
+                .as_ref()
+                .expect("Must have source chain to query the source chain")
+                .query(ChainQueryFilter::new().action_type(ActionType::OpenChain))
+                .await
+                .map_err(|e| host_error(e.to_string()))?;
+            let prev_dna_hash = match open_chain.first().map(|r| r.action()) {
+                Some(Action::OpenChain(open_chain)) => open_chain.prev_dna_hash.clone(),
+                _ => {
+                    return Err(host_error(
+                        "This chain was not opened by a migration".into(),
+                    ))
+                }
+            };
+
+            call_context
+                .host_context()
+                .maybe_call_zome_handle()
+                .ok_or_else(|| host_error("The previous chain can't be queried from here".into()))?
+                .query_previous_chain(prev_dna_hash, input)
+                .await
+                .map_err(|e| host_error(e.to_string()))
+        }),
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "query_previous_chain".into(),
+            )
+            .to_string(),
+        ))
+        .into()),
+    }
+}
@@ -1,12 +1,179 @@
 use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::RibosomeError;
 use crate::core::ribosome::RibosomeT;
+use holochain_types::access::Permission;
+use holochain_wasmer_host::prelude::*;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use wasmer::RuntimeError;
 
+/// The number of zome calls currently sleeping in this process.
+///
+/// This is shared by all conductors in the process, as are the async runtime
+/// threads which the sleeping calls hold on to.
+static SLEEPING: AtomicUsize = AtomicUsize::new(0);
+
+/// Sleep for the given duration.
+///
+/// This does not yield the zome call back to the async runtime. Wasm runs
+/// synchronously and host functions can't suspend the guest, so the thread
+/// running the wasm instance is occupied for the whole sleep. If that thread
+/// is an async runtime worker, its other tasks are first handed off to another
+/// thread, so they keep making progress, but each sleeping call still costs
+/// the runtime a thread. To bound that cost, no more than the conductor's
+/// `max_concurrent_zome_sleeps` calls may sleep at once, and further sleeps
+/// fail with an error rather than waiting for a free slot.
+///
+/// Sleeping for longer than the conductor's `max_zome_sleep_duration` is an
+/// error rather than a silently shortened sleep, in every context which may
+/// sleep.
 pub fn sleep(
     _ribosome: Arc<impl RibosomeT>,
-    _call_context: Arc<CallContext>,
-    _input: core::time::Duration,
+    call_context: Arc<CallContext>,
+    input: core::time::Duration,
 ) -> Result<(), RuntimeError> {
-    unimplemented!()
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            non_determinism: Permission::Allow,
+            ..
+        } => {
+            let tuning_params = call_context.host_context().conductor_tuning_params();
+            let max = tuning_params.max_zome_sleep_duration();
+            if input > max {
+                return Err(wasm_error!(WasmErrorInner::Host(
+                    RibosomeError::SleepTooLong(
+                        call_context.zome.zome_name().clone(),
+                        call_context.function_name().clone(),
+                        input,
+                        max,
+                    )
+                    .to_string(),
+                ))
+                .into());
+            }
+            let max_sleeping = tuning_params.max_concurrent_zome_sleeps();
+            let _guard = SleepGuard::acquire(max_sleeping).ok_or_else(|| {
+                wasm_error!(WasmErrorInner::Host(
+                    RibosomeError::TooManySleeps(
+                        call_context.zome.zome_name().clone(),
+                        call_context.function_name().clone(),
+                        max_sleeping,
+                    )
+                    .to_string(),
+                ))
+            })?;
+            yield_while_sleeping(input);
+            Ok(())
+        }
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "sleep".into(),
+            )
+            .to_string(),
+        ))
+        .into()),
+    }
+}
+
+/// A slot in [`SLEEPING`], released when dropped.
+struct SleepGuard;
+
+impl SleepGuard {
+    /// Take a slot if fewer than `max` calls are sleeping.
+    fn acquire(max: usize) -> Option<Self> {
+        SLEEPING
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| {
+                (n < max).then_some(n + 1)
+            })
+            .ok()
+            .map(|_| Self)
+    }
+}
+
+impl Drop for SleepGuard {
+    fn drop(&mut self) {
+        SLEEPING.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// Sleep on the current thread. If it is a worker of a multi-threaded async
+/// runtime, its other tasks are moved to another thread for the duration,
+/// rather than nesting a runtime inside the wasm call to wait on a timer.
+fn yield_while_sleeping(duration: core::time::Duration) {
+    match tokio::runtime::Handle::try_current().map(|handle| handle.runtime_flavor()) {
+        Ok(tokio::runtime::RuntimeFlavor::MultiThread) => {
+            tokio::task::block_in_place(|| std::thread::sleep(duration))
+        }
+        _ => std::thread::sleep(duration),
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "slow_tests")]
+pub mod wasm_test {
+    use crate::core::ribosome::wasm_test::RibosomeTestFixture;
+    use crate::sweettest::*;
+    use holochain_wasm_test_utils::TestWasm;
+    use std::time::Duration;
+    use std::time::Instant;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn invoke_import_sleep_test() {
+        holochain_trace::test_run().ok();
+        let RibosomeTestFixture {
+            conductor, alice, ..
+        } = RibosomeTestFixture::new(TestWasm::SysTime).await;
+
+        let wake_after = Duration::from_millis(200);
+        let start = Instant::now();
+        let _: () = conductor.call(&alice, "sleep", wake_after).await;
+        assert!(start.elapsed() >= wake_after);
+
+        // sleeping longer than the conductor allows is an error
+        let result: Result<(), _> = conductor
+            .call_fallible(&alice, "sleep", Duration::from_secs(60 * 60))
+            .await;
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("tried to sleep for"));
+
+        // sleeping while the conductor's limit of sleeping calls is reached is an error.
+        // The count of sleeping calls is process wide, so this is checked here rather
+        // than in a separate test which could run alongside the sleeps above.
+        let mut conductor = SweetConductor::from_config(
+            SweetConductorConfig::standard()
+                .tune_conductor(|params| params.max_concurrent_zome_sleeps = Some(1)),
+        )
+        .await;
+        let (dna_file, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::SysTime]).await;
+        let (alice,) = conductor
+            .setup_app("app", [&dna_file])
+            .await
+            .unwrap()
+            .into_tuple();
+        let zome = alice.zome(TestWasm::SysTime);
+
+        let long_sleep = {
+            let conductor = conductor.sweet_handle();
+            let zome = zome.clone();
+            tokio::spawn(async move {
+                let _: () = conductor.call(&zome, "sleep", Duration::from_secs(2)).await;
+            })
+        };
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        let result: Result<(), _> = conductor.call_fallible(&zome, "sleep", wake_after).await;
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("zome calls are already sleeping"));
+
+        // once the sleeping call wakes up its slot is free again
+        long_sleep.await.unwrap();
+        let _: () = conductor.call(&zome, "sleep", wake_after).await;
+    }
 }
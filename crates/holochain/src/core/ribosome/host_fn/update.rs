@@ -1,9 +1,11 @@
 use super::delete::get_original_entry_data;
-use crate::core::ribosome::weigh_placeholder;
+use crate::core::ribosome::entry_rate_weight;
+use crate::core::ribosome::weigh_action;
 use crate::core::ribosome::CallContext;
 use crate::core::ribosome::HostFnAccess;
 use crate::core::ribosome::RibosomeError;
 use crate::core::ribosome::RibosomeT;
+use holochain_state::source_chain::SourceChainError;
 use holochain_wasmer_host::prelude::*;
 use wasmer::RuntimeError;
 
@@ -12,7 +14,7 @@ use std::sync::Arc;
 
 #[allow(clippy::extra_unused_lifetimes)]
 pub fn update<'a>(
-    _ribosome: Arc<impl RibosomeT>,
+    ribosome: Arc<impl RibosomeT>,
     call_context: Arc<CallContext>,
     input: UpdateInput,
 ) -> Result<ActionHash, RuntimeError> {
@@ -31,10 +33,10 @@ pub fn update<'a>(
             let (original_entry_address, entry_type) =
                 get_original_entry_data(call_context.clone(), original_action_address.clone())?;
 
-            let weight = weigh_placeholder();
-
             // Countersigned entries have different action handling.
             match entry {
+                // Every party to a countersigning session must agree on the
+                // weight of the entry, so countersigned entries are not weighed.
                 Entry::CounterSign(_, _) => tokio_helper::block_forever_on(async move {
                     call_context
                         .host_context
@@ -42,7 +44,7 @@ pub fn update<'a>(
                         .source_chain()
                         .as_ref()
                         .expect("Must have source chain if write_workspace access is given")
-                        .put_countersigned(entry, chain_top_ordering, weight)
+                        .put_countersigned(entry, chain_top_ordering, EntryRateWeight::default())
                         .await
                         .map_err(|source_chain_error| -> RuntimeError {
                             wasm_error!(WasmErrorInner::Host(source_chain_error.to_string())).into()
@@ -52,6 +54,11 @@ pub fn update<'a>(
                     // build the entry hash
                     let entry_hash = EntryHash::with_data_sync(&entry);
 
+                    let zome_index = match &entry_type {
+                        EntryType::App(app_entry_def) => Some(app_entry_def.zome_index),
+                        _ => None,
+                    };
+
                     // build an action for the entry being updated
                     let action_builder = builder::Update {
                         original_entry_address,
@@ -72,7 +79,21 @@ pub fn update<'a>(
                             .expect("Must have source chain if write_workspace access is given");
                         // push the action and the entry into the source chain
                         let action_hash = source_chain
-                            .put_weightless(action_builder, Some(entry), chain_top_ordering)
+                            .put_weighed_with(
+                                action_builder,
+                                Some(entry),
+                                chain_top_ordering,
+                                |action, entry| {
+                                    let entry = entry.expect("The entry was just put");
+                                    let weight = weigh_action(
+                                        ribosome.as_ref(),
+                                        zome_index,
+                                        WeighInput::Update(action.clone(), entry.clone()),
+                                    )
+                                    .map_err(SourceChainError::other)?;
+                                    Ok(entry_rate_weight(weight, entry))
+                                },
+                            )
                             .await
                             .map_err(|source_chain_error| -> RuntimeError {
                                 wasm_error!(WasmErrorInner::Host(source_chain_error.to_string()))
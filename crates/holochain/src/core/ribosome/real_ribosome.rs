@@ -3,11 +3,13 @@ use super::guest_callback::init::InitHostAccess;
 use super::guest_callback::migrate_agent::MigrateAgentHostAccess;
 use super::guest_callback::post_commit::PostCommitHostAccess;
 use super::guest_callback::validate::ValidateHostAccess;
+use super::guest_callback::weigh::WeighHostAccess;
 use super::host_fn::delete_clone_cell::delete_clone_cell;
 use super::host_fn::disable_clone_cell::disable_clone_cell;
 use super::host_fn::enable_clone_cell::enable_clone_cell;
 use super::host_fn::get_agent_activity::get_agent_activity;
 use super::host_fn::HostFnApi;
+use super::is_callback;
 use super::HostContext;
 use super::ZomeCallHostAccess;
 use crate::core::ribosome::error::RibosomeError;
@@ -27,6 +29,7 @@ use crate::core::ribosome::guest_callback::migrate_agent::MigrateAgentResult;
 use crate::core::ribosome::guest_callback::post_commit::PostCommitInvocation;
 use crate::core::ribosome::guest_callback::validate::ValidateInvocation;
 use crate::core::ribosome::guest_callback::validate::ValidateResult;
+use crate::core::ribosome::guest_callback::weigh::WeighInvocation;
 use crate::core::ribosome::guest_callback::CallIterator;
 use crate::core::ribosome::host_fn::accept_countersigning_preflight_request::accept_countersigning_preflight_request;
 use crate::core::ribosome::host_fn::agent_info::agent_info;
@@ -51,12 +54,14 @@ use crate::core::ribosome::host_fn::get::get;
 use crate::core::ribosome::host_fn::get_details::get_details;
 use crate::core::ribosome::host_fn::get_link_details::get_link_details;
 use crate::core::ribosome::host_fn::get_links::get_links;
+use crate::core::ribosome::host_fn::get_meta::get_meta;
 use crate::core::ribosome::host_fn::hash::hash;
 use crate::core::ribosome::host_fn::must_get_action::must_get_action;
 use crate::core::ribosome::host_fn::must_get_agent_activity::must_get_agent_activity;
 use crate::core::ribosome::host_fn::must_get_entry::must_get_entry;
 use crate::core::ribosome::host_fn::must_get_valid_record::must_get_valid_record;
 use crate::core::ribosome::host_fn::query::query;
+use crate::core::ribosome::host_fn::query_previous_chain::query_previous_chain;
 use crate::core::ribosome::host_fn::random_bytes::random_bytes;
 use crate::core::ribosome::host_fn::schedule::schedule;
 use crate::core::ribosome::host_fn::send_remote_signal::send_remote_signal;
@@ -351,6 +356,25 @@ impl RealRibosome {
         }
     }
 
+    /// The functions the zome exports, leaving out the functions which are
+    /// exported by the HDK for the host, such as the allocation functions.
+    fn zome_exports(&self, zome: &Zome) -> RibosomeResult<Vec<FunctionName>> {
+        let mut exports: Vec<FunctionName> = match zome.zome_def() {
+            ZomeDef::Wasm(_) => self
+                .get_module_for_zome(zome)?
+                .exports()
+                .functions()
+                .map(|f| FunctionName(f.name().to_string()))
+                .collect(),
+            ZomeDef::Inline {
+                inline_zome: zome, ..
+            } => zome.0.functions(),
+        };
+        exports.retain(|f| !f.0.starts_with("__"));
+        exports.sort();
+        Ok(exports)
+    }
+
     pub fn build_instance_with_store(
         &self,
         module: Arc<Module>,
@@ -421,6 +445,7 @@ impl RealRibosome {
             },
             integrity_zomes: Default::default(),
             coordinator_zomes: Default::default(),
+            rate_limits: Default::default(),
         };
         let empty_dna_file = DnaFile::new(empty_dna_def, vec![]).await;
         let empty_ribosome = RealRibosome::new(
@@ -535,6 +560,7 @@ impl RealRibosome {
             .with_host_function(&mut ns, "__hc__capability_info_1", capability_info)
             .with_host_function(&mut ns, "__hc__get_1", get)
             .with_host_function(&mut ns, "__hc__get_details_1", get_details)
+            .with_host_function(&mut ns, "__hc__get_meta_1", get_meta)
             .with_host_function(&mut ns, "__hc__get_links_1", get_links)
             .with_host_function(&mut ns, "__hc__get_link_details_1", get_link_details)
             .with_host_function(&mut ns, "__hc__count_links_1", count_links)
@@ -552,6 +578,11 @@ impl RealRibosome {
                 must_get_agent_activity,
             )
             .with_host_function(&mut ns, "__hc__query_1", query)
+            .with_host_function(
+                &mut ns,
+                "__hc__query_previous_chain_1",
+                query_previous_chain,
+            )
             .with_host_function(&mut ns, "__hc__send_remote_signal_1", send_remote_signal)
             .with_host_function(&mut ns, "__hc__call_1", call)
             .with_host_function(&mut ns, "__hc__create_1", create)
@@ -873,6 +904,18 @@ impl RibosomeT for RealRibosome {
         }
     }
 
+    fn list_callbacks(&self, zome: &Zome) -> RibosomeResult<Vec<FunctionName>> {
+        let mut exports = self.zome_exports(zome)?;
+        exports.retain(is_callback);
+        Ok(exports)
+    }
+
+    fn list_zome_fns(&self, zome: &Zome) -> RibosomeResult<Vec<FunctionName>> {
+        let mut exports = self.zome_exports(zome)?;
+        exports.retain(|f| !is_callback(f));
+        Ok(exports)
+    }
+
     fn call_iterator<I: crate::core::ribosome::Invocation>(
         &self,
         host_context: HostContext,
@@ -945,6 +988,21 @@ impl RibosomeT for RealRibosome {
         do_callback!(self, host_access, invocation, ValidateCallbackResult)
     }
 
+    fn run_weigh(
+        &self,
+        host_access: WeighHostAccess,
+        invocation: WeighInvocation,
+    ) -> RibosomeResult<RateWeight> {
+        match self.call_iterator(host_access.into(), invocation).next() {
+            Ok(Some((_zome, extern_io))) => Ok(extern_io
+                .decode()
+                .map_err(|e| -> RuntimeError { wasm_error!(e).into() })?),
+            // Zomes which don't weigh their actions leave them weightless.
+            Ok(None) => Ok(RateWeight::default()),
+            Err((_zome, ribosome_error)) => Err(ribosome_error),
+        }
+    }
+
     fn run_init(
         &self,
         host_access: InitHostAccess,
@@ -1198,6 +1256,7 @@ pub mod wasm_test {
                 "__hc__get_details_1",
                 "__hc__get_link_details_1",
                 "__hc__get_links_1",
+                "__hc__get_meta_1",
                 "__hc__hash_1",
                 "__hc__must_get_action_1",
                 "__hc__must_get_agent_activity_1",
@@ -222,10 +222,38 @@ pub async fn check_chain_rollback(
     }
 }
 
-/// Placeholder for future spam check.
-/// Check action timestamps don't exceed MAX_PUBLISH_FREQUENCY
-pub async fn check_spam(_action: &Action) -> SysValidationResult<()> {
-    Ok(())
+/// Check that an action doesn't overflow any of the rate limiting buckets
+/// declared by the DNA.
+///
+/// The prior actions are the author's earlier actions, in chain order, which
+/// can still affect the level of the buckets. They have already been
+/// validated, so any of them which overflow a bucket are skipped rather than
+/// failing this check.
+pub fn check_rate_limits(
+    action: &Action,
+    prior_actions: &[Action],
+    rate_limits: &RateLimits,
+) -> SysValidationResult<()> {
+    let mut buckets = RateBuckets::new(rate_limits);
+    for prior_action in prior_actions {
+        // Prior actions which overflowed were rejected, so they fill no bucket.
+        let _ = buckets.change(&prior_action.rate_data(), prior_action.timestamp());
+    }
+    buckets
+        .change(&action.rate_data(), action.timestamp())
+        .map_err(|e| ValidationOutcome::RateLimitExceeded(e, action.clone()).into())
+}
+
+/// The earliest time from which an author's prior actions can still affect
+/// the level of the DNA's rate limiting buckets at the given time.
+pub fn rate_limit_window_start(timestamp: Timestamp, rate_limits: &RateLimits) -> Timestamp {
+    let window_ms = rate_limits
+        .values()
+        .map(RateLimit::full_drain_ms)
+        .max()
+        .unwrap_or(0);
+    let window_micros = i64::try_from(window_ms.saturating_mul(1000)).unwrap_or(i64::MAX);
+    Timestamp::from_micros(timestamp.as_micros().saturating_sub(window_micros))
 }
 
 /// Check that created agents are always paired with an AgentValidationPkg and vice versa
@@ -275,6 +303,22 @@ pub fn check_agent_validation_pkg_predecessor(
     }
 }
 
+/// Check that nothing is added to a chain after it was closed by a migration
+pub fn check_prev_action_not_close_chain(
+    action: &Action,
+    prev_action: &Action,
+) -> SysValidationResult<()> {
+    if let Action::CloseChain(_) = prev_action {
+        Err(PrevActionErrorKind::InvalidSuccessor(
+            "A CloseChain action must be the last action of a chain".to_string(),
+            Box::new((prev_action.clone(), action.clone())),
+        ))
+        .map_err(|e| ValidationOutcome::PrevActionError((e, action.clone()).into()).into())
+    } else {
+        Ok(())
+    }
+}
+
 /// Check that the author didn't change between actions
 pub fn check_prev_author(action: &Action, prev_action: &Action) -> SysValidationResult<()> {
     // Agent updates will be valid when DPKI support lands
@@ -122,6 +122,8 @@ pub enum ValidationOutcome {
     EntryTypeMismatch,
     #[error("The visibility for {0:?} didn't match the zome")]
     EntryVisibility(AppEntryDef),
+    #[error("The key of the author {0} was not valid in DPKI at {1}")]
+    InvalidAuthorKey(AgentPubKey, Timestamp),
     #[error(
         "The link tag size {0} was larger than the MAX_TAG_SIZE {}",
         super::MAX_TAG_SIZE
@@ -139,6 +141,8 @@ pub enum ValidationOutcome {
     PrevActionError(#[from] PrevActionError),
     #[error("Private entry data should never be included in any op other than StoreEntry.")]
     PrivateEntryLeaked,
+    #[error("The action {1:?} exceeded the rate limits of the DNA: {0}")]
+    RateLimitExceeded(RateBucketError, Action),
     #[error("The DNA does not belong in this space! Action has {0:?}, expected {1:?}")]
     WrongDna(DnaHash, DnaHash),
     #[error("Update original: {0:?} doesn't match new: {1:?}")]
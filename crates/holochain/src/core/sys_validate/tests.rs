@@ -9,6 +9,7 @@
 //!     TODO refactor to not look in the db)
 //! - Timestamps must increase monotonically
 //! - Sequence numbers must increment by 1 for each new action
+//! - Nothing can follow a CloseChain action
 //! - Entry type in the action matches the entry variant
 //! - Hash integrity check. The hash of an entry always matches what's in the action.
 //! - The size of an entry does not exceed the max.
@@ -17,6 +18,7 @@
 //! - Check the AppEntryDef is valid for the zome and the EntryDefId and ZomeIndex are in range.
 //! - Check that StoreEntry never contains a private entry type
 //! - Test that a given sequence of actions constitutes a valid chain w.r.t. its backlinks
+//! - Actions can't overflow the rate limiting buckets declared by the DNA
 //!
 //! TO TEST:
 //! - Create and Update Agent can only be preceded by AgentValidationPkg
@@ -496,6 +498,87 @@ async fn check_previous_seq() {
     );
 }
 
+/// Nothing can follow a CloseChain action
+#[test]
+fn check_prev_action_not_close_chain_test() {
+    let mut g = random_generator();
+
+    let action: Action = CreateLink::arbitrary(&mut g).unwrap().into();
+    let prev_action: Action = Create::arbitrary(&mut g).unwrap().into();
+    assert!(check_prev_action_not_close_chain(&action, &prev_action).is_ok());
+
+    let prev_action: Action = CloseChain::arbitrary(&mut g).unwrap().into();
+    assert_matches!(
+        check_prev_action_not_close_chain(&action, &prev_action)
+            .unwrap_err()
+            .into_outcome(),
+        Some(ValidationOutcome::PrevActionError(PrevActionError {
+            source: PrevActionErrorKind::InvalidSuccessor(_, _),
+            ..
+        }))
+    );
+}
+
+/// Actions can't overflow the rate limiting buckets declared by the DNA
+#[test]
+fn check_rate_limits_test() {
+    let mut g = random_generator();
+    let rate_limits: RateLimits = [(
+        1,
+        RateLimit {
+            capacity: 20,
+            drain_amount: 10,
+            drain_interval_ms: 1000,
+        },
+    )]
+    .into();
+    let link_at = |g: &mut Unstructured, ms: i64, bucket_id: RateBucketId| -> Action {
+        let mut link = CreateLink::arbitrary(g).unwrap();
+        link.timestamp = Timestamp::from_micros(ms * 1000);
+        link.weight = RateWeight {
+            bucket_id,
+            units: 10,
+        };
+        link.into()
+    };
+
+    let prior = vec![link_at(&mut g, 0, 1), link_at(&mut g, 100, 1)];
+    // The bucket is full
+    assert_matches!(
+        check_rate_limits(&link_at(&mut g, 200, 1), &prior, &rate_limits),
+        Err(SysValidationError::ValidationOutcome(
+            ValidationOutcome::RateLimitExceeded(RateBucketError::BucketOverflow { .. }, _)
+        ))
+    );
+    // Buckets without a declared limit are not limited
+    assert_matches!(
+        check_rate_limits(&link_at(&mut g, 200, 2), &prior, &rate_limits),
+        Ok(())
+    );
+    // Once an interval has passed the bucket has drained enough
+    assert_matches!(
+        check_rate_limits(&link_at(&mut g, 1000, 1), &prior, &rate_limits),
+        Ok(())
+    );
+    // Prior actions which overflowed the bucket don't fill it
+    let mut overflowed = prior.clone();
+    overflowed.push(link_at(&mut g, 200, 1));
+    assert_matches!(
+        check_rate_limits(&link_at(&mut g, 1000, 1), &overflowed, &rate_limits),
+        Ok(())
+    );
+    // Nothing is limited without rate limits
+    assert_matches!(
+        check_rate_limits(&link_at(&mut g, 200, 1), &prior, &RateLimits::new()),
+        Ok(())
+    );
+
+    assert_eq!(
+        rate_limit_window_start(Timestamp::from_micros(5_000_000), &rate_limits),
+        Timestamp::from_micros(3_000_000)
+    );
+}
+
 /// Entry type in the action matches the entry variant
 #[test]
 fn check_entry_type_test() {
@@ -0,0 +1,186 @@
+//! Checking the proofs carried by warrants.
+//!
+//! A warrant's signature only shows who issued it. Before a warrant published
+//! to us is stored or acted upon, the actions its proof points to are fetched
+//! and the claimed misbehaviour is checked again locally.
+
+use crate::conductor::Conductor;
+use crate::core::ribosome::RibosomeT;
+use crate::core::sys_validate::verify_action_signature;
+use crate::core::validation::OutcomeOrError;
+use crate::core::workflow::app_validation_workflow;
+use crate::core::workflow::app_validation_workflow::AppValidationWorkspace;
+use crate::core::workflow::app_validation_workflow::Outcome;
+use crate::core::workflow::sys_validation_workflow::sys_validate_record;
+use crate::core::workflow::WorkflowResult;
+use crate::core::ValidationOutcome;
+use holochain_cascade::Cascade;
+use holochain_p2p::actor::GetOptions as NetworkGetOptions;
+use holochain_p2p::HolochainP2pDna;
+use holochain_types::prelude::*;
+use parking_lot::Mutex;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Check that the proof of a warrant holds.
+///
+/// A [`ChainIntegrityWarrant::ChainFork`] holds if both actions are signed by
+/// the warrantee, sit at the same sequence number and differ.
+/// A [`ChainIntegrityWarrant::InvalidChainOp`] holds if the action is signed by
+/// the warrantee and validation of the claimed type rejects it again.
+///
+/// A proof whose actions can't be fetched, or whose validation is left waiting
+/// on dependencies, does not hold.
+pub(crate) async fn verify_warrant_proof<R: RibosomeT>(
+    proof: &WarrantProof,
+    workspace: &AppValidationWorkspace,
+    network: &HolochainP2pDna,
+    ribosome: &R,
+    conductor: &Conductor,
+) -> WorkflowResult<bool> {
+    let cascade = Arc::new(workspace.full_cascade(network.clone()));
+    match proof {
+        WarrantProof::ChainIntegrity(ChainIntegrityWarrant::ChainFork {
+            chain_author,
+            action_pair: (first, second),
+        }) => {
+            let first = fetch_signed_action(cascade.as_ref(), chain_author, first).await?;
+            let second = fetch_signed_action(cascade.as_ref(), chain_author, second).await?;
+            match (first, second) {
+                (Some(first), Some(second)) => Ok(is_chain_fork(&first, &second)),
+                _ => Ok(false),
+            }
+        }
+        WarrantProof::ChainIntegrity(ChainIntegrityWarrant::InvalidChainOp {
+            action_author,
+            action: (action_hash, signature),
+            validation_type,
+        }) => {
+            let record = match cascade
+                .retrieve(
+                    action_hash.clone().into(),
+                    NetworkGetOptions::must_get_options(),
+                )
+                .await?
+            {
+                Some((record, _)) => record,
+                None => return Ok(false),
+            };
+            let action = record.action();
+            if ActionHash::with_data_sync(action) != *action_hash
+                || action.author() != action_author
+                || verify_action_signature(signature, action).await.is_err()
+            {
+                return Ok(false);
+            }
+
+            match validation_type {
+                ValidationType::Sys => match sys_validate_record(&record, cascade).await {
+                    Ok(()) => Ok(false),
+                    Err(OutcomeOrError::Outcome(
+                        ValidationOutcome::Counterfeit(_, _)
+                        | ValidationOutcome::DepMissingFromDht(_),
+                    )) => Ok(false),
+                    Err(OutcomeOrError::Outcome(_)) => Ok(true),
+                    Err(OutcomeOrError::Err(e)) => Err(e.into()),
+                },
+                ValidationType::App => {
+                    let host_fn_workspace = workspace.validation_workspace().await?;
+                    for op_type in action_to_op_types(action) {
+                        // Private entries are never served, so there is nothing to
+                        // check for their StoreEntry ops.
+                        if op_type == DhtOpType::StoreEntry && record.entry().as_option().is_none()
+                        {
+                            continue;
+                        }
+                        let op = match app_validation_workflow::record_to_op(
+                            record.clone(),
+                            op_type,
+                            cascade.clone(),
+                        )
+                        .await
+                        {
+                            Ok((op, _)) => op,
+                            Err(OutcomeOrError::Outcome(Outcome::Rejected(_))) => return Ok(true),
+                            Err(OutcomeOrError::Outcome(_)) => continue,
+                            Err(OutcomeOrError::Err(e)) => return Err(e.into()),
+                        };
+                        let outcome = app_validation_workflow::validate_op(
+                            &op,
+                            host_fn_workspace.clone(),
+                            Arc::new(Mutex::new(HashSet::new())),
+                            network,
+                            ribosome,
+                            conductor,
+                        )
+                        .await
+                        .or_else(Outcome::try_from)?;
+                        if matches!(outcome, Outcome::Rejected(_)) {
+                            return Ok(true);
+                        }
+                    }
+                    Ok(false)
+                }
+            }
+        }
+    }
+}
+
+/// Fetch the action a warrant refers to, if it has the claimed hash and
+/// carries the claimed signature of the claimed author.
+async fn fetch_signed_action(
+    cascade: &impl Cascade,
+    author: &AgentPubKey,
+    (action_hash, signature): &ActionHashAndSig,
+) -> WorkflowResult<Option<Action>> {
+    let action = match cascade
+        .retrieve_action(action_hash.clone(), NetworkGetOptions::must_get_options())
+        .await?
+    {
+        Some((action, _)) => action.hashed.into_content(),
+        None => return Ok(None),
+    };
+    if ActionHash::with_data_sync(&action) != *action_hash
+        || action.author() != author
+        || verify_action_signature(signature, &action).await.is_err()
+    {
+        return Ok(None);
+    }
+    Ok(Some(action))
+}
+
+/// Two actions fork a chain if they are by the same author, at the same
+/// sequence number, and are not the same action.
+fn is_chain_fork(first: &Action, second: &Action) -> bool {
+    first.author() == second.author()
+        && first.action_seq() == second.action_seq()
+        && ActionHash::with_data_sync(first) != ActionHash::with_data_sync(second)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::fixt::prelude::*;
+
+    #[test]
+    fn chain_fork_needs_same_author_same_seq_and_different_actions() {
+        let author = fixt!(AgentPubKey);
+        let mut first = fixt!(Create);
+        first.author = author.clone();
+        first.action_seq = 5;
+        let mut second = first.clone();
+        second.timestamp = Timestamp::from_micros(first.timestamp.as_micros() + 1);
+
+        let first = Action::Create(first);
+        assert!(!is_chain_fork(&first, &first));
+        assert!(is_chain_fork(&first, &Action::Create(second.clone())));
+
+        let mut other_seq = second.clone();
+        other_seq.action_seq = 6;
+        assert!(!is_chain_fork(&first, &Action::Create(other_seq)));
+
+        let mut other_author = second;
+        other_author.author = fixt!(AgentPubKey);
+        assert!(!is_chain_fork(&first, &Action::Create(other_author)));
+    }
+}
@@ -10,6 +10,7 @@ use crate::core::queue_consumer::WorkComplete;
 use crate::core::ribosome::guest_callback::validate::ValidateHostAccess;
 use crate::core::ribosome::guest_callback::validate::ValidateInvocation;
 use crate::core::ribosome::guest_callback::validate::ValidateResult;
+use crate::core::ribosome::weigh_action;
 use crate::core::ribosome::RibosomeT;
 use crate::core::ribosome::ZomesToInvoke;
 use crate::core::SysValidationError;
@@ -100,6 +101,8 @@ async fn app_validation_workflow_inner(
     let iter = sorted_ops.into_iter().map({
         let network = network.clone();
         let workspace = workspace.clone();
+        let conductor = conductor.clone();
+        let dna_hash = dna_hash.clone();
         move |so| {
             let network = network.clone();
             let conductor = conductor.clone();
@@ -112,6 +115,7 @@ async fn app_validation_workflow_inner(
                 let action = op.action();
                 let dependency = op.sys_validation_dependency();
                 let op_lite = op.to_lite();
+                let author_and_sig = (action.author().clone(), op.signature().clone());
 
                 // If this is agent activity, track it for the cache.
                 let activity = matches!(op_type, DhtOpType::RegisterAgentActivity).then(|| {
@@ -138,7 +142,7 @@ async fn app_validation_workflow_inner(
                     }
                     Err(e) => Err(e),
                 };
-                (op_hash, dependency, op_lite, r, activity)
+                (op_hash, dependency, op_lite, author_and_sig, r, activity)
             }
         }
     });
@@ -148,15 +152,17 @@ async fn app_validation_workflow_inner(
     tracing::debug!("Committing {} ops", validation_results.len());
     let mut ops_validated = 0;
     let sleuth_id = sleuth_id.clone();
-    let (accepted_ops, awaiting_ops, rejected_ops, activity) = workspace
+    let (accepted_ops, awaiting_ops, rejected_ops, activity, warrant_proofs) = workspace
         .dht_db
         .write_async(move |txn| {
             let mut accepted = 0;
             let mut awaiting = 0;
             let mut rejected = 0;
             let mut agent_activity = Vec::new();
+            let mut warrant_proofs = Vec::new();
             for outcome in validation_results {
-                let (op_hash, dependency, op_lite, outcome, activity) = outcome;
+                let (op_hash, dependency, op_lite, (author, signature), outcome, activity) =
+                    outcome;
                 // Get the outcome or return the error
                 let outcome = outcome.or_else(|outcome_or_err| outcome_or_err.try_into())?;
 
@@ -204,6 +210,13 @@ async fn app_validation_workflow_inner(
                             "Received invalid op. The op author will be blocked.\nOp: {:?}",
                             op_lite
                         );
+                        warrant_proofs.push(WarrantProof::ChainIntegrity(
+                            ChainIntegrityWarrant::InvalidChainOp {
+                                action_author: author,
+                                action: (op_lite.action_hash().clone(), signature),
+                                validation_type: ValidationType::App,
+                            },
+                        ));
                         if dependency.is_none() {
                             put_integrated(txn, &op_hash, ValidationStatus::Rejected)?;
                         } else {
@@ -212,10 +225,18 @@ async fn app_validation_workflow_inner(
                     }
                 }
             }
-            WorkflowResult::Ok((accepted, awaiting, rejected, agent_activity))
+            WorkflowResult::Ok((accepted, awaiting, rejected, agent_activity, warrant_proofs))
         })
         .await?;
 
+    // Warrant the authors of rejected ops now that the outcomes are committed.
+    if let Err(e) = conductor.issue_warrants(&dna_hash, warrant_proofs).await {
+        warn!(
+            ?e,
+            "Failed to issue warrants for ops which failed app validation"
+        );
+    }
+
     // Once the database transaction is committed, add agent activity to the cache
     // that is ready for integration.
     for (author, seq, has_no_dependency) in activity {
@@ -426,12 +447,12 @@ pub async fn validate_op<R>(
     validation_dependencies: Arc<Mutex<HashSet<AnyDhtHash>>>,
     network: &HolochainP2pDna,
     ribosome: &R,
-    conductor_handle: &ConductorHandle,
+    conductor: &Conductor,
 ) -> AppValidationOutcome<Outcome>
 where
     R: RibosomeT,
 {
-    check_entry_def(op, &network.dna_hash(), conductor_handle)
+    check_entry_def(op, &network.dna_hash(), conductor)
         .await
         .map_err(AppValidationError::SysValidationError)?;
 
@@ -475,6 +496,8 @@ where
         }) => create_link_zomes_to_invoke(action, ribosome)?,
     };
 
+    check_weight(op, ribosome)?;
+
     let invocation = ValidateInvocation::new(zomes_to_invoke, op)
         .map_err(|e| AppValidationError::RibosomeError(e.into()))?;
     let outcome = run_validation_callback_inner(
@@ -489,6 +512,85 @@ where
     Ok(outcome)
 }
 
+/// Check that the weight of an action is the weight given to it by the
+/// `weigh` callback of its integrity zome, so that authors can't evade the
+/// DNA's rate limits by under-weighing their actions.
+///
+/// A weight of zero units fills no bucket, so it is the same as the default
+/// weight whichever bucket it names. Zomes without a `weigh` callback give
+/// every action the default weight, as do authors whose actions predate
+/// weighing. Only the bucket and units are checked, since the rate bytes of
+/// an entry are not held to any limit.
+///
+/// Countersigned entries are not weighed, so their weights aren't checked.
+fn check_weight(op: &Op, ribosome: &impl RibosomeT) -> AppValidationOutcome<()> {
+    let weigh = |zome_index: Option<ZomeIndex>, input: WeighInput| {
+        weigh_action(ribosome, zome_index, input).map_err(AppValidationError::RibosomeError)
+    };
+    let (expected, actual) = match op {
+        Op::StoreEntry(StoreEntry { action, entry })
+            if !matches!(entry, Entry::CounterSign(..)) =>
+        {
+            let zome_index = action
+                .hashed
+                .content
+                .app_entry_def()
+                .map(|app_entry_def| app_entry_def.zome_index);
+            let (input, actual) = match &action.hashed.content {
+                EntryCreationAction::Create(create) => (
+                    WeighInput::Create(create.clone().unweighed(), entry.clone()),
+                    Action::Create(create.clone()),
+                ),
+                EntryCreationAction::Update(update) => (
+                    WeighInput::Update(update.clone().unweighed(), entry.clone()),
+                    Action::Update(update.clone()),
+                ),
+            };
+            (weigh(zome_index, input)?, actual)
+        }
+        Op::RegisterCreateLink(RegisterCreateLink { create_link, .. }) => {
+            let create_link = &create_link.hashed.content;
+            let input = WeighInput::Link(create_link.clone().unweighed());
+            (
+                weigh(Some(create_link.zome_index), input)?,
+                Action::CreateLink(create_link.clone()),
+            )
+        }
+        Op::RegisterDelete(RegisterDelete {
+            delete,
+            original_action,
+            ..
+        }) => {
+            let delete = &delete.hashed.content;
+            let zome_index = original_action
+                .app_entry_def()
+                .map(|app_entry_def| app_entry_def.zome_index);
+            let input = WeighInput::Delete(delete.clone().unweighed());
+            (weigh(zome_index, input)?, Action::Delete(delete.clone()))
+        }
+        _ => return Ok(()),
+    };
+    if normalized_weight(expected.clone()) == normalized_weight(actual.rate_data()) {
+        Ok(())
+    } else {
+        Err(Outcome::rejected(format!(
+            "The action {:?} was not weighed by its integrity zome. Expected weight {:?} but got {:?}",
+            actual,
+            expected,
+            actual.rate_data()
+        )))
+    }
+}
+
+/// Weights of zero units fill no bucket, so they all count as the default weight.
+fn normalized_weight(weight: RateWeight) -> RateWeight {
+    if weight.units == 0 {
+        RateWeight::default()
+    } else {
+        weight
+    }
+}
+
 /// Check the AppEntryDef is valid for the zome.
 /// Check the EntryDefId and ZomeIndex are in range.
 pub async fn check_entry_def(
@@ -404,6 +404,7 @@ async fn check_app_entry_def_test() {
             coordinator_zomes: vec![TestZomes::from(TestWasm::EntryDefs)
                 .coordinator
                 .into_inner()],
+            rate_limits: Default::default(),
         },
         [integrity, coordinator],
     )
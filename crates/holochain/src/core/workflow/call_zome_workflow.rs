@@ -13,6 +13,9 @@ use crate::core::ribosome::guest_callback::post_commit::send_post_commit;
 use crate::core::ribosome::RibosomeT;
 use crate::core::ribosome::ZomeCallHostAccess;
 use crate::core::ribosome::ZomeCallInvocation;
+use crate::core::sys_validate::check_rate_limits;
+use crate::core::sys_validate::rate_limit_window_start;
+use crate::core::sys_validate::SysValidationError;
 use crate::core::workflow::WorkflowError;
 use holochain_keystore::MetaLairClient;
 use holochain_p2p::HolochainP2pDna;
@@ -238,10 +241,23 @@ where
         network.clone(),
     ));
 
+    let rate_limits = ribosome.dna_def().rate_limits.clone();
+
     let to_app_validate = {
         // collect all the records we need to validate in wasm
         let scratch_records = workspace.source_chain().scratch_records()?;
         let mut to_app_validate: Vec<Record> = Vec::with_capacity(scratch_records.len());
+        // The committed actions which can still affect the rate limiting buckets
+        let mut prior_actions = match scratch_records.first() {
+            Some(record) if !rate_limits.is_empty() => {
+                let since = rate_limit_window_start(record.action().timestamp(), &rate_limits);
+                workspace
+                    .source_chain()
+                    .committed_actions_since(since)
+                    .await?
+            }
+            _ => Vec::new(),
+        };
         // Loop forwards through all the new records
         for record in scratch_records {
             sys_validate_record(&record, cascade.clone())
@@ -250,6 +266,15 @@ where
                 // If the validation failed, exit with an InvalidCommit
                 // If it was ok continue
                 .or_else(|outcome_or_err| outcome_or_err.invalid_call_zome_commit())?;
+            if !rate_limits.is_empty() {
+                match check_rate_limits(record.action(), &prior_actions, &rate_limits) {
+                    Ok(()) => prior_actions.push(record.action().clone()),
+                    Err(SysValidationError::ValidationOutcome(outcome)) => {
+                        return Err(SourceChainError::InvalidCommit(outcome.to_string()).into())
+                    }
+                    Err(e) => return Err(e.into()),
+                }
+            }
             to_app_validate.push(record);
         }
 
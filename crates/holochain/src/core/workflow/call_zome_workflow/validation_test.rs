@@ -1,7 +1,9 @@
 use crate::conductor::api::error::ConductorApiError;
+use crate::conductor::api::error::ConductorApiResult;
 use crate::conductor::CellError;
 use crate::conductor::ConductorHandle;
 use crate::core::workflow::WorkflowError;
+use crate::core::workflow::ZomeCallResult;
 use crate::core::SourceChainError;
 use crate::test_utils::new_zome_call;
 use crate::test_utils::setup_app_with_names;
@@ -32,6 +34,7 @@ async fn direct_validation_test() {
             },
             integrity_zomes: vec![TestZomes::from(TestWasm::Update).integrity.into_inner()],
             coordinator_zomes: vec![TestZomes::from(TestWasm::Update).coordinator.into_inner()],
+            rate_limits: Default::default(),
         },
         [integrity, coordinator],
     )
@@ -48,6 +51,119 @@ async fn direct_validation_test() {
     handle.shutdown().await.unwrap().unwrap();
 }
 
+/// Commits which exceed the DNA's rate limits fail the zome call
+#[tokio::test(flavor = "multi_thread")]
+async fn rate_limit_exceeded_test() {
+    holochain_trace::test_run().ok();
+
+    let TestWasmPair::<DnaWasm> {
+        integrity,
+        coordinator,
+    } = TestWasm::RateLimits.into();
+    // Links weigh 10 units in bucket 1, which barely drains during the test
+    let rate_limits: RateLimits = [(
+        1,
+        RateLimit {
+            capacity: 20,
+            drain_amount: 1,
+            drain_interval_ms: 60 * 60 * 1000,
+        },
+    )]
+    .into();
+    let dna_file = DnaFile::new(
+        DnaDef {
+            name: "rate_limit_exceeded_test".to_string(),
+            modifiers: DnaModifiers {
+                network_seed: "c2bd3b28-1a3b-4c0e-9bd6-7d35e1a4f3a1".to_string(),
+                properties: SerializedBytes::try_from(()).unwrap(),
+                origin_time: Timestamp::HOLOCHAIN_EPOCH,
+                quantum_time: holochain_p2p::dht::spacetime::STANDARD_QUANTUM_TIME,
+            },
+            integrity_zomes: vec![TestZomes::from(TestWasm::RateLimits).integrity.into_inner()],
+            coordinator_zomes: vec![TestZomes::from(TestWasm::RateLimits)
+                .coordinator
+                .into_inner()],
+            rate_limits,
+        },
+        [integrity, coordinator],
+    )
+    .await;
+
+    let alice_agent_id = fake_agent_pubkey_1();
+    let alice_cell_id = CellId::new(dna_file.dna_hash().to_owned(), alice_agent_id.clone());
+
+    let (_tmpdir, _app_api, handle) =
+        setup_app_with_names(alice_agent_id, vec![("test_app", vec![(dna_file, None)])]).await;
+
+    let create_thing = |n: u32| {
+        let handle = handle.clone();
+        let cell_id = alice_cell_id.clone();
+        async move {
+            match call_rate_limits(&handle, &cell_id, "create_thing", n)
+                .await
+                .unwrap()
+                .unwrap()
+            {
+                ZomeCallResponse::Ok(io) => io.decode::<ActionHash>().unwrap(),
+                other => panic!("Expected Ok got {:?}", other),
+            }
+        }
+    };
+    let base = create_thing(0).await;
+    let target = create_thing(1).await;
+    let link = (base, target);
+
+    // Two links fill the bucket
+    for _ in 0..2 {
+        call_rate_limits(&handle, &alice_cell_id, "link_things", link.clone())
+            .await
+            .unwrap()
+            .unwrap();
+    }
+
+    // and a third overflows it
+    let result = call_rate_limits(&handle, &alice_cell_id, "link_things", link).await;
+    match &result {
+        Err(ConductorApiError::CellError(CellError::WorkflowError(wfe))) => match &**wfe {
+            WorkflowError::SourceChainError(SourceChainError::InvalidCommit(reason)) => {
+                assert!(
+                    reason.contains("exceeded the rate limits"),
+                    "Expected the rate limits to be exceeded, got {}",
+                    reason
+                );
+            }
+            _ => panic!("Expected InvalidCommit got {:?}", result),
+        },
+        _ => panic!("Expected InvalidCommit got {:?}", result),
+    }
+
+    // Entries are weighed into another bucket, which has no limit
+    create_thing(2).await;
+
+    handle.shutdown().await.unwrap().unwrap();
+}
+
+async fn call_rate_limits<P>(
+    handle: &ConductorHandle,
+    cell_id: &CellId,
+    fn_name: &str,
+    payload: P,
+) -> ConductorApiResult<ZomeCallResult>
+where
+    P: serde::Serialize + std::fmt::Debug,
+{
+    let invocation = new_zome_call(
+        handle.keystore(),
+        cell_id,
+        fn_name,
+        payload,
+        TestWasm::RateLimits,
+    )
+    .await
+    .unwrap();
+    handle.call_zome(invocation).await
+}
+
 /// - Commit a valid update should pass
 /// - Commit an invalid update should fail the zome call
 async fn run_test(alice_cell_id: CellId, handle: ConductorHandle) {
@@ -4,6 +4,7 @@ use std::sync::Arc;
 use holo_hash::{ActionHash, AgentPubKey, DhtOpHash, EntryHash, OpBasis};
 use holochain_keystore::AgentPubKeyExt;
 use holochain_p2p::{HolochainP2pDna, HolochainP2pDnaT};
+use holochain_state::countersigning::*;
 use holochain_state::integrate::authored_ops_to_dht_db_without_check;
 use holochain_state::mutations;
 use holochain_state::prelude::*;
@@ -13,23 +14,27 @@ use rusqlite::{named_params, Transaction};
 use crate::conductor::interface::SignalBroadcaster;
 use crate::conductor::space::Space;
 use crate::core::queue_consumer::{QueueTriggers, TriggerSender, WorkComplete};
-use crate::core::ribosome::weigh_placeholder;
 
 use holochain_p2p::event::CountersigningSessionNegotiationMessage;
 
 use super::{error::WorkflowResult, incoming_dht_ops_workflow::incoming_dht_ops_workflow};
 
 #[derive(Clone)]
-/// A cheaply clonable, thread safe and in-memory store for
-/// active countersigning sessions.
+/// A cheaply clonable, thread safe store for active countersigning sessions.
+///
+/// Sessions are kept in memory and written through to the DHT database,
+/// from which they are recovered when the conductor restarts.
 pub struct CountersigningWorkspace {
     inner: Share<CountersigningWorkspaceInner>,
+    dht_db: DbWrite<DbKindDht>,
 }
 
 #[derive(Default)]
 /// Pending countersigning sessions.
 pub struct CountersigningWorkspaceInner {
     pending: HashMap<EntryHash, Session>,
+    /// Whether the sessions held in the database have been loaded.
+    recovered: bool,
 }
 
 #[derive(Default)]
@@ -47,7 +52,7 @@ struct Session {
 // TODO: PERF: This takes a lock on the workspace which could
 // block other incoming DhtOps if there are many active sessions.
 // We could create an incoming buffer if this actually becomes an issue.
-pub(crate) fn incoming_countersigning(
+pub(crate) async fn incoming_countersigning(
     ops: Vec<(DhtOpHash, DhtOp)>,
     workspace: &CountersigningWorkspace,
     trigger: TriggerSender,
@@ -58,12 +63,16 @@ pub(crate) fn incoming_countersigning(
     // entry hash, required actions and expires time.
     for (hash, op) in ops {
         // Must be a store entry op.
-        if let DhtOp::StoreEntry(_, _, entry) = &op {
+        if let DhtOp::StoreEntry(_, action, entry) = &op {
             // Must have a counter sign entry type.
             if let Entry::CounterSign(session_data, _) = entry {
                 let entry_hash = EntryHash::with_data_sync(entry);
                 // Get the required actions for this session.
-                let weight = weigh_placeholder();
+                // Every party weighs the same entry, so shares the same weight.
+                let weight = match action {
+                    NewEntryAction::Create(create) => create.weight.clone(),
+                    NewEntryAction::Update(update) => update.weight.clone(),
+                };
                 let action_set = session_data.build_action_set(entry_hash, weight)?;
 
                 // Get the expires time for this session.
@@ -81,7 +90,15 @@ pub(crate) fn incoming_countersigning(
                     // Check if already timed out.
                     if holochain_zome_types::prelude::Timestamp::now() < expires {
                         // Put this op in the pending map.
-                        workspace.put(entry_hash, hash, op, required_actions, expires);
+                        workspace
+                            .put(CountersigningSessionOp {
+                                entry_hash,
+                                op_hash: hash,
+                                op,
+                                required_actions,
+                                expires_at: expires,
+                            })
+                            .await?;
                         // We have new ops so we should trigger the workflow.
                         should_trigger = true;
                     }
@@ -104,8 +121,14 @@ pub(crate) async fn countersigning_workflow(
     network: impl HolochainP2pDnaT + Send + Sync,
     sys_validation_trigger: TriggerSender,
 ) -> WorkflowResult<WorkComplete> {
+    // Recover the sessions held before the conductor restarted.
+    space.countersigning_workspace.recover().await?;
+
     // Get any complete sessions.
-    let complete_sessions = space.countersigning_workspace.get_complete_sessions();
+    let complete_sessions = space
+        .countersigning_workspace
+        .get_complete_sessions()
+        .await?;
     let mut notify_agents = Vec::with_capacity(complete_sessions.len());
 
     // For each complete session send the ops to validation.
@@ -219,12 +242,15 @@ pub(crate) async fn countersigning_success(
 
     // Verify signatures of actions.
     let mut i_am_an_author = false;
+    let mut weight = EntryRateWeight::default();
     for SignedAction(action, signature) in &signed_actions {
         if !action.author().verify_signature(signature, action).await? {
             return Ok(());
         }
         if action.author() == &author {
             i_am_an_author = true;
+            // The weight of the entry is the same for every party in the session.
+            weight = action.entry_rate_data().unwrap_or_default();
         }
     }
     // Countersigning success is ultimately between authors to agree and publish.
@@ -246,7 +272,6 @@ pub(crate) async fn countersigning_success(
             if let Some((cs_entry_hash, cs)) = current_countersigning_session(txn, Arc::new(author.clone()))? {
                 // Check we have the right session.
                 if cs_entry_hash == entry_hash {
-                    let weight = weigh_placeholder();
                     let stored_actions = cs.build_action_set(entry_hash, weight)?;
                     if stored_actions.len() == incoming_actions.len() {
                         // Check all stored action hashes match an incoming action hash.
@@ -348,44 +373,83 @@ type Ops = Vec<(DhtOpHash, DhtOp)>;
 type SignedActions = Vec<SignedAction>;
 
 impl CountersigningWorkspace {
-    /// Create a new empty countersigning workspace.
-    pub fn new() -> CountersigningWorkspace {
+    /// Create a new empty countersigning workspace, which holds its
+    /// sessions in the given DHT database.
+    pub fn new(dht_db: DbWrite<DbKindDht>) -> CountersigningWorkspace {
         Self {
             inner: Share::new(Default::default()),
+            dht_db,
         }
     }
 
-    /// Put a single signers store entry op in the workspace.
-    fn put(
-        &self,
-        entry_hash: EntryHash,
-        op_hash: DhtOpHash,
-        op: DhtOp,
-        required_actions: Vec<ActionHash>,
-        expires: Timestamp,
-    ) {
-        // hash the action of this ops.
-        let action_hash = ActionHash::with_data_sync(&op.action());
+    /// Load the sessions held in the database into the workspace,
+    /// unless they have been loaded already.
+    async fn recover(&self) -> WorkflowResult<()> {
+        let recovered = self.inner.share_ref(|i| Ok(i.recovered)).unwrap_or(true);
+        if recovered {
+            return Ok(());
+        }
+        let now = Timestamp::now();
+        let ops = self
+            .dht_db
+            .read_async(move |txn| list_countersigning_session_ops(&txn, now))
+            .await?;
         self.inner
             .share_mut(|i, _| {
-                // Get the session at this entry or create an empty one.
-                let session = i.pending.entry(entry_hash).or_default();
-
-                // Insert the op into the session.
-                session
-                    .map
-                    .insert(action_hash, (op_hash, op, required_actions));
+                if !i.recovered {
+                    i.recovered = true;
+                    for op in ops {
+                        i.insert(op);
+                    }
+                }
+                Ok(())
+            })
+            // We don't close this share so we can ignore this error.
+            .ok();
+        Ok(())
+    }
 
-                // Set the expires time.
-                session.expires = Some(expires);
+    /// Put a single signers store entry op in the workspace.
+    async fn put(&self, op: CountersigningSessionOp) -> WorkflowResult<()> {
+        self.dht_db
+            .write_async({
+                let op = op.clone();
+                move |txn| insert_countersigning_session_op(txn, &op)
+            })
+            .await?;
+        self.inner
+            .share_mut(|i, _| {
+                i.insert(op);
                 Ok(())
             })
             // We don't close this share so we can ignore this error.
             .ok();
+        Ok(())
     }
 
-    fn get_complete_sessions(&self) -> Vec<(AgentsToNotify, Ops, SignedActions)> {
+    async fn get_complete_sessions(
+        &self,
+    ) -> WorkflowResult<Vec<(AgentsToNotify, Ops, SignedActions)>> {
         let now = holochain_zome_types::prelude::Timestamp::now();
+        let (complete, sessions) = self.take_complete_sessions(now);
+        self.dht_db
+            .write_async(move |txn| {
+                delete_expired_countersigning_sessions(txn, now)?;
+                for entry_hash in complete.iter() {
+                    delete_countersigning_session(txn, entry_hash)?;
+                }
+                StateMutationResult::Ok(())
+            })
+            .await?;
+        Ok(sessions)
+    }
+
+    /// Remove the expired and the complete sessions from memory, returning the
+    /// entry hashes and the contents of the complete sessions.
+    fn take_complete_sessions(
+        &self,
+        now: Timestamp,
+    ) -> (Vec<EntryHash>, Vec<(AgentsToNotify, Ops, SignedActions)>) {
         self.inner
             .share_mut(|i, _| {
                 // Remove any expired sessions.
@@ -417,8 +481,8 @@ impl CountersigningWorkspace {
                 // For each complete session remove from the pending map
                 // and fold into the signed actions to send to the agents
                 // and the ops to validate.
-                for hash in complete {
-                    if let Some(session) = i.pending.remove(&hash) {
+                for hash in complete.iter() {
+                    if let Some(session) = i.pending.remove(hash) {
                         let map = session.map;
                         let r = map.into_iter().fold(
                             (Vec::new(), Vec::new(), Vec::new()),
@@ -437,105 +501,147 @@ impl CountersigningWorkspace {
                         ret.push(r);
                     }
                 }
-                Ok(ret)
+                Ok((complete, ret))
             })
             .unwrap_or_default()
     }
 }
 
-impl Default for CountersigningWorkspace {
-    fn default() -> Self {
-        Self::new()
+impl CountersigningWorkspaceInner {
+    fn insert(&mut self, op: CountersigningSessionOp) {
+        let CountersigningSessionOp {
+            entry_hash,
+            op_hash,
+            op,
+            required_actions,
+            expires_at,
+        } = op;
+        // hash the action of this ops.
+        let action_hash = ActionHash::with_data_sync(&op.action());
+
+        // Get the session at this entry or create an empty one.
+        let session = self.pending.entry(entry_hash).or_default();
+
+        // Insert the op into the session.
+        session
+            .map
+            .insert(action_hash, (op_hash, op, required_actions));
+
+        // Set the expires time.
+        session.expires = Some(expires_at);
     }
 }
 
 #[cfg(test)]
 mod tests {
     use arbitrary::Arbitrary;
+    use holochain_state::test_utils::test_dht_db;
 
     use super::*;
 
-    #[test]
+    /// Create the ops of the signers of a session which expires at the given time.
+    fn session_ops(
+        u: &mut arbitrary::Unstructured,
+        signers: usize,
+        expires_at: Timestamp,
+    ) -> Vec<CountersigningSessionOp> {
+        let entry_hash = EntryHash::arbitrary(u).unwrap();
+        let ops: Vec<_> = (0..signers)
+            .map(|_| {
+                (
+                    DhtOpHash::arbitrary(u).unwrap(),
+                    DhtOp::arbitrary(u).unwrap(),
+                )
+            })
+            .collect();
+        let required_actions: Vec<_> = ops
+            .iter()
+            .map(|(_, op)| ActionHash::with_data_sync(&op.action()))
+            .collect();
+        ops.into_iter()
+            .map(|(op_hash, op)| CountersigningSessionOp {
+                entry_hash: entry_hash.clone(),
+                op_hash,
+                op,
+                required_actions: required_actions.clone(),
+                expires_at,
+            })
+            .collect()
+    }
+
+    fn pending_sessions(workspace: &CountersigningWorkspace) -> usize {
+        workspace.inner.share_ref(|i| Ok(i.pending.len())).unwrap()
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
     /// Test that a session of 5 actions is complete when
     /// the expiry time is in the future and all required actions
     /// are present.
-    fn gets_complete_sessions() {
+    async fn gets_complete_sessions() {
         let mut u = arbitrary::Unstructured::new(&holochain_zome_types::prelude::NOISE);
-        let workspace = CountersigningWorkspace::new();
-
-        // - Create the ops.
-        let data = |u: &mut arbitrary::Unstructured| {
-            let op_hash = DhtOpHash::arbitrary(u).unwrap();
-            let op = DhtOp::arbitrary(u).unwrap();
-            let action = op.action();
-            (op_hash, op, action)
-        };
-        let entry_hash = EntryHash::arbitrary(&mut u).unwrap();
-        let mut op_hashes = Vec::new();
-        let mut ops = Vec::new();
-        let mut required_actions = Vec::new();
-        for _ in 0..5 {
-            let (op_hash, op, action) = data(&mut u);
-            let action_hash = ActionHash::with_data_sync(&action);
-            op_hashes.push(op_hash);
-            ops.push(op);
-            required_actions.push(action_hash);
-        }
+        let workspace = CountersigningWorkspace::new(test_dht_db().to_db());
 
         // - Put the ops in the workspace with expiry set to one hour from now.
-        for (op_h, op) in op_hashes.into_iter().zip(ops.into_iter()) {
-            let expires = (Timestamp::now() + std::time::Duration::from_secs(60 * 60)).unwrap();
-            workspace.put(
-                entry_hash.clone(),
-                op_h,
-                op,
-                required_actions.clone(),
-                expires,
-            );
+        let expires = (Timestamp::now() + std::time::Duration::from_secs(60 * 60)).unwrap();
+        for op in session_ops(&mut u, 5, expires) {
+            workspace.put(op).await.unwrap();
         }
 
         // - Get all complete sessions.
-        let r = workspace.get_complete_sessions();
+        let r = workspace.get_complete_sessions().await.unwrap();
         // - Expect we have one.
         assert_eq!(r.len(), 1);
 
-        workspace
-            .inner
-            .share_mut(|i, _| {
-                // - Check we have none pending.
-                assert_eq!(i.pending.len(), 0);
-                Ok(())
-            })
-            .unwrap();
+        // - Check we have none pending.
+        assert_eq!(pending_sessions(&workspace), 0);
     }
 
-    #[test]
+    #[tokio::test(flavor = "multi_thread")]
     /// Test that expired sessions are removed.
-    fn expired_sessions_removed() {
+    async fn expired_sessions_removed() {
         let mut u = arbitrary::Unstructured::new(&holochain_zome_types::prelude::NOISE);
-        let workspace = CountersigningWorkspace::new();
-
-        // - Create an op for a session that has expired in the past.
-        let op_hash = DhtOpHash::arbitrary(&mut u).unwrap();
-        let op = DhtOp::arbitrary(&mut u).unwrap();
-        let action = op.action();
-        let entry_hash = EntryHash::arbitrary(&mut u).unwrap();
-        let action_hash = ActionHash::with_data_sync(&action);
-        let expires = (Timestamp::now() - std::time::Duration::from_secs(60 * 60)).unwrap();
+        let workspace = CountersigningWorkspace::new(test_dht_db().to_db());
 
-        // - Add it to the workspace.
-        workspace.put(entry_hash, op_hash, op, vec![action_hash], expires);
-        let r = workspace.get_complete_sessions();
+        // - Add an op for a session that has expired in the past.
+        let expires = (Timestamp::now() - std::time::Duration::from_secs(60 * 60)).unwrap();
+        for op in session_ops(&mut u, 1, expires) {
+            workspace.put(op).await.unwrap();
+        }
+        let r = workspace.get_complete_sessions().await.unwrap();
 
         // - Expect we have no complete sessions.
         assert_eq!(r.len(), 0);
-        workspace
-            .inner
-            .share_mut(|i, _| {
-                // - Check we have none pending.
-                assert_eq!(i.pending.len(), 0);
-                Ok(())
-            })
-            .unwrap();
+        // - Check we have none pending.
+        assert_eq!(pending_sessions(&workspace), 0);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    /// Test that the ops of a session are recovered from the database
+    /// by a new workspace, as after a restart of the conductor.
+    async fn sessions_are_recovered() {
+        let mut u = arbitrary::Unstructured::new(&holochain_zome_types::prelude::NOISE);
+        let dht_db = test_dht_db();
+        let workspace = CountersigningWorkspace::new(dht_db.to_db());
+
+        // - Put all but one of the ops of a session in the workspace.
+        let expires = (Timestamp::now() + std::time::Duration::from_secs(60 * 60)).unwrap();
+        let mut ops = session_ops(&mut u, 3, expires);
+        let last_op = ops.pop().unwrap();
+        for op in ops {
+            workspace.put(op).await.unwrap();
+        }
+        assert!(workspace.get_complete_sessions().await.unwrap().is_empty());
+
+        // - Put the last op in a new workspace on the same database.
+        let workspace = CountersigningWorkspace::new(dht_db.to_db());
+        workspace.recover().await.unwrap();
+        assert_eq!(pending_sessions(&workspace), 1);
+        workspace.put(last_op).await.unwrap();
+
+        // - Expect the session is complete and no longer held.
+        assert_eq!(workspace.get_complete_sessions().await.unwrap().len(), 1);
+        let workspace = CountersigningWorkspace::new(dht_db.to_db());
+        workspace.recover().await.unwrap();
+        assert_eq!(pending_sessions(&workspace), 0);
     }
 }
@@ -112,14 +112,14 @@ where
         return Err(WorkflowError::GenesisFailure(reason));
     }
 
-    // NB: this is just a placeholder for a real DPKI request to show intent
-    if !api
-        .conductor_services()
-        .dpki
-        .is_key_valid(agent_pubkey.clone(), Timestamp::now())
-        .await?
-    {
-        return Err(WorkflowError::AgentInvalid(agent_pubkey.clone()));
+    // The chain can only be started with a key which DPKI, if there is one, holds valid
+    if let Some(dpki) = api.conductor_services().dpki {
+        if !dpki
+            .is_key_valid(agent_pubkey.clone(), Timestamp::now())
+            .await?
+        {
+            return Err(WorkflowError::AgentInvalid(agent_pubkey.clone()));
+        }
     }
 
     source_chain::genesis(
@@ -180,7 +180,7 @@ mod tests {
     use super::*;
 
     use crate::conductor::api::MockCellConductorApiT;
-    use crate::conductor::conductor::{mock_app_store, mock_dpki, ConductorServices};
+    use crate::conductor::conductor::{mock_dpki, ConductorServices};
     use crate::core::ribosome::MockRibosomeT;
     use holochain_keystore::test_keystore;
     use holochain_state::prelude::test_dht_db;
@@ -208,8 +208,8 @@ mod tests {
             let mut api = MockCellConductorApiT::new();
             api.expect_conductor_services()
                 .return_const(ConductorServices {
-                    dpki: Arc::new(mock_dpki()),
-                    app_store: Arc::new(mock_app_store()),
+                    dpki: Some(Arc::new(mock_dpki())),
+                    app_store: None,
                 });
             api.expect_keystore().return_const(keystore.clone());
             let mut ribosome = MockRibosomeT::new();
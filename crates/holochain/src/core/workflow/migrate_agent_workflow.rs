@@ -0,0 +1,110 @@
+//! Migrating an agent from one DNA to another.
+//!
+//! The migration is done in two halves: the chain on the old DNA is closed with a
+//! [`CloseChain`] action pointing to the new DNA, and the chain on the new DNA is
+//! opened with an [`OpenChain`] action pointing to the old one. The `migrate_agent`
+//! callbacks of both DNAs are run with [`migrate_agent_callback`] before either
+//! half is committed with [`migrate_agent_workflow`], so that either DNA can abort
+//! the migration.
+
+use super::error::WorkflowResult;
+use crate::conductor::ConductorHandle;
+use crate::core::ribosome::guest_callback::migrate_agent::MigrateAgentHostAccess;
+use crate::core::ribosome::guest_callback::migrate_agent::MigrateAgentInvocation;
+use crate::core::ribosome::guest_callback::migrate_agent::MigrateAgentResult;
+use crate::core::ribosome::RibosomeT;
+use derive_more::Constructor;
+use holochain_p2p::HolochainP2pDna;
+use holochain_state::host_fn_workspace::SourceChainWorkspace;
+use holochain_types::prelude::*;
+use holochain_zome_types::action::builder;
+
+#[derive(Constructor)]
+pub struct MigrateAgentWorkflowArgs<Ribosome>
+where
+    Ribosome: RibosomeT + 'static,
+{
+    pub ribosome: Ribosome,
+    pub conductor_handle: ConductorHandle,
+    /// Whether this cell's chain is being closed or opened
+    pub migrate_agent: MigrateAgent,
+    /// The DNA on the other side of the migration
+    pub other_dna_hash: DnaHash,
+}
+
+/// Run the `migrate_agent` callback of the DNA, without committing anything.
+pub fn migrate_agent_callback<Ribosome>(
+    workspace: SourceChainWorkspace,
+    ribosome: &Ribosome,
+    migrate_agent: MigrateAgent,
+) -> WorkflowResult<MigrateAgentResult>
+where
+    Ribosome: RibosomeT,
+{
+    let host_access = MigrateAgentHostAccess::new(workspace.into());
+    let invocation =
+        MigrateAgentInvocation::new(ribosome.dna_def().as_content().clone(), migrate_agent);
+    Ok(ribosome.run_migrate_agent(host_access, invocation)?)
+}
+
+/// Commit the `CloseChain` or `OpenChain` action of this half of the migration.
+/// The `migrate_agent` callbacks must have passed already.
+pub async fn migrate_agent_workflow<Ribosome>(
+    workspace: SourceChainWorkspace,
+    network: HolochainP2pDna,
+    args: MigrateAgentWorkflowArgs<Ribosome>,
+) -> WorkflowResult<()>
+where
+    Ribosome: RibosomeT + Clone + 'static,
+{
+    let MigrateAgentWorkflowArgs {
+        ribosome,
+        conductor_handle,
+        migrate_agent,
+        other_dna_hash,
+    } = args;
+
+    // FIXME: For the same reason as in the init workflow, putting without
+    // spawning here means this future is never polled again.
+    let ws = workspace.clone();
+    tokio::task::spawn(async move {
+        let chain = ws.source_chain();
+        match migrate_agent {
+            MigrateAgent::Close => {
+                chain
+                    .put(
+                        builder::CloseChain {
+                            new_dna_hash: other_dna_hash,
+                        },
+                        None,
+                        ChainTopOrdering::Strict,
+                    )
+                    .await
+            }
+            MigrateAgent::Open => {
+                chain
+                    .put(
+                        builder::OpenChain {
+                            prev_dna_hash: other_dna_hash,
+                        },
+                        None,
+                        ChainTopOrdering::Strict,
+                    )
+                    .await
+            }
+        }
+    })
+    .await??;
+
+    super::inline_validation(
+        workspace.clone(),
+        network.clone(),
+        conductor_handle,
+        ribosome,
+    )
+    .await?;
+
+    workspace.source_chain().flush(&network).await?;
+
+    Ok(())
+}
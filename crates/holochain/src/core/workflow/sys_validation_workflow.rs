@@ -22,6 +22,7 @@
 //! - For a [`DhtOp::RegisterAgentActivity`]
 //!    - Check that the [`Action`] is either a [`Action::Dna`] at sequence number 0, or has a previous action with sequence number strictly greater than 0.
 //!    - If the [`Action`] is a [`Action::Dna`], then verify the contained DNA hash matches the DNA hash that sys validation is being run for.
+//!    - If the DNA declares [`RateLimits`], then check that the weight of the [`Action`] doesn't overflow any rate limiting bucket, given the author's prior actions which are held locally.
 //!    - Run the [store record checks](#store-record-checks).
 //! - For a [`DhtOp::RegisterUpdatedContent`]
 //!    - The [`Update::original_action_address`] reference to the [`Action`] being updated must point to an [`Action`] that can be found locally. Once the [`Action`] address has been resolved, the [`Update::original_entry_address`] is checked against the entry address that the referenced [`Action`] specified.
@@ -74,11 +75,14 @@
 //! - Once all ops have an outcome, the workflow is complete and will wait to be triggered again by new incoming ops.
 //!
 
+use crate::conductor::conductor::DpkiService;
+use crate::conductor::error::ConductorResult;
 use crate::core::queue_consumer::TriggerSender;
 use crate::core::queue_consumer::WorkComplete;
 use crate::core::sys_validate::*;
 use crate::core::validation::*;
 use crate::core::workflow::error::WorkflowResult;
+use futures::future::BoxFuture;
 use futures::FutureExt;
 use futures::StreamExt;
 use holo_hash::DhtOpHash;
@@ -120,24 +124,41 @@ mod validate_op_tests;
     trigger_app_validation,
     trigger_self,
     network,
-    config
+    config,
+    dpki,
+    issue_warrants
 ))]
-pub async fn sys_validation_workflow<Network: HolochainP2pDnaT + Clone + 'static>(
+#[allow(clippy::too_many_arguments)]
+pub async fn sys_validation_workflow<Network: HolochainP2pDnaT + Clone + 'static, W>(
     workspace: Arc<SysValidationWorkspace>,
     current_validation_dependencies: Arc<Mutex<ValidationDependencies>>,
     trigger_app_validation: TriggerSender,
     trigger_self: TriggerSender,
     network: Network,
     config: Arc<ConductorConfig>,
-) -> WorkflowResult<WorkComplete> {
+    dpki: Option<Arc<dyn DpkiService>>,
+    issue_warrants: W,
+) -> WorkflowResult<WorkComplete>
+where
+    W: Fn(Vec<WarrantProof>) -> BoxFuture<'static, ConductorResult<Vec<SignedWarrant>>>,
+{
     // Run the actual sys validation using data we have locally
-    let outcome_summary = sys_validation_workflow_inner(
+    let mut outcome_summary = sys_validation_workflow_inner(
         workspace.clone(),
         current_validation_dependencies.clone(),
         config,
+        dpki,
     )
     .await?;
 
+    // Warrant the authors of invalid ops and forked chains now that the outcomes are committed.
+    let warrant_proofs = std::mem::take(&mut outcome_summary.warrant_proofs);
+    if !warrant_proofs.is_empty() {
+        if let Err(e) = issue_warrants(warrant_proofs).await {
+            tracing::warn!(error = ?e, "Failed to issue warrants");
+        }
+    }
+
     // trigger app validation to process any ops that have been processed so far
     if outcome_summary.accepted > 0 {
         tracing::debug!("Sys validation accepted {} ops", outcome_summary.accepted);
@@ -217,6 +238,7 @@ async fn sys_validation_workflow_inner(
     workspace: Arc<SysValidationWorkspace>,
     current_validation_dependencies: Arc<Mutex<ValidationDependencies>>,
     config: Arc<ConductorConfig>,
+    dpki: Option<Arc<dyn DpkiService>>,
 ) -> WorkflowResult<OutcomeSummary> {
     let db = workspace.dht_db.clone();
     let mut sorted_ops = validation_query::get_ops_to_sys_validate(&db).await?;
@@ -242,6 +264,7 @@ async fn sys_validation_workflow_inner(
 
     let cascade = Arc::new(workspace.local_cascade());
     let dna_def = DnaDefHashed::from_content_sync((*workspace.dna_def()).clone());
+    let rate_limits = workspace.dna_def().rate_limits.clone();
 
     retrieve_previous_actions_for_ops(
         current_validation_dependencies.clone(),
@@ -262,13 +285,44 @@ async fn sys_validation_workflow_inner(
         // This is an optimization to skip app validation and integration for ops that are
         // rejected and don't have dependencies.
         let dependency = op_type.sys_validation_dependency(&action);
+        let signed_action = (
+            action.author().clone(),
+            (ActionHash::with_data_sync(&action), op.signature().clone()),
+        );
+        let activity_seq =
+            matches!(op_type, DhtOpType::RegisterAgentActivity).then(|| action.action_seq());
 
         // Note that this is async only because of the signature checks done during countersigning.
         // In most cases this will be a fast synchronous call.
         let r = validate_op(&op, &dna_def, current_validation_dependencies.clone()).await;
 
+        // The agent activity authorities hold the author's chain, so they
+        // check that it doesn't exceed the DNA's rate limits.
+        let r = match (r, &op) {
+            (Ok(Outcome::Accepted), DhtOp::RegisterAgentActivity(_, action))
+                if !rate_limits.is_empty() =>
+            {
+                validate_rate_limits(&workspace, action, &rate_limits).await
+            }
+            (r, _) => r,
+        };
+
+        // The author's key must have been valid in DPKI when the action was authored.
+        let r = match (r, &dpki) {
+            (Ok(Outcome::Accepted), Some(dpki)) => {
+                validate_author_key(dpki.as_ref(), &action).await
+            }
+            (r, _) => r,
+        };
+
         match r {
-            Ok(outcome) => validation_outcomes.push((op_hash, outcome, dependency)),
+            Ok(outcome) => validation_outcomes.push((
+                op_hash,
+                outcome,
+                dependency,
+                signed_action,
+                activity_seq,
+            )),
             Err(e) => {
                 tracing::error!(error = ?e, "Error validating op");
             }
@@ -279,10 +333,24 @@ async fn sys_validation_workflow_inner(
         .dht_db
         .write_async(move |txn| {
             let mut summary = OutcomeSummary::default();
-            for (op_hash, outcome, dependency) in validation_outcomes {
+            for (op_hash, outcome, dependency, (author, action), activity_seq) in
+                validation_outcomes
+            {
                 match outcome {
                     Outcome::Accepted => {
                         summary.accepted += 1;
+                        // The agent activity authorities can see when an author
+                        // has two different actions at the same position in their chain.
+                        if let Some(seq) = activity_seq {
+                            if let Some(other) = find_chain_fork(txn, &author, seq, &action.0)? {
+                                summary.warrant_proofs.push(WarrantProof::ChainIntegrity(
+                                    ChainIntegrityWarrant::ChainFork {
+                                        chain_author: author,
+                                        action_pair: (action, other),
+                                    },
+                                ));
+                            }
+                        }
                         put_validation_limbo(txn, &op_hash, ValidationStage::SysValidated)?;
                         aitia::trace!(&hc_sleuth::Event::SysValidated {
                             by: sleuth_id.clone(),
@@ -296,6 +364,13 @@ async fn sys_validation_workflow_inner(
                     }
                     Outcome::Rejected(_) => {
                         summary.rejected += 1;
+                        summary.warrant_proofs.push(WarrantProof::ChainIntegrity(
+                            ChainIntegrityWarrant::InvalidChainOp {
+                                action_author: author,
+                                action,
+                                validation_type: ValidationType::Sys,
+                            },
+                        ));
                         if dependency.is_none() {
                             put_integrated(txn, &op_hash, ValidationStatus::Rejected)?;
                         } else {
@@ -525,6 +600,47 @@ pub(crate) async fn validate_op(
     }
 }
 
+/// Check that an action doesn't overflow any of the DNA's rate limiting
+/// buckets, given the author's prior actions held by this authority.
+async fn validate_rate_limits(
+    workspace: &SysValidationWorkspace,
+    action: &Action,
+    rate_limits: &RateLimits,
+) -> WorkflowResult<Outcome> {
+    let prior_actions = workspace.prior_actions_for_rate_limits(action).await?;
+    match check_rate_limits(action, &prior_actions, rate_limits) {
+        Ok(()) => Ok(Outcome::Accepted),
+        Err(SysValidationError::ValidationOutcome(e)) => {
+            warn!(
+                msg = "DhtOp was rejected for exceeding the rate limits.",
+                ?action,
+                error = ?e,
+                error_msg = %e
+            );
+            Ok(handle_failed(&e))
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+async fn validate_author_key(dpki: &dyn DpkiService, action: &Action) -> WorkflowResult<Outcome> {
+    if dpki
+        .is_key_valid(action.author().clone(), action.timestamp())
+        .await?
+    {
+        Ok(Outcome::Accepted)
+    } else {
+        let e = ValidationOutcome::InvalidAuthorKey(action.author().clone(), action.timestamp());
+        warn!(
+            msg = "DhtOp was rejected because its author's key was not valid.",
+            ?action,
+            error = ?e,
+            error_msg = %e
+        );
+        Ok(handle_failed(&e))
+    }
+}
+
 /// For now errors result in an outcome but in the future
 /// we might find it useful to include the reason something
 /// was rejected etc.
@@ -810,6 +926,7 @@ fn store_record(
         check_prev_timestamp(action, prev_action)?;
         check_prev_seq(action, prev_action)?;
         check_agent_validation_pkg_predecessor(action, prev_action)?;
+        check_prev_action_not_close_chain(action, prev_action)?;
     }
 
     Ok(())
@@ -1081,6 +1198,24 @@ impl SysValidationWorkspace {
         Ok(!action_seq_is_not_empty)
     }
 
+    /// The author's actions which come before this action on its chain and can
+    /// still affect the level of the DNA's rate limiting buckets, in chain order.
+    ///
+    /// Only the valid, integrated actions this action's chain is built on are
+    /// counted, so actions on other forks and rejected or not yet validated
+    /// actions fill no bucket.
+    pub async fn prior_actions_for_rate_limits(
+        &self,
+        action: &Action,
+    ) -> SourceChainResult<Vec<Action>> {
+        let prev_action = action.prev_action().cloned();
+        let since = rate_limit_window_start(action.timestamp(), &self.dna_def.rate_limits);
+        Ok(self
+            .dht_db
+            .read_async(move |txn| valid_chain_actions_since(&txn, prev_action.as_ref(), since))
+            .await?)
+    }
+
     /// Create a cascade with local data only
     pub fn local_cascade(&self) -> CascadeImpl {
         let cascade = CascadeImpl::empty()
@@ -1149,6 +1284,7 @@ struct OutcomeSummary {
     accepted: usize,
     missing: usize,
     rejected: usize,
+    warrant_proofs: Vec<WarrantProof>,
 }
 
 impl OutcomeSummary {
@@ -1157,6 +1293,7 @@ impl OutcomeSummary {
             accepted: 0,
             missing: 0,
             rejected: 0,
+            warrant_proofs: Vec::new(),
         }
     }
 }
@@ -11,6 +11,7 @@ use crate::prelude::AgentValidationPkgFixturator;
 use crate::prelude::CreateFixturator;
 use crate::prelude::SignatureFixturator;
 use fixt::*;
+use futures::FutureExt;
 use hdk::prelude::Dna as HdkDna;
 use holo_hash::AgentPubKey;
 use holo_hash::DhtOpHash;
@@ -383,6 +384,8 @@ impl TestCase {
             self.self_trigger.0.clone(),
             actual_network,
             config,
+            None,
+            |_| async move { Ok(Vec::new()) }.boxed(),
         )
         .await
         .unwrap()
@@ -25,6 +25,7 @@ use crate::test_utils::fake_genesis;
 use ::fixt::prelude::*;
 pub use holo_hash::fixt::*;
 use holo_hash::WasmHash;
+use holochain_conductor_api::conductor::ConductorTuningParams;
 use holochain_keystore::test_keystore;
 use holochain_keystore::MetaLairClient;
 use holochain_p2p::HolochainP2pDnaFixturator;
@@ -396,9 +397,14 @@ fixturator!(
     constructor fn new(CoordinatorZome, SignedActionHashedVec);
 );
 
+fixturator!(
+    ConductorTuningParams;
+    constructor fn new();
+);
+
 fixturator!(
     PostCommitHostAccess;
-    constructor fn new(HostFnWorkspace, MetaLairClient, HolochainP2pDna, SignalBroadcaster);
+    constructor fn new(HostFnWorkspace, MetaLairClient, HolochainP2pDna, SignalBroadcaster, ConductorTuningParams);
 );
 
 fixturator!(
@@ -501,6 +501,7 @@ async fn setup(
                 .map(TestZomes::from)
                 .map(|z| z.coordinator.into_inner())
                 .collect(),
+            rate_limits: Default::default(),
         },
         zomes.into_iter().map(Into::into),
     )
@@ -20,6 +20,7 @@ pub async fn app_bundle_from_dnas<'a>(
                 name: dr.role(),
                 dna: AppRoleDnaManifest {
                     location: Some(DnaLocation::Bundled(path.clone())),
+                    sha256: None,
                     modifiers,
                     installed_hash: Some(installed_dna_hash.into()),
                     clone_limit: 255,
@@ -14,6 +14,9 @@ use crate::conductor::{
 use ::fixt::prelude::StdRng;
 use hdk::prelude::*;
 use holo_hash::DnaHash;
+use holochain_conductor_api::AppAuthenticationRequest;
+use holochain_conductor_api::AppAuthenticationResponse;
+use holochain_conductor_api::IssueAppAuthenticationTokenPayload;
 use holochain_keystore::MetaLairClient;
 use holochain_state::prelude::test_db_dir;
 use holochain_state::test_utils::TestDir;
@@ -139,7 +142,11 @@ impl SweetConductor {
         // to actually access those databases.
         // As a TODO, we can remove the need for TestEnvs in sweettest or have
         // some other better integration between the two.
-        let spaces = Spaces::new(config.clone()).unwrap();
+        let db_master_key = handle.spaces.db_master_key.read().clone();
+        let mut spaces = Spaces::new(config.clone(), db_master_key).unwrap();
+        // Share the master key, so that the databases opened here are
+        // encrypted with the current key after it is rotated.
+        spaces.db_master_key = handle.spaces.db_master_key.clone();
 
         let keystore = handle.keystore().clone();
 
@@ -527,14 +534,46 @@ impl SweetConductor {
     }
 
     /// Create a new app interface and get a websocket client which can send requests
-    /// to it.
-    pub async fn app_ws_client(&self) -> (WebsocketSender, WebsocketReceiver) {
+    /// to it, authenticated for the given app.
+    pub async fn app_ws_client(
+        &self,
+        installed_app_id: InstalledAppId,
+    ) -> (WebsocketSender, WebsocketReceiver) {
         let port = self
             .raw_handle()
-            .add_app_interface(either::Either::Left(0), AllowedOrigins::Any)
+            .add_app_interface(either::Either::Left(0), AllowedOrigins::Any, None)
             .await
             .expect("Couldn't create app interface");
-        websocket_client_by_port(port).await.unwrap()
+        let (tx, rx) = websocket_client_by_port(port).await.unwrap();
+        self.authenticate_app_ws_client(&tx, installed_app_id).await;
+        (tx, rx)
+    }
+
+    /// Authenticate a websocket client connected to an app interface,
+    /// so that it can make requests for the given app.
+    pub async fn authenticate_app_ws_client(
+        &self,
+        app_tx: &WebsocketSender,
+        installed_app_id: InstalledAppId,
+    ) {
+        let issued = self
+            .raw_handle()
+            .issue_app_authentication_token(
+                IssueAppAuthenticationTokenPayload::for_installed_app_id(installed_app_id),
+            )
+            .await
+            .expect("Couldn't issue app authentication token");
+        let response: AppAuthenticationResponse = app_tx
+            .authenticate(AppAuthenticationRequest {
+                token: issued.token,
+            })
+            .await
+            .expect("Couldn't authenticate app websocket client");
+        assert_eq!(
+            response,
+            AppAuthenticationResponse::Authenticated,
+            "App websocket client was not authenticated"
+        );
     }
 
     /// Shutdown this conductor.
@@ -30,10 +30,13 @@ impl From<KitsuneP2pConfig> for SweetConductorConfig {
                 driver: InterfaceDriver::Websocket {
                     port: 0,
                     allowed_origins: AllowedOrigins::Any,
+                    bind_address: None,
+                    tls: None,
                 },
             }]),
             tuning_params: Some(ConductorTuningParams {
                 sys_validation_retry_delay: Some(std::time::Duration::from_secs(1)),
+                ..Default::default()
             }),
             ..Default::default()
         }
@@ -445,6 +445,8 @@ pub async fn setup_app_inner(
             driver: InterfaceDriver::Websocket {
                 port: 0,
                 allowed_origins: AllowedOrigins::Any,
+                bind_address: None,
+                tls: None,
             },
         }]),
         network: network.unwrap_or_default(),
@@ -214,9 +214,12 @@ async fn many_concurrent_zome_calls_dont_gunk_up_the_works() {
         .iter()
         .map(|c| c.zome(TestWasm::MultipleCalls))
         .collect();
-    let mut clients: Vec<_> =
-        future::join_all((0..NUM_AGENTS).map(|_| conductor.app_ws_client().map(|(tx, _)| tx)))
-            .await;
+    let mut clients: Vec<_> = future::join_all(apps.iter().map(|app| {
+        conductor
+            .app_ws_client(app.installed_app_id().clone())
+            .map(|(tx, _)| tx)
+    }))
+    .await;
 
     async fn all_call(
         conductor: &SweetConductor,
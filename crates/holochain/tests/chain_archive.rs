@@ -0,0 +1,134 @@
+#![cfg(feature = "test_utils")]
+
+use hdk::prelude::*;
+use holochain::conductor::api::error::ConductorApiError;
+use holochain::conductor::error::ConductorError;
+use holochain::sweettest::{DynSweetRendezvous, SweetConductor, SweetDnaFile, SweetInlineZomes};
+use holochain::test_utils::inline_zomes::{simple_crud_zome, AppString};
+use holochain_conductor_api::conductor::ConductorConfig;
+use holochain_keystore::KeystoreError;
+use holochain_sqlite::db::{DbKindAuthored, DbWrite};
+use holochain_sqlite::error::DatabaseResult;
+use holochain_types::prelude::{SignedChainArchiveExt, CHAIN_ARCHIVE_VERSION};
+
+async fn get_op_actions(db: DbWrite<DbKindAuthored>) -> Vec<ActionHash> {
+    db.read_async(move |txn| -> DatabaseResult<Vec<ActionHash>> {
+        let hashes = txn
+            .prepare("SELECT DISTINCT action_hash FROM DhtOp")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        Ok(hashes)
+    })
+    .await
+    .unwrap()
+}
+
+async fn get_chain(db: DbWrite<DbKindAuthored>) -> Vec<ActionHash> {
+    db.read_async(move |txn| -> DatabaseResult<Vec<ActionHash>> {
+        let chain = txn
+            .prepare("SELECT hash FROM Action ORDER BY seq")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        Ok(chain)
+    })
+    .await
+    .unwrap()
+}
+
+/// Test that a chain exported from one conductor can be restored onto
+/// a fresh conductor with the same keystore, without forking the chain.
+#[tokio::test(flavor = "multi_thread")]
+async fn export_and_import_chain() {
+    let (dna_file, _, _) = SweetDnaFile::unique_from_inline_zomes(simple_crud_zome()).await;
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let keystore = conductor.keystore();
+
+    let apps = conductor.setup_app("app", [&dna_file]).await.unwrap();
+    let (alice,) = apps.into_tuple();
+    let zome = alice.zome(SweetInlineZomes::COORDINATOR);
+    let _: ActionHash = conductor
+        .call(&zome, "create_string", AppString::new("backed up"))
+        .await;
+
+    let chain = get_chain(alice.authored_db().clone()).await;
+    let archive = conductor
+        .raw_handle()
+        .export_chain(alice.cell_id())
+        .await
+        .unwrap();
+    archive.validate().await.unwrap();
+    assert_eq!(CHAIN_ARCHIVE_VERSION, archive.archive.version);
+    assert_eq!(
+        chain,
+        archive
+            .archive
+            .records
+            .iter()
+            .map(|r| r.action_address().clone())
+            .collect::<Vec<_>>()
+    );
+
+    // Restore the chain onto a fresh conductor before the app is installed.
+    let mut conductor2 = SweetConductor::create_with_defaults(
+        ConductorConfig::default(),
+        Some(keystore),
+        None::<DynSweetRendezvous>,
+    )
+    .await;
+    conductor2.register_dna(dna_file.clone()).await.unwrap();
+
+    // A tampered archive is refused.
+    let mut tampered = archive.clone();
+    tampered.archive.records.pop();
+    let result = conductor2.raw_handle().import_chain(tampered, true).await;
+    assert!(matches!(
+        result,
+        Err(ConductorApiError::KeystoreError(
+            KeystoreError::InvalidSignature(_, _)
+        ))
+    ));
+
+    conductor2
+        .raw_handle()
+        .import_chain(archive.clone(), true)
+        .await
+        .unwrap();
+
+    // Installing the app finds the restored genesis, so no second genesis
+    // is written, and every op there is to publish is of the restored chain.
+    let apps = conductor2
+        .setup_app_for_agent("app", alice.agent_pubkey().clone(), [&dna_file])
+        .await
+        .unwrap();
+    let (alice2,) = apps.into_tuple();
+    assert_eq!(chain, get_chain(alice2.authored_db().clone()).await);
+    assert!(get_op_actions(alice2.authored_db().clone())
+        .await
+        .iter()
+        .all(|hash| chain.contains(hash)));
+
+    // The restored chain carries on from where it was exported.
+    let zome = alice2.zome(SweetInlineZomes::COORDINATOR);
+    let hash: ActionHash = conductor2
+        .call(&zome, "create_string", AppString::new("restored"))
+        .await;
+    let restored_chain = get_chain(alice2.authored_db().clone()).await;
+    assert_eq!(chain[..], restored_chain[..chain.len()]);
+    assert_eq!(Some(&hash), restored_chain.last());
+    assert_eq!(chain.len() + 1, restored_chain.len());
+
+    // A chain can't be restored over an installed cell.
+    let result = conductor2.raw_handle().import_chain(archive, true).await;
+    assert!(matches!(
+        result,
+        Err(ConductorApiError::ConductorError(
+            ConductorError::CellAlreadyExists(_)
+        ))
+    ));
+}
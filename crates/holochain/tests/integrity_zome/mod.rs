@@ -236,6 +236,7 @@ async fn test_update_admin_interface() {
             hash: None,
             dylib: None,
             location: ZomeLocation::Bundled(TestCoordinatorWasm::CoordinatorZomeUpdate.into()),
+            sha256: None,
             dependencies: Some(vec![ZomeDependency {
                 name: TestIntegrityWasm::IntegrityZome.into(),
             }]),
@@ -3,8 +3,8 @@ use std::sync::Arc;
 use holochain::sweettest::{
     SweetConductor, SweetConductorConfig, SweetDnaFile, SweetLocalRendezvous,
 };
-use holochain_conductor_api::AppResponse;
-use holochain_types::signal::Signal;
+use holochain_conductor_api::{AppRequest, AppResponse};
+use holochain_types::signal::{SequencedSignal, Signal};
 use holochain_types::websocket::AllowedOrigins;
 use holochain_wasm_test_utils::TestWasm;
 use holochain_websocket::{ConnectRequest, WebsocketConfig};
@@ -29,17 +29,20 @@ async fn send_signal_after_conductor_restart() {
     // add app interface
     let app_interface_port_1 = (*conductor)
         .clone()
-        .add_app_interface(either::Either::Left(0), AllowedOrigins::Any)
+        .add_app_interface(either::Either::Left(0), AllowedOrigins::Any, None)
         .await
         .unwrap();
 
     // connect app websocket
-    let (_, mut app_ws_rx_1) = holochain_websocket::connect(
+    let (app_ws_tx_1, mut app_ws_rx_1) = holochain_websocket::connect(
         Arc::new(WebsocketConfig::CLIENT_DEFAULT),
         ConnectRequest::new(([127, 0, 0, 1], app_interface_port_1).into()),
     )
     .await
     .unwrap();
+    conductor
+        .authenticate_app_ws_client(&app_ws_tx_1, "app_id".to_string())
+        .await;
 
     // emit a signal
     let _: () = conductor
@@ -60,7 +63,7 @@ async fn send_signal_after_conductor_restart() {
             cell_id,
             zome_name,
             signal,
-        }) = Signal::try_from_vec(v)
+        }) = SequencedSignal::try_from_vec(v).map(|signal| signal.signal)
         {
             assert_eq!(cell_id, alice_cell_id);
             assert_eq!(zome_name, TestWasm::EmitSignal.coordinator_zome_name());
@@ -95,17 +98,20 @@ async fn send_signal_after_conductor_restart() {
     let app_interface_port_1 = app_interfaces[0];
 
     // reconnect app websocket
-    let (_, mut app_ws_rx_1) = holochain_websocket::connect(
+    let (app_ws_tx_1, mut app_ws_rx_1) = holochain_websocket::connect(
         Arc::new(WebsocketConfig::CLIENT_DEFAULT),
         ConnectRequest::new(([127, 0, 0, 1], app_interface_port_1).into()),
     )
     .await
     .unwrap();
+    conductor
+        .authenticate_app_ws_client(&app_ws_tx_1, "app_id".to_string())
+        .await;
 
     // add a second app interface without websocket connection
     let _ = (*conductor)
         .clone()
-        .add_app_interface(either::Either::Left(0), AllowedOrigins::Any)
+        .add_app_interface(either::Either::Left(0), AllowedOrigins::Any, None)
         .await
         .unwrap();
 
@@ -129,7 +135,7 @@ async fn send_signal_after_conductor_restart() {
             cell_id,
             zome_name,
             signal,
-        }) = Signal::try_from_vec(v)
+        }) = SequencedSignal::try_from_vec(v).map(|signal| signal.signal)
         {
             assert_eq!(cell_id, alice_cell_id);
             assert_eq!(zome_name, TestWasm::EmitSignal.coordinator_zome_name());
@@ -141,3 +147,80 @@ async fn send_signal_after_conductor_restart() {
         panic!("not the expected app signal");
     }
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn resume_signals_after_reconnecting() {
+    let conductor = SweetConductor::from_config(SweetConductorConfig::standard()).await;
+    let (dna_file, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::EmitSignal]).await;
+    let app = conductor.setup_app("app_id", &[dna_file]).await.unwrap();
+    let alice = app.agent();
+    let alice_cell_id = app.cells()[0].cell_id().to_owned();
+
+    let app_interface_port = (*conductor)
+        .clone()
+        .add_app_interface(either::Either::Left(0), AllowedOrigins::Any, None)
+        .await
+        .unwrap();
+
+    let connect = || async {
+        let (app_ws_tx, mut app_ws_rx) = holochain_websocket::connect(
+            Arc::new(WebsocketConfig::CLIENT_DEFAULT),
+            ConnectRequest::new(([127, 0, 0, 1], app_interface_port).into()),
+        )
+        .await
+        .unwrap();
+        conductor
+            .authenticate_app_ws_client(&app_ws_tx, "app_id".to_string())
+            .await;
+        let (signal_tx, signal_rx) = tokio::sync::mpsc::unbounded_channel();
+        let task = tokio::task::spawn(async move {
+            while let Ok(holochain_websocket::ReceiveMessage::Signal(v)) =
+                app_ws_rx.recv::<AppResponse>().await
+            {
+                signal_tx
+                    .send(SequencedSignal::try_from_vec(v).unwrap())
+                    .unwrap();
+            }
+        });
+        (app_ws_tx, signal_rx, task)
+    };
+    let emit = || async {
+        let _: () = conductor
+            .easy_call_zome(
+                alice,
+                None,
+                alice_cell_id.clone(),
+                TestWasm::EmitSignal.coordinator_zome_name(),
+                "emit",
+                (),
+            )
+            .await
+            .unwrap();
+    };
+
+    let (_app_ws_tx, mut signal_rx, task) = connect().await;
+    emit().await;
+    emit().await;
+    assert_eq!(1, signal_rx.recv().await.unwrap().seq);
+    assert_eq!(2, signal_rx.recv().await.unwrap().seq);
+    task.abort();
+
+    // this signal is emitted while the client is disconnected
+    emit().await;
+
+    let (app_ws_tx, mut signal_rx, _task) = connect().await;
+    let response: AppResponse = app_ws_tx
+        .request(AppRequest::ResumeSignals { from_seq: 3 })
+        .await
+        .unwrap();
+    assert!(matches!(
+        response,
+        AppResponse::SignalsResumed {
+            oldest_seq: 1,
+            next_seq: 4
+        }
+    ));
+    let signal = signal_rx.recv().await.unwrap();
+    assert_eq!(3, signal.seq);
+    assert!(matches!(signal.signal, Signal::App { cell_id, .. } if cell_id == alice_cell_id));
+}
@@ -29,7 +29,10 @@ use futures::Future;
 use holochain_conductor_api::conductor::ConductorConfig;
 use holochain_conductor_api::conductor::KeystoreConfig;
 use holochain_conductor_api::AdminInterfaceConfig;
+use holochain_conductor_api::AppAuthenticationRequest;
+use holochain_conductor_api::AppAuthenticationResponse;
 use holochain_conductor_api::InterfaceDriver;
+use holochain_conductor_api::IssueAppAuthenticationTokenPayload;
 use matches::assert_matches;
 use serde::Serialize;
 use std::time::Duration;
@@ -164,6 +167,9 @@ pub async fn attach_app_interface(client: &mut WebsocketSender, port: Option<u16
     let request = AdminRequest::AttachAppInterface {
         port,
         allowed_origins: AllowedOrigins::Any,
+        installed_app_id: None,
+        bind_address: None,
+        tls: None,
     };
     let response = client.request(request);
     let response = check_timeout(response, 3000).await;
@@ -173,6 +179,27 @@ pub async fn attach_app_interface(client: &mut WebsocketSender, port: Option<u16
     }
 }
 
+pub async fn authenticate_app_ws_client(
+    app_tx: &WebsocketSender,
+    admin_tx: &WebsocketSender,
+    installed_app_id: InstalledAppId,
+) {
+    let request = AdminRequest::IssueAppAuthenticationToken(
+        IssueAppAuthenticationTokenPayload::for_installed_app_id(installed_app_id),
+    );
+    let response = admin_tx.request(request);
+    let response = check_timeout(response, 3000).await;
+    let token = match response {
+        AdminResponse::AppAuthenticationTokenIssued(issued) => issued.token,
+        _ => panic!("Issue app authentication token failed: {:?}", response),
+    };
+    let response: AppAuthenticationResponse = app_tx
+        .authenticate(AppAuthenticationRequest { token })
+        .await
+        .unwrap();
+    assert_eq!(response, AppAuthenticationResponse::Authenticated);
+}
+
 pub async fn retry_admin_interface(
     port: u16,
     mut attempts: usize,
@@ -252,6 +279,7 @@ pub async fn register_and_install_dna_named(
         name: role_name,
         dna: AppRoleDnaManifest {
             location: Some(DnaLocation::Bundled(dna_path.clone())),
+            sha256: None,
             modifiers: mods,
             installed_hash: Some(dna_hash.clone().into()),
             clone_limit: 0,
@@ -342,6 +370,8 @@ pub fn create_config(port: u16, data_root_path: DataRootPath) -> ConductorConfig
             driver: InterfaceDriver::Websocket {
                 port,
                 allowed_origins: AllowedOrigins::Any,
+                bind_address: None,
+                tls: None,
             },
         }]),
         data_root_path: Some(data_root_path),
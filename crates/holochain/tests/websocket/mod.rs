@@ -16,7 +16,12 @@ use holochain::{
     fixt::*,
 };
 
-use holochain_conductor_api::{AdminInterfaceConfig, AppRequest, InterfaceDriver};
+use holochain_conductor_api::{
+    AdminInterfaceConfig, AppAuthenticationRequest, AppAuthenticationResponse, AppRequest,
+    CountersigningSessionState, ExternalApiWireError, InterfaceDriver, InterfaceTlsConfig,
+    ZomeCall,
+};
+use holochain_types::websocket::AllowedOrigins;
 use holochain_types::{
     prelude::*,
     test_utils::{fake_dna_zomes, write_fake_dna_file},
@@ -206,6 +211,7 @@ async fn call_zome() {
 
     let (mut app_tx, app_rx) = websocket_client_by_port(app_port).await.unwrap();
     let _app_rx = PollRecv::new::<AppResponse>(app_rx);
+    authenticate_app_ws_client(&app_tx, &admin_tx, "test".to_string()).await;
 
     // Call Zome
     tracing::info!("Calling zome");
@@ -255,6 +261,7 @@ async fn call_zome() {
 
     let (app_tx, app_rx) = websocket_client_by_port(app_port).await.unwrap();
     let _app_rx = PollRecv::new::<AppResponse>(app_rx);
+    authenticate_app_ws_client(&app_tx, &admin_tx, "test".to_string()).await;
 
     // Call Zome again on the existing app interface port
     tracing::info!("Calling zome again");
@@ -419,6 +426,7 @@ async fn emit_signals() {
     // Emit signals (the real test!)
 
     let (app_tx_1, mut app_rx_1) = websocket_client_by_port(app_port).await.unwrap();
+    authenticate_app_ws_client(&app_tx_1, &admin_tx, "test".to_string()).await;
     let (sig1_send, sig1_recv) = tokio::sync::oneshot::channel();
     let mut sig1_send = Some(sig1_send);
     let sig1_task = tokio::task::spawn(async move {
@@ -434,7 +442,8 @@ async fn emit_signals() {
         }
     });
 
-    let (_, mut app_rx_2) = websocket_client_by_port(app_port).await.unwrap();
+    let (app_tx_2, mut app_rx_2) = websocket_client_by_port(app_port).await.unwrap();
+    authenticate_app_ws_client(&app_tx_2, &admin_tx, "test".to_string()).await;
     let (sig2_send, sig2_recv) = tokio::sync::oneshot::channel();
     let mut sig2_send = Some(sig2_send);
     let sig2_task = tokio::task::spawn(async move {
@@ -461,16 +470,19 @@ async fn emit_signals() {
     )
     .await;
 
-    let sig1 = Signal::try_from_vec(sig1_recv.await.unwrap()).unwrap();
-    let sig2 = Signal::try_from_vec(sig2_recv.await.unwrap()).unwrap();
+    let sig1 = SequencedSignal::try_from_vec(sig1_recv.await.unwrap()).unwrap();
+    let sig2 = SequencedSignal::try_from_vec(sig2_recv.await.unwrap()).unwrap();
     sig1_task.abort();
     sig2_task.abort();
 
     assert_eq!(
-        Signal::App {
-            cell_id,
-            zome_name,
-            signal: AppSignal::new(ExternIO::encode(()).unwrap()),
+        SequencedSignal {
+            seq: 1,
+            signal: Signal::App {
+                cell_id,
+                zome_name,
+                signal: AppSignal::new(ExternIO::encode(()).unwrap()),
+            },
         },
         sig1,
     );
@@ -840,6 +852,8 @@ async fn admin_allowed_origins() {
             driver: InterfaceDriver::Websocket {
                 port: 0,
                 allowed_origins: "http://localhost:3000".to_string().into(),
+                bind_address: None,
+                tls: None,
             },
         }])
         .await
@@ -868,16 +882,76 @@ async fn admin_allowed_origins() {
 }
 
 #[tokio::test(flavor = "multi_thread")]
-async fn app_allowed_origins() {
+async fn admin_interface_tls() {
     holochain_trace::test_run().ok();
 
     let conductor = SweetConductor::from_standard_config().await;
 
+    let cert = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+    let cert_pem = cert.serialize_pem().unwrap();
+    let dir = TempDir::new().unwrap();
+    let cert_path = dir.path().join("cert.pem");
+    let key_path = dir.path().join("key.pem");
+    std::fs::write(&cert_path, &cert_pem).unwrap();
+    std::fs::write(&key_path, cert.serialize_private_key_pem()).unwrap();
+
+    let ports = conductor
+        .clone()
+        .add_admin_interfaces(vec![AdminInterfaceConfig {
+            driver: InterfaceDriver::Websocket {
+                port: 0,
+                allowed_origins: AllowedOrigins::Any,
+                bind_address: Some([127, 0, 0, 1].into()),
+                tls: Some(InterfaceTlsConfig {
+                    cert_path,
+                    key_path,
+                }),
+            },
+        }])
+        .await
+        .unwrap();
+    let port = *ports.first().unwrap();
+
+    // A client which doesn't use TLS can't connect
+    assert!(websocket_client_by_port(port).await.is_err());
+
+    let (client, rx) = connect(
+        Arc::new(WebsocketConfig::CLIENT_DEFAULT),
+        ConnectRequest::new(([127, 0, 0, 1], port).into())
+            .try_set_root_certificate(cert_pem.as_bytes())
+            .unwrap()
+            .try_set_server_name("localhost")
+            .unwrap(),
+    )
+    .await
+    .unwrap();
+    let _rx = PollRecv::new::<AdminResponse>(rx);
+
+    let response: AdminResponse = client
+        .request(AdminRequest::ListApps {
+            status_filter: None,
+        })
+        .await
+        .unwrap();
+    assert_matches!(response, AdminResponse::AppsListed(_));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn app_allowed_origins() {
+    holochain_trace::test_run().ok();
+
+    let mut conductor = SweetConductor::from_standard_config().await;
+    conductor
+        .setup_app("app", &[SweetDnaFile::unique_empty().await])
+        .await
+        .unwrap();
+
     let port = conductor
         .clone()
         .add_app_interface(
             either::Either::Left(0),
             "http://localhost:3000".to_string().into(),
+            None,
         )
         .await
         .unwrap();
@@ -889,20 +963,25 @@ async fn app_allowed_origins() {
     .await
     .is_err());
 
-    check_app_port(port, "http://localhost:3000").await;
+    check_app_port(&conductor, port, "http://localhost:3000").await;
 }
 
 #[tokio::test(flavor = "multi_thread")]
 async fn app_allowed_origins_independence() {
     holochain_trace::test_run().ok();
 
-    let conductor = SweetConductor::from_standard_config().await;
+    let mut conductor = SweetConductor::from_standard_config().await;
+    conductor
+        .setup_app("app", &[SweetDnaFile::unique_empty().await])
+        .await
+        .unwrap();
 
     let port_1 = conductor
         .clone()
         .add_app_interface(
             either::Either::Left(0),
             "http://localhost:3001".to_string().into(),
+            None,
         )
         .await
         .unwrap();
@@ -912,6 +991,7 @@ async fn app_allowed_origins_independence() {
         .add_app_interface(
             either::Either::Left(0),
             "http://localhost:3002".to_string().into(),
+            None,
         )
         .await
         .unwrap();
@@ -938,11 +1018,479 @@ async fn app_allowed_origins_independence() {
 
     // Check that correct access is allowed
 
-    check_app_port(port_1, "http://localhost:3001").await;
-    check_app_port(port_2, "http://localhost:3002").await;
+    check_app_port(&conductor, port_1, "http://localhost:3001").await;
+    check_app_port(&conductor, port_2, "http://localhost:3002").await;
+}
+
+#[cfg(unix)]
+#[tokio::test(flavor = "multi_thread")]
+async fn unix_socket_interfaces() {
+    use holochain_conductor_api::IssueAppAuthenticationTokenPayload;
+    use std::os::unix::fs::PermissionsExt;
+
+    holochain_trace::test_run().ok();
+
+    let mut conductor = SweetConductor::from_standard_config().await;
+    conductor
+        .setup_app("app", &[SweetDnaFile::unique_empty().await])
+        .await
+        .unwrap();
+
+    let dir = TempDir::new().unwrap();
+    let admin_path = dir.path().join("admin.sock");
+    let app_path = dir.path().join("app.sock");
+
+    // Unix socket interfaces have no port
+    let ports = conductor
+        .clone()
+        .add_admin_interfaces(vec![AdminInterfaceConfig {
+            driver: InterfaceDriver::UnixSocket {
+                path: admin_path.clone(),
+                mode: Some(0o600),
+            },
+        }])
+        .await
+        .unwrap();
+    assert!(ports.is_empty());
+    assert_eq!(
+        0o600,
+        std::fs::metadata(&admin_path).unwrap().permissions().mode() & 0o777
+    );
+
+    let (admin_tx, rx) =
+        connect_unix_socket(Arc::new(WebsocketConfig::CLIENT_DEFAULT), &admin_path)
+            .await
+            .unwrap();
+    let _admin_rx = PollRecv::new::<AdminResponse>(rx);
+
+    let response: AdminResponse = admin_tx
+        .request(AdminRequest::AttachAppUnixSocketInterface {
+            path: app_path.clone(),
+            mode: None,
+            installed_app_id: None,
+        })
+        .await
+        .unwrap();
+    assert_matches!(response, AdminResponse::AppUnixSocketInterfaceAttached);
+
+    // Only websocket app interfaces are listed
+    let response: AdminResponse = admin_tx
+        .request(AdminRequest::ListAppInterfaces)
+        .await
+        .unwrap();
+    assert_matches!(response, AdminResponse::AppInterfacesListed(ports) if ports.is_empty());
+
+    let (app_tx, rx) = connect_unix_socket(Arc::new(WebsocketConfig::CLIENT_DEFAULT), &app_path)
+        .await
+        .unwrap();
+    let _app_rx = PollRecv::new::<AppResponse>(rx);
+
+    let token = conductor
+        .issue_app_authentication_token(IssueAppAuthenticationTokenPayload::for_installed_app_id(
+            "app".to_string(),
+        ))
+        .await
+        .unwrap()
+        .token;
+    let response: AppAuthenticationResponse = app_tx
+        .authenticate(AppAuthenticationRequest { token })
+        .await
+        .unwrap();
+    assert_eq!(response, AppAuthenticationResponse::Authenticated);
+
+    let response: AppResponse = app_tx
+        .request(AppRequest::AppInfo {
+            installed_app_id: "app".to_string(),
+        })
+        .await
+        .unwrap();
+    assert_matches!(response, AppResponse::AppInfo(Some(_)));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn admin_block_requests() {
+    holochain_trace::test_run().ok();
+
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let app = conductor
+        .setup_app("app", &[SweetDnaFile::unique_empty().await])
+        .await
+        .unwrap();
+    let cell_id = app.cells()[0].cell_id().clone();
+
+    let (client, rx) = conductor.admin_ws_client().await;
+    let _rx = PollRecv::new::<AdminResponse>(rx);
+
+    let interval = InclusiveTimestampInterval::try_new(Timestamp::now(), Timestamp::MAX).unwrap();
+    let cell_block = BlockSpan {
+        target: BlockTarget::Cell(cell_id, CellBlockReason::BadCrypto),
+        interval: interval.clone(),
+    };
+    let response: AdminResponse = client
+        .request(AdminRequest::Block(cell_block.clone()))
+        .await
+        .unwrap();
+    assert_matches!(response, AdminResponse::Blocked);
+
+    // The proxied transport of the standard config can't tell which address a
+    // peer connects from, so an IP block is refused rather than silently never
+    // enforced
+    let ip_block = BlockSpan {
+        target: BlockTarget::Ip(
+            "192.0.2.1".parse().unwrap(),
+            IpBlockReason::Kitsune(kitsune_p2p_block::IpBlockReason::DOS),
+        ),
+        interval,
+    };
+    let response: AdminResponse = client.request(AdminRequest::Block(ip_block)).await.unwrap();
+    assert_matches!(
+        response,
+        AdminResponse::Error(ExternalApiWireError::InternalError(e)) if e.contains("192.0.2.1")
+    );
+
+    let response: AdminResponse = client.request(AdminRequest::ListBlocks).await.unwrap();
+    assert_matches!(response, AdminResponse::BlocksListed(blocks) if blocks == vec![cell_block.clone()]);
+
+    let response: AdminResponse = client
+        .request(AdminRequest::Unblock(cell_block))
+        .await
+        .unwrap();
+    assert_matches!(response, AdminResponse::Unblocked);
+    let response: AdminResponse = client.request(AdminRequest::ListBlocks).await.unwrap();
+    assert_matches!(response, AdminResponse::BlocksListed(blocks) if blocks.is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn admin_block_ip_rejects_connections_from_it() {
+    use holochain_p2p::DnaHashExt;
+    use kitsune_p2p::test_util::mock_network::{mock_network, to_kitsune_channel, KitsuneMock};
+    use kitsune_p2p_types::config::{KitsuneP2pConfig, TransportConfig};
+    use kitsune_p2p_types::tx2::tx2_adapter::AdapterFactory;
+    use kitsune_p2p_types::tx2::tx2_utils::TxUrl;
+    use kitsune_p2p_types::tx2::MsgId;
+
+    holochain_trace::test_run().ok();
+
+    // The mock network addresses its connections by the urls it is given,
+    // so it can tell which address a peer connects from.
+    let (from_kitsune_tx, mut from_kitsune_rx) = tokio::sync::mpsc::channel(1000);
+    let (to_kitsune_tx, to_kitsune_rx) = to_kitsune_channel(1000);
+    tokio::spawn(async move { while from_kitsune_rx.recv().await.is_some() {} });
+    let mock_network: AdapterFactory = Arc::new(mock_network(from_kitsune_tx, to_kitsune_rx));
+    let mut network = KitsuneP2pConfig::default();
+    network.transport_pool = vec![TransportConfig::Mock {
+        mock_network: mock_network.into(),
+    }];
+    let mut conductor = SweetConductor::from_config(SweetConductorConfig::from(network)).await;
+    let dna_file = SweetDnaFile::unique_empty().await;
+    conductor.setup_app("app", [&dna_file]).await.unwrap();
+    let space = dna_file.dna_hash().to_kitsune();
+
+    let mut next_id = 0;
+    let mut peer_query = |cert: u8, url: &str| {
+        next_id += 1;
+        let (respond, response) = tokio::sync::oneshot::channel();
+        let request = KitsuneMock::request(
+            MsgId::new(next_id).as_req(),
+            vec![cert; 32].into(),
+            TxUrl::from_str_panicking(url),
+            kitsune_p2p::wire::Wire::peer_query(
+                space.clone(),
+                kitsune_p2p::dht_arc::DhtLocation::new(0),
+            ),
+            respond,
+        );
+        let to_kitsune_tx = to_kitsune_tx.clone();
+        async move {
+            to_kitsune_tx.send(request).await.unwrap();
+            tokio::time::timeout(Duration::from_secs(10), response)
+                .await
+                .ok()
+                .and_then(|r| r.ok())
+        }
+    };
+
+    let (client, rx) = conductor.admin_ws_client().await;
+    let _rx = PollRecv::new::<AdminResponse>(rx);
+    let ip_block = BlockSpan {
+        target: BlockTarget::Ip(
+            "192.0.2.1".parse().unwrap(),
+            IpBlockReason::Kitsune(kitsune_p2p_block::IpBlockReason::DOS),
+        ),
+        interval: InclusiveTimestampInterval::try_new(Timestamp::now(), Timestamp::MAX).unwrap(),
+    };
+    let response: AdminResponse = client
+        .request(AdminRequest::Block(ip_block.clone()))
+        .await
+        .unwrap();
+    assert_matches!(response, AdminResponse::Blocked);
+
+    // A connection from the blocked address is closed without an answer
+    assert!(peer_query(1, "kitsune-quic://192.0.2.1:5778")
+        .await
+        .is_none());
+
+    // while one from any other address is answered
+    assert!(peer_query(2, "kitsune-quic://192.0.2.2:5778")
+        .await
+        .is_some());
+
+    // and so is one from the address once it is unblocked
+    let response: AdminResponse = client
+        .request(AdminRequest::Unblock(ip_block))
+        .await
+        .unwrap();
+    assert_matches!(response, AdminResponse::Unblocked);
+    assert!(peer_query(3, "kitsune-quic://192.0.2.1:5779")
+        .await
+        .is_some());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[cfg(feature = "slow_tests")]
+async fn countersigning_session_requests_across_restart() {
+    holochain_trace::test_run().ok();
+
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let (dna_file, _, _) =
+        SweetDnaFile::unique_from_test_wasms(vec![TestWasm::CounterSigning]).await;
+    let (alice_pubkey, bob_pubkey) = SweetAgents::two(conductor.keystore()).await;
+    let (alice,) = conductor
+        .setup_app_for_agent("alice", alice_pubkey.clone(), [&dna_file])
+        .await
+        .unwrap()
+        .into_tuple();
+    let (bob,) = conductor
+        .setup_app_for_agent("bob", bob_pubkey.clone(), [&dna_file])
+        .await
+        .unwrap()
+        .into_tuple();
+    let alice_zome = alice.zome(TestWasm::CounterSigning);
+    let bob_zome = bob.zome(TestWasm::CounterSigning);
+    let cell_id = Box::new(alice.cell_id().clone());
+
+    let _: ActionHash = conductor.call(&alice_zome, "create_a_thing", ()).await;
+    let _: ActionHash = conductor.call(&bob_zome, "create_a_thing", ()).await;
+
+    // Alice commits the session entry but bob never does.
+    let preflight_request: PreflightRequest = conductor
+        .call(
+            &alice_zome,
+            "generate_countersigning_preflight_request",
+            vec![(alice_pubkey, vec![Role(0)]), (bob_pubkey, vec![])],
+        )
+        .await;
+    let mut responses = Vec::new();
+    for zome in [&alice_zome, &bob_zome] {
+        let acceptance: PreflightRequestAcceptance = conductor
+            .call(
+                zome,
+                "accept_countersigning_preflight_request",
+                preflight_request.clone(),
+            )
+            .await;
+        match acceptance {
+            PreflightRequestAcceptance::Accepted(response) => responses.push(response),
+            something_else => panic!("{:?}", something_else),
+        }
+    }
+    let (session_action_hash, entry_hash): (ActionHash, EntryHash) = conductor
+        .call(
+            &alice_zome,
+            "create_a_countersigned_thing_with_entry_hash",
+            responses,
+        )
+        .await;
+
+    // The session survives a restart of the conductor.
+    conductor.shutdown().await;
+    conductor.startup().await;
+
+    let (client, rx) = conductor.app_ws_client("alice".to_string()).await;
+    let _rx = PollRecv::new::<AppResponse>(rx);
+    let response: AppResponse = client
+        .request(AppRequest::GetCountersigningSessionState(cell_id.clone()))
+        .await
+        .unwrap();
+    match response {
+        AppResponse::CountersigningSessionState(state) => match *state {
+            Some(CountersigningSessionState::SignaturesCollecting {
+                entry_hash: session_entry_hash,
+                session_data,
+                ..
+            }) => {
+                assert_eq!(entry_hash, session_entry_hash);
+                assert_eq!(&preflight_request, session_data.preflight_request());
+            }
+            something_else => panic!("{:?}", something_else),
+        },
+        something_else => panic!("{:?}", something_else),
+    }
+
+    let response: AppResponse = client
+        .request(AppRequest::PublishCountersigningSession(cell_id.clone()))
+        .await
+        .unwrap();
+    assert_matches!(response, AppResponse::PublishCountersigningSessionTriggered);
+
+    let response: AppResponse = client
+        .request(AppRequest::AbandonCountersigningSession(cell_id.clone()))
+        .await
+        .unwrap();
+    assert_matches!(response, AppResponse::CountersigningSessionAbandoned);
+    let response: AppResponse = client
+        .request(AppRequest::GetCountersigningSessionState(cell_id.clone()))
+        .await
+        .unwrap();
+    assert_matches!(response, AppResponse::CountersigningSessionState(state) if state.is_none());
+
+    // The session entry may already have been sent to its authorities, so it
+    // stays at the head of the chain and the next commit follows it.
+    let next_action_hash: ActionHash = conductor.call(&alice_zome, "create_a_thing", ()).await;
+    let next_action: SignedActionHashed = conductor
+        .call(&alice_zome, "must_get_action", next_action_hash)
+        .await;
+    assert_eq!(
+        Some(&session_action_hash),
+        next_action.action().prev_action()
+    );
+
+    // There is no longer a session to abandon.
+    let response: AppResponse = client
+        .request(AppRequest::AbandonCountersigningSession(cell_id))
+        .await
+        .unwrap();
+    assert_matches!(response, AppResponse::Error(_));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn app_interface_requires_authentication() {
+    holochain_trace::test_run().ok();
+
+    let mut conductor = SweetConductor::from_standard_config().await;
+    conductor
+        .setup_app("app", &[SweetDnaFile::unique_empty().await])
+        .await
+        .unwrap();
+    let port = conductor
+        .clone()
+        .add_app_interface(either::Either::Left(0), AllowedOrigins::Any, None)
+        .await
+        .unwrap();
+
+    // A request without authenticating first closes the connection
+    let (client, rx) = websocket_client_by_port(port).await.unwrap();
+    let _rx = PollRecv::new::<AppResponse>(rx);
+    let response: Result<AppResponse, _> = client
+        .request(AppRequest::AppInfo {
+            installed_app_id: "app".to_string(),
+        })
+        .await;
+    assert!(response.is_err());
+
+    // So does an invalid token, after the client is told authentication failed
+    let (client, rx) = websocket_client_by_port(port).await.unwrap();
+    let _rx = PollRecv::new::<AppResponse>(rx);
+    let response: AppAuthenticationResponse = client
+        .authenticate(AppAuthenticationRequest { token: vec![0; 16] })
+        .await
+        .unwrap();
+    assert_matches!(response, AppAuthenticationResponse::AuthenticationFailed(_));
+    let response: Result<AppResponse, _> = client
+        .request(AppRequest::AppInfo {
+            installed_app_id: "app".to_string(),
+        })
+        .await;
+    assert!(response.is_err());
+
+    // A valid token grants access to the app it was issued for
+    let (client, rx) = websocket_client_by_port(port).await.unwrap();
+    let _rx = PollRecv::new::<AppResponse>(rx);
+    conductor
+        .authenticate_app_ws_client(&client, "app".to_string())
+        .await;
+    let response: AppResponse = client
+        .request(AppRequest::AppInfo {
+            installed_app_id: "app".to_string(),
+        })
+        .await
+        .unwrap();
+    assert_matches!(response, AppResponse::AppInfo(Some(_)));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn app_interface_authentication_is_scoped_to_app() {
+    holochain_trace::test_run().ok();
+
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let dna = SweetDnaFile::unique_empty().await;
+    let app_1 = conductor.setup_app("app_1", [&dna]).await.unwrap();
+    let app_2 = conductor.setup_app("app_2", [&dna]).await.unwrap();
+
+    // An interface bound to app 1 rejects tokens for app 2
+    let port = conductor
+        .clone()
+        .add_app_interface(
+            either::Either::Left(0),
+            AllowedOrigins::Any,
+            Some("app_1".to_string()),
+        )
+        .await
+        .unwrap();
+    let (client, rx) = websocket_client_by_port(port).await.unwrap();
+    let _rx = PollRecv::new::<AppResponse>(rx);
+    conductor
+        .authenticate_app_ws_client(&client, "app_2".to_string())
+        .await;
+    let response: Result<AppResponse, _> = client.request(AppRequest::ListWasmHostFunctions).await;
+    assert!(response.is_err());
+
+    // A client authenticated for app 1 can't make requests for app 2
+    let (client, rx) = websocket_client_by_port(port).await.unwrap();
+    let _rx = PollRecv::new::<AppResponse>(rx);
+    conductor
+        .authenticate_app_ws_client(&client, "app_1".to_string())
+        .await;
+    let response: AppResponse = client
+        .request(AppRequest::AppInfo {
+            installed_app_id: "app_2".to_string(),
+        })
+        .await
+        .unwrap();
+    assert_matches!(response, AppResponse::Error(_));
+
+    let (nonce, expires_at) = holochain_nonce::fresh_nonce(Timestamp::now()).unwrap();
+    let call = ZomeCall::try_from_unsigned_zome_call(
+        conductor.keystore(),
+        ZomeCallUnsigned {
+            provenance: app_2.agent().clone(),
+            cell_id: app_2.cells()[0].cell_id().clone(),
+            zome_name: "zome".into(),
+            fn_name: "fn".into(),
+            cap_secret: None,
+            payload: ExternIO::encode(()).unwrap(),
+            nonce,
+            expires_at,
+        },
+    )
+    .await
+    .unwrap();
+    let response: AppResponse = client
+        .request(AppRequest::CallZome(Box::new(call)))
+        .await
+        .unwrap();
+    assert_matches!(response, AppResponse::Error(_));
+
+    let response: AppResponse = client
+        .request(AppRequest::AppInfo {
+            installed_app_id: app_1.installed_app_id().clone(),
+        })
+        .await
+        .unwrap();
+    assert_matches!(response, AppResponse::AppInfo(Some(_)));
 }
 
-async fn check_app_port(port: u16, origin: &str) {
+async fn check_app_port(conductor: &SweetConductor, port: u16, origin: &str) {
     let (client, rx) = connect(
         Arc::new(WebsocketConfig::CLIENT_DEFAULT),
         ConnectRequest::new(([127, 0, 0, 1], port).into())
@@ -953,6 +1501,9 @@ async fn check_app_port(port: u16, origin: &str) {
     .unwrap();
 
     let _rx = PollRecv::new::<AppResponse>(rx);
+    conductor
+        .authenticate_app_ws_client(&client, "app".to_string())
+        .await;
 
     let request = AppRequest::ListWasmHostFunctions;
     let _: AppResponse = client.request(request).await.unwrap();
@@ -23,17 +23,20 @@ fn merge_hashes(
     let mut valid = HashSet::new();
     let mut rejected = HashSet::new();
     let mut merged_highest_observed = None;
+    let mut warrants = HashSet::new();
     for result in results {
         let AgentActivityResponse {
             agent: the_agent,
             highest_observed,
             valid_activity,
             rejected_activity,
+            warrants: these_warrants,
             ..
         } = result;
         if the_agent != agent {
             continue;
         }
+        warrants.extend(these_warrants);
 
         match (merged_highest_observed.take(), highest_observed) {
             (None, None) => {}
@@ -85,6 +88,7 @@ fn merge_hashes(
         valid_activity,
         rejected_activity,
         highest_observed: merged_highest_observed,
+        warrants: warrants.into_iter().collect(),
     }
 }
 
@@ -148,16 +152,19 @@ fn merge_status_only(
 ) -> AgentActivityResponse<ActionHash> {
     let mut merged_status = None;
     let mut merged_highest_observed = None;
+    let mut warrants = HashSet::new();
     for result in results {
         let AgentActivityResponse {
             status,
             agent: the_agent,
             highest_observed,
+            warrants: these_warrants,
             ..
         } = result;
         if the_agent != agent {
             continue;
         }
+        warrants.extend(these_warrants);
         match (merged_highest_observed.take(), highest_observed) {
             (None, None) => {}
             (Some(h), None) | (None, Some(h)) => {
@@ -235,5 +242,6 @@ fn merge_status_only(
         valid_activity: ChainItems::NotRequested,
         rejected_activity: ChainItems::NotRequested,
         highest_observed: merged_highest_observed,
+        warrants: warrants.into_iter().collect(),
     }
 }
@@ -4,6 +4,7 @@ use self::get_agent_activity_query::hashes::GetAgentActivityQuery;
 use self::get_agent_activity_query::must_get_agent_activity::must_get_agent_activity;
 use self::get_entry_ops_query::GetEntryOpsQuery;
 use self::get_links_ops_query::GetLinksOpsQuery;
+use self::get_meta_query::GetMetaQuery;
 use self::{
     get_agent_activity_query::deterministic::DeterministicGetAgentActivityQuery,
     get_record_query::GetRecordOpsQuery,
@@ -12,9 +13,12 @@ use self::{
 use super::error::CascadeResult;
 use holo_hash::ActionHash;
 use holo_hash::AgentPubKey;
+use holo_hash::AnyDhtHash;
 use holochain_state::query::link::GetLinksQuery;
 use holochain_state::query::Query;
+use holochain_state::query::StateQueryResult;
 use holochain_state::query::Txn;
+use holochain_state::warrant::list_warrants;
 use holochain_types::prelude::*;
 use holochain_zome_types::agent_activity::DeterministicGetAgentActivityFilter;
 use tracing::*;
@@ -25,6 +29,7 @@ mod test;
 pub(crate) mod get_agent_activity_query;
 pub(crate) mod get_entry_ops_query;
 pub(crate) mod get_links_ops_query;
+pub(crate) mod get_meta_query;
 pub(crate) mod get_record_query;
 
 /// Handler for get_entry query to an Entry authority
@@ -53,6 +58,20 @@ pub async fn handle_get_record(
     Ok(results)
 }
 
+/// Handler for get_meta query to a Record/Entry authority
+#[instrument(skip(env))]
+pub async fn handle_get_meta(
+    env: DbRead<DbKindDht>,
+    hash: AnyDhtHash,
+    options: holochain_p2p::event::GetMetaOptions,
+) -> CascadeResult<MetadataSet> {
+    let query = GetMetaQuery::new(hash, options.metadata_request);
+    let results = env
+        .read_async(move |txn| query.run(Txn::from(&txn)))
+        .await?;
+    Ok(results)
+}
+
 /// Handler for get_agent_activity query to an Activity authority
 #[instrument(skip(env))]
 pub async fn handle_get_agent_activity(
@@ -61,9 +80,13 @@ pub async fn handle_get_agent_activity(
     query: ChainQueryFilter,
     options: holochain_p2p::event::GetActivityOptions,
 ) -> CascadeResult<AgentActivityResponse<ActionHash>> {
-    let query = GetAgentActivityQuery::new(agent, query, options);
+    let query = GetAgentActivityQuery::new(agent.clone(), query, options);
     let results = env
-        .read_async(move |txn| query.run(Txn::from(&txn)))
+        .read_async(move |txn| -> StateQueryResult<_> {
+            let mut response = query.run(Txn::from(&txn))?;
+            response.warrants = list_warrants(&txn, &agent)?;
+            Ok(response)
+        })
         .await?;
     Ok(results)
 }
@@ -164,6 +164,7 @@ impl Query for GetAgentActivityQuery {
             rejected_activity,
             status,
             highest_observed,
+            warrants: Vec::with_capacity(0),
         })
     }
 }
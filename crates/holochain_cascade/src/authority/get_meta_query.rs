@@ -0,0 +1,184 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use holo_hash::ActionHash;
+use holo_hash::AnyDhtHash;
+use holochain_sqlite::rusqlite::named_params;
+use holochain_sqlite::rusqlite::Row;
+use holochain_state::prelude::*;
+use holochain_state::query::StateQueryError;
+
+#[derive(Debug, Clone)]
+pub struct GetMetaQuery(AnyDhtHash, MetadataRequest);
+
+impl GetMetaQuery {
+    pub fn new(hash: AnyDhtHash, request: MetadataRequest) -> Self {
+        Self(hash, request)
+    }
+}
+
+pub struct Item {
+    op_type: DhtOpType,
+    action: SignedAction,
+}
+
+#[derive(Debug, Default)]
+pub struct State {
+    meta: MetadataSet,
+    /// The valid actions which created the hash as an entry.
+    creates: HashSet<ActionHash>,
+    /// The actions deleted by a valid delete.
+    deleted: HashSet<ActionHash>,
+    /// The valid links on the hash as a base.
+    links: HashSet<ActionHash>,
+    /// The links deleted by a valid delete.
+    deleted_links: HashSet<ActionHash>,
+}
+
+impl Query for GetMetaQuery {
+    type Item = Judged<Item>;
+    type State = State;
+    type Output = MetadataSet;
+
+    fn query(&self) -> String {
+        "
+        SELECT Action.blob AS action_blob, DhtOp.type AS dht_type,
+        DhtOp.validation_status AS status
+        FROM DhtOp
+        JOIN Action On DhtOp.action_hash = Action.hash
+        WHERE DhtOp.type IN (
+            :store_record, :store_entry, :delete, :delete_entry, :update, :update_content,
+            :create_link, :delete_link
+        )
+        AND
+        DhtOp.basis_hash = :hash
+        AND
+        DhtOp.when_integrated IS NOT NULL
+        "
+        .into()
+    }
+
+    fn params(&self) -> Vec<Params> {
+        let params = named_params! {
+            ":store_record": DhtOpType::StoreRecord,
+            ":store_entry": DhtOpType::StoreEntry,
+            ":delete": DhtOpType::RegisterDeletedBy,
+            ":delete_entry": DhtOpType::RegisterDeletedEntryAction,
+            ":update": DhtOpType::RegisterUpdatedRecord,
+            ":update_content": DhtOpType::RegisterUpdatedContent,
+            ":create_link": DhtOpType::RegisterAddLink,
+            ":delete_link": DhtOpType::RegisterRemoveLink,
+            ":hash": self.0,
+        };
+        params.to_vec()
+    }
+
+    fn as_map(&self) -> Arc<dyn Fn(&Row) -> StateQueryResult<Self::Item>> {
+        let f = |row: &Row| {
+            let action =
+                from_blob::<SignedAction>(row.get(row.as_ref().column_index("action_blob")?)?)?;
+            let op_type = row.get(row.as_ref().column_index("dht_type")?)?;
+            let validation_status = row.get(row.as_ref().column_index("status")?)?;
+            Ok(Judged::raw(Item { op_type, action }, validation_status))
+        };
+        Arc::new(f)
+    }
+
+    fn init_fold(&self) -> StateQueryResult<Self::State> {
+        Ok(Default::default())
+    }
+
+    fn fold(&self, mut state: Self::State, dht_op: Self::Item) -> StateQueryResult<Self::State> {
+        let status = dht_op.validation_status();
+        let is_valid = status == Some(ValidationStatus::Valid);
+        let Item { op_type, action } = dht_op.data;
+        let action = ActionHashed::from_content_sync(action.0);
+        match op_type {
+            DhtOpType::StoreRecord | DhtOpType::StoreEntry => {
+                if op_type == DhtOpType::StoreRecord {
+                    state.meta.validation_status = status;
+                }
+                match status {
+                    Some(ValidationStatus::Valid) => {
+                        if op_type == DhtOpType::StoreEntry {
+                            state.creates.insert(action.as_hash().clone());
+                        }
+                        state.meta.actions.insert(action.into());
+                    }
+                    Some(ValidationStatus::Rejected) => {
+                        state.meta.invalid_actions.insert(action.into());
+                    }
+                    _ => (),
+                }
+            }
+            DhtOpType::RegisterDeletedBy | DhtOpType::RegisterDeletedEntryAction => {
+                if is_valid {
+                    if let Action::Delete(delete) = action.as_content() {
+                        state.deleted.insert(delete.deletes_address.clone());
+                    }
+                    state.meta.deletes.insert(action.into());
+                }
+            }
+            DhtOpType::RegisterUpdatedRecord | DhtOpType::RegisterUpdatedContent => {
+                if is_valid {
+                    state.meta.updates.insert(action.into());
+                }
+            }
+            DhtOpType::RegisterAddLink => {
+                if is_valid {
+                    state.links.insert(action.into_hash());
+                }
+            }
+            DhtOpType::RegisterRemoveLink => {
+                if is_valid {
+                    if let Action::DeleteLink(delete_link) = action.as_content() {
+                        state
+                            .deleted_links
+                            .insert(delete_link.link_add_address.clone());
+                    }
+                }
+            }
+            _ => return Err(StateQueryError::UnexpectedOp(op_type)),
+        }
+        Ok(state)
+    }
+
+    fn render<S>(&self, state: Self::State, _stores: S) -> StateQueryResult<Self::Output>
+    where
+        S: Store,
+    {
+        let State {
+            mut meta,
+            creates,
+            deleted,
+            links,
+            deleted_links,
+        } = state;
+        let request = &self.1;
+        meta.link_count = links.difference(&deleted_links).count();
+        if request.entry_dht_status && *self.0.hash_type() == holo_hash::hash_type::AnyDht::Entry {
+            meta.entry_dht_status = if creates.iter().any(|h| !deleted.contains(h)) {
+                Some(EntryDhtStatus::Live)
+            } else if !creates.is_empty() {
+                Some(EntryDhtStatus::Dead)
+            } else if !meta.invalid_actions.is_empty() {
+                Some(EntryDhtStatus::Rejected)
+            } else {
+                None
+            };
+        }
+        if !request.all_valid_actions {
+            meta.actions.clear();
+        }
+        if !request.all_invalid_actions {
+            meta.invalid_actions.clear();
+        }
+        if !request.all_deletes {
+            meta.deletes.clear();
+        }
+        if !request.all_updates {
+            meta.updates.clear();
+        }
+        Ok(meta)
+    }
+}
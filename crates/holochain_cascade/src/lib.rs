@@ -34,6 +34,7 @@ use holo_hash::AnyDhtHash;
 use holo_hash::EntryHash;
 use holochain_p2p::actor::GetActivityOptions;
 use holochain_p2p::actor::GetLinksOptions;
+use holochain_p2p::actor::GetMetaOptions;
 use holochain_p2p::actor::GetOptions as NetworkGetOptions;
 use holochain_p2p::HolochainP2pDna;
 use holochain_p2p::HolochainP2pDnaT;
@@ -947,6 +948,29 @@ where
         Ok(links.len())
     }
 
+    /// Get the metadata the authorities for a hash hold about it.
+    ///
+    /// If this agent is an authority for the hash, only its own metadata is returned.
+    #[instrument(skip(self, options))]
+    pub async fn dht_get_meta(
+        &self,
+        hash: AnyDhtHash,
+        options: GetMetaOptions,
+    ) -> CascadeResult<Vec<MetadataSet>> {
+        if self.am_i_an_authority(hash.clone().into()).await? {
+            if let Some(dht) = &self.dht {
+                let mut meta =
+                    authority::handle_get_meta(dht.clone(), hash, (&options).into()).await?;
+                meta.is_authority = true;
+                return Ok(vec![meta]);
+            }
+        }
+        match &self.network {
+            Some(network) => Ok(network.get_meta(hash, options).await?),
+            None => Ok(Vec::new()),
+        }
+    }
+
     /// Request a hash bounded chain query.
     pub async fn must_get_agent_activity(
         &self,
@@ -1077,6 +1101,7 @@ where
             rejected_activity,
             status,
             highest_observed,
+            warrants,
         } = merged_response;
         let valid_activity = match valid_activity {
             ChainItems::Hashes(hashes) => {
@@ -1131,6 +1156,7 @@ where
             rejected_activity,
             status,
             highest_observed,
+            warrants,
         };
         Ok(r)
     }
@@ -108,10 +108,19 @@ impl HolochainP2pDnaT for PassThroughNetwork {
 
     async fn get_meta(
         &self,
-        _dht_hash: holo_hash::AnyDhtHash,
-        _options: actor::GetMetaOptions,
+        dht_hash: holo_hash::AnyDhtHash,
+        options: actor::GetMetaOptions,
     ) -> actor::HolochainP2pResult<Vec<MetadataSet>> {
-        todo!()
+        let mut out = Vec::new();
+        for env in &self.envs {
+            let mut r =
+                authority::handle_get_meta(env.clone(), dht_hash.clone(), (&options).into())
+                    .await
+                    .map_err(|e| HolochainP2pError::Other(e.into()))?;
+            r.is_authority = true;
+            out.push(r);
+        }
+        Ok(out)
     }
 
     async fn get_links(
@@ -236,6 +245,14 @@ impl HolochainP2pDnaT for PassThroughNetwork {
         todo!()
     }
 
+    async fn publish_warrants(
+        &self,
+        _basis_hash: holo_hash::OpBasis,
+        _warrants: Vec<SignedWarrant>,
+    ) -> actor::HolochainP2pResult<()> {
+        todo!()
+    }
+
     async fn send_validation_receipts(
         &self,
         _to_agent: AgentPubKey,
@@ -442,6 +459,14 @@ impl HolochainP2pDnaT for MockNetwork {
         todo!()
     }
 
+    async fn publish_warrants(
+        &self,
+        _basis_hash: holo_hash::OpBasis,
+        _warrants: Vec<SignedWarrant>,
+    ) -> actor::HolochainP2pResult<()> {
+        todo!()
+    }
+
     async fn send_validation_receipts(
         &self,
         _to_agent: AgentPubKey,
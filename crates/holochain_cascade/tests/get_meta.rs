@@ -0,0 +1,71 @@
+use holochain_cascade::test_utils::*;
+use holochain_cascade::CascadeImpl;
+use holochain_p2p::actor::GetMetaOptions;
+use holochain_state::prelude::*;
+
+fn action_hashes(set: &std::collections::BTreeSet<TimedActionHash>) -> Vec<ActionHash> {
+    set.iter().map(|a| a.action_hash.clone()).collect()
+}
+
+// Checks that the metadata of an entry can be fetched from a remote peer who is an authority on the entry
+#[tokio::test(flavor = "multi_thread")]
+async fn get_meta_not_authority() {
+    holochain_trace::test_run().ok();
+
+    // Environments
+    let cache = test_cache_db();
+    let authority = test_dht_db();
+
+    // Data
+    let td = EntryTestData::create();
+    fill_db(&authority.to_db(), td.store_entry_op.clone()).await;
+    fill_db(&authority.to_db(), td.update_content_op.clone()).await;
+    fill_db(&authority.to_db(), td.create_link_op.clone()).await;
+
+    // Network
+    let network = PassThroughNetwork::authority_for_nothing(vec![authority.to_db().clone().into()]);
+
+    // Cascade
+    let cascade = CascadeImpl::empty().with_network(network, cache.to_db());
+
+    let options = GetMetaOptions {
+        metadata_request: MetadataRequest {
+            entry_dht_status: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let meta = cascade
+        .dht_get_meta(td.hash.clone().into(), options.clone())
+        .await
+        .unwrap();
+
+    assert_eq!(meta.len(), 1);
+    assert!(meta[0].is_authority);
+    assert_eq!(
+        action_hashes(&meta[0].actions),
+        vec![td.create_hash.clone()]
+    );
+    assert_eq!(
+        action_hashes(&meta[0].updates),
+        vec![td.update_hash.clone()]
+    );
+    assert!(meta[0].deletes.is_empty());
+    assert_eq!(meta[0].link_count, 1);
+    assert_eq!(meta[0].entry_dht_status, Some(EntryDhtStatus::Live));
+
+    fill_db(&authority.to_db(), td.delete_entry_action_op.clone()).await;
+    fill_db(&authority.to_db(), td.delete_link_op.clone()).await;
+
+    let meta = cascade
+        .dht_get_meta(td.hash.clone().into(), options)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        action_hashes(&meta[0].deletes),
+        vec![td.delete_hash.clone()]
+    );
+    assert_eq!(meta[0].link_count, 0);
+    assert_eq!(meta[0].entry_dht_status, Some(EntryDhtStatus::Dead));
+}
@@ -3,8 +3,10 @@ use holochain_types::prelude::*;
 use holochain_types::websocket::AllowedOrigins;
 use holochain_zome_types::cell::CellId;
 use kitsune_p2p_types::agent_info::AgentInfoSigned;
+use std::net::IpAddr;
+use std::path::PathBuf;
 
-use crate::{AppInfo, FullStateDump, StorageInfo};
+use crate::{AppAuthenticationToken, AppInfo, FullStateDump, InterfaceTlsConfig, StorageInfo};
 
 /// Represents the available conductor functions to call over an admin interface.
 ///
@@ -73,6 +75,18 @@ pub enum AdminRequest {
     /// [`EnableApp`]: AdminRequest::EnableApp
     InstallApp(Box<InstallAppPayload>),
 
+    /// Install an app whose [`AppBundle`] is fetched from the app store service,
+    /// by its [`AppHash`].
+    ///
+    /// Apart from where the bundle comes from, this behaves exactly like
+    /// [`AdminRequest::InstallApp`].
+    /// See [`InstallAppFromStorePayload`] for full details on the configuration.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::AppInstalled`]
+    InstallAppFromStore(Box<InstallAppFromStorePayload>),
+
     /// Uninstalls the app specified by argument `installed_app_id` from the conductor.
     ///
     /// The app will be removed from the list of installed apps, and any cells
@@ -80,12 +94,38 @@ pub enum AdminRequest {
     /// any persisted data.
     /// Cells which are still referenced by other installed apps will not be removed.
     ///
+    /// If other apps use cells created by this app, through the `use_existing` or
+    /// `create_if_not_exists` provisioning strategies, the uninstallation fails
+    /// unless `cascade` is set, in which case those apps are uninstalled too.
+    ///
     /// # Returns
     ///
     /// [`AdminResponse::AppUninstalled`]
     UninstallApp {
         /// The app ID to uninstall
         installed_app_id: InstalledAppId,
+        /// Also uninstall the apps which use cells created by this app
+        #[serde(default)]
+        cascade: bool,
+    },
+
+    /// Migrate the agent of a cell to a new DNA.
+    ///
+    /// The agent must already have a running cell of the new DNA, typically from
+    /// installing a new version of the app with the same agent key.
+    /// The chain on the old DNA is closed with a `CloseChain` action, and the chain
+    /// on the new DNA is opened with an `OpenChain` action which references the old DNA,
+    /// after the `migrate_agent` callbacks of both DNAs have passed. Zomes of the new DNA
+    /// can then read the records of the old chain with `query_previous_chain`.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::AgentMigrated`]
+    MigrateAgent {
+        /// The cell whose chain is closed
+        old_cell_id: CellId,
+        /// The DNA of the cell whose chain is opened
+        new_dna_hash: DnaHash,
     },
 
     /// List the hashes of all installed DNAs.
@@ -95,13 +135,39 @@ pub enum AdminRequest {
     /// [`AdminResponse::DnasListed`]
     ListDnas,
 
-    /// Generate a new [`AgentPubKey`].
+    /// Generate a new [`AgentPubKey`] and register it with DPKI.
     ///
     /// # Returns
     ///
     /// [`AdminResponse::AgentPubKeyGenerated`]
     GenerateAgentPubKey,
 
+    /// Replace an [`AgentPubKey`] in DPKI with a newly generated one.
+    ///
+    /// Actions authored with the old key after the rotation are invalid,
+    /// so all apps installed with the old key are disabled.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::AgentKeyRotated`]
+    RotateAgentKey {
+        /// The key to replace
+        agent_key: AgentPubKey,
+    },
+
+    /// Revoke an [`AgentPubKey`] in DPKI, without replacing it.
+    ///
+    /// Actions authored with the key after the revocation are invalid,
+    /// so all apps installed with the key are disabled.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::AgentKeyRevoked`]
+    RevokeAgentKey {
+        /// The key to revoke
+        agent_key: AgentPubKey,
+    },
+
     /// List the IDs of all live cells currently running in the conductor.
     ///
     /// # Returns
@@ -168,6 +234,15 @@ pub enum AdminRequest {
     /// An `allowed_origins` parameter to control which origins are allowed to connect
     /// to the app interface.
     ///
+    /// Optionally an `installed_app_id` parameter can be passed to bind the interface
+    /// to a single app. Only clients authenticated for that app can then connect.
+    ///
+    /// Optionally a `bind_address` can be passed to listen on another address than
+    /// `127.0.0.1`, and a `tls` certificate to serve `wss://`.
+    ///
+    /// Every client must authenticate its connection with a token issued by
+    /// [`AdminRequest::IssueAppAuthenticationToken`] before it can make requests.
+    ///
     /// [`AppRequest`]: super::AppRequest
     AttachAppInterface {
         /// Optional port number
@@ -182,8 +257,74 @@ pub enum AdminRequest {
         ///
         /// Connections from any origin which is not permitted by this config will be rejected.
         allowed_origins: AllowedOrigins,
+
+        /// Optional app to bind this interface to.
+        ///
+        /// If set, only tokens issued for this app are accepted on the interface.
+        #[serde(default)]
+        installed_app_id: Option<InstalledAppId>,
+
+        /// Optional address to bind to, instead of `127.0.0.1`.
+        #[serde(default)]
+        bind_address: Option<IpAddr>,
+
+        /// Optional certificate to serve `wss://` with.
+        #[serde(default)]
+        tls: Option<InterfaceTlsConfig>,
     },
 
+    /// Open up a new app interface on a Unix domain socket, for processing
+    /// [`AppRequest`]s with the same protocol as [`AdminRequest::AttachAppInterface`].
+    ///
+    /// Access to the interface is governed by the file system permissions of the
+    /// socket file rather than by origins. Clients must still authenticate their
+    /// connections. The interface is persisted like websocket app interfaces, but
+    /// is not included in [`AdminRequest::ListAppInterfaces`], which lists ports.
+    ///
+    /// Only available on unix platforms.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::AppUnixSocketInterfaceAttached`]
+    ///
+    /// [`AppRequest`]: super::AppRequest
+    AttachAppUnixSocketInterface {
+        /// The path of the socket file.
+        path: PathBuf,
+
+        /// The permissions to set on the socket file, `0o600` if not set.
+        #[serde(default)]
+        mode: Option<u32>,
+
+        /// Optional app to bind this interface to.
+        ///
+        /// If set, only tokens issued for this app are accepted on the interface.
+        #[serde(default)]
+        installed_app_id: Option<InstalledAppId>,
+    },
+
+    /// Issue a token which a client can use to authenticate a connection to an
+    /// app interface.
+    ///
+    /// The token is scoped to a single app: an authenticated connection can only
+    /// make requests on behalf of that app. Tokens expire after `expiry_seconds`
+    /// and, unless `single_use` is `false`, can only be used once.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::AppAuthenticationTokenIssued`]
+    IssueAppAuthenticationToken(IssueAppAuthenticationTokenPayload),
+
+    /// Revoke a token issued by [`AdminRequest::IssueAppAuthenticationToken`],
+    /// so that it can no longer be used to authenticate.
+    ///
+    /// Connections which were already authenticated with the token are not closed.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::AppAuthenticationTokenRevoked`]
+    RevokeAppAuthenticationToken(AppAuthenticationToken),
+
     /// List all the app interfaces currently attached with [`AttachAppInterface`].
     ///
     /// # Returns
@@ -335,6 +476,48 @@ pub enum AdminRequest {
         records: Vec<Record>,
     },
 
+    /// Export the source chain of a cell into an archive, to back it up.
+    ///
+    /// The archive holds every record of the chain with its private entries,
+    /// including capability grants and claims, and the locks of countersigning
+    /// sessions in progress. It is signed by the agent of the cell.
+    ///
+    /// Private entries are not encrypted in the archive, so it should be kept
+    /// as safe as the keys of the agent.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::ChainExported`]
+    ExportChain {
+        /// The cell whose chain is exported.
+        cell_id: CellId,
+    },
+
+    /// Restore the source chain of a cell from an archive made by
+    /// [`AdminRequest::ExportChain`].
+    ///
+    /// The chain is restored before the cell is installed: restore the agent
+    /// key into the keystore, import the archive, and then install the app for
+    /// the agent. Installing the app finds the restored genesis and doesn't run
+    /// genesis again, so the agent never publishes a second chain that would
+    /// fork the restored one. Importing fails if the cell is already installed
+    /// or already has a chain on this conductor.
+    ///
+    /// The signature of the archive and the structure of the chain are checked
+    /// before the records are written. To validate the records, the DNA must
+    /// be registered first with [`AdminRequest::RegisterDna`].
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::ChainImported`]
+    ImportChain {
+        /// The archive to restore the chain from.
+        archive: Box<SignedChainArchive>,
+        /// If this is `true`, then the records will be validated before they
+        /// are imported. This is much slower but catches invalid chains.
+        validate: bool,
+    },
+
     /// Request capability grant for making zome calls.
     ///
     /// # Returns
@@ -351,6 +534,56 @@ pub enum AdminRequest {
 
     /// Info about storage used by apps
     StorageInfo,
+
+    /// Replace the key the databases of the conductor are encrypted with,
+    /// and re-encrypt the databases in place.
+    ///
+    /// Every database has its own key, derived from a master key which is
+    /// stored encrypted by the keystore. A new master key is generated and
+    /// every database is rekeyed, open ones once their transactions have
+    /// finished. The replaced master key is discarded after every database
+    /// has been rekeyed.
+    ///
+    /// Databases are only encrypted if the conductor was built with the
+    /// `sqlite-encrypted` feature.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::DatabaseKeyRotated`]
+    RotateDatabaseKey,
+
+    /// Block a cell, a node, a node in the network of a DNA, or an IP address
+    /// for an interval of time.
+    ///
+    /// Blocks are kept per target and reason, so a target can be blocked for
+    /// several reasons, and is blocked for as long as any of its blocks lasts.
+    /// Blocked nodes and IP addresses are disconnected as soon as they connect.
+    ///
+    /// An IP address can only be blocked if the network transport tells which
+    /// address a peer connects from. Neither tx5 nor a proxied tx2 transport
+    /// does, so with those, blocking an IP address is an error.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::Blocked`]
+    Block(BlockSpan),
+
+    /// Lift a block for an interval of time.
+    ///
+    /// The target and reason must match those of the block exactly. Blocks
+    /// outside the interval remain in place.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::Unblocked`]
+    Unblock(BlockSpan),
+
+    /// List the blocks which have not ended yet, in order of their start.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::BlocksListed`]
+    ListBlocks,
 }
 
 /// Represents the possible responses to an [`AdminRequest`]
@@ -378,7 +611,8 @@ pub enum AdminResponse {
     /// The successful response to an [`AdminRequest::UpdateCoordinators`]
     CoordinatorsUpdated,
 
-    /// The successful response to an [`AdminRequest::InstallApp`].
+    /// The successful response to an [`AdminRequest::InstallApp`]
+    /// or [`AdminRequest::InstallAppFromStore`].
     ///
     /// The resulting [`AppInfo`] contains the app ID,
     /// the [`RoleName`]s and, most usefully, [`CellInfo`](crate::CellInfo)s
@@ -400,6 +634,21 @@ pub enum AdminResponse {
     /// Contains a new [`AgentPubKey`] generated by the keystore.
     AgentPubKeyGenerated(AgentPubKey),
 
+    /// The successful response to an [`AdminRequest::MigrateAgent`].
+    ///
+    /// It means both chains were migrated successfully.
+    AgentMigrated,
+
+    /// The successful response to an [`AdminRequest::RotateAgentKey`].
+    ///
+    /// Contains the new [`AgentPubKey`] which replaced the old one.
+    AgentKeyRotated(AgentPubKey),
+
+    /// The successful response to an [`AdminRequest::RevokeAgentKey`].
+    ///
+    /// It means the key was revoked successfully.
+    AgentKeyRevoked,
+
     /// The successful response to an [`AdminRequest::ListDnas`].
     ///
     /// Contains a list of the hashes of all installed DNAs.
@@ -423,9 +672,20 @@ pub enum AdminResponse {
         port: u16,
     },
 
+    /// The successful response to an [`AdminRequest::AttachAppUnixSocketInterface`].
+    AppUnixSocketInterfaceAttached,
+
     /// The list of attached app interfaces.
     AppInterfacesListed(Vec<u16>),
 
+    /// The successful response to an [`AdminRequest::IssueAppAuthenticationToken`].
+    ///
+    /// Contains the token and the time at which it expires.
+    AppAuthenticationTokenIssued(AppAuthenticationTokenIssued),
+
+    /// The successful response to an [`AdminRequest::RevokeAppAuthenticationToken`].
+    AppAuthenticationTokenRevoked,
+
     /// The successful response to an [`AdminRequest::EnableApp`].
     ///
     /// It means the app was enabled successfully. If it was possible to
@@ -484,6 +744,14 @@ pub enum AdminResponse {
     /// The successful response to an [`AdminRequest::GraftRecords`].
     RecordsGrafted,
 
+    /// The successful response to an [`AdminRequest::ExportChain`].
+    ///
+    /// This is the signed archive of the chain.
+    ChainExported(Box<SignedChainArchive>),
+
+    /// The successful response to an [`AdminRequest::ImportChain`].
+    ChainImported,
+
     /// The successful response to an [`AdminRequest::GrantZomeCallCapability`].
     ZomeCallCapabilityGranted,
 
@@ -492,6 +760,18 @@ pub enum AdminResponse {
 
     /// The successful response to an [`AdminRequest::StorageInfo`].
     StorageInfo(StorageInfo),
+
+    /// The successful response to an [`AdminRequest::RotateDatabaseKey`].
+    DatabaseKeyRotated,
+
+    /// The successful response to an [`AdminRequest::Block`].
+    Blocked,
+
+    /// The successful response to an [`AdminRequest::Unblock`].
+    Unblocked,
+
+    /// The successful response to an [`AdminRequest::ListBlocks`].
+    BlocksListed(Vec<BlockSpan>),
 }
 
 /// Error type that goes over the websocket wire.
@@ -538,6 +818,70 @@ pub enum AppStatusFilter {
     Paused,
 }
 
+/// The default number of seconds after which an app authentication token expires.
+pub const DEFAULT_APP_AUTHENTICATION_TOKEN_EXPIRY_SECONDS: u64 = 30;
+
+fn default_expiry_seconds() -> u64 {
+    DEFAULT_APP_AUTHENTICATION_TOKEN_EXPIRY_SECONDS
+}
+
+fn default_single_use() -> bool {
+    true
+}
+
+/// Arguments for [`AdminRequest::IssueAppAuthenticationToken`].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, SerializedBytes)]
+pub struct IssueAppAuthenticationTokenPayload {
+    /// The app which the token grants access to.
+    pub installed_app_id: InstalledAppId,
+
+    /// The number of seconds after which the token expires.
+    ///
+    /// Set to `0` for a token which never expires. Defaults to
+    /// [`DEFAULT_APP_AUTHENTICATION_TOKEN_EXPIRY_SECONDS`].
+    #[serde(default = "default_expiry_seconds")]
+    pub expiry_seconds: u64,
+
+    /// Whether the token is revoked after it was used once to authenticate.
+    /// Defaults to `true`.
+    #[serde(default = "default_single_use")]
+    pub single_use: bool,
+}
+
+impl IssueAppAuthenticationTokenPayload {
+    /// A single use token for the given app, with the default expiry.
+    pub fn for_installed_app_id(installed_app_id: InstalledAppId) -> Self {
+        Self {
+            installed_app_id,
+            expiry_seconds: DEFAULT_APP_AUTHENTICATION_TOKEN_EXPIRY_SECONDS,
+            single_use: true,
+        }
+    }
+
+    /// Set the number of seconds after which the token expires.
+    pub fn expiry_seconds(mut self, expiry_seconds: u64) -> Self {
+        self.expiry_seconds = expiry_seconds;
+        self
+    }
+
+    /// Set whether the token can only be used once.
+    pub fn single_use(mut self, single_use: bool) -> Self {
+        self.single_use = single_use;
+        self
+    }
+}
+
+/// The response to [`AdminRequest::IssueAppAuthenticationToken`].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, SerializedBytes)]
+pub struct AppAuthenticationTokenIssued {
+    /// The token, to be sent in an [`AppAuthenticationRequest`](crate::AppAuthenticationRequest) when connecting
+    /// to an app interface.
+    pub token: AppAuthenticationToken,
+
+    /// The time at which the token expires, or `None` if it never expires.
+    pub expires_at: Option<Timestamp>,
+}
+
 #[test]
 fn admin_request_serialization() {
     use rmp_serde::Deserializer;
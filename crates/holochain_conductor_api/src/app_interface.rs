@@ -1,3 +1,4 @@
+use crate::signal_subscription::SignalSubscription;
 use crate::ExternalApiWireError;
 use holo_hash::AgentPubKey;
 use holochain_keystore::LairResult;
@@ -6,6 +7,31 @@ use holochain_types::prelude::*;
 use kitsune_p2p_types::fetch_pool::FetchPoolInfo;
 use std::collections::HashMap;
 
+/// A token which authenticates a connection to an app interface,
+/// issued with [`AdminRequest::IssueAppAuthenticationToken`](crate::AdminRequest::IssueAppAuthenticationToken).
+pub type AppAuthenticationToken = Vec<u8>;
+
+/// The message a client must send to authenticate its connection to an
+/// app interface, before making any [`AppRequest`].
+///
+/// It is sent as a websocket `Authenticate` message rather than a request,
+/// and is responded to with an [`AppAuthenticationResponse`].
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize, SerializedBytes)]
+pub struct AppAuthenticationRequest {
+    /// The token to authenticate with.
+    pub token: AppAuthenticationToken,
+}
+
+/// The response to an [`AppAuthenticationRequest`].
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+#[serde(rename_all = "snake_case", tag = "type", content = "data")]
+pub enum AppAuthenticationResponse {
+    /// The connection is authenticated, and [`AppRequest`]s can be made on it.
+    Authenticated,
+    /// The token was not accepted, for the given reason, and the connection is closed.
+    AuthenticationFailed(String),
+}
+
 /// Represents the available conductor functions to call over an app interface
 /// and will result in a corresponding [`AppResponse`] message being sent back over the
 /// interface connection.
@@ -68,6 +94,17 @@ pub enum AppRequest {
     /// [`AppResponse::CloneCellEnabled`]
     EnableCloneCell(Box<EnableCloneCellPayload>),
 
+    /// Instantiate the cell of a role whose provisioning was deferred in the app manifest.
+    ///
+    /// Genesis is run for the cell with the given membrane proof, and the cell
+    /// is started if the app is running. Until then, the role is reported as a
+    /// [`CellInfo::Stem`] in the [`AppInfo`].
+    ///
+    /// # Returns
+    ///
+    /// [`AppResponse::DeferredCellProvisioned`]
+    ProvisionDeferredCell(Box<ProvisionDeferredCellPayload>),
+
     /// Info about networking processes
     ///
     /// # Returns
@@ -81,6 +118,85 @@ pub enum AppRequest {
     ///
     /// [`AppResponse::ListWasmHostFunctions`]
     ListWasmHostFunctions,
+
+    /// Set which signals of an app are sent to the clients of this app interface.
+    ///
+    /// Signals emitted by the app's cells are checked against the [`SignalFilterSet`]
+    /// of the subscription before they are sent over the interface. The subscription
+    /// replaces any previous subscription of the app and persists across restarts.
+    ///
+    /// [`SignalFilterSet`]: crate::signal_subscription::SignalFilterSet
+    ///
+    /// # Returns
+    ///
+    /// [`AppResponse::SignalSubscriptionSet`]
+    SetSignalSubscription(Box<SignalSubscription>),
+
+    /// Resend the signals of the app, starting from the signal with the given
+    /// sequence number, e.g. after reconnecting to the app interface.
+    ///
+    /// Every signal sent to the client carries a sequence number, which increases
+    /// by one for every signal of the app. A client which passes the sequence number
+    /// following the last signal it received gets every signal it missed, as long
+    /// as they are still buffered by the conductor. If the oldest requested signals
+    /// are no longer buffered, signals are resent from the oldest buffered one.
+    ///
+    /// Sequence numbers start from 1 again when the conductor restarts, so a
+    /// `next_seq` in the response which is lower than `from_seq` means that the
+    /// conductor was restarted and the client should reload its state. In that
+    /// case signals are sent from `next_seq` on.
+    ///
+    /// # Returns
+    ///
+    /// [`AppResponse::SignalsResumed`]
+    ResumeSignals {
+        /// The sequence number of the first signal to resend
+        from_seq: u64,
+    },
+
+    /// Get the state of the countersigning session the given cell is taking part in.
+    ///
+    /// # Returns
+    ///
+    /// [`AppResponse::CountersigningSessionState`]
+    GetCountersigningSessionState(Box<CellId>),
+
+    /// Abandon the countersigning session the given cell is taking part in,
+    /// unlocking the chain of the cell before the session expires.
+    ///
+    /// This is the same as letting the session time out. If the cell has already
+    /// committed its session entry, the entry stays at the head of the chain,
+    /// since it may already have been sent to the entry authorities, and the
+    /// session may still complete if the other signers publish their actions,
+    /// so only abandon a session that is known to be stuck.
+    ///
+    /// # Returns
+    ///
+    /// [`AppResponse::CountersigningSessionAbandoned`]
+    AbandonCountersigningSession(Box<CellId>),
+
+    /// Publish the committed session entry of the given cell to the authorities
+    /// of the session again, e.g. when they missed it or restarted before the
+    /// session completed.
+    ///
+    /// # Returns
+    ///
+    /// [`AppResponse::PublishCountersigningSessionTriggered`]
+    PublishCountersigningSession(Box<CellId>),
+
+    /// List the zome functions and callbacks exported by the zomes of the
+    /// provisioned cell of each role of the app.
+    ///
+    /// This lets clients discover the API of the zomes of an app without any
+    /// schema provided out of band.
+    ///
+    /// # Returns
+    ///
+    /// [`AppResponse::ZomeExportsListed`]
+    ListZomeExports {
+        /// The app to list the zome exports of
+        installed_app_id: InstalledAppId,
+    },
 }
 
 /// Represents the possible responses to an [`AppRequest`].
@@ -121,11 +237,81 @@ pub enum AppResponse {
     /// is returned.
     CloneCellEnabled(ClonedCell),
 
+    /// The successful response to an [`AppRequest::ProvisionDeferredCell`].
+    ///
+    /// The cell of the deferred role has been created. The [`ProvisionedCell`]
+    /// is returned.
+    DeferredCellProvisioned(ProvisionedCell),
+
     /// NetworkInfo is returned
     NetworkInfo(Vec<NetworkInfo>),
 
     /// All the wasm host functions supported by this conductor.
     ListWasmHostFunctions(Vec<String>),
+
+    /// The successful response to an [`AppRequest::SetSignalSubscription`].
+    SignalSubscriptionSet,
+
+    /// The successful response to an [`AppRequest::ResumeSignals`].
+    SignalsResumed {
+        /// The sequence number of the oldest signal still buffered
+        oldest_seq: u64,
+        /// The sequence number of the next signal the app emits
+        next_seq: u64,
+    },
+
+    /// The successful response to an [`AppRequest::GetCountersigningSessionState`].
+    ///
+    /// `None` if the cell is not taking part in a countersigning session.
+    CountersigningSessionState(Box<Option<CountersigningSessionState>>),
+
+    /// The successful response to an [`AppRequest::AbandonCountersigningSession`].
+    CountersigningSessionAbandoned,
+
+    /// The successful response to an [`AppRequest::PublishCountersigningSession`].
+    PublishCountersigningSessionTriggered,
+
+    /// The successful response to an [`AppRequest::ListZomeExports`].
+    ///
+    /// The exports of each zome of the provisioned cell of each role, in
+    /// the order the zomes are defined in the DNA.
+    ZomeExportsListed(HashMap<RoleName, Vec<ZomeExports>>),
+}
+
+/// The functions a zome exports.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ZomeExports {
+    /// The name of the zome.
+    pub zome_name: ZomeName,
+    /// The zome functions, which can be called by clients and other zomes.
+    pub zome_fns: Vec<FunctionName>,
+    /// The callbacks the zome implements, such as `init` or `validate`,
+    /// which are called by the conductor.
+    pub callbacks: Vec<FunctionName>,
+    /// The zome functions which are currently scheduled for the cell.
+    pub scheduled_fns: Vec<FunctionName>,
+}
+
+/// The state of the countersigning session a cell is taking part in.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case", tag = "type", content = "data")]
+pub enum CountersigningSessionState {
+    /// The cell has accepted the preflight request of the session and locked
+    /// its chain, but has not committed the session entry yet.
+    Accepted {
+        /// When the lock on the chain expires.
+        expires_at: Timestamp,
+    },
+    /// The cell has committed the session entry and is waiting for the
+    /// signatures of the other signers to be collected by the authorities.
+    SignaturesCollecting {
+        /// The hash of the session entry.
+        entry_hash: EntryHash,
+        /// The session data of the entry.
+        session_data: Box<CounterSigningSessionData>,
+        /// When the lock on the chain expires.
+        expires_at: Timestamp,
+    },
 }
 
 /// The data provided over an app interface in order to make a zome call
@@ -225,7 +411,7 @@ pub enum CellInfo {
     Cloned(ClonedCell),
 
     /// Potential cells with deferred installation as defined in the bundle.
-    /// Not yet implemented.
+    /// They can be instantiated with [`AppRequest::ProvisionDeferredCell`].
     Stem(StemCell),
 }
 
@@ -258,7 +444,6 @@ impl CellInfo {
 }
 
 /// Cell whose instantiation has been deferred.
-/// Not yet implemented.
 #[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct StemCell {
     /// The hash of the DNA that this cell would be instantiated from
@@ -337,11 +522,22 @@ impl AppInfo {
                 } else {
                     tracing::error!("no DNA definition found for cell id {}", provisioned_cell);
                 }
-            } else {
-                // no provisioned cell, thus there must be a deferred cell
-                // this is not implemented as of now
-                unimplemented!()
-            };
+            } else if role_assignment.is_deferred() {
+                // the provisioning of the base cell was deferred, so report it as a stem cell
+                if let Some(dna_def) = dna_definitions.get(role_assignment.cell_id()) {
+                    cell_info_for_role.push(CellInfo::Stem(StemCell {
+                        original_dna_hash: dna_def.hash.clone(),
+                        dna_modifiers: dna_def.modifiers.to_owned(),
+                        name: Some(dna_def.name.to_owned()),
+                    }));
+                } else {
+                    tracing::error!(
+                        "no DNA definition found for deferred cell id {}",
+                        role_assignment.cell_id()
+                    );
+                }
+            }
+            // otherwise the role is clone-only and has no base cell
 
             // push enabled clone cells to the vector of cell infos
             if let Some(clone_cells) = app.clone_cells_for_role_name(role_name) {
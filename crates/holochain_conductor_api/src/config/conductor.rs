@@ -9,6 +9,7 @@ use serde::Deserialize;
 use serde::Serialize;
 
 mod admin_interface_config;
+mod app_store_config;
 mod dpki_config;
 #[allow(missing_docs)]
 mod error;
@@ -20,6 +21,7 @@ pub mod process;
 //mod signal_config;
 
 pub use super::*;
+pub use app_store_config::AppStoreConfig;
 pub use dpki_config::DpkiConfig;
 //pub use logger_config::LoggerConfig;
 pub use error::*;
@@ -51,6 +53,11 @@ pub struct ConductorConfig {
     /// keys for new instances.
     pub dpki: Option<DpkiConfig>,
 
+    /// Optional app store configuration, naming the installed app whose cell
+    /// serves the app bundles installed with `InstallAppFromStore`.
+    #[serde(default)]
+    pub app_store: Option<AppStoreConfig>,
+
     /// Setup admin interfaces to control this conductor through a websocket connection.
     pub admin_interfaces: Option<Vec<AdminInterfaceConfig>>,
 
@@ -145,6 +152,20 @@ pub struct ConductorTuningParams {
     /// The delay between retries of sys validation when there are missing dependencies waiting to be found on the DHT.
     /// Default: 10 seconds
     pub sys_validation_retry_delay: Option<std::time::Duration>,
+    /// The longest duration a zome is allowed to `sleep` for in a single call.
+    /// Longer sleeps fail with an error instead of sleeping.
+    /// Default: 30 seconds
+    pub max_zome_sleep_duration: Option<std::time::Duration>,
+    /// The most zome calls which may be in a `sleep` at the same time.
+    /// Each sleeping call holds on to a thread, so further sleeps fail with an
+    /// error instead of taking threads away from the rest of the conductor.
+    /// Default: 16
+    pub max_concurrent_zome_sleeps: Option<usize>,
+    /// The number of signals kept per app, so that app interface clients which are
+    /// slow or reconnect can catch up on the signals they missed.
+    /// When more signals are emitted, the oldest are dropped.
+    /// Default: 1024
+    pub signal_replay_buffer_size: Option<usize>,
 }
 
 impl ConductorTuningParams {
@@ -152,6 +173,9 @@ impl ConductorTuningParams {
     pub fn new() -> Self {
         Self {
             sys_validation_retry_delay: None,
+            max_zome_sleep_duration: None,
+            max_concurrent_zome_sleeps: None,
+            signal_replay_buffer_size: None,
         }
     }
 
@@ -160,6 +184,22 @@ impl ConductorTuningParams {
         self.sys_validation_retry_delay
             .unwrap_or_else(|| std::time::Duration::from_secs(10))
     }
+
+    /// Get the current value of `max_zome_sleep_duration` or its default value.
+    pub fn max_zome_sleep_duration(&self) -> std::time::Duration {
+        self.max_zome_sleep_duration
+            .unwrap_or_else(|| std::time::Duration::from_secs(30))
+    }
+
+    /// Get the current value of `max_concurrent_zome_sleeps` or its default value.
+    pub fn max_concurrent_zome_sleeps(&self) -> usize {
+        self.max_concurrent_zome_sleeps.unwrap_or(16)
+    }
+
+    /// Get the current value of `signal_replay_buffer_size` or its default value.
+    pub fn signal_replay_buffer_size(&self) -> usize {
+        self.signal_replay_buffer_size.unwrap_or(1024)
+    }
 }
 
 impl Default for ConductorTuningParams {
@@ -167,6 +207,9 @@ impl Default for ConductorTuningParams {
         let empty = Self::new();
         Self {
             sys_validation_retry_delay: Some(empty.sys_validation_retry_delay()),
+            max_zome_sleep_duration: Some(empty.max_zome_sleep_duration()),
+            max_concurrent_zome_sleeps: Some(empty.max_concurrent_zome_sleeps()),
+            signal_replay_buffer_size: Some(empty.signal_replay_buffer_size()),
         }
     }
 }
@@ -214,6 +257,7 @@ mod tests {
                 data_root_path: Some(PathBuf::from("/path/to/env").into()),
                 network: Default::default(),
                 dpki: None,
+                app_store: None,
                 keystore: KeystoreConfig::DangerTestKeystore,
                 admin_interfaces: None,
                 db_sync_strategy: DbSyncStrategy::default(),
@@ -241,6 +285,9 @@ mod tests {
       instance_id: some_id
       init_params: some_params
 
+    app_store:
+      installed_app_id: some_store
+
     admin_interfaces:
       - driver:
           type: websocket
@@ -295,11 +342,16 @@ mod tests {
                     instance_id: "some_id".into(),
                     init_params: "some_params".into()
                 }),
+                app_store: Some(AppStoreConfig {
+                    installed_app_id: "some_store".into()
+                }),
                 keystore: KeystoreConfig::LairServerInProc { lair_root: None },
                 admin_interfaces: Some(vec![AdminInterfaceConfig {
                     driver: InterfaceDriver::Websocket {
                         port: 1234,
-                        allowed_origins: AllowedOrigins::Any
+                        allowed_origins: AllowedOrigins::Any,
+                        bind_address: None,
+                        tls: None,
                     }
                 }]),
                 network: network_config,
@@ -329,6 +381,7 @@ mod tests {
                 data_root_path: Some(PathBuf::from("/path/to/env").into()),
                 network: Default::default(),
                 dpki: None,
+                app_store: None,
                 keystore: KeystoreConfig::LairServer {
                     connection_url: url2::url2!("unix:///var/run/lair-keystore/socket?k=EcRDnP3xDIZ9Rk_1E-egPE0mGZi5CcszeRxVkb2QXXQ"),
                 },
@@ -340,4 +393,30 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn test_config_unix_socket_admin_interface() {
+        let yaml = r#"---
+    data_root_path: /path/to/env
+
+    keystore:
+      type: danger_test_keystore
+
+    admin_interfaces:
+      - driver:
+          type: unix_socket
+          path: /var/run/holochain/admin.sock
+          mode: 0o600
+    "#;
+        let result: ConductorConfig = config_from_yaml(yaml).unwrap();
+        assert_eq!(
+            result.admin_interfaces,
+            Some(vec![AdminInterfaceConfig {
+                driver: InterfaceDriver::UnixSocket {
+                    path: PathBuf::from("/var/run/holochain/admin.sock"),
+                    mode: Some(0o600),
+                }
+            }])
+        );
+    }
 }
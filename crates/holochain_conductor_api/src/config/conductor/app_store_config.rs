@@ -0,0 +1,12 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Configure which installed app runs the app store service.
+///
+/// The app must have exactly one provisioned cell, whose DNA serves bundles
+/// to the built-in app store service.
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq)]
+pub struct AppStoreConfig {
+    /// The id of the installed app which runs the app store DNA
+    pub installed_app_id: String,
+}
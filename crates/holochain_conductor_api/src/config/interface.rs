@@ -1,12 +1,16 @@
 use holochain_types::websocket::AllowedOrigins;
 use serde::Deserialize;
 use serde::Serialize;
+use std::net::IpAddr;
+use std::net::Ipv4Addr;
+use std::path::PathBuf;
 
 /// Information neeeded to spawn an admin interface
 #[derive(Clone, Deserialize, Serialize, Debug, PartialEq)]
 pub struct AdminInterfaceConfig {
     /// By what means the interface will be exposed.
-    /// Currently the only option is a local websocket running on a configurable port.
+    /// Either a local websocket running on a configurable port,
+    /// or a unix domain socket at a configurable path.
     pub driver: InterfaceDriver,
 }
 
@@ -36,23 +40,90 @@ pub enum InterfaceDriver {
         ///
         /// Connections from any origin which is not permitted by this config will be rejected.
         allowed_origins: AllowedOrigins,
+
+        /// The address to bind to.
+        ///
+        /// Defaults to `127.0.0.1`, so that the interface can only be reached from the
+        /// same host. Binding to another address, e.g. `0.0.0.0` to reach the interface
+        /// from another container, should be combined with `tls`.
+        #[serde(default)]
+        bind_address: Option<IpAddr>,
+
+        /// If set, the interface serves `wss://` with this certificate.
+        #[serde(default)]
+        tls: Option<InterfaceTlsConfig>,
+    },
+
+    /// An interface implemented via websockets over a unix domain socket.
+    ///
+    /// Access to the interface is governed by the file system permissions of
+    /// the socket file, rather than by origins. Only available on unix platforms.
+    UnixSocket {
+        /// The path of the socket file.
+        ///
+        /// A stale socket file at this path is replaced when the interface starts,
+        /// and the file is removed when the interface stops.
+        path: PathBuf,
+
+        /// The permissions to set on the socket file, e.g. `0o600` to only allow
+        /// the user running the conductor to connect.
+        ///
+        /// If not set, the permissions are `0o600`.
+        #[serde(default)]
+        mode: Option<u32>,
     },
 }
 
+/// The certificate with which a websocket interface serves `wss://`.
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq)]
+pub struct InterfaceTlsConfig {
+    /// The path of a PEM file containing the certificate chain.
+    pub cert_path: PathBuf,
+
+    /// The path of a PEM file containing the private key of the certificate.
+    pub key_path: PathBuf,
+}
+
+/// The address interfaces bind to unless another is configured.
+pub const DEFAULT_INTERFACE_BIND_ADDRESS: IpAddr = IpAddr::V4(Ipv4Addr::LOCALHOST);
+
 impl InterfaceDriver {
-    /// Get the port for this driver.
-    pub fn port(&self) -> u16 {
+    /// Get the port for this driver, if it listens on a port.
+    pub fn port(&self) -> Option<u16> {
         match self {
-            InterfaceDriver::Websocket { port, .. } => *port,
+            InterfaceDriver::Websocket { port, .. } => Some(*port),
+            InterfaceDriver::UnixSocket { .. } => None,
         }
     }
 
     /// Get the allowed origins for this driver.
+    ///
+    /// A unix socket can't be connected to from a browser, so it allows any origin.
     pub fn allowed_origins(&self) -> &AllowedOrigins {
+        static ANY: AllowedOrigins = AllowedOrigins::Any;
         match self {
             InterfaceDriver::Websocket {
                 allowed_origins, ..
             } => allowed_origins,
+            InterfaceDriver::UnixSocket { .. } => &ANY,
+        }
+    }
+
+    /// Get the address to bind to, if this driver listens on a port.
+    pub fn bind_address(&self) -> Option<IpAddr> {
+        match self {
+            InterfaceDriver::Websocket { bind_address, .. } => {
+                Some(bind_address.unwrap_or(DEFAULT_INTERFACE_BIND_ADDRESS))
+            }
+            InterfaceDriver::UnixSocket { .. } => None,
+        }
+    }
+
+    /// Get the path of the socket file, if this is a unix socket driver.
+    pub fn path(&self) -> Option<&PathBuf> {
+        match self {
+            InterfaceDriver::Websocket { .. } => None,
+            InterfaceDriver::UnixSocket { path, .. } => Some(path),
         }
     }
 }
@@ -1,8 +1,11 @@
 use holochain_serialized_bytes::prelude::*;
 use holochain_types::app::InstalledAppId;
+use holochain_types::signal::Signal;
 use holochain_zome_types::cell::CellId;
+use holochain_zome_types::prelude::ZomeName;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::collections::HashSet;
 
 /// Declares updated Signal subscription settings for an App.
 /// This message is part of the AppInterfaceApi
@@ -14,6 +17,31 @@ pub struct SignalSubscription {
     filters: SignalFilterSet,
 }
 
+impl SignalSubscription {
+    /// Constructor
+    pub fn new(installed_app_id: InstalledAppId, filters: SignalFilterSet) -> Self {
+        Self {
+            installed_app_id,
+            filters,
+        }
+    }
+
+    /// The app for which the subscription is managed
+    pub fn installed_app_id(&self) -> &InstalledAppId {
+        &self.installed_app_id
+    }
+
+    /// The per-cell filters of this subscription
+    pub fn filters(&self) -> &SignalFilterSet {
+        &self.filters
+    }
+
+    /// Break this subscription into its parts
+    pub fn into_inner(self) -> (InstalledAppId, SignalFilterSet) {
+        (self.installed_app_id, self.filters)
+    }
+}
+
 /// Associate a SignalFilter with each Cell in an App.
 /// The filtering can be interpreted as inclusive or exclusive,
 /// depending on the use case.
@@ -47,22 +75,251 @@ impl SignalFilterSet {
     pub fn block_all() -> Self {
         SignalFilterSet::Include(HashMap::new())
     }
+
+    /// Check whether a signal emitted by one of the app's cells passes this
+    /// filter set.
+    ///
+    /// Signals which are not attributable to a cell, like most system signals,
+    /// are only matched by filters which explicitly select
+    /// [`SignalKind::System`], whichever cell they are set for: an Include set
+    /// lets them through if such a filter matches, and an Exclude set blocks
+    /// them if such a filter matches. So excluding everything from a cell
+    /// does not exclude system signals, and they must be included explicitly.
+    pub fn allows(&self, signal: &Signal) -> bool {
+        let system_match = |filters: &HashMap<CellId, SignalFilter>| {
+            filters
+                .values()
+                .any(|f| f.selects_system() && f.matches(signal))
+        };
+        match (self, signal.cell_id()) {
+            (Self::Include(filters), Some(cell_id)) => filters
+                .get(cell_id)
+                .map(|filter| filter.matches(signal))
+                .unwrap_or(false),
+            (Self::Exclude(filters), Some(cell_id)) => !filters
+                .get(cell_id)
+                .map(|filter| filter.matches(signal))
+                .unwrap_or(false),
+            (Self::Include(filters), None) => system_match(filters),
+            (Self::Exclude(filters), None) => !system_match(filters),
+        }
+    }
 }
 
-/// Specifies fine-grained filter controls for the signals
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, SerializedBytes)]
-pub struct SignalFilter;
+/// The broad category of a [`Signal`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignalKind {
+    /// A signal emitted by a zome via `emit_signal`
+    App,
+    /// A signal emitted by the Holochain system
+    System,
+}
 
-impl Default for SignalFilter {
-    fn default() -> Self {
-        // The default is no filter
-        Self::empty()
+impl From<&Signal> for SignalKind {
+    fn from(signal: &Signal) -> Self {
+        match signal {
+            Signal::App { .. } => Self::App,
+            Signal::System(_) => Self::System,
+        }
     }
 }
 
+/// Specifies fine-grained filter controls for the signals.
+///
+/// A filter matches a signal when every criterion which is set matches.
+/// Criteria which are `None` match any signal, so the [`SignalFilter::empty`]
+/// filter matches everything.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, SerializedBytes)]
+pub struct SignalFilter {
+    /// Only match signals of these kinds
+    #[serde(default)]
+    pub kinds: Option<HashSet<SignalKind>>,
+    /// Only match app signals emitted by these zomes.
+    /// System signals never match a filter which sets this.
+    #[serde(default)]
+    pub zome_names: Option<HashSet<ZomeName>>,
+    /// Only match signals with these variant names,
+    /// see [`Signal::variant_name`]
+    #[serde(default)]
+    pub variants: Option<HashSet<String>>,
+}
+
 impl SignalFilter {
     /// A passthrough filter which filters nothing
     pub fn empty() -> Self {
-        SignalFilter
+        Self::default()
+    }
+
+    /// Only match signals of the given kind
+    pub fn with_kind(mut self, kind: SignalKind) -> Self {
+        self.kinds.get_or_insert_with(HashSet::new).insert(kind);
+        self
+    }
+
+    /// Only match app signals emitted by the given zome
+    pub fn with_zome_name(mut self, zome_name: impl Into<ZomeName>) -> Self {
+        self.zome_names
+            .get_or_insert_with(HashSet::new)
+            .insert(zome_name.into());
+        self
+    }
+
+    /// Only match signals with the given variant name
+    pub fn with_variant(mut self, variant: impl Into<String>) -> Self {
+        self.variants
+            .get_or_insert_with(HashSet::new)
+            .insert(variant.into());
+        self
+    }
+
+    /// Whether this filter explicitly selects system signals
+    fn selects_system(&self) -> bool {
+        self.kinds
+            .as_ref()
+            .map(|kinds| kinds.contains(&SignalKind::System))
+            .unwrap_or(false)
+    }
+
+    /// Check whether the signal matches all criteria of this filter
+    pub fn matches(&self, signal: &Signal) -> bool {
+        if let Some(kinds) = &self.kinds {
+            if !kinds.contains(&SignalKind::from(signal)) {
+                return false;
+            }
+        }
+        if let Some(zome_names) = &self.zome_names {
+            match signal {
+                Signal::App { zome_name, .. } if zome_names.contains(zome_name) => (),
+                _ => return false,
+            }
+        }
+        if let Some(variants) = &self.variants {
+            match signal.variant_name() {
+                Some(variant) if variants.contains(&variant) => (),
+                _ => return false,
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use holo_hash::AgentPubKey;
+    use holo_hash::DnaHash;
+    use holochain_types::signal::SystemSignal;
+    use holochain_zome_types::prelude::*;
+
+    #[derive(Serialize, Deserialize, Debug)]
+    #[serde(tag = "type")]
+    enum TestSignal {
+        Created,
+        Deleted,
+    }
+
+    fn fake_cell_id(n: u8) -> CellId {
+        CellId::new(
+            DnaHash::from_raw_36(vec![n; 36]),
+            AgentPubKey::from_raw_36(vec![n; 36]),
+        )
+    }
+
+    fn app_signal(cell_id: &CellId, zome_name: &str, signal: TestSignal) -> Signal {
+        Signal::App {
+            cell_id: cell_id.clone(),
+            zome_name: zome_name.into(),
+            signal: AppSignal::new(ExternIO::encode(signal).unwrap()),
+        }
+    }
+
+    #[test]
+    fn filter_matches_criteria() {
+        let cell_id = fake_cell_id(1);
+        let created = app_signal(&cell_id, "posts", TestSignal::Created);
+        let deleted = app_signal(&cell_id, "comments", TestSignal::Deleted);
+        let system = Signal::System(SystemSignal::Test("test".into()));
+
+        assert!(SignalFilter::empty().matches(&created));
+        assert!(SignalFilter::empty().matches(&system));
+
+        let by_kind = SignalFilter::empty().with_kind(SignalKind::System);
+        assert!(!by_kind.matches(&created));
+        assert!(by_kind.matches(&system));
+
+        let by_zome = SignalFilter::empty().with_zome_name("posts");
+        assert!(by_zome.matches(&created));
+        assert!(!by_zome.matches(&deleted));
+        assert!(!by_zome.matches(&system));
+
+        let by_variant = SignalFilter::empty()
+            .with_variant("Deleted")
+            .with_variant("Test");
+        assert!(!by_variant.matches(&created));
+        assert!(by_variant.matches(&deleted));
+        assert!(by_variant.matches(&system));
+    }
+
+    #[test]
+    fn filter_set_include_and_exclude() {
+        let cell_1 = fake_cell_id(1);
+        let cell_2 = fake_cell_id(2);
+        let from_1 = app_signal(&cell_1, "posts", TestSignal::Created);
+        let from_2 = app_signal(&cell_2, "posts", TestSignal::Created);
+        let system = Signal::System(SystemSignal::Test("test".into()));
+
+        assert!(SignalFilterSet::allow_all().allows(&from_1));
+        assert!(SignalFilterSet::allow_all().allows(&system));
+        assert!(!SignalFilterSet::block_all().allows(&from_1));
+        assert!(!SignalFilterSet::block_all().allows(&system));
+
+        let include = SignalFilterSet::Include(
+            [(
+                cell_1.clone(),
+                SignalFilter::empty().with_kind(SignalKind::App),
+            )]
+            .into(),
+        );
+        assert!(include.allows(&from_1));
+        assert!(!include.allows(&from_2));
+        assert!(!include.allows(&system));
+
+        let exclude = SignalFilterSet::Exclude([(cell_1.clone(), SignalFilter::empty())].into());
+        assert!(!exclude.allows(&from_1));
+        assert!(exclude.allows(&from_2));
+        assert!(exclude.allows(&system));
+    }
+
+    #[test]
+    fn filter_set_system_signals() {
+        let cell_1 = fake_cell_id(1);
+        let from_1 = app_signal(&cell_1, "posts", TestSignal::Created);
+        let system = Signal::System(SystemSignal::Test("test".into()));
+        let countersigning = Signal::System(SystemSignal::SuccessfulCountersigning(
+            EntryHash::from_raw_36(vec![0; 36]),
+        ));
+        let only_system = SignalFilter::empty().with_kind(SignalKind::System);
+
+        // Filters which do not select system signals never match them
+        let include = SignalFilterSet::Include([(cell_1.clone(), SignalFilter::empty())].into());
+        assert!(include.allows(&from_1));
+        assert!(!include.allows(&system));
+
+        let include = SignalFilterSet::Include([(cell_1.clone(), only_system.clone())].into());
+        assert!(!include.allows(&from_1));
+        assert!(include.allows(&system));
+        assert!(include.allows(&countersigning));
+
+        let exclude = SignalFilterSet::Exclude([(cell_1.clone(), only_system.clone())].into());
+        assert!(exclude.allows(&from_1));
+        assert!(!exclude.allows(&system));
+        assert!(!exclude.allows(&countersigning));
+
+        let exclude = SignalFilterSet::Exclude(
+            [(cell_1, only_system.with_variant("SuccessfulCountersigning"))].into(),
+        );
+        assert!(exclude.allows(&system));
+        assert!(!exclude.allows(&countersigning));
     }
 }
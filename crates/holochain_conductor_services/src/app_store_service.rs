@@ -9,10 +9,16 @@ use crate::CellRunner;
 #[mockall::automock]
 #[allow(clippy::needless_lifetimes)]
 pub trait AppStoreService: Send + Sync {
-    /// Fetch a DNA bundle from the store
+    /// Fetch a DNA bundle from the store.
+    ///
+    /// A bundle is only returned if the hash of its DNA, without any modifiers
+    /// applied, is the requested hash.
     async fn get_dna_bundle(&self, dna_hash: DnaHash) -> AppStoreServiceResult<Option<DnaBundle>>;
 
-    /// Fetch an app bundle from the store
+    /// Fetch an app bundle from the store.
+    ///
+    /// A bundle is only returned if its hash, as computed by [`AppHash::from_bundle_bytes`],
+    /// is the requested hash.
     async fn get_app_bundle(&self, app_hash: AppHash) -> AppStoreServiceResult<Option<AppBundle>>;
 
     /// The CellIds in use by this service, which need to be protected
@@ -21,42 +27,120 @@ pub trait AppStoreService: Send + Sync {
 
 /// The errors which can be produced by the AppStoreService
 #[derive(thiserror::Error, Debug)]
-pub enum AppStoreServiceError {}
+#[allow(missing_docs)]
+pub enum AppStoreServiceError {
+    #[error("AppStore DNA could not be called: {0}")]
+    ZomeCallFailed(anyhow::Error),
+
+    #[error("AppStore DNA returned an unexpected response: {0}")]
+    BadResponse(#[from] SerializedBytesError),
+
+    #[error("AppStore DNA returned an invalid DNA bundle: {0}")]
+    InvalidDnaBundle(#[from] DnaError),
+
+    #[error("AppStore DNA returned an invalid app bundle: {0}")]
+    InvalidAppBundle(#[from] AppBundleError),
+
+    #[error("AppStore DNA returned an app bundle with hash {actual} for hash {expected}")]
+    AppHashMismatch { expected: AppHash, actual: AppHash },
+
+    #[error("AppStore DNA returned a DNA bundle with hash {actual} for hash {expected}")]
+    DnaHashMismatch { expected: DnaHash, actual: DnaHash },
+}
 /// Alias
 pub type AppStoreServiceResult<T> = Result<T, AppStoreServiceError>;
 
-/// This doesn't exist yet. We need to define it.
-pub enum AppHash {}
+/// The zome of the app store DNA which serves bundles
+pub const APP_STORE_ZOME_NAME: &str = "app_store";
 
 /// The built-in implementation of the app store service, which runs a DNA
+///
+/// The DNA is expected to expose two functions in its [`APP_STORE_ZOME_NAME`] zome,
+/// `get_dna_bundle` and `get_app_bundle`, which take a [`DnaHash`] and an [`AppHash`]
+/// respectively, and return the encoded bundle as `Option<Vec<u8>>`.
+/// App bundles whose bytes don't hash to the requested [`AppHash`], and DNA bundles
+/// whose DNA doesn't hash to the requested [`DnaHash`], are rejected.
 pub struct AppStoreBuiltin {
-    _runner: Arc<dyn CellRunner>,
-    _cell_id: CellId,
+    runner: Arc<dyn CellRunner>,
+    cell_id: CellId,
 }
 
 impl AppStoreBuiltin {
     /// Constructor
     pub fn new(runner: Arc<impl CellRunner>, cell_id: CellId) -> Arc<Self> {
-        Arc::new(Self {
-            _runner: runner,
-            _cell_id: cell_id,
-        })
+        Arc::new(Self { runner, cell_id })
+    }
+
+    /// Call a function of the app store zome, returning the encoded bundle, if any
+    async fn fetch_bundle<I>(
+        &self,
+        fn_name: &str,
+        input: I,
+    ) -> AppStoreServiceResult<Option<Vec<u8>>>
+    where
+        I: serde::Serialize + std::fmt::Debug,
+    {
+        let cell_id = self.cell_id.clone();
+        let provenance = cell_id.agent_pubkey().clone();
+        let payload = ExternIO::encode(input)?;
+        let response = self
+            .runner
+            .call_zome(
+                &provenance,
+                None,
+                cell_id,
+                APP_STORE_ZOME_NAME.into(),
+                fn_name.into(),
+                payload,
+            )
+            .await
+            .map_err(AppStoreServiceError::ZomeCallFailed)?;
+        Ok(response.decode()?)
     }
 }
 
 #[async_trait::async_trait]
 #[allow(clippy::needless_lifetimes)]
 impl AppStoreService for AppStoreBuiltin {
-    async fn get_dna_bundle(&self, _dna_hash: DnaHash) -> AppStoreServiceResult<Option<DnaBundle>> {
-        todo!("placeholder")
+    async fn get_dna_bundle(&self, dna_hash: DnaHash) -> AppStoreServiceResult<Option<DnaBundle>> {
+        let bytes = match self
+            .fetch_bundle("get_dna_bundle", dna_hash.clone())
+            .await?
+        {
+            Some(bytes) => bytes,
+            None => return Ok(None),
+        };
+        let (dna_file, _) = DnaBundle::decode(&bytes)?
+            .into_dna_file(DnaModifiersOpt::none())
+            .await?;
+        let actual = dna_file.dna_hash().clone();
+        if actual != dna_hash {
+            return Err(AppStoreServiceError::DnaHashMismatch {
+                expected: dna_hash,
+                actual,
+            });
+        }
+        Ok(Some(DnaBundle::decode(&bytes)?))
     }
 
-    async fn get_app_bundle(&self, _app_hash: AppHash) -> AppStoreServiceResult<Option<AppBundle>> {
-        todo!("placeholder")
+    async fn get_app_bundle(&self, app_hash: AppHash) -> AppStoreServiceResult<Option<AppBundle>> {
+        self.fetch_bundle("get_app_bundle", app_hash.clone())
+            .await?
+            .map(|bytes| {
+                let actual = AppHash::from_bundle_bytes(&bytes);
+                if actual != app_hash {
+                    return Err(AppStoreServiceError::AppHashMismatch {
+                        expected: app_hash,
+                        actual,
+                    });
+                }
+                Ok(AppBundle::decode(&bytes)?)
+            })
+            .transpose()
     }
 
     fn cell_ids<'a>(&'a self) -> std::collections::HashSet<&'a CellId> {
-        [&self._cell_id].into_iter().collect()
+        [&self.cell_id].into_iter().collect()
     }
 }
 
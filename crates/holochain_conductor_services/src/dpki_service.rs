@@ -14,6 +14,14 @@ pub trait DpkiService: Send + Sync {
     async fn is_key_valid(&self, key: AgentPubKey, timestamp: Timestamp)
         -> DpkiServiceResult<bool>;
 
+    /// Look up the state of the key as-at the given Timestamp, which tells a key
+    /// that was never registered apart from one that was replaced or revoked
+    async fn key_state(
+        &self,
+        key: AgentPubKey,
+        timestamp: Timestamp,
+    ) -> DpkiServiceResult<KeyState>;
+
     /// Defines the different ways that keys can be created and destroyed:
     /// If an old key is specified, it will be destroyed
     /// If a new key is specified, it will be registered
@@ -35,6 +43,12 @@ pub trait DpkiService: Send + Sync {
 pub enum DpkiServiceError {
     #[error("DPKI DNA could not be called: {0}")]
     ZomeCallFailed(anyhow::Error),
+
+    #[error("DPKI DNA returned an unexpected response: {0}")]
+    BadResponse(#[from] SerializedBytesError),
+
+    #[error("Could not sign with the new key: {0}")]
+    SigningFailed(anyhow::Error),
 }
 /// Alias
 pub type DpkiServiceResult<T> = Result<T, DpkiServiceError>;
@@ -63,7 +77,44 @@ pub trait DpkiServiceExt: DpkiService {
     }
 }
 
+/// The zome of the DPKI DNA which manages keys
+pub const DPKI_ZOME_NAME: &str = "deepkey";
+
+/// Input to the `key_state` function of the DPKI DNA
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct KeyStateInput {
+    /// The key to look up
+    pub key: AgentPubKey,
+    /// The time at which the state of the key is wanted
+    pub timestamp: Timestamp,
+}
+
+/// The state of a key at some point in time, as returned by the DPKI DNA
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum KeyState {
+    /// The key was never registered
+    NotFound,
+    /// The key was registered and not yet replaced or revoked
+    Valid,
+    /// The key was replaced or revoked before the given time
+    Invalidated,
+}
+
+/// Input to the `key_mutation` function of the DPKI DNA
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct KeyMutationInput {
+    /// The key to replace or revoke, if any
+    pub old_key: Option<AgentPubKey>,
+    /// The key to register, if any, along with its signature of the
+    /// DPKI cell's agent key, which proves that the conductor holds the new key
+    pub new_key: Option<(AgentPubKey, Signature)>,
+}
+
 /// The built-in implementation of the DPKI service contract, which runs a DNA
+///
+/// The DNA is expected to expose the `key_state` and `key_mutation` functions
+/// in its [`DPKI_ZOME_NAME`] zome, taking a [`KeyStateInput`] and a [`KeyMutationInput`]
+/// and returning a [`KeyState`] and `()` respectively.
 #[derive(derive_more::Constructor)]
 pub struct DeepkeyBuiltin {
     runner: Arc<dyn CellRunner>,
@@ -71,37 +122,49 @@ pub struct DeepkeyBuiltin {
     cell_id: CellId,
 }
 
-#[allow(unreachable_code)]
-#[allow(unused_variables)]
-#[allow(clippy::needless_lifetimes)]
-#[async_trait::async_trait]
-impl DpkiService for DeepkeyBuiltin {
-    async fn is_key_valid(
-        &self,
-        key: AgentPubKey,
-        timestamp: Timestamp,
-    ) -> DpkiServiceResult<bool> {
-        let keystore = self.keystore.clone();
+impl DeepkeyBuiltin {
+    async fn call_deepkey<I, O>(&self, fn_name: &str, input: I) -> DpkiServiceResult<O>
+    where
+        I: serde::Serialize + std::fmt::Debug,
+        O: serde::de::DeserializeOwned + std::fmt::Debug,
+    {
         let cell_id = self.cell_id.clone();
-        let zome_name: ZomeName = "TODO: depends on dna implementation".into();
-        let fn_name: FunctionName = "TODO: depends on dna implementation".into();
-        let payload = todo!("TODO: depends on dna implementation");
-        let cap_secret = None;
         let provenance = cell_id.agent_pubkey().clone();
+        let payload = ExternIO::encode(input)?;
         let response = self
             .runner
             .call_zome(
                 &provenance,
-                cap_secret,
+                None,
                 cell_id,
-                zome_name,
-                fn_name,
+                DPKI_ZOME_NAME.into(),
+                fn_name.into(),
                 payload,
             )
             .await
             .map_err(DpkiServiceError::ZomeCallFailed)?;
-        let is_valid = todo!("deserialize response");
-        Ok(is_valid)
+        Ok(response.decode()?)
+    }
+}
+
+#[allow(clippy::needless_lifetimes)]
+#[async_trait::async_trait]
+impl DpkiService for DeepkeyBuiltin {
+    async fn is_key_valid(
+        &self,
+        key: AgentPubKey,
+        timestamp: Timestamp,
+    ) -> DpkiServiceResult<bool> {
+        Ok(self.key_state(key, timestamp).await? == KeyState::Valid)
+    }
+
+    async fn key_state(
+        &self,
+        key: AgentPubKey,
+        timestamp: Timestamp,
+    ) -> DpkiServiceResult<KeyState> {
+        self.call_deepkey("key_state", KeyStateInput { key, timestamp })
+            .await
     }
 
     async fn key_mutation(
@@ -109,7 +172,22 @@ impl DpkiService for DeepkeyBuiltin {
         old_key: Option<AgentPubKey>,
         new_key: Option<AgentPubKey>,
     ) -> DpkiServiceResult<()> {
-        todo!()
+        if old_key.is_none() && new_key.is_none() {
+            return Ok(());
+        }
+        let new_key = match new_key {
+            Some(key) => {
+                let data: Arc<[u8]> = self.cell_id.agent_pubkey().get_raw_39().into();
+                let signature = key
+                    .sign_raw(&self.keystore, data)
+                    .await
+                    .map_err(|e| DpkiServiceError::SigningFailed(e.into()))?;
+                Some((key, signature))
+            }
+            None => None,
+        };
+        self.call_deepkey("key_mutation", KeyMutationInput { old_key, new_key })
+            .await
     }
 
     fn cell_ids<'a>(&'a self) -> std::collections::HashSet<&'a CellId> {
@@ -123,6 +201,8 @@ pub fn mock_dpki() -> MockDpkiService {
     let mut dpki = MockDpkiService::new();
     dpki.expect_is_key_valid()
         .returning(|_, _| async move { Ok(true) }.boxed());
+    dpki.expect_key_state()
+        .returning(|_, _| async move { Ok(KeyState::Valid) }.boxed());
     dpki.expect_cell_ids()
         .return_const(std::collections::HashSet::new());
     dpki
@@ -31,10 +31,10 @@ pub trait CellRunner: Send + Sync + 'static {
 /// The set of all Conductor Services available to the conductor
 #[derive(Clone)]
 pub struct ConductorServices {
-    /// The DPKI service
-    pub dpki: Arc<dyn DpkiService>,
-    /// The AppStore service
-    pub app_store: Arc<dyn AppStoreService>,
+    /// The DPKI service, if one is configured
+    pub dpki: Option<Arc<dyn DpkiService>>,
+    /// The AppStore service, if one is configured
+    pub app_store: Option<Arc<dyn AppStoreService>>,
 }
 
 impl ConductorServices {
@@ -45,25 +45,33 @@ impl ConductorServices {
         cell_ids: ConductorServiceCells,
     ) -> Self {
         Self {
-            dpki: Arc::new(DeepkeyBuiltin::new(runner.clone(), keystore, cell_ids.dpki)),
-            app_store: AppStoreBuiltin::new(runner, cell_ids.app_store),
+            dpki: cell_ids.dpki.map(|cell_id| {
+                Arc::new(DeepkeyBuiltin::new(runner.clone(), keystore, cell_id))
+                    as Arc<dyn DpkiService>
+            }),
+            app_store: cell_ids
+                .app_store
+                .map(|cell_id| AppStoreBuiltin::new(runner, cell_id) as Arc<dyn AppStoreService>),
         }
     }
 
     /// Get the list of any CellIds which may be protected due to being in use by ConductorServices
     pub fn protected_cell_ids(&self) -> HashSet<&CellId> {
-        self.dpki
-            .cell_ids()
-            .union(&self.app_store.cell_ids())
-            .copied()
-            .collect()
+        let mut cell_ids = std::collections::HashSet::new();
+        if let Some(dpki) = &self.dpki {
+            cell_ids.extend(dpki.cell_ids());
+        }
+        if let Some(app_store) = &self.app_store {
+            cell_ids.extend(app_store.cell_ids());
+        }
+        cell_ids
     }
 }
 
 /// Initialized for ConductorService: just the CellIds that are used for each service
 pub struct ConductorServiceCells {
-    /// The CellId to use for DPKI
-    pub dpki: CellId,
-    /// The CellId to use for the AppStore
-    pub app_store: CellId,
+    /// The CellId to use for DPKI, if there is one
+    pub dpki: Option<CellId>,
+    /// The CellId to use for the AppStore, if there is one
+    pub app_store: Option<CellId>,
 }
@@ -1,7 +1,10 @@
 //! Rate limiting data types
 
+use std::collections::BTreeMap;
+
 use holochain_serialized_bytes::prelude::*;
 
+use crate::timestamp::Timestamp;
 use crate::{Create, CreateLink, Delete, Entry, Update};
 
 /// Input to the `weigh` callback. Includes an "unweighed" action, and Entry
@@ -108,3 +111,286 @@ impl From<EntryRateWeight> for RateWeight {
         }
     }
 }
+
+/// The limit on one rate limiting bucket, declared by the DNA.
+///
+/// Buckets are "leaky": every action weighed into the bucket fills it by the
+/// action's [`RateUnits`], and every `drain_interval_ms` the bucket drains by
+/// `drain_amount`. An agent whose actions fill a bucket beyond its `capacity`
+/// has exceeded the rate limit, and its actions will be rejected.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    serde::Serialize,
+    serde::Deserialize,
+    PartialEq,
+    Eq,
+    SerializedBytes,
+    Hash,
+    PartialOrd,
+    Ord,
+)]
+#[cfg_attr(
+    feature = "fuzzing",
+    derive(arbitrary::Arbitrary, proptest_derive::Arbitrary)
+)]
+pub struct RateLimit {
+    /// The most units the bucket can hold.
+    pub capacity: RateBucketCapacity,
+    /// The number of units which drain from the bucket every interval.
+    pub drain_amount: RateBucketCapacity,
+    /// The length of the drain interval in milliseconds.
+    pub drain_interval_ms: u64,
+}
+
+impl RateLimit {
+    /// The time it takes for a full bucket to drain completely, in milliseconds.
+    /// Actions older than this can no longer affect the level of the bucket.
+    pub fn full_drain_ms(&self) -> u64 {
+        if self.drain_amount == 0 {
+            return u64::MAX;
+        }
+        let capacity = self.capacity as u64;
+        let drain_amount = self.drain_amount as u64;
+        let intervals = capacity / drain_amount + u64::from(capacity % drain_amount != 0);
+        intervals.saturating_mul(self.drain_interval_ms)
+    }
+}
+
+/// The rate limits declared by a DNA, by bucket.
+///
+/// Actions weighed into a bucket which has no declared limit are not limited.
+pub type RateLimits = BTreeMap<RateBucketId, RateLimit>;
+
+/// The error produced when a [`RateBucket`] can't accept a change.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum RateBucketError {
+    /// The change would fill the bucket beyond its capacity.
+    BucketOverflow {
+        /// The bucket which overflowed
+        bucket_id: RateBucketId,
+        /// The level the bucket would have reached
+        level: RateBucketCapacity,
+        /// The capacity of the bucket
+        capacity: RateBucketCapacity,
+    },
+    /// The change happened before the last change to the bucket.
+    /// Changes must be applied in chain order, whose timestamps only increase.
+    NonMonotonicTimestamp(Timestamp, Timestamp),
+}
+
+impl std::error::Error for RateBucketError {}
+
+impl core::fmt::Display for RateBucketError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RateBucketError::BucketOverflow {
+                bucket_id,
+                level,
+                capacity,
+            } => write!(
+                f,
+                "Rate limit bucket {} overflowed: level {} exceeds capacity {}",
+                bucket_id, level, capacity
+            ),
+            RateBucketError::NonMonotonicTimestamp(last, next) => write!(
+                f,
+                "Rate limit bucket was changed at {:?}, which is before its last change at {:?}",
+                next, last
+            ),
+        }
+    }
+}
+
+/// A leaky bucket which tracks how full one [`RateLimit`] is over a sequence
+/// of actions by the same author.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateBucket {
+    bucket_id: RateBucketId,
+    limit: RateLimit,
+    level: RateBucketCapacity,
+    last_change: Option<Timestamp>,
+    last_drain: Option<Timestamp>,
+}
+
+impl RateBucket {
+    /// An empty bucket for the given limit.
+    pub fn new(bucket_id: RateBucketId, limit: RateLimit) -> Self {
+        Self {
+            bucket_id,
+            limit,
+            level: 0,
+            last_change: None,
+            last_drain: None,
+        }
+    }
+
+    /// The current level of the bucket, as of its last change.
+    pub fn level(&self) -> RateBucketCapacity {
+        self.level
+    }
+
+    /// Drain the bucket for the whole intervals elapsed since it last drained, then fill
+    /// it with the given units. Returns the new level of the bucket.
+    pub fn change(
+        &mut self,
+        units: RateUnits,
+        timestamp: Timestamp,
+    ) -> Result<RateBucketCapacity, RateBucketError> {
+        if let Some(last_change) = self.last_change {
+            if timestamp < last_change {
+                return Err(RateBucketError::NonMonotonicTimestamp(
+                    last_change,
+                    timestamp,
+                ));
+            }
+        }
+        match self.last_drain {
+            Some(last_drain) if self.limit.drain_interval_ms > 0 => {
+                // Only whole intervals drain, so the remainder carries over
+                // to the next change rather than being lost.
+                let elapsed_ms = (timestamp.as_micros() - last_drain.as_micros()) as u64 / 1000;
+                let intervals = elapsed_ms / self.limit.drain_interval_ms;
+                let drained = intervals.saturating_mul(self.limit.drain_amount as u64);
+                self.level = (self.level as u64).saturating_sub(drained) as RateBucketCapacity;
+                if self.level == 0 {
+                    self.last_drain = Some(timestamp);
+                } else {
+                    let drained_micros = intervals
+                        .saturating_mul(self.limit.drain_interval_ms)
+                        .saturating_mul(1000) as i64;
+                    self.last_drain = Some(Timestamp::from_micros(
+                        last_drain.as_micros().saturating_add(drained_micros),
+                    ));
+                }
+            }
+            // A zero interval drains continuously.
+            Some(_) => {
+                self.level = 0;
+                self.last_drain = Some(timestamp);
+            }
+            None => self.last_drain = Some(timestamp),
+        }
+        self.last_change = Some(timestamp);
+
+        let level = self.level.saturating_add(units as RateBucketCapacity);
+        if level > self.limit.capacity {
+            return Err(RateBucketError::BucketOverflow {
+                bucket_id: self.bucket_id,
+                level,
+                capacity: self.limit.capacity,
+            });
+        }
+        self.level = level;
+        Ok(level)
+    }
+}
+
+/// The [`RateBucket`]s for every [`RateLimit`] declared by a DNA.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateBuckets(BTreeMap<RateBucketId, RateBucket>);
+
+impl RateBuckets {
+    /// Empty buckets for all of the given limits.
+    pub fn new(limits: &RateLimits) -> Self {
+        Self(
+            limits
+                .iter()
+                .map(|(bucket_id, limit)| (*bucket_id, RateBucket::new(*bucket_id, *limit)))
+                .collect(),
+        )
+    }
+
+    /// Weigh an action into its bucket.
+    /// Weights for buckets without a declared limit are ignored.
+    pub fn change(
+        &mut self,
+        weight: &RateWeight,
+        timestamp: Timestamp,
+    ) -> Result<(), RateBucketError> {
+        match self.0.get_mut(&weight.bucket_id) {
+            Some(bucket) => bucket.change(weight.units, timestamp).map(|_| ()),
+            None => Ok(()),
+        }
+    }
+
+    /// The longest time any bucket takes to drain completely, in milliseconds.
+    pub fn full_drain_ms(&self) -> u64 {
+        self.0
+            .values()
+            .map(|bucket| bucket.limit.full_drain_ms())
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at_ms(ms: i64) -> Timestamp {
+        Timestamp::from_micros(ms * 1000)
+    }
+
+    #[test]
+    fn bucket_fills_and_drains() {
+        let limit = RateLimit {
+            capacity: 10,
+            drain_amount: 2,
+            drain_interval_ms: 100,
+        };
+        assert_eq!(limit.full_drain_ms(), 500);
+
+        let mut bucket = RateBucket::new(0, limit);
+        assert_eq!(bucket.change(6, at_ms(0)), Ok(6));
+        assert_eq!(bucket.change(4, at_ms(50)), Ok(10));
+        assert_eq!(
+            bucket.change(1, at_ms(99)),
+            Err(RateBucketError::BucketOverflow {
+                bucket_id: 0,
+                level: 11,
+                capacity: 10
+            })
+        );
+        // two intervals have passed since the bucket started draining
+        assert_eq!(bucket.change(1, at_ms(250)), Ok(7));
+        // partial intervals accumulate across changes
+        assert_eq!(bucket.change(0, at_ms(300)), Ok(5));
+        assert_eq!(bucket.change(0, at_ms(10_000)), Ok(0));
+        assert_eq!(
+            bucket.change(0, at_ms(0)),
+            Err(RateBucketError::NonMonotonicTimestamp(
+                at_ms(10_000),
+                at_ms(0)
+            ))
+        );
+    }
+
+    #[test]
+    fn undeclared_buckets_are_unlimited() {
+        let limits: RateLimits = [(
+            1,
+            RateLimit {
+                capacity: 1,
+                drain_amount: 1,
+                drain_interval_ms: 1000,
+            },
+        )]
+        .into();
+        let mut buckets = RateBuckets::new(&limits);
+        let limited = RateWeight {
+            bucket_id: 1,
+            units: 1,
+        };
+        let unlimited = RateWeight {
+            bucket_id: 2,
+            units: 255,
+        };
+        buckets.change(&unlimited, at_ms(0)).unwrap();
+        buckets.change(&unlimited, at_ms(0)).unwrap();
+        buckets.change(&limited, at_ms(0)).unwrap();
+        buckets.change(&limited, at_ms(1)).unwrap_err();
+        assert_eq!(buckets.full_drain_ms(), 1000);
+    }
+}
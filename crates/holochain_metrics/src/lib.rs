@@ -31,6 +31,18 @@
 //!     - `HOLOCHAIN_INFLUXIVE_EXTERNAL_TOKEN=[my influxdb auth token]`
 //!   - The influxdb auth token must have permission to write to all buckets
 //!   - Metrics will be set up to report to this already running InfluxDB.
+//! - Prometheus, scraping metrics from an http endpoint served by Holochain.
+//!   - Requires the `prometheus` feature of this crate, which is enabled in
+//!     Holochain with the `metrics_prometheus` feature.
+//!   - Enable via environment variable: `HOLOCHAIN_PROMETHEUS_EXPORTER=1`
+//!   - Configure via environment variable:
+//!     - `HOLOCHAIN_PROMETHEUS_EXPORTER_ADDR=[address]` the local address to
+//!       serve the `/metrics` endpoint on, `127.0.0.1:9464` by default.
+//!   - Metric names are converted to the Prometheus conventions, e.g.
+//!     `hc.conductor.workflow.duration` is exported as the histogram
+//!     `hc_conductor_workflow_duration_seconds`.
+//!   - A Grafana dashboard for these metrics is provided in
+//!     `src/dashboards/grafana/holochain.json`.
 //!
 //! ## Metric Naming Conventions
 //!
@@ -145,6 +157,13 @@ pub enum HolochainMetricsConfig {
         /// The meter provider config for setting up opentelemetry.
         otel_config: influxive::InfluxiveMeterProviderConfig,
     },
+
+    #[cfg(feature = "prometheus")]
+    /// Serve metrics over http for scraping by Prometheus.
+    Prometheus {
+        /// The address to serve the `/metrics` endpoint on.
+        bind_addr: std::net::SocketAddr,
+    },
 }
 
 impl HolochainMetricsConfig {
@@ -206,6 +225,26 @@ impl HolochainMetricsConfig {
             }
         }
 
+        #[cfg(feature = "prometheus")]
+        {
+            const E_PROMETHEUS: &str = "HOLOCHAIN_PROMETHEUS_EXPORTER";
+            const E_PROMETHEUS_ADDR: &str = "HOLOCHAIN_PROMETHEUS_EXPORTER_ADDR";
+
+            if std::env::var(E_PROMETHEUS).as_deref() == Ok("1") {
+                let bind_addr = match std::env::var(E_PROMETHEUS_ADDR) {
+                    Ok(addr) => match addr.parse() {
+                        Ok(addr) => addr,
+                        Err(err) => {
+                            tracing::error!(env = %E_PROMETHEUS_ADDR, ?err, "invalid");
+                            return Self::Disabled;
+                        }
+                    },
+                    Err(_) => std::net::SocketAddr::from(([127, 0, 0, 1], 9464)),
+                };
+                return Self::Prometheus { bind_addr };
+            }
+        }
+
         #[cfg(not(feature = "influxive"))]
         {
             let _root_path = root_path;
@@ -237,6 +276,10 @@ impl HolochainMetricsConfig {
             } => {
                 Self::init_influxive_child_svc(*child_svc_config, otel_config).await;
             }
+            #[cfg(feature = "prometheus")]
+            Self::Prometheus { bind_addr } => {
+                Self::init_prometheus(bind_addr);
+            }
         }
     }
 
@@ -301,4 +344,141 @@ impl HolochainMetricsConfig {
             }
         }
     }
+
+    #[cfg(feature = "prometheus")]
+    fn init_prometheus(bind_addr: std::net::SocketAddr) {
+        tracing::info!(%bind_addr, "initializing holochain_metrics");
+
+        match start_prometheus_exporter(bind_addr) {
+            Ok((local_addr, meter_provider)) => {
+                // setup opentelemetry to use our metrics collector
+                opentelemetry_api::global::set_meter_provider(meter_provider);
+
+                tracing::info!(%local_addr, "prometheus metrics running");
+            }
+            Err(err) => {
+                tracing::warn!(?err, %bind_addr, "unable to initialize prometheus metrics");
+            }
+        }
+    }
+}
+
+/// Serve the metrics recorded with the returned meter provider on the
+/// `/metrics` endpoint of an http server bound to `bind_addr`.
+/// Returns the address the server is bound to.
+#[cfg(feature = "prometheus")]
+fn start_prometheus_exporter(
+    bind_addr: std::net::SocketAddr,
+) -> Result<
+    (
+        std::net::SocketAddr,
+        opentelemetry_sdk::metrics::MeterProvider,
+    ),
+    Box<dyn std::error::Error + Send + Sync>,
+> {
+    let registry = prometheus::Registry::new();
+    let exporter = opentelemetry_prometheus::exporter()
+        .with_registry(registry.clone())
+        .build()?;
+
+    let server = hyper::Server::try_bind(&bind_addr)?;
+    let make_service = hyper::service::make_service_fn(move |_| {
+        let registry = registry.clone();
+        async move {
+            Ok::<_, std::convert::Infallible>(hyper::service::service_fn(move |req| {
+                let response = serve_prometheus_metrics(&registry, req);
+                async move { Ok::<_, std::convert::Infallible>(response) }
+            }))
+        }
+    });
+    let server = server.serve(make_service);
+    let local_addr = server.local_addr();
+    tokio::task::spawn(async move {
+        if let Err(err) = server.await {
+            tracing::warn!(?err, "prometheus metrics server stopped");
+        }
+    });
+
+    let meter_provider = opentelemetry_sdk::metrics::MeterProvider::builder()
+        .with_reader(exporter)
+        .build();
+    Ok((local_addr, meter_provider))
+}
+
+/// Respond to a request to the prometheus metrics server with the metrics
+/// gathered by the registry, in the Prometheus text format.
+#[cfg(feature = "prometheus")]
+fn serve_prometheus_metrics(
+    registry: &prometheus::Registry,
+    req: hyper::Request<hyper::Body>,
+) -> hyper::Response<hyper::Body> {
+    use prometheus::Encoder;
+
+    if req.method() != hyper::Method::GET || req.uri().path() != "/metrics" {
+        let mut response = hyper::Response::new(hyper::Body::empty());
+        *response.status_mut() = hyper::StatusCode::NOT_FOUND;
+        return response;
+    }
+
+    let encoder = prometheus::TextEncoder::new();
+    let mut body = Vec::new();
+    if let Err(err) = encoder.encode(&registry.gather(), &mut body) {
+        tracing::warn!(?err, "unable to encode prometheus metrics");
+        let mut response = hyper::Response::new(hyper::Body::empty());
+        *response.status_mut() = hyper::StatusCode::INTERNAL_SERVER_ERROR;
+        return response;
+    }
+    let mut response = hyper::Response::new(hyper::Body::from(body));
+    if let Ok(content_type) = hyper::header::HeaderValue::from_str(encoder.format_type()) {
+        response
+            .headers_mut()
+            .insert(hyper::header::CONTENT_TYPE, content_type);
+    }
+    response
+}
+
+#[cfg(all(test, feature = "prometheus"))]
+mod tests {
+    use opentelemetry_api::metrics::{MeterProvider, Unit};
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn prometheus_exporter_serves_metrics() {
+        let (addr, meter_provider) =
+            super::start_prometheus_exporter(([127, 0, 0, 1], 0).into()).unwrap();
+        meter_provider
+            .meter("hc.conductor")
+            .f64_histogram("hc.conductor.workflow.duration")
+            .with_unit(Unit::new("s"))
+            .init()
+            .record(0.5, &[]);
+
+        let client = hyper::Client::new();
+        let get = |path: &str| {
+            client.get(
+                format!("http://{}{}", addr, path)
+                    .parse()
+                    .expect("invalid uri"),
+            )
+        };
+
+        let response = get("/metrics").await.unwrap();
+        assert_eq!(hyper::StatusCode::OK, response.status());
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        assert!(
+            body.contains("# TYPE hc_conductor_workflow_duration_seconds histogram"),
+            "{}",
+            body
+        );
+        assert!(
+            body.contains("hc_conductor_workflow_duration_seconds_count"),
+            "{}",
+            body
+        );
+
+        let response = get("/").await.unwrap();
+        assert_eq!(hyper::StatusCode::NOT_FOUND, response.status());
+        let response = get("/metrics/other").await.unwrap();
+        assert_eq!(hyper::StatusCode::NOT_FOUND, response.status());
+    }
 }
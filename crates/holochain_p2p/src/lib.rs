@@ -97,6 +97,14 @@ pub trait HolochainP2pDnaT: Send + Sync {
         op: DhtOp,
     ) -> actor::HolochainP2pResult<()>;
 
+    /// Publish warrants to the agent activity authorities of the agent
+    /// they are about, whose pubkey is the basis hash.
+    async fn publish_warrants(
+        &self,
+        basis_hash: holo_hash::OpBasis,
+        warrants: Vec<SignedWarrant>,
+    ) -> actor::HolochainP2pResult<()>;
+
     /// Get an entry from the DHT.
     async fn get(
         &self,
@@ -223,6 +231,11 @@ mockall::mock! {
             basis_hash: holo_hash::OpBasis,
             op: DhtOp,
         ) -> actor::HolochainP2pResult<()>;
+        async fn publish_warrants(
+            &self,
+            basis_hash: holo_hash::OpBasis,
+            warrants: Vec<SignedWarrant>,
+        ) -> actor::HolochainP2pResult<()>;
         async fn get(
             &self,
             dht_hash: holo_hash::AnyDhtHash,
@@ -412,6 +425,17 @@ impl HolochainP2pDnaT for HolochainP2pDna {
             .await
     }
 
+    /// Publish warrants to the agent activity authorities of the warrantee.
+    async fn publish_warrants(
+        &self,
+        basis_hash: holo_hash::OpBasis,
+        warrants: Vec<SignedWarrant>,
+    ) -> actor::HolochainP2pResult<()> {
+        self.sender
+            .publish_warrants((*self.dna_hash).clone(), basis_hash, warrants)
+            .await
+    }
+
     /// Get [`DhtOp::StoreRecord`] or [`DhtOp::StoreEntry`] from the DHT.
     async fn get(
         &self,
@@ -162,6 +162,17 @@ impl WrapEvtSender {
         }, %op_count, "(hp2p:handle) publish")
     }
 
+    fn publish_warrants(
+        &self,
+        dna_hash: DnaHash,
+        warrants: Vec<SignedWarrant>,
+    ) -> impl Future<Output = HolochainP2pResult<()>> + 'static + Send {
+        let warrant_count = warrants.len();
+        timing_trace!({
+            self.0.publish_warrants(dna_hash, warrants)
+        }, %warrant_count, "(hp2p:handle) publish_warrants")
+    }
+
     fn get(
         &self,
         dna_hash: DnaHash,
@@ -591,6 +602,21 @@ impl HolochainP2pActor {
         .into())
     }
 
+    /// receiving incoming warrants from a remote node
+    fn handle_incoming_publish_warrants(
+        &mut self,
+        dna_hash: DnaHash,
+        warrants: Vec<SignedWarrant>,
+    ) -> kitsune_p2p::actor::KitsuneP2pHandlerResult<()> {
+        let evt_sender = self.evt_sender.clone();
+        Ok(async move {
+            evt_sender.publish_warrants(dna_hash, warrants).await?;
+            Ok(())
+        }
+        .boxed()
+        .into())
+    }
+
     /// receiving an incoming validation receipt from a remote node
     fn handle_incoming_validation_receipt(
         &mut self,
@@ -812,6 +838,13 @@ impl kitsune_p2p::event::KitsuneP2pEventHandler for HolochainP2pActor {
                 )
                 .into())
             }
+            // holochain_p2p only broadcasts this message.
+            crate::wire::WireMessage::PublishWarrants { .. } => {
+                Err(HolochainP2pError::invalid_p2p_message(
+                    "invalid: warrants are broadcast, not requests".to_string(),
+                )
+                .into())
+            }
         }
     }
 
@@ -902,6 +935,9 @@ impl kitsune_p2p::event::KitsuneP2pEventHandler for HolochainP2pActor {
             crate::wire::WireMessage::PublishCountersign { flag, op } => {
                 self.handle_incoming_publish(space, false, flag, vec![op])
             }
+            crate::wire::WireMessage::PublishWarrants { warrants } => {
+                self.handle_incoming_publish_warrants(space, warrants)
+            }
         }
     }
 
@@ -1238,6 +1274,30 @@ impl HolochainP2pHandler for HolochainP2pActor {
         .into())
     }
 
+    #[tracing::instrument(skip(self), level = "trace")]
+    fn handle_publish_warrants(
+        &mut self,
+        dna_hash: DnaHash,
+        basis_hash: holo_hash::OpBasis,
+        warrants: Vec<SignedWarrant>,
+    ) -> HolochainP2pHandlerResult<()> {
+        let space = dna_hash.into_kitsune();
+        let basis = basis_hash.to_kitsune();
+        let timeout = self.config.tuning_params.implicit_timeout();
+
+        let kitsune_p2p = self.kitsune_p2p.clone();
+        Ok(async move {
+            let payload = crate::wire::WireMessage::publish_warrants(warrants).encode()?;
+
+            kitsune_p2p
+                .broadcast(space, basis, timeout, BroadcastData::User(payload))
+                .await?;
+            Ok(())
+        }
+        .boxed()
+        .into())
+    }
+
     #[tracing::instrument(skip(self, dna_hash, dht_hash, options), level = "trace")]
     fn handle_get(
         &mut self,
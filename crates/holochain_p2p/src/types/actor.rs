@@ -308,6 +308,14 @@ ghost_actor::ghost_chan! {
             op: DhtOp,
         ) -> ();
 
+        /// Publish warrants to the agent activity authorities of the agent
+        /// they are about.
+        fn publish_warrants(
+            dna_hash: DnaHash,
+            basis_hash: holo_hash::OpBasis,
+            warrants: Vec<SignedWarrant>,
+        ) -> ();
+
         /// Get an entry from the DHT.
         fn get(
             dna_hash: DnaHash,
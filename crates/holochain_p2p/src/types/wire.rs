@@ -82,6 +82,9 @@ pub enum WireMessage {
         flag: bool,
         op: DhtOp,
     },
+    PublishWarrants {
+        warrants: Vec<SignedWarrant>,
+    },
 }
 
 #[allow(missing_docs)]
@@ -98,6 +101,10 @@ impl WireMessage {
         Self::PublishCountersign { flag, op }
     }
 
+    pub fn publish_warrants(warrants: Vec<SignedWarrant>) -> WireMessage {
+        Self::PublishWarrants { warrants }
+    }
+
     /// For an outgoing remote call.
     #[allow(clippy::too_many_arguments)]
     pub fn call_remote(
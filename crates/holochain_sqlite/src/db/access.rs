@@ -1,13 +1,14 @@
 use crate::db::conn::PConn;
 use crate::db::databases::DATABASE_HANDLES;
 use crate::db::guard::{PConnGuard, PTxnGuard};
+use crate::db::key::{DbKey, DbMasterKey};
 use crate::db::kind::{DbKind, DbKindT};
 use crate::db::pool::{
     initialize_connection, new_connection_pool, num_read_threads, ConnectionPool, DbSyncLevel,
 };
 use crate::error::{DatabaseError, DatabaseResult};
 use derive_more::Into;
-use parking_lot::Mutex;
+use parking_lot::{Mutex, RwLock};
 use rusqlite::*;
 use shrinkwraprs::Shrinkwrap;
 use std::sync::atomic::{AtomicU64, Ordering};
@@ -76,7 +77,12 @@ impl<Kind: DbKindT> ReadAccess<Kind> for DbRead<Kind> {
 pub struct DbRead<Kind: DbKindT> {
     kind: Kind,
     path: PathBuf,
-    connection_pool: ConnectionPool,
+    sync_level: DbSyncLevel,
+    /// The key the database is encrypted with.
+    key: Arc<RwLock<DbKey>>,
+    /// The pool is replaced when the database is rekeyed, to close the
+    /// connections which were opened with the previous key.
+    connection_pool: Arc<RwLock<ConnectionPool>>,
     write_semaphore: Arc<Semaphore>,
     read_semaphore: Arc<Semaphore>,
     long_read_semaphore: Arc<Semaphore>,
@@ -173,7 +179,8 @@ impl<Kind: DbKindT> DbRead<Kind> {
     /// TODO: We should eventually swap this for an async solution.
     fn get_connection_from_pool(&self) -> DatabaseResult<PConn> {
         let now = Instant::now();
-        let r = Ok(PConn::new(self.connection_pool.get()?));
+        let pool = self.connection_pool.read().clone();
+        let r = Ok(PConn::new(pool.get()?));
         let el = now.elapsed();
         if el.as_millis() > 20 {
             // TODO Convert to a metric
@@ -230,9 +237,23 @@ impl<Kind: DbKindT + Send + Sync + 'static> DbWrite<Kind> {
         path_prefix: &Path,
         kind: Kind,
         sync_level: DbSyncLevel,
+    ) -> DatabaseResult<Self> {
+        Self::open_with_key(path_prefix, kind, sync_level, &DbMasterKey::default())
+    }
+
+    /// Create or open an existing database reference, encrypted with the
+    /// key derived for it from the master key.
+    ///
+    /// A database which is still encrypted with a key the master key replaced
+    /// is rekeyed when it is opened.
+    pub fn open_with_key(
+        path_prefix: &Path,
+        kind: Kind,
+        sync_level: DbSyncLevel,
+        master_key: &DbMasterKey,
     ) -> DatabaseResult<Self> {
         DATABASE_HANDLES.get_or_insert(&kind, path_prefix, |kind| {
-            Self::new(Some(path_prefix), kind, sync_level, None)
+            Self::new(Some(path_prefix), kind, sync_level, master_key, None)
         })
     }
 
@@ -240,8 +261,10 @@ impl<Kind: DbKindT + Send + Sync + 'static> DbWrite<Kind> {
         path_prefix: Option<&Path>,
         kind: Kind,
         sync_level: DbSyncLevel,
+        master_key: &DbMasterKey,
         statement_trace_fn: Option<fn(&str)>,
     ) -> DatabaseResult<Self> {
+        let key = master_key.db_key(&kind);
         let path = match path_prefix {
             Some(path_prefix) => {
                 let path = path_prefix.join(kind.filename());
@@ -254,7 +277,7 @@ impl<Kind: DbKindT + Send + Sync + 'static> DbWrite<Kind> {
                 }
                 // Check if the database is valid and take the appropriate
                 // action if it isn't.
-                match Self::check_database_file(&path, sync_level) {
+                match check_database_file(&path, sync_level, &key) {
                     Ok(path) => path,
                     // These are the two errors that can
                     // occur if the database is not valid.
@@ -272,14 +295,21 @@ impl<Kind: DbKindT + Send + Sync + 'static> DbWrite<Kind> {
                         },
                         ..,
                     )) => {
+                        // Check if the database is still encrypted with a previous key.
+                        if let Some(_previous_key) =
+                            Self::find_previous_key(&path, sync_level, &kind, master_key)
+                        {
+                            #[cfg(feature = "sqlite-encrypted")]
+                            rekey_database(&path, &_previous_key, &key)?;
+                        }
                         // Check if the database might be unencrypted.
-                        if "true"
+                        else if "true"
                             == std::env::var("HOLOCHAIN_MIGRATE_UNENCRYPTED")
                                 .unwrap_or_default()
                                 .as_str()
                         {
                             #[cfg(feature = "sqlite-encrypted")]
-                            encrypt_unencrypted_database(&path)?;
+                            encrypt_unencrypted_database(&path, &key)?;
                         }
                         // Check if this database kind requires wiping.
                         else if kind.if_corrupt_wipe() {
@@ -290,7 +320,7 @@ impl<Kind: DbKindT + Send + Sync + 'static> DbWrite<Kind> {
                         }
 
                         // Now that we've taken the appropriate action we can try again.
-                        match Self::check_database_file(&path, sync_level) {
+                        match check_database_file(&path, sync_level, &key) {
                             Ok(path) => path,
                             Err(e) => return Err(e.into()),
                         }
@@ -303,7 +333,7 @@ impl<Kind: DbKindT + Send + Sync + 'static> DbWrite<Kind> {
         };
 
         // Now we know the database file is valid we can open a connection pool.
-        let pool = new_connection_pool(path.as_ref().map(|p| p.as_ref()), sync_level);
+        let pool = new_connection_pool(path.as_ref().map(|p| p.as_ref()), sync_level, key.clone());
         let mut conn = pool.get()?;
         // set to faster write-ahead-log mode
         conn.pragma_update(None, "journal_mode", "WAL".to_string())?;
@@ -319,7 +349,9 @@ impl<Kind: DbKindT + Send + Sync + 'static> DbWrite<Kind> {
             num_readers: Arc::new(AtomicUsize::new(0)),
             kind: kind.clone(),
             path: path.unwrap_or_default(),
-            connection_pool: pool,
+            sync_level,
+            key: Arc::new(RwLock::new(key)),
+            connection_pool: Arc::new(RwLock::new(pool)),
             statement_trace_fn,
             use_time_metric,
         };
@@ -361,6 +393,60 @@ impl<Kind: DbKindT + Send + Sync + 'static> DbWrite<Kind> {
         })?.map_err(DatabaseError::from)?
     }
 
+    /// Re-encrypt the database in place with the key derived for it from the
+    /// master key.
+    ///
+    /// This waits for all transactions on the database to finish, and the
+    /// connections to the database are reopened with the new key.
+    pub async fn rekey(&self, master_key: &DbMasterKey) -> DatabaseResult<()> {
+        let new_key = master_key.db_key(self.kind());
+
+        // Hold every permit, so that no connections are in use while the
+        // database is rekeyed.
+        let num_readers = num_read_threads() as u32;
+        let _write_permit = self.acquire_writer_permit().await;
+        let _read_permits = self
+            .read_semaphore
+            .clone()
+            .acquire_many_owned(num_readers)
+            .await
+            .expect("We don't ever close these semaphores");
+        let _long_read_permits = self
+            .long_read_semaphore
+            .clone()
+            .acquire_many_owned(num_readers)
+            .await
+            .expect("We don't ever close these semaphores");
+
+        let db = self.clone();
+        tokio::task::spawn_blocking(move || db.rekey_blocking(new_key)).await?
+    }
+
+    fn rekey_blocking(&self, new_key: DbKey) -> DatabaseResult<()> {
+        let mut key = self.key.write();
+        // In-memory databases are not stored, so there is nothing to re-encrypt.
+        if *key == new_key || self.path.as_os_str().is_empty() {
+            return Ok(());
+        }
+
+        // Replacing the pool closes its idle connections, so that the
+        // database is not in use while it is rekeyed.
+        *self.connection_pool.write() =
+            new_connection_pool(Some(&self.path), self.sync_level, key.clone());
+        #[cfg(feature = "sqlite-encrypted")]
+        rekey_database(&self.path, &key, &new_key)?;
+        *self.connection_pool.write() =
+            new_connection_pool(Some(&self.path), self.sync_level, new_key.clone());
+
+        // Rekeying leaves the write-ahead-log mode, so switch back to it.
+        let pool = self.connection_pool.read().clone();
+        pool.get()?
+            .pragma_update(None, "journal_mode", "WAL".to_string())?;
+
+        *key = new_key;
+        Ok(())
+    }
+
     pub fn available_writer_count(&self) -> usize {
         self.write_semaphore.available_permits()
     }
@@ -405,29 +491,50 @@ impl<Kind: DbKindT + Send + Sync + 'static> DbWrite<Kind> {
             .clone()
     }
 
-    fn check_database_file(
+    /// Find the key the database was encrypted with before the master key
+    /// was rotated, if it can be opened with one.
+    fn find_previous_key(
         path: &Path,
         sync_level: DbSyncLevel,
-    ) -> rusqlite::Result<Option<PathBuf>> {
-        Connection::open(path)
-            // For some reason calling pragma_update is necessary to prove the database file is valid.
-            .and_then(|mut c| {
-                initialize_connection(&mut c, sync_level)?;
-                c.pragma_update(None, "synchronous", "0".to_string())?;
-                Ok(c.path().map(PathBuf::from))
-            })
+        kind: &Kind,
+        master_key: &DbMasterKey,
+    ) -> Option<DbKey> {
+        #[cfg(feature = "sqlite-encrypted")]
+        {
+            master_key
+                .previous_db_keys(kind)
+                .into_iter()
+                .find(|key| check_database_file(path, sync_level, key).is_ok())
+        }
+        #[cfg(not(feature = "sqlite-encrypted"))]
+        {
+            let _ = (path, sync_level, kind, master_key);
+            None
+        }
     }
 
     /// Create a unique db in a temp dir with no static management of the
     /// connection pool, useful for testing.
     #[cfg(any(test, feature = "test_utils"))]
     pub fn test(path: &Path, kind: Kind) -> DatabaseResult<Self> {
-        Self::new(Some(path), kind, DbSyncLevel::default(), None)
+        Self::new(
+            Some(path),
+            kind,
+            DbSyncLevel::default(),
+            &DbMasterKey::default(),
+            None,
+        )
     }
 
     #[cfg(any(test, feature = "test_utils"))]
     pub fn test_in_mem(kind: Kind) -> DatabaseResult<Self> {
-        Self::new(None, kind, DbSyncLevel::default(), None)
+        Self::new(
+            None,
+            kind,
+            DbSyncLevel::default(),
+            &DbMasterKey::default(),
+            None,
+        )
     }
 
     #[cfg(all(any(test, feature = "test_utils"), not(loom)))]
@@ -446,7 +553,7 @@ impl<Kind: DbKindT + Send + Sync + 'static> DbWrite<Kind> {
 
 // The method for this function is taken from https://discuss.zetetic.net/t/how-to-encrypt-a-plaintext-sqlite-database-to-use-sqlcipher-and-avoid-file-is-encrypted-or-is-not-a-database-errors/868
 #[cfg(feature = "sqlite-encrypted")]
-pub fn encrypt_unencrypted_database(path: &Path) -> DatabaseResult<()> {
+pub fn encrypt_unencrypted_database(path: &Path, key: &DbKey) -> DatabaseResult<()> {
     // e.g. conductor/conductor.sqlite3 -> conductor/conductor-encrypted.sqlite3
     let encrypted_path = path
         .parent()
@@ -483,7 +590,7 @@ pub fn encrypt_unencrypted_database(path: &Path) -> DatabaseResult<()> {
             "ATTACH DATABASE :db_name AS encrypted KEY :key",
             rusqlite::named_params! {
                 ":db_name": encrypted_path.to_str(),
-                ":key": super::pool::FAKE_KEY,
+                ":key": key.pragma(),
             },
         )?;
 
@@ -502,6 +609,72 @@ pub fn encrypt_unencrypted_database(path: &Path) -> DatabaseResult<()> {
     Ok(())
 }
 
+fn check_database_file(
+    path: &Path,
+    sync_level: DbSyncLevel,
+    key: &DbKey,
+) -> rusqlite::Result<Option<PathBuf>> {
+    Connection::open(path)
+        // For some reason calling pragma_update is necessary to prove the database file is valid.
+        .and_then(|mut c| {
+            initialize_connection(&mut c, sync_level, key)?;
+            c.pragma_update(None, "synchronous", "0".to_string())?;
+            Ok(c.path().map(PathBuf::from))
+        })
+}
+
+/// Re-encrypt every database in the databases directory which is not open
+/// with the key derived for it from the master key.
+///
+/// Open databases are rekeyed with [`DbWrite::rekey`]. Together they make sure
+/// no database is left encrypted with a key the master key replaced.
+pub fn rekey_closed_databases(path_prefix: &Path, master_key: &DbMasterKey) -> DatabaseResult<()> {
+    DATABASE_HANDLES.rekey_closed(path_prefix, master_key)
+}
+
+/// Re-encrypt the database file at `path`, which is stored at `filename`
+/// relative to the databases directory, if it is still encrypted with a key
+/// the master key replaced.
+pub(super) fn rekey_database_file(
+    path: &Path,
+    filename: &Path,
+    master_key: &DbMasterKey,
+) -> DatabaseResult<()> {
+    #[cfg(feature = "sqlite-encrypted")]
+    {
+        let sync_level = DbSyncLevel::default();
+        let key = master_key.file_key(filename);
+        if let Err(err) = check_database_file(path, sync_level, &key) {
+            let previous_key = master_key
+                .previous_file_keys(filename)
+                .into_iter()
+                .find(|key| check_database_file(path, sync_level, key).is_ok())
+                .ok_or(err)?;
+            rekey_database(path, &previous_key, &key)?;
+        }
+    }
+    #[cfg(not(feature = "sqlite-encrypted"))]
+    {
+        let _ = (path, filename, master_key);
+    }
+    Ok(())
+}
+
+/// Re-encrypt a database which is not in use with a new key.
+#[cfg(feature = "sqlite-encrypted")]
+fn rekey_database(path: &Path, old_key: &DbKey, new_key: &DbKey) -> DatabaseResult<()> {
+    tracing::info!("Rekeying database: {:?}", path);
+
+    let conn = Connection::open(path)?;
+    conn.pragma_update(None, "key", old_key.pragma())?;
+    // Rekeying is not supported in write-ahead-log mode.
+    conn.pragma_update(None, "journal_mode", "DELETE".to_string())?;
+    conn.pragma_update(None, "rekey", new_key.pragma())?;
+    conn.close().map_err(|(_, err)| err)?;
+
+    Ok(())
+}
+
 #[cfg(feature = "test_utils")]
 pub fn set_acquire_timeout(timeout_ms: u64) {
     ACQUIRE_TIMEOUT_MS.store(timeout_ms, Ordering::Relaxed);
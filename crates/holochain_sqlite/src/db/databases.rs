@@ -1,4 +1,5 @@
-use crate::db::access::DbWrite;
+use crate::db::access::{rekey_database_file, DbWrite};
+use crate::db::key::DbMasterKey;
 use crate::db::kind::DbKindT;
 use crate::prelude::*;
 use once_cell::sync::Lazy;
@@ -88,4 +89,35 @@ impl Databases {
             },
         }
     }
+
+    /// Rekey every database file in the databases directory which has not
+    /// been opened.
+    ///
+    /// No database can be opened while this runs, so that none is opened
+    /// while its file is being rekeyed.
+    pub(super) fn rekey_closed(
+        &self,
+        path_prefix: &Path,
+        master_key: &DbMasterKey,
+    ) -> DatabaseResult<()> {
+        let dbs = self.dbs.write();
+        // Databases are stored one directory deep, e.g. `dht/dht-<dna>.sqlite3`.
+        for dir in std::fs::read_dir(path_prefix)? {
+            let dir = dir?.path();
+            if !dir.is_dir() {
+                continue;
+            }
+            for file in std::fs::read_dir(&dir)? {
+                let path = file?.path();
+                if path.extension() != Some("sqlite3".as_ref()) || dbs.contains_key(&path) {
+                    continue;
+                }
+                let filename = path
+                    .strip_prefix(path_prefix)
+                    .expect("the file was found in the databases directory");
+                rekey_database_file(&path, filename, master_key)?;
+            }
+        }
+        Ok(())
+    }
 }
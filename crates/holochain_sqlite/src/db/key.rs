@@ -0,0 +1,217 @@
+//! Keys for the at-rest encryption of databases.
+
+use crate::db::kind::DbKindT;
+use crate::error::DatabaseResult;
+use std::path::Path;
+use std::sync::Arc;
+
+/// The key all databases were encrypted with before they had their own keys.
+/// Databases which are still encrypted with it are rekeyed when they are opened.
+const LEGACY_KEY: &str = "x'98483C6EB40B6C31A448C22A66DED3B5E5E8D5119CAC8327B655C8B5C483648101010101010101010101010101010101'";
+
+/// The key a single database is encrypted with.
+///
+/// Keys are only applied with the `sqlite-encrypted` feature, otherwise
+/// databases are not encrypted and the key is ignored.
+#[derive(Clone, PartialEq, Eq)]
+pub struct DbKey(Arc<str>);
+
+impl std::fmt::Debug for DbKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("DbKey(<redacted>)")
+    }
+}
+
+impl DbKey {
+    /// Use these bytes as the raw key of a database.
+    pub fn from_bytes(key: &[u8; 32]) -> Self {
+        let hex: String = key.iter().map(|b| format!("{:02X}", b)).collect();
+        Self(format!("x'{}'", hex).into())
+    }
+
+    /// The key databases were encrypted with before they had their own keys.
+    pub fn legacy() -> Self {
+        Self(LEGACY_KEY.into())
+    }
+
+    /// The value to pass to the `key` and `rekey` pragmas.
+    #[cfg_attr(not(feature = "sqlite-encrypted"), allow(dead_code))]
+    pub(crate) fn pragma(&self) -> &str {
+        &self.0
+    }
+}
+
+/// The secret the keys of the databases of a conductor are derived from.
+///
+/// Every database gets its own key, derived from the master key and the path
+/// of the database, so that the key of one database can't be used to decrypt
+/// any other database.
+///
+/// The master key also holds the master keys it replaced, so that databases
+/// which were not rekeyed when the key was rotated can still be opened, after
+/// which they are rekeyed to the current key.
+#[derive(Clone, Default)]
+pub struct DbMasterKey {
+    /// The current master key. If there is none, every database uses the
+    /// [legacy key](DbKey::legacy).
+    current: Option<Arc<[u8; 32]>>,
+    /// The master keys this one replaced, newest first.
+    retired: Vec<Arc<[u8; 32]>>,
+}
+
+impl std::fmt::Debug for DbMasterKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DbMasterKey")
+            .field("retired", &self.retired.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl DbMasterKey {
+    /// Construct a master key from its current and retired keys.
+    pub fn new(current: [u8; 32], retired: Vec<[u8; 32]>) -> Self {
+        Self {
+            current: Some(Arc::new(current)),
+            retired: retired.into_iter().map(Arc::new).collect(),
+        }
+    }
+
+    /// Generate a new random master key.
+    pub fn generate() -> DatabaseResult<Self> {
+        Ok(Self::new(Self::random_key()?, Vec::new()))
+    }
+
+    /// Generate a new random master key which replaces this one.
+    pub fn rotate(&self) -> DatabaseResult<Self> {
+        let mut retired = self.retired.clone();
+        if let Some(current) = &self.current {
+            retired.insert(0, current.clone());
+        }
+        Ok(Self {
+            current: Some(Arc::new(Self::random_key()?)),
+            retired,
+        })
+    }
+
+    /// This master key without the master keys it replaced, once every
+    /// database has been rekeyed to it.
+    pub fn without_retired(&self) -> Self {
+        Self {
+            current: self.current.clone(),
+            retired: Vec::new(),
+        }
+    }
+
+    /// The current master key, if there is one.
+    pub fn current(&self) -> Option<&[u8; 32]> {
+        self.current.as_deref()
+    }
+
+    /// The master keys this one replaced, newest first.
+    pub fn retired(&self) -> impl Iterator<Item = &[u8; 32]> {
+        self.retired.iter().map(|k| k.as_ref())
+    }
+
+    /// The key of the database of this kind.
+    pub fn db_key<Kind: DbKindT>(&self, kind: &Kind) -> DbKey {
+        self.file_key(&kind.filename())
+    }
+
+    /// The keys the database of this kind may still be encrypted with, if it
+    /// was not rekeyed after the master key was rotated.
+    pub(crate) fn previous_db_keys<Kind: DbKindT>(&self, kind: &Kind) -> Vec<DbKey> {
+        self.previous_file_keys(&kind.filename())
+    }
+
+    /// The key of the database stored at this path, relative to the
+    /// databases directory.
+    pub(crate) fn file_key(&self, filename: &Path) -> DbKey {
+        match &self.current {
+            Some(master) => Self::derive(master, filename),
+            None => DbKey::legacy(),
+        }
+    }
+
+    /// The keys the database stored at this path, relative to the databases
+    /// directory, may still be encrypted with.
+    pub(crate) fn previous_file_keys(&self, filename: &Path) -> Vec<DbKey> {
+        let mut keys: Vec<DbKey> = self
+            .retired
+            .iter()
+            .map(|master| Self::derive(master, filename))
+            .collect();
+        if self.current.is_some() {
+            keys.push(DbKey::legacy());
+        }
+        keys
+    }
+
+    fn derive(master: &[u8; 32], filename: &Path) -> DbKey {
+        let hash = blake2b_simd::Params::new()
+            .hash_length(32)
+            .key(master)
+            .personal(b"holochain_db_key")
+            .hash(filename.to_string_lossy().as_bytes());
+        let mut key = [0; 32];
+        key.copy_from_slice(hash.as_bytes());
+        DbKey::from_bytes(&key)
+    }
+
+    fn random_key() -> DatabaseResult<[u8; 32]> {
+        let mut key = [0; 32];
+        getrandom::getrandom(&mut key)?;
+        Ok(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{DbKindConductor, DbKindWasm};
+
+    #[test]
+    fn db_keys_are_separate() {
+        let master = DbMasterKey::generate().unwrap();
+        assert_ne!(master.db_key(&DbKindConductor), master.db_key(&DbKindWasm));
+        assert_ne!(master.db_key(&DbKindConductor), DbKey::legacy());
+        assert_eq!(
+            master.db_key(&DbKindConductor),
+            master.db_key(&DbKindConductor)
+        );
+
+        let other = DbMasterKey::generate().unwrap();
+        assert_ne!(
+            master.db_key(&DbKindConductor),
+            other.db_key(&DbKindConductor)
+        );
+    }
+
+    #[test]
+    fn rotated_keys_are_retired() {
+        assert_eq!(
+            DbMasterKey::default().db_key(&DbKindConductor),
+            DbKey::legacy()
+        );
+
+        let master = DbMasterKey::generate().unwrap();
+        let rotated = master.rotate().unwrap();
+        assert_ne!(
+            rotated.db_key(&DbKindConductor),
+            master.db_key(&DbKindConductor)
+        );
+        assert_eq!(
+            rotated.previous_db_keys(&DbKindConductor),
+            vec![master.db_key(&DbKindConductor), DbKey::legacy()]
+        );
+
+        let rekeyed = rotated.without_retired();
+        assert_eq!(
+            rekeyed.db_key(&DbKindConductor),
+            rotated.db_key(&DbKindConductor)
+        );
+        assert_eq!(
+            rekeyed.previous_db_keys(&DbKindConductor),
+            vec![DbKey::legacy()]
+        );
+    }
+}
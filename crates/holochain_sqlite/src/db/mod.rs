@@ -4,6 +4,7 @@ mod access;
 mod conn;
 mod databases;
 mod guard;
+mod key;
 mod kind;
 mod metrics;
 mod pool;
@@ -11,8 +12,9 @@ mod pool;
 #[cfg(all(test, not(loom)))]
 mod tests;
 
-pub use access::{DbRead, DbWrite, ReadAccess};
+pub use access::{rekey_closed_databases, DbRead, DbWrite, ReadAccess};
 pub use guard::PTxnGuard;
+pub use key::{DbKey, DbMasterKey};
 pub use kind::{
     DbKind, DbKindAuthored, DbKindCache, DbKindConductor, DbKindDht, DbKindOp, DbKindP2pAgents,
     DbKindP2pMetrics, DbKindT, DbKindWasm,
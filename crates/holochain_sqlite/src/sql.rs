@@ -22,6 +22,7 @@ pub mod sql_cell {
         include_str!("sql/cell/fetch_hashes/fetch_op_hashes_p2.sql");
 
     pub const FETCH_OP_REGION: &str = include_str!("sql/cell/fetch_op_region.sql");
+    pub const FETCH_OP_REGION_DATA: &str = include_str!("sql/cell/fetch_op_region_data.sql");
     pub const FETCH_OPS_BY_REGION: &str = include_str!("sql/cell/fetch_ops_by_region.sql");
     pub const FETCH_REGION_OP_HASHES: &str = include_str!("sql/cell/fetch_region_op_hashes.sql");
 
@@ -63,6 +64,7 @@ pub mod sql_conductor {
     pub const FROM_BLOCK_SPAN_WHERE_OVERLAPPING: &str =
         include_str!("sql/conductor/from_block_span_where_overlapping.sql");
     pub const IS_BLOCKED: &str = include_str!("sql/conductor/is_blocked.sql");
+    pub const SELECT_BLOCK_SPANS: &str = include_str!("sql/conductor/select_block_spans.sql");
     pub const SELECT_VALID_CAP_GRANT_FOR_CAP_SECRET: &str =
         include_str!("sql/conductor/select_valid_cap_grant_for_cap_secret.sql");
     pub const SELECT_VALID_UNRESTRICTED_CAP_GRANT: &str =
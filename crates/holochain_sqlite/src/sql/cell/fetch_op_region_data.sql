@@ -0,0 +1,20 @@
+SELECT
+  DhtOp.rowid AS rowid,
+  DhtOp.hash AS hash,
+  DhtOp.storage_center_loc AS storage_center_loc,
+  DhtOp.authored_timestamp AS authored_timestamp,
+  LENGTH(Action.blob) AS action_size,
+  -- We need to only account for entry data in the size count when the op contains the entry itself.
+  -- Other ops refer to actions that refer to entries, but we don't want to include that in the size.
+  CASE
+    WHEN DhtOp.type IN ('StoreEntry', 'StoreRecord') THEN COALESCE(LENGTH(Entry.blob), 0)
+    ELSE 0
+  END AS entry_size
+FROM
+  DhtOp
+  JOIN Action ON DhtOp.action_hash = Action.hash
+  LEFT JOIN Entry ON Action.entry_hash = Entry.hash
+WHERE
+  DhtOp.rowid > :rowid
+ORDER BY
+  DhtOp.rowid
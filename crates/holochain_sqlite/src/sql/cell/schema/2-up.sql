@@ -0,0 +1,12 @@
+-- no-sql-format --
+
+CREATE TABLE IF NOT EXISTS Warrant (
+    hash            BLOB           PRIMARY KEY ON CONFLICT IGNORE,
+    -- The agent who issued the warrant.
+    author          BLOB           NOT NULL,
+    -- The agent who the warrant is about.
+    warrantee       BLOB           NOT NULL,
+    timestamp       INTEGER        NOT NULL,
+    blob            BLOB           NOT NULL
+);
+CREATE INDEX IF NOT EXISTS Warrant_warrantee_idx ON Warrant ( warrantee );
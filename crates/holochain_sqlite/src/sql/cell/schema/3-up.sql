@@ -0,0 +1,13 @@
+-- no-sql-format --
+
+CREATE TABLE IF NOT EXISTS CountersigningSessionOp (
+    -- The hash of the StoreEntry op of one of the signers of the session.
+    op_hash         BLOB           PRIMARY KEY ON CONFLICT IGNORE,
+    -- The hash of the session entry.
+    entry_hash      BLOB           NOT NULL,
+    -- The end of the session, after which the op is dropped.
+    expires_at      INTEGER        NOT NULL,
+    -- The op and the hashes of the actions of all signers of the session.
+    blob            BLOB           NOT NULL
+);
+CREATE INDEX IF NOT EXISTS CountersigningSessionOp_entry_hash_idx ON CountersigningSessionOp ( entry_hash );
@@ -0,0 +1,12 @@
+-- all block spans which have not ended by :time_us
+SELECT
+  target_id,
+  target_reason,
+  start_us,
+  end_us
+FROM
+  BlockSpan
+WHERE
+  :time_us <= end_us
+ORDER BY
+  start_us
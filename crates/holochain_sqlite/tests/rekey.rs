@@ -0,0 +1,68 @@
+#[cfg(feature = "sqlite-encrypted")]
+#[tokio::test]
+async fn rekey() {
+    use holochain_sqlite::{
+        db::{rekey_closed_databases, DbKindConductor, DbMasterKey, DbSyncLevel, DbWrite},
+        error::DatabaseResult,
+    };
+
+    holochain_trace::test_run().unwrap();
+
+    let tmp_dir = tempfile::TempDir::new().unwrap();
+    let open = |master_key: &DbMasterKey| {
+        DbWrite::new(
+            Some(tmp_dir.path()),
+            DbKindConductor,
+            DbSyncLevel::default(),
+            master_key,
+            None,
+        )
+    };
+    let read = |db: DbWrite<DbKindConductor>| async move {
+        db.read_async(|txn| -> DatabaseResult<String> {
+            Ok(txn.query_row(
+                "SELECT name FROM rekey_me LIMIT 1",
+                (),
+                |row| -> Result<String, rusqlite::Error> { row.get(0) },
+            )?)
+        })
+        .await
+        .unwrap()
+    };
+
+    let master_key = DbMasterKey::generate().unwrap();
+    let db = open(&master_key).unwrap();
+    db.write_async(|txn| -> DatabaseResult<()> {
+        txn.execute("CREATE TABLE rekey_me (name TEXT NOT NULL)", ())?;
+        txn.execute("INSERT INTO rekey_me (name) VALUES ('hello_rekeyed')", ())?;
+        Ok(())
+    })
+    .await
+    .unwrap();
+
+    // Rekey the open database, after which it is still usable.
+    let rotated = master_key.rotate().unwrap();
+    db.rekey(&rotated).await.unwrap();
+    assert_eq!(read(db.clone()).await, "hello_rekeyed");
+    drop(db);
+
+    // The old key can no longer open the database.
+    let err = open(&master_key).unwrap_err();
+    assert_eq!(err.to_string(), "file is not a database");
+
+    // A database which was not rekeyed is rekeyed when it is opened.
+    let rotated_again = rotated.rotate().unwrap();
+    let db = open(&rotated_again).unwrap();
+    assert_eq!(read(db.clone()).await, "hello_rekeyed");
+    drop(db);
+    let only_current = DbMasterKey::new(*rotated_again.current().unwrap(), Vec::new());
+    let db = open(&only_current).unwrap();
+    assert_eq!(read(db).await, "hello_rekeyed");
+
+    // A database which is not open is rekeyed in place, after which the
+    // replaced keys are no longer needed to open it.
+    let rotated_closed = rotated_again.rotate().unwrap();
+    rekey_closed_databases(tmp_dir.path(), &rotated_closed).unwrap();
+    let db = open(&rotated_closed.without_retired()).unwrap();
+    assert_eq!(read(db).await, "hello_rekeyed");
+}
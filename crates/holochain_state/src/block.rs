@@ -1,13 +1,19 @@
 use crate::mutations;
 use crate::query::prelude::named_params;
+use holochain_serialized_bytes::SerializedBytesError;
+use holochain_sqlite::prelude::DatabaseError;
 use holochain_sqlite::prelude::DatabaseResult;
 use holochain_sqlite::prelude::DbWrite;
 use holochain_sqlite::rusqlite::Transaction;
 use holochain_sqlite::sql::sql_conductor;
 use holochain_types::prelude::DbKindConductor;
+use holochain_types::prelude::InclusiveTimestampInterval;
 use holochain_types::prelude::Timestamp;
 use holochain_zome_types::block::Block;
+use holochain_zome_types::block::BlockSpan;
+use holochain_zome_types::block::BlockTarget;
 use holochain_zome_types::block::BlockTargetId;
+use holochain_zome_types::block::BlockTargetReason;
 
 pub async fn block(db: &DbWrite<DbKindConductor>, input: Block) -> DatabaseResult<()> {
     tracing::warn!(?input, "blocking node!");
@@ -36,6 +42,41 @@ pub fn query_is_blocked(
     )?)
 }
 
+/// All blocks which have not ended by the given timestamp, in order of their start.
+pub fn query_block_spans(
+    txn: &Transaction<'_>,
+    timestamp: Timestamp,
+) -> DatabaseResult<Vec<BlockSpan>> {
+    let mut stmt = txn.prepare(sql_conductor::SELECT_BLOCK_SPANS)?;
+    let rows = stmt
+        .query_map(named_params! { ":time_us": timestamp }, |row| {
+            Ok((
+                row.get::<_, Vec<u8>>(0)?,
+                row.get::<_, Vec<u8>>(1)?,
+                row.get::<_, Timestamp>(2)?,
+                row.get::<_, Timestamp>(3)?,
+            ))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    rows.into_iter()
+        .map(|(target_id, target_reason, start, end)| {
+            let target_id: BlockTargetId = holochain_serialized_bytes::decode(&target_id)?;
+            let target_reason: BlockTargetReason =
+                holochain_serialized_bytes::decode(&target_reason)?;
+            let target = BlockTarget::from_parts(target_id, target_reason).ok_or_else(|| {
+                DatabaseError::SerializedBytes(SerializedBytesError::Deserialize(
+                    "Stored block has a target and reason of different kinds".to_string(),
+                ))
+            })?;
+            Ok(BlockSpan {
+                target,
+                interval: InclusiveTimestampInterval::try_new(start, end)
+                    .map_err(DatabaseError::Timestamp)?,
+            })
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod test {
     use crate::prelude::*;
@@ -564,4 +605,47 @@ mod test {
             );
         }
     }
+
+    // Listed blocks are put back together from their stored target and reason.
+    #[tokio::test(flavor = "multi_thread")]
+    async fn block_spans_are_listed_until_they_end() {
+        let db = test_conductor_db();
+
+        let cell_target = BlockTarget::Cell(::fixt::fixt!(CellId), CellBlockReason::BadCrypto);
+        let ip_target = BlockTarget::Ip(
+            "2001:db8::1".parse().unwrap(),
+            IpBlockReason::Kitsune(kitsune_p2p_block::IpBlockReason::DOS),
+        );
+        for (target, start, end) in [(cell_target.clone(), 0, 10), (ip_target.clone(), 5, 20)] {
+            super::block(
+                &db,
+                Block::new(
+                    target,
+                    InclusiveTimestampInterval::try_new(Timestamp(start), Timestamp(end)).unwrap(),
+                ),
+            )
+            .await
+            .unwrap();
+        }
+
+        let spans = db
+            .read_async(|txn| super::query_block_spans(&txn, Timestamp(0)))
+            .await
+            .unwrap();
+        assert_eq!(
+            vec![cell_target, ip_target.clone()],
+            spans
+                .into_iter()
+                .map(|span| span.target)
+                .collect::<Vec<_>>()
+        );
+
+        let spans = db
+            .read_async(|txn| super::query_block_spans(&txn, Timestamp(11)))
+            .await
+            .unwrap();
+        assert_eq!(1, spans.len());
+        assert_eq!(ip_target, spans[0].target);
+        assert_eq!(Timestamp(5), spans[0].interval.start());
+    }
 }
@@ -66,3 +66,34 @@ pub fn is_lock_expired(
     // If there's no lock then it's expired.
     Ok(r.unwrap_or(true))
 }
+
+/// The locks on the chain of the given author which have not expired yet,
+/// with the time each of them expires.
+pub fn live_chain_locks(
+    txn: &Transaction,
+    author: &AgentPubKey,
+) -> StateMutationResult<Vec<(Vec<u8>, Timestamp)>> {
+    let mut stmt = txn.prepare(
+        "
+        SELECT lock, expires_at_timestamp
+        FROM ChainLock
+        WHERE expires_at_timestamp >= :now
+        AND author = :author
+        ",
+    )?;
+    let locks = stmt
+        .query_map(
+            named_params! {
+                ":author": author,
+                ":now": holochain_zome_types::prelude::Timestamp::now()
+            },
+            |row| {
+                let mut lock: Vec<u8> = row.get("lock")?;
+                // Locks are stored with the author appended.
+                lock.truncate(lock.len().saturating_sub(author.get_raw_39().len()));
+                Ok((lock, row.get::<_, Timestamp>("expires_at_timestamp")?))
+            },
+        )?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(locks)
+}
@@ -0,0 +1,169 @@
+//! Module for items related to countersigning sessions held by the authorities
+//! of session entries, so that sessions survive restarts of the conductor.
+
+use holo_hash::ActionHash;
+use holo_hash::DhtOpHash;
+use holo_hash::EntryHash;
+use holochain_sqlite::rusqlite::named_params;
+use holochain_sqlite::rusqlite::Transaction;
+use holochain_types::dht_op::DhtOp;
+use holochain_zome_types::prelude::Timestamp;
+use mutations::StateMutationResult;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::mutations;
+use crate::prelude::from_blob;
+use crate::prelude::to_blob;
+use crate::prelude::StateQueryResult;
+
+/// The StoreEntry op of one signer of a countersigning session, held by an
+/// authority of the session entry until the session is complete or expires.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CountersigningSessionOp {
+    /// The hash of the session entry.
+    pub entry_hash: EntryHash,
+    /// The hash of the op.
+    pub op_hash: DhtOpHash,
+    /// The op.
+    pub op: DhtOp,
+    /// The hashes of the actions of all signers of the session.
+    pub required_actions: Vec<ActionHash>,
+    /// The end of the session.
+    pub expires_at: Timestamp,
+}
+
+/// Hold the op of a signer of a countersigning session.
+pub fn insert_countersigning_session_op(
+    txn: &mut Transaction,
+    op: &CountersigningSessionOp,
+) -> StateMutationResult<()> {
+    txn.execute(
+        "
+        INSERT INTO CountersigningSessionOp (op_hash, entry_hash, expires_at, blob)
+        VALUES (:op_hash, :entry_hash, :expires_at, :blob)
+        ",
+        named_params! {
+            ":op_hash": op.op_hash,
+            ":entry_hash": op.entry_hash,
+            ":expires_at": op.expires_at,
+            ":blob": to_blob(op)?,
+        },
+    )?;
+    Ok(())
+}
+
+/// Get the ops of all countersigning sessions which have not expired by `now`.
+pub fn list_countersigning_session_ops(
+    txn: &Transaction,
+    now: Timestamp,
+) -> StateQueryResult<Vec<CountersigningSessionOp>> {
+    let mut stmt = txn.prepare(
+        "
+        SELECT blob FROM CountersigningSessionOp
+        WHERE expires_at > :now
+        ",
+    )?;
+    let iter = stmt.query_and_then(
+        named_params! {
+            ":now": now
+        },
+        |row| from_blob::<CountersigningSessionOp>(row.get("blob")?),
+    )?;
+    iter.collect()
+}
+
+/// Drop the ops of a countersigning session, once it is complete.
+pub fn delete_countersigning_session(
+    txn: &mut Transaction,
+    entry_hash: &EntryHash,
+) -> StateMutationResult<()> {
+    txn.execute(
+        "DELETE FROM CountersigningSessionOp WHERE entry_hash = :entry_hash",
+        named_params! {
+            ":entry_hash": entry_hash
+        },
+    )?;
+    Ok(())
+}
+
+/// Drop the ops of all countersigning sessions which have expired by `now`.
+pub fn delete_expired_countersigning_sessions(
+    txn: &mut Transaction,
+    now: Timestamp,
+) -> StateMutationResult<()> {
+    txn.execute(
+        "DELETE FROM CountersigningSessionOp WHERE expires_at <= :now",
+        named_params! {
+            ":now": now
+        },
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use ::fixt::prelude::*;
+    use arbitrary::Arbitrary;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn session_ops_are_held_until_expired_or_deleted() {
+        let mut u = arbitrary::Unstructured::new(&holochain_zome_types::prelude::NOISE);
+        let db = test_dht_db();
+        let now = Timestamp::now();
+        let hour = std::time::Duration::from_secs(60 * 60);
+
+        let mut session_op =
+            |entry_hash: EntryHash, expires_at: Timestamp| CountersigningSessionOp {
+                entry_hash,
+                op_hash: fixt!(DhtOpHash),
+                op: DhtOp::arbitrary(&mut u).unwrap(),
+                required_actions: vec![fixt!(ActionHash)],
+                expires_at,
+            };
+        let live = session_op(fixt!(EntryHash), (now + hour).unwrap());
+        let expired = session_op(fixt!(EntryHash), (now - hour).unwrap());
+
+        db.to_db()
+            .write_async({
+                let live = live.clone();
+                move |txn| {
+                    insert_countersigning_session_op(txn, &live)?;
+                    insert_countersigning_session_op(txn, &expired)?;
+                    // Holding the same op twice is a no-op.
+                    insert_countersigning_session_op(txn, &live)
+                }
+            })
+            .await
+            .unwrap();
+
+        let held = db
+            .to_db()
+            .read_async(move |txn| list_countersigning_session_ops(&txn, now))
+            .await
+            .unwrap();
+        assert_eq!(vec![live.clone()], held);
+
+        db.to_db()
+            .write_async(move |txn| {
+                delete_expired_countersigning_sessions(txn, now)?;
+                delete_countersigning_session(txn, &live.entry_hash)
+            })
+            .await
+            .unwrap();
+        let held = db
+            .to_db()
+            .read_async(move |txn| {
+                let count: usize =
+                    txn.query_row("SELECT COUNT(*) FROM CountersigningSessionOp", [], |row| {
+                        row.get(0)
+                    })?;
+                StateQueryResult::Ok(count)
+            })
+            .await
+            .unwrap();
+        assert_eq!(0, held);
+    }
+}
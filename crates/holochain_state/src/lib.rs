@@ -30,6 +30,7 @@
 #[allow(missing_docs)]
 pub mod block;
 pub mod chain_lock;
+pub mod countersigning;
 #[allow(missing_docs)]
 pub mod dna_def;
 pub mod entry_def;
@@ -46,6 +47,7 @@ pub mod scratch;
 pub mod source_chain;
 pub mod validation_db;
 pub mod validation_receipts;
+pub mod warrant;
 #[allow(missing_docs)]
 pub mod wasm;
 pub mod workspace;
@@ -184,6 +184,24 @@ pub fn insert_validation_receipt(
     Ok(())
 }
 
+/// Insert a [`SignedWarrant`] into the database.
+pub fn insert_warrant(txn: &mut Transaction, warrant: SignedWarrant) -> StateMutationResult<()> {
+    let warrantee = warrant.warrantee().clone();
+    let author = warrant.warrant.author.clone();
+    let timestamp = warrant.warrant.timestamp;
+    let bytes: UnsafeBytes = SerializedBytes::try_from(warrant)?.into();
+    let bytes: Vec<u8> = bytes.into();
+    let hash = blake2b_256(&bytes);
+    sql_insert!(txn, Warrant, {
+        "hash": hash,
+        "author": author,
+        "warrantee": warrantee,
+        "timestamp": timestamp,
+        "blob": bytes,
+    })?;
+    Ok(())
+}
+
 /// Insert a [`DnaWasm`](holochain_types::prelude::DnaWasm) into the database.
 pub fn insert_wasm(txn: &mut Transaction, wasm: DnaWasmHashed) -> StateMutationResult<()> {
     let (wasm, hash) = wasm.into_inner();
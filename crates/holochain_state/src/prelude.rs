@@ -4,6 +4,7 @@ pub use crate::scratch::*;
 pub use crate::source_chain::*;
 pub use crate::validation_db::*;
 pub use crate::validation_receipts::*;
+pub use crate::warrant::*;
 pub use crate::wasm::*;
 pub use crate::workspace::*;
 
@@ -70,3 +70,32 @@ pub fn live_scheduled_fns(
     }
     Ok(ret)
 }
+
+/// All the functions the author has scheduled, whether or not they are due.
+pub fn scheduled_fns(
+    txn: &Transaction,
+    author: &AgentPubKey,
+) -> StateMutationResult<Vec<ScheduledFn>> {
+    let mut stmt = txn.prepare(
+        "
+        SELECT
+        zome_name,
+        scheduled_fn
+        FROM ScheduledFunctions
+        WHERE
+        author = :author
+        ORDER BY zome_name, scheduled_fn",
+    )?;
+    let rows = stmt.query_map(
+        named_params! {
+            ":author": author,
+        },
+        |row| {
+            Ok(ScheduledFn::new(
+                ZomeName(row.get::<_, String>(0)?.into()),
+                FunctionName(row.get(1)?),
+            ))
+        },
+    )?;
+    Ok(rows.collect::<Result<Vec<_>, _>>()?)
+}
@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
@@ -182,6 +183,26 @@ impl SourceChain {
         chain_top_ordering: ChainTopOrdering,
         weight: W,
     ) -> SourceChainResult<ActionHash> {
+        self.put_weighed_with(action_builder, maybe_entry, chain_top_ordering, |_, _| {
+            Ok(weight)
+        })
+        .await
+    }
+
+    /// Put a new record at the end of the source chain, using a ActionBuilder
+    /// and weighing the built action for rate limiting with the given function.
+    pub async fn put_weighed_with<W, U, B, F>(
+        &self,
+        action_builder: B,
+        maybe_entry: Option<Entry>,
+        chain_top_ordering: ChainTopOrdering,
+        weigh: F,
+    ) -> SourceChainResult<ActionHash>
+    where
+        U: ActionUnweighed<Weight = W>,
+        B: ActionBuilder<U>,
+        F: FnOnce(&U, Option<&Entry>) -> SourceChainResult<W>,
+    {
         let HeadInfo {
             action: prev_action,
             seq: chain_head_seq,
@@ -206,8 +227,10 @@ impl SourceChain {
             action_seq,
             prev_action,
         };
+        let action = action_builder.build(common);
+        let weight = weigh(&action, maybe_entry.as_ref())?;
         self.put_with_action(
-            action_builder.build(common).weighed(weight).into(),
+            action.weighed(weight).into(),
             maybe_entry,
             chain_top_ordering,
         )
@@ -658,6 +681,83 @@ where
         Ok(maybe_cap_grant)
     }
 
+    /// The capability grants on this chain which have neither been updated
+    /// nor deleted, filtered by the given query.
+    pub async fn live_cap_grants(
+        &self,
+        query: CapGrantQuery,
+    ) -> SourceChainResult<Vec<LiveCapGrant>> {
+        Ok(self
+            .live_records_of_entry_type(EntryType::CapGrant)
+            .await?
+            .into_iter()
+            .filter_map(|record| {
+                let action_hash = record.action_address().clone();
+                match record.into_inner().1.into_option()? {
+                    Entry::CapGrant(grant) if query.matches(&grant) => {
+                        Some(LiveCapGrant { action_hash, grant })
+                    }
+                    _ => None,
+                }
+            })
+            .collect())
+    }
+
+    /// The capability claims on this chain which have not been deleted,
+    /// filtered by the given query.
+    pub async fn live_cap_claims(
+        &self,
+        query: CapClaimQuery,
+    ) -> SourceChainResult<Vec<LiveCapClaim>> {
+        Ok(self
+            .live_records_of_entry_type(EntryType::CapClaim)
+            .await?
+            .into_iter()
+            .filter_map(|record| {
+                let action_hash = record.action_address().clone();
+                match record.into_inner().1.into_option()? {
+                    Entry::CapClaim(claim) if query.matches(&claim) => {
+                        Some(LiveCapClaim { action_hash, claim })
+                    }
+                    _ => None,
+                }
+            })
+            .collect())
+    }
+
+    /// All records on this chain, including the scratch, which create an entry
+    /// of the given type and which have neither been updated nor deleted.
+    async fn live_records_of_entry_type(
+        &self,
+        entry_type: EntryType,
+    ) -> SourceChainResult<Vec<Record>> {
+        let records = self
+            .query(
+                QueryFilter::new()
+                    .entry_type(entry_type)
+                    .include_entries(true),
+            )
+            .await?;
+        let replaced: HashSet<ActionHash> = self
+            .query(
+                QueryFilter::new()
+                    .action_type(ActionType::Update)
+                    .action_type(ActionType::Delete),
+            )
+            .await?
+            .iter()
+            .filter_map(|record| match record.action() {
+                Action::Update(update) => Some(update.original_action_address.clone()),
+                Action::Delete(delete) => Some(delete.deletes_address.clone()),
+                _ => None,
+            })
+            .collect();
+        Ok(records
+            .into_iter()
+            .filter(|record| !replaced.contains(record.action_address()))
+            .collect())
+    }
+
     /// Query Actions in the source chain.
     /// This returns a Vec rather than an iterator because it is intended to be
     /// used by the `query` host function, which crosses the wasm boundary
@@ -852,6 +952,20 @@ where
         Ok(query.filter_records(records))
     }
 
+    /// The actions on this chain which have been committed, and so are not
+    /// in the scratch, and were authored no earlier than the given timestamp,
+    /// in chain order.
+    pub async fn committed_actions_since(
+        &self,
+        since: Timestamp,
+    ) -> SourceChainResult<Vec<Action>> {
+        let author = self.author.clone();
+        Ok(self
+            .vault
+            .read_async(move |txn| author_actions_since(&txn, &author, since))
+            .await?)
+    }
+
     pub async fn is_chain_locked(&self, lock: Vec<u8>) -> SourceChainResult<bool> {
         let author = self.author.clone();
         Ok(self
@@ -1139,6 +1253,103 @@ pub fn chain_head_db_nonempty(
     chain_head_db(txn, author)?.ok_or(SourceChainError::ChainEmpty)
 }
 
+/// The actions by the given author which were authored no earlier than the
+/// given timestamp, in chain order.
+///
+/// This is meant for an authored database, which only holds the author's own
+/// chain, so there are no forks or invalid actions to leave out.
+pub fn author_actions_since(
+    txn: &Transaction,
+    author: &AgentPubKey,
+    since: Timestamp,
+) -> StateQueryResult<Vec<Action>> {
+    let mut stmt = txn.prepare(
+        "
+        SELECT Action.blob FROM Action
+        WHERE Action.author = :author
+        ORDER BY Action.seq DESC
+        ",
+    )?;
+    let mut rows = stmt.query(named_params! {
+        ":author": author,
+    })?;
+    let mut actions = Vec::new();
+    while let Some(row) = rows.next()? {
+        let SignedAction(action, _) = from_blob::<SignedAction>(row.get("blob")?)?;
+        // Timestamps only increase along a chain, so every earlier action is older.
+        if action.timestamp() < since {
+            break;
+        }
+        actions.push(action);
+    }
+    actions.reverse();
+    Ok(actions)
+}
+
+/// The actions which the chain ending with the given action is built on and
+/// were authored no earlier than the given timestamp, in chain order, out of
+/// those held in a DHT database.
+///
+/// The chain is followed back through each action's previous action, so the
+/// actions of other forks of the author's chain are never included. Only
+/// actions whose agent activity op has been integrated as valid are returned,
+/// while rejected or not yet integrated actions are skipped. The chain is
+/// followed until an action isn't held at all.
+pub fn valid_chain_actions_since(
+    txn: &Transaction,
+    last_action: Option<&ActionHash>,
+    since: Timestamp,
+) -> StateQueryResult<Vec<Action>> {
+    let mut stmt = txn.prepare(
+        "
+        SELECT Action.blob,
+        EXISTS(
+            SELECT 1 FROM DhtOp
+            WHERE DhtOp.action_hash = Action.hash
+            AND DhtOp.type = :activity
+            AND DhtOp.validation_status = :valid
+            AND DhtOp.when_integrated IS NOT NULL
+        ) AS is_valid
+        FROM Action
+        WHERE Action.hash = :hash
+        ",
+    )?;
+    let mut actions = Vec::new();
+    let mut next = last_action.cloned();
+    while let Some(hash) = next.take() {
+        let row = stmt
+            .query_row(
+                named_params! {
+                    ":hash": hash,
+                    ":activity": DhtOpType::RegisterAgentActivity,
+                    ":valid": ValidationStatus::Valid,
+                },
+                |row| {
+                    Ok((
+                        row.get::<_, Vec<u8>>("blob")?,
+                        row.get::<_, bool>("is_valid")?,
+                    ))
+                },
+            )
+            .optional()?;
+        let (blob, is_valid) = match row {
+            Some(row) => row,
+            None => break,
+        };
+        let SignedAction(action, _) = from_blob::<SignedAction>(blob)?;
+        // Timestamps only increase along a chain, so every earlier action is older.
+        if action.timestamp() < since {
+            break;
+        }
+        next = action.prev_action().cloned();
+        if is_valid {
+            actions.push(action);
+        }
+    }
+    actions.reverse();
+    Ok(actions)
+}
+
 /// Check if there is a current countersigning session and if so, return the
 /// session data and the entry hash.
 pub fn current_countersigning_session(
@@ -1299,6 +1510,116 @@ pub async fn dump_state(
         .await?)
 }
 
+/// All the records authored by the given author, in chain order and with
+/// their private entries, for exporting the chain.
+pub async fn export_records(
+    vault: DbRead<DbKindAuthored>,
+    author: AgentPubKey,
+) -> SourceChainResult<Vec<Record>> {
+    Ok(vault
+        .read_async(move |txn| {
+            txn.prepare(
+                "
+                SELECT
+                Action.blob AS action_blob, Entry.blob AS entry_blob,
+                Action.hash AS action_hash
+                FROM Action
+                LEFT JOIN Entry ON Action.entry_hash = Entry.hash
+                WHERE
+                Action.author = :author
+                ORDER BY Action.seq ASC
+                ",
+            )?
+            .query_and_then(
+                named_params! {
+                    ":author": author,
+                },
+                |row| {
+                    let SignedAction(action, signature) = from_blob(row.get("action_blob")?)?;
+                    let hash: ActionHash = row.get("action_hash")?;
+                    let entry: Option<Vec<u8>> = row.get("entry_blob")?;
+                    let entry: Option<Entry> = match entry {
+                        Some(entry) => Some(from_blob(entry)?),
+                        None => None,
+                    };
+                    let action = SignedActionHashed::with_presigned(
+                        ActionHashed::with_pre_hashed(action, hash),
+                        signature,
+                    );
+                    StateQueryResult::Ok(Record::new(action, entry))
+                },
+            )?
+            .collect::<StateQueryResult<Vec<_>>>()
+        })
+        .await?)
+}
+
+/// Check that the records form a whole chain by the given author, starting
+/// from genesis, that every action is signed by the author and hashes to its
+/// address, and that every entry is present and matches its action.
+///
+/// This checks the structure of the chain only, not the validity of its
+/// records according to the DNA.
+pub async fn check_chain_integrity(
+    author: &AgentPubKey,
+    records: &[Record],
+) -> SourceChainResult<()> {
+    match records.first().map(|record| record.action()) {
+        Some(Action::Dna(_)) => (),
+        _ => {
+            return Err(SourceChainError::InvalidStructure(
+                ChainInvalidReason::GenesisDataMissing,
+            ))
+        }
+    }
+    let mut prev_action: Option<&ActionHash> = None;
+    for (seq, record) in records.iter().enumerate() {
+        let action = record.action();
+        if action.author() != author {
+            return Err(SourceChainError::InvalidCommit(format!(
+                "Action {} was authored by {} rather than {}",
+                record.action_address(),
+                action.author(),
+                author
+            )));
+        }
+        if action.action_seq() as usize != seq || action.prev_action() != prev_action {
+            return Err(SourceChainError::InvalidPreviousAction(format!(
+                "Action {} at sequence number {} does not follow {:?}",
+                record.action_address(),
+                action.action_seq(),
+                prev_action
+            )));
+        }
+        if ActionHash::with_data_sync(action) != *record.action_address() {
+            return Err(SourceChainError::InvalidCommit(format!(
+                "Action {} does not hash to its address",
+                record.action_address()
+            )));
+        }
+        if !author.verify_signature(record.signature(), action).await? {
+            return Err(SourceChainError::InvalidSignature);
+        }
+        if let Some(entry_hash) = action.entry_hash() {
+            match record.entry().as_option() {
+                Some(entry) if EntryHash::with_data_sync(entry) == *entry_hash => (),
+                Some(_) => {
+                    return Err(SourceChainError::InvalidStructure(
+                        ChainInvalidReason::ActionAndEntryMismatch(entry_hash.clone()),
+                    ))
+                }
+                None => {
+                    return Err(SourceChainError::InvalidStructure(
+                        ChainInvalidReason::MissingData(entry_hash.clone()),
+                    ))
+                }
+            }
+        }
+        prev_action = Some(record.action_address());
+    }
+    Ok(())
+}
+
 impl From<SourceChain> for SourceChainRead {
     fn from(chain: SourceChain) -> Self {
         SourceChainRead {
@@ -1560,6 +1881,147 @@ mod tests {
         Ok(())
     }
 
+    async fn put_entry(
+        chain: &SourceChain,
+        entry: Entry,
+        entry_type: EntryType,
+    ) -> SourceChainResult<(ActionHash, EntryHash)> {
+        let (entry, entry_hash) = EntryHashed::from_content_sync(entry).into_inner();
+        let action_builder = builder::Create {
+            entry_type,
+            entry_hash: entry_hash.clone(),
+        };
+        let action_hash = chain
+            .put_weightless(action_builder, Some(entry), ChainTopOrdering::default())
+            .await?;
+        Ok((action_hash, entry_hash))
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_live_cap_grants_and_claims() -> SourceChainResult<()> {
+        let test_db = test_authored_db();
+        let dht_db = test_dht_db();
+        let dht_db_cache = DhtDbQueryCache::new(dht_db.to_db().into());
+        let keystore = test_keystore();
+        let db = test_db.to_db();
+        let mut mock = MockHolochainP2pDnaT::new();
+        mock.expect_authority_for_hash().returning(|_| Ok(false));
+        mock.expect_chc().return_const(None);
+
+        let mut agents = AgentPubKeyFixturator::new(Predictable);
+        let alice = agents.next().unwrap();
+        let bob = agents.next().unwrap();
+        source_chain::genesis(
+            db.clone(),
+            dht_db.to_db(),
+            &dht_db_cache,
+            keystore.clone(),
+            fake_dna_hash(1),
+            alice.clone(),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        let chain = SourceChain::new(
+            db.clone(),
+            dht_db.to_db(),
+            dht_db_cache.clone(),
+            keystore.clone(),
+            alice.clone(),
+        )
+        .await?;
+
+        let functions =
+            GrantedFunctions::Listed([("foo".into(), "bar".into())].into_iter().collect());
+        let secret = || CapSecretFixturator::new(Unpredictable).next().unwrap();
+        let transferable =
+            ZomeCallCapGrant::new("a".into(), CapAccess::from(secret()), functions.clone());
+        let assigned = ZomeCallCapGrant::new(
+            "b".into(),
+            CapAccess::from((secret(), bob.clone())),
+            functions.clone(),
+        );
+        let rolled =
+            ZomeCallCapGrant::new("a".into(), CapAccess::from(secret()), functions.clone());
+        let claim = CapClaim::new("c".into(), bob.clone(), secret());
+
+        let (transferable_hash, transferable_entry_hash) =
+            put_entry(&chain, Entry::CapGrant(transferable), EntryType::CapGrant).await?;
+        let (assigned_hash, _) = put_entry(
+            &chain,
+            Entry::CapGrant(assigned.clone()),
+            EntryType::CapGrant,
+        )
+        .await?;
+        let (claim_hash, _) =
+            put_entry(&chain, Entry::CapClaim(claim.clone()), EntryType::CapClaim).await?;
+        chain.flush(&mock).await.unwrap();
+
+        // roll the transferable grant, which replaces the original one
+        let chain = SourceChain::new(
+            db.clone(),
+            dht_db.to_db(),
+            dht_db_cache.clone(),
+            keystore.clone(),
+            alice.clone(),
+        )
+        .await?;
+        let (entry, entry_hash) =
+            EntryHashed::from_content_sync(Entry::CapGrant(rolled.clone())).into_inner();
+        let rolled_hash = chain
+            .put_weightless(
+                builder::Update {
+                    entry_type: EntryType::CapGrant,
+                    entry_hash,
+                    original_action_address: transferable_hash,
+                    original_entry_address: transferable_entry_hash,
+                },
+                Some(entry),
+                ChainTopOrdering::default(),
+            )
+            .await?;
+
+        // the update in the scratch is taken into account before flushing
+        let grants = chain.live_cap_grants(CapGrantQuery::default()).await?;
+        assert_eq!(grants.len(), 2);
+        assert!(grants.contains(&LiveCapGrant {
+            action_hash: assigned_hash.clone(),
+            grant: assigned.clone(),
+        }));
+        assert!(grants.contains(&LiveCapGrant {
+            action_hash: rolled_hash,
+            grant: rolled,
+        }));
+
+        let by_tag = chain
+            .live_cap_grants(CapGrantQuery::default().tag("b"))
+            .await?;
+        assert_eq!(by_tag.len(), 1);
+        assert_eq!(by_tag[0].action_hash, assigned_hash);
+
+        let by_grantee = chain
+            .live_cap_grants(CapGrantQuery::default().grantee(bob.clone()))
+            .await?;
+        assert_eq!(by_grantee.len(), 1);
+        assert_eq!(by_grantee[0].grant, assigned);
+
+        let claims = chain.live_cap_claims(CapClaimQuery::default()).await?;
+        assert_eq!(
+            claims,
+            vec![LiveCapClaim {
+                action_hash: claim_hash,
+                claim,
+            }]
+        );
+        assert!(chain
+            .live_cap_claims(CapClaimQuery::default().grantor(alice.clone()))
+            .await?
+            .is_empty());
+
+        Ok(())
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_get_cap_grant() -> SourceChainResult<()> {
         let test_db = test_authored_db();
@@ -2450,4 +2912,141 @@ mod tests {
         let zomes_initialized = chain.zomes_initialized().await.unwrap();
         assert!(zomes_initialized);
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn exported_chain_is_checked_for_integrity() {
+        let test_db = test_authored_db();
+        let dht_db = test_dht_db();
+        let keystore = test_keystore();
+        let db = test_db.to_db();
+        let alice = fixt!(AgentPubKey, Predictable, 0);
+        let dht_db_cache = DhtDbQueryCache::new(dht_db.to_db().into());
+
+        source_chain::genesis(
+            db.clone(),
+            dht_db.to_db(),
+            &dht_db_cache,
+            keystore.clone(),
+            fake_dna_hash(1),
+            alice.clone(),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let records = export_records(db.clone().into(), alice.clone())
+            .await
+            .unwrap();
+        assert_eq!(3, records.len());
+        assert!(records[2].entry().as_option().is_some());
+        check_chain_integrity(&alice, &records).await.unwrap();
+
+        // Another author
+        let bob = fixt!(AgentPubKey, Predictable, 1);
+        assert_matches!(
+            check_chain_integrity(&bob, &records).await,
+            Err(SourceChainError::InvalidCommit(_))
+        );
+
+        // Missing genesis
+        assert_matches!(
+            check_chain_integrity(&alice, &records[1..]).await,
+            Err(SourceChainError::InvalidStructure(
+                ChainInvalidReason::GenesisDataMissing
+            ))
+        );
+
+        // A gap in the chain
+        let gapped = vec![records[0].clone(), records[2].clone()];
+        assert_matches!(
+            check_chain_integrity(&alice, &gapped).await,
+            Err(SourceChainError::InvalidPreviousAction(_))
+        );
+
+        // A missing entry
+        let (action, _) = records[2].clone().into_inner();
+        let missing_entry = vec![
+            records[0].clone(),
+            records[1].clone(),
+            Record::new(action, None),
+        ];
+        assert_matches!(
+            check_chain_integrity(&alice, &missing_entry).await,
+            Err(SourceChainError::InvalidStructure(
+                ChainInvalidReason::MissingData(_)
+            ))
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn valid_chain_actions_since_follows_the_valid_chain() {
+        holochain_trace::test_run().ok();
+        let test_db = test_dht_db();
+        let db = test_db.to_db();
+        let author = fixt!(AgentPubKey);
+
+        let action = |prev_action: ActionHash, seq: u32, secs: i64| {
+            let mut create = fixt!(Create);
+            create.author = author.clone();
+            create.prev_action = prev_action;
+            create.action_seq = seq;
+            create.timestamp = Timestamp::from_micros(secs * 1_000_000);
+            let action = Action::Create(create);
+            (ActionHash::with_data_sync(&action), action)
+        };
+        // The oldest action is authored before the window, and the chain forks after `a1`.
+        let (h0, a0) = action(fixt!(ActionHash), 5, 1);
+        let (h1, a1) = action(h0.clone(), 6, 10);
+        let (h2, a2) = action(h1.clone(), 7, 11);
+        let (_, fork) = action(h1.clone(), 7, 12);
+        let (h3, a3) = action(h2.clone(), 8, 13);
+
+        let ops: Vec<_> = [
+            (a0.clone(), Some(ValidationStatus::Valid)),
+            (a1.clone(), Some(ValidationStatus::Valid)),
+            (a2.clone(), Some(ValidationStatus::Rejected)),
+            (fork, Some(ValidationStatus::Valid)),
+            (a3.clone(), None),
+        ]
+        .into_iter()
+        .map(|(action, status)| {
+            let op = DhtOpHashed::from_content_sync(DhtOp::RegisterAgentActivity(
+                fixt!(Signature),
+                action,
+            ));
+            (op, status)
+        })
+        .collect();
+        let a3_op_hash = ops[4].0.as_hash().clone();
+        db.test_write(move |txn| {
+            for (op, status) in ops {
+                insert_op(txn, &op).unwrap();
+                if let Some(status) = status {
+                    set_validation_status(txn, op.as_hash(), status).unwrap();
+                    set_when_integrated(txn, op.as_hash(), Timestamp::now()).unwrap();
+                }
+            }
+        });
+
+        let since = Timestamp::from_micros(5_000_000);
+        let query = |last_action: Option<ActionHash>| {
+            db.test_read(move |txn| {
+                valid_chain_actions_since(&txn, last_action.as_ref(), since).unwrap()
+            })
+        };
+
+        // Rejected, unintegrated and forked actions, and actions before the window, are left out.
+        assert_eq!(query(Some(h3.clone())), vec![a1.clone()]);
+        // The chain isn't followed past an action which isn't held.
+        assert_eq!(query(Some(fixt!(ActionHash))), vec![]);
+        assert_eq!(query(None), vec![]);
+
+        // Once integrated as valid, the action is counted.
+        db.test_write(move |txn| {
+            set_validation_status(txn, &a3_op_hash, ValidationStatus::Valid).unwrap();
+            set_when_integrated(txn, &a3_op_hash, Timestamp::now()).unwrap();
+        });
+        assert_eq!(query(Some(h3)), vec![a1, a3]);
+    }
 }
@@ -0,0 +1,176 @@
+//! Module for items related to warrants held by agent activity authorities
+
+use holo_hash::ActionHash;
+use holo_hash::AgentPubKey;
+use holochain_sqlite::rusqlite::named_params;
+use holochain_sqlite::rusqlite::Transaction;
+use holochain_zome_types::prelude::SignedAction;
+use holochain_zome_types::warrant::ActionHashAndSig;
+use holochain_zome_types::warrant::SignedWarrant;
+use mutations::StateMutationResult;
+
+use crate::mutations;
+use crate::prelude::from_blob;
+use crate::prelude::StateQueryResult;
+
+/// Get all the warrants about an agent.
+pub fn list_warrants(
+    txn: &Transaction,
+    warrantee: &AgentPubKey,
+) -> StateQueryResult<Vec<SignedWarrant>> {
+    let mut stmt = txn.prepare(
+        "
+        SELECT blob FROM Warrant WHERE warrantee = :warrantee
+        ORDER BY timestamp ASC
+        ",
+    )?;
+    let iter = stmt.query_and_then(
+        named_params! {
+            ":warrantee": warrantee
+        },
+        |row| from_blob::<SignedWarrant>(row.get("blob")?),
+    )?;
+    iter.collect()
+}
+
+/// Add a warrant unless the same warrant is already held.
+pub fn add_warrant_if_unique(
+    txn: &mut Transaction,
+    warrant: SignedWarrant,
+) -> StateMutationResult<()> {
+    mutations::insert_warrant(txn, warrant)
+}
+
+/// Find an action by the given author at the given sequence number
+/// which is not the given action, which proves that the author forked their chain.
+pub fn find_chain_fork(
+    txn: &Transaction,
+    author: &AgentPubKey,
+    seq: u32,
+    action_hash: &ActionHash,
+) -> StateQueryResult<Option<ActionHashAndSig>> {
+    let mut stmt = txn.prepare(
+        "
+        SELECT hash, blob FROM Action
+        WHERE author = :author AND seq = :seq AND hash != :hash
+        LIMIT 1
+        ",
+    )?;
+    let mut rows = stmt.query(named_params! {
+        ":author": author,
+        ":seq": seq,
+        ":hash": action_hash,
+    })?;
+    match rows.next()? {
+        Some(row) => {
+            let hash: ActionHash = row.get("hash")?;
+            let SignedAction(_, signature) = from_blob::<SignedAction>(row.get("blob")?)?;
+            Ok(Some((hash, signature)))
+        }
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use ::fixt::prelude::*;
+    use holochain_keystore::test_keystore;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn warrants_are_listed_by_warrantee() {
+        holochain_trace::test_run().ok();
+
+        let keystore = test_keystore();
+        let db = test_dht_db();
+        let author = keystore.new_sign_keypair_random().await.unwrap();
+        let warrantee = fixt!(AgentPubKey);
+
+        let warrant = Warrant::new(
+            WarrantProof::ChainIntegrity(ChainIntegrityWarrant::InvalidChainOp {
+                action_author: warrantee.clone(),
+                action: (fixt!(ActionHash), fixt!(Signature)),
+                validation_type: ValidationType::App,
+            }),
+            author,
+            Timestamp::now(),
+        );
+        let warrant = SignedWarrant::sign(&keystore, warrant).await.unwrap();
+        warrant.validate().await.unwrap();
+
+        {
+            let warrant = warrant.clone();
+            db.to_db()
+                .write_async(move |txn| -> StateMutationResult<()> {
+                    // Adding the same warrant twice only stores it once.
+                    add_warrant_if_unique(txn, warrant.clone())?;
+                    add_warrant_if_unique(txn, warrant)
+                })
+                .await
+                .unwrap();
+        }
+
+        let other_agent = fixt!(AgentPubKey);
+        let (warrants, other_warrants) = db
+            .to_db()
+            .read_async(move |txn| -> StateQueryResult<_> {
+                Ok((
+                    list_warrants(&txn, &warrantee)?,
+                    list_warrants(&txn, &other_agent)?,
+                ))
+            })
+            .await
+            .unwrap();
+        assert_eq!(warrants, vec![warrant]);
+        assert!(other_warrants.is_empty());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn chain_fork_is_found_by_author_and_seq() {
+        holochain_trace::test_run().ok();
+
+        let db = test_dht_db();
+        let author = fixt!(AgentPubKey);
+        let mut create = fixt!(Create);
+        create.author = author.clone();
+        create.action_seq = 5;
+        let mut fork = create.clone();
+        fork.timestamp = (create.timestamp + std::time::Duration::from_secs(1)).unwrap();
+
+        let action = SignedActionHashed::with_presigned(
+            ActionHashed::from_content_sync(Action::Create(create)),
+            fixt!(Signature),
+        );
+        let forked = SignedActionHashed::with_presigned(
+            ActionHashed::from_content_sync(Action::Create(fork)),
+            fixt!(Signature),
+        );
+        let action_hash = action.as_hash().clone();
+        let expected = (forked.as_hash().clone(), forked.signature().clone());
+
+        db.to_db()
+            .write_async({
+                let action = action.clone();
+                move |txn| -> StateMutationResult<()> {
+                    mutations::insert_action(txn, &action)?;
+                    mutations::insert_action(txn, &forked)
+                }
+            })
+            .await
+            .unwrap();
+
+        let (found, not_found) = db
+            .to_db()
+            .read_async(move |txn| -> StateQueryResult<_> {
+                Ok((
+                    find_chain_fork(&txn, &author, 5, &action_hash)?,
+                    find_chain_fork(&txn, &author, 6, &action_hash)?,
+                ))
+            })
+            .await
+            .unwrap();
+        assert_eq!(found, Some(expected));
+        assert!(not_found.is_none());
+    }
+}
@@ -1,7 +1,9 @@
 use anyhow::anyhow;
 use holo_hash::{AgentPubKey, DnaHash};
 use holochain_conductor_api::{
-    AdminRequest, AdminResponse, AppInfo, AppRequest, AppResponse, CellInfo, NetworkInfo,
+    AdminRequest, AdminResponse, AppAuthenticationRequest, AppAuthenticationResponse,
+    AppAuthenticationToken, AppInfo, AppRequest, AppResponse, CellInfo,
+    IssueAppAuthenticationTokenPayload, NetworkInfo,
 };
 use holochain_types::prelude::{InstalledAppId, NetworkInfoRequestPayload};
 use holochain_websocket::{connect, ConnectRequest, WebsocketConfig, WebsocketSender};
@@ -19,13 +21,23 @@ impl Drop for AppClient {
 }
 
 impl AppClient {
-    /// Creates a App websocket client which can send messages but ignores any incoming messages
-    async fn connect(addr: std::net::SocketAddr) -> anyhow::Result<Self> {
+    /// Creates a App websocket client which can send messages but ignores any incoming messages.
+    /// The connection is authenticated with the given token.
+    async fn connect(
+        addr: std::net::SocketAddr,
+        token: AppAuthenticationToken,
+    ) -> anyhow::Result<Self> {
         let (tx, mut rx) = connect(
             Arc::new(WebsocketConfig::CLIENT_DEFAULT),
             ConnectRequest::new(addr).try_set_header("origin", "hcterm")?,
         )
         .await?;
+        match tx.authenticate(AppAuthenticationRequest { token }).await? {
+            AppAuthenticationResponse::Authenticated => (),
+            AppAuthenticationResponse::AuthenticationFailed(reason) => {
+                anyhow::bail!("App interface authentication failed: {reason}")
+            }
+        }
 
         let rx = tokio::task::spawn(async move { while rx.recv::<AppResponse>().await.is_ok() {} });
 
@@ -120,7 +132,10 @@ impl AdminClient {
         Ok(AdminClient { tx, rx, addr })
     }
 
-    pub async fn connect_app_client(&mut self) -> anyhow::Result<AppClient> {
+    pub async fn connect_app_client(
+        &mut self,
+        installed_app_id: InstalledAppId,
+    ) -> anyhow::Result<AppClient> {
         let app_interfaces = self.list_app_interfaces().await?;
         let app_port = if app_interfaces.is_empty() {
             self.attach_app_interface(0).await?
@@ -129,8 +144,25 @@ impl AdminClient {
         };
 
         let app_addr = (self.addr.ip(), app_port).into();
+        let token = self
+            .issue_app_authentication_token(installed_app_id)
+            .await?;
 
-        AppClient::connect(app_addr).await
+        AppClient::connect(app_addr, token).await
+    }
+
+    async fn issue_app_authentication_token(
+        &mut self,
+        installed_app_id: InstalledAppId,
+    ) -> anyhow::Result<AppAuthenticationToken> {
+        let msg = AdminRequest::IssueAppAuthenticationToken(
+            IssueAppAuthenticationTokenPayload::for_installed_app_id(installed_app_id),
+        );
+        let response = self.send(msg).await?;
+        match response {
+            AdminResponse::AppAuthenticationTokenIssued(issued) => Ok(issued.token),
+            _ => unreachable!("Unexpected response {:?}", response),
+        }
     }
 
     async fn list_app_interfaces(&mut self) -> anyhow::Result<Vec<u16>> {
@@ -146,6 +178,9 @@ impl AdminClient {
         let msg = AdminRequest::AttachAppInterface {
             port: Some(port),
             allowed_origins: "hcterm".to_string().into(),
+            installed_app_id: None,
+            bind_address: None,
+            tls: None,
         };
         let response = self.send(msg).await?;
         match response {
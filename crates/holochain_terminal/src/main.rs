@@ -21,6 +21,7 @@ fn main() -> anyhow::Result<()> {
     let args = Args::parse();
     args.validate()?;
 
+    let app_id = args.app_id.clone();
     let (admin_client, app_client) = if let Some(admin_url) = &args.admin_url {
         match block_on(
             async {
@@ -39,13 +40,18 @@ fn main() -> anyhow::Result<()> {
                 };
 
                 let mut admin_client = AdminClient::connect(addr).await?;
-                let app_client = admin_client.connect_app_client().await?;
+                // App clients must authenticate for an app, so one can only be
+                // connected if an app was given
+                let app_client = match app_id {
+                    Some(app_id) => Some(admin_client.connect_app_client(app_id).await?),
+                    None => None,
+                };
 
                 Ok((admin_client, app_client))
             },
             Duration::from_secs(10),
         ) {
-            Ok(Ok((admin_client, app_client))) => (Some(admin_client), Some(app_client)),
+            Ok(Ok((admin_client, app_client))) => (Some(admin_client), app_client),
             Ok(Err(e)) => {
                 return Err(e);
             }
@@ -100,6 +100,17 @@ pub struct CreateCloneCellPayload {
     pub name: Option<String>,
 }
 
+/// The arguments to instantiate the cell of a role whose provisioning was deferred.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct ProvisionDeferredCellPayload {
+    /// The app id that the deferred role belongs to
+    pub app_id: InstalledAppId,
+    /// The name of the deferred role
+    pub role_name: RoleName,
+    /// Optionally set a proof of membership for the new cell
+    pub membrane_proof: Option<MembraneProof>,
+}
+
 /// Arguments to specify the clone cell to be disabled.
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct DisableCloneCellPayload {
@@ -169,6 +180,71 @@ impl AppBundleSource {
     }
 }
 
+/// The identifier of an app bundle published in an app store:
+/// the hash of the app entry whose bytes are the encoded bundle.
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    derive_more::Display,
+    derive_more::From,
+    Into,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+#[serde(transparent)]
+pub struct AppHash(pub EntryHash);
+
+impl AppHash {
+    /// The hash of an encoded app bundle, stored as the bytes of an app entry
+    pub fn from_bundle_bytes(bytes: &[u8]) -> Self {
+        let entry = Entry::App(AppEntryBytes(SerializedBytes::from(UnsafeBytes::from(
+            bytes.to_vec(),
+        ))));
+        Self(EntryHash::with_data_sync(&entry))
+    }
+}
+
+/// Arguments to install an app which is fetched from the app store service
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct InstallAppFromStorePayload {
+    /// The hash of the app bundle in the app store.
+    pub app_hash: AppHash,
+
+    /// The agent to use when creating Cells for this App.
+    pub agent_key: AgentPubKey,
+
+    /// The unique identifier for an installed app in this conductor.
+    /// If not specified, it will be derived from the app name in the bundle manifest.
+    pub installed_app_id: Option<InstalledAppId>,
+
+    /// Include proof-of-membrane-membership data for cells that require it,
+    /// keyed by the RoleName specified in the app bundle manifest.
+    #[serde(default)]
+    pub membrane_proofs: HashMap<RoleName, MembraneProof>,
+
+    /// Optional: overwrites all network seeds for all DNAs of Cells created by this app.
+    pub network_seed: Option<NetworkSeed>,
+}
+
+impl InstallAppFromStorePayload {
+    /// Turn this into an [`InstallAppPayload`] for the bundle which was
+    /// fetched from the app store.
+    pub fn into_install_app_payload(self, bundle: AppBundle) -> InstallAppPayload {
+        InstallAppPayload {
+            source: AppBundleSource::Bundle(bundle),
+            agent_key: self.agent_key,
+            installed_app_id: self.installed_app_id,
+            membrane_proofs: self.membrane_proofs,
+            network_seed: self.network_seed,
+            #[cfg(feature = "chc")]
+            ignore_genesis_failure: false,
+        }
+    }
+}
+
 /// Information needed to specify a DNA as part of an App
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct InstallAppDnaPayload {
@@ -444,6 +520,32 @@ impl InstalledAppCommon {
             .filter_map(|(role_name, role)| role.provisioned_cell().map(|c| (role_name, c)))
     }
 
+    /// Accessor for the provisioned cells which were created by this app,
+    /// as opposed to existing cells of other apps which this app uses
+    pub fn own_provisioned_cells(&self) -> impl Iterator<Item = (&RoleName, &CellId)> {
+        self.role_assignments
+            .iter()
+            .filter(|(_, role)| !role.uses_existing())
+            .filter_map(|(role_name, role)| role.provisioned_cell().map(|c| (role_name, c)))
+    }
+
+    /// Accessor for the existing cells of other apps which this app uses
+    pub fn existing_cells(&self) -> impl Iterator<Item = (&RoleName, &CellId)> {
+        self.role_assignments
+            .iter()
+            .filter(|(_, role)| role.uses_existing())
+            .map(|(role_name, role)| (role_name, role.cell_id()))
+    }
+
+    /// Accessor for the cells of roles whose provisioning was deferred and
+    /// which have not been provisioned yet
+    pub fn deferred_cells(&self) -> impl Iterator<Item = (&RoleName, &CellId)> {
+        self.role_assignments
+            .iter()
+            .filter(|(_, role)| role.is_deferred())
+            .map(|(role_name, role)| (role_name, role.cell_id()))
+    }
+
     /// Accessor
     pub fn into_provisioned_cells(self) -> impl Iterator<Item = (RoleName, CellId)> {
         self.role_assignments
@@ -541,6 +643,38 @@ impl InstalledAppCommon {
         &self.role_assignments
     }
 
+    /// Claim the cell of a deferred role for provisioning, returning its id.
+    ///
+    /// The role is no longer deferred, so it can't be claimed twice, but its cell
+    /// stays unprovisioned until genesis has run and [`Self::provision_deferred_cell`]
+    /// is called, or the claim is given up with [`Self::release_deferred_cell`].
+    pub fn claim_deferred_cell(&mut self, role_name: &RoleName) -> AppResult<CellId> {
+        let role = self.role_mut(role_name)?;
+        if !role.is_deferred() {
+            return Err(AppError::RoleNotDeferred(role_name.clone()));
+        }
+        role.is_deferred = false;
+        Ok(role.cell_id().clone())
+    }
+
+    /// Mark the claimed cell of a deferred role as provisioned, returning its id.
+    ///
+    /// Genesis must already have been run for the cell.
+    pub fn provision_deferred_cell(&mut self, role_name: &RoleName) -> AppResult<CellId> {
+        let role = self.role_mut(role_name)?;
+        role.is_provisioned = true;
+        Ok(role.cell_id().clone())
+    }
+
+    /// Defer a claimed role again, when genesis of its cell failed.
+    pub fn release_deferred_cell(&mut self, role_name: &RoleName) -> AppResult<()> {
+        let role = self.role_mut(role_name)?;
+        if !role.is_provisioned {
+            role.is_deferred = true;
+        }
+        Ok(())
+    }
+
     /// Add a clone cell.
     pub fn add_clone(&mut self, role_name: &RoleName, cell_id: &CellId) -> AppResult<CloneId> {
         let app_role_assignment = self.role_mut(role_name)?;
@@ -762,6 +896,8 @@ impl InstalledAppCommon {
                 let role = AppRoleAssignment {
                     base_cell_id: cell_id,
                     is_provisioned: true,
+                    is_deferred: false,
+                    uses_existing: false,
                     clones: HashMap::new(),
                     clone_limit: 256,
                     next_clone_index: 0,
@@ -1002,6 +1138,16 @@ pub struct AppRoleAssignment {
     /// If false, then `base_cell_id` is just recording what that cell will be
     /// called in the future.
     is_provisioned: bool,
+    /// Records whether provisioning of the base cell was deferred at installation.
+    /// A deferred cell is not provisioned until it is explicitly requested,
+    /// usually along with a membrane proof which was not available at installation.
+    #[serde(default)]
+    is_deferred: bool,
+    /// Records whether the base cell was created by another app and is only
+    /// used by this one. The other app can't be uninstalled while this one
+    /// depends on its cell.
+    #[serde(default)]
+    uses_existing: bool,
     /// The number of allowed clone cells.
     clone_limit: u32,
     /// The index of the next clone cell to be created.
@@ -1021,6 +1167,8 @@ impl AppRoleAssignment {
         Self {
             base_cell_id,
             is_provisioned,
+            is_deferred: false,
+            uses_existing: false,
             clone_limit,
             clones: HashMap::new(),
             next_clone_index: 0,
@@ -1028,6 +1176,22 @@ impl AppRoleAssignment {
         }
     }
 
+    /// Constructor for a role whose base cell was created by another app.
+    pub fn new_existing(base_cell_id: CellId, clone_limit: u32) -> Self {
+        Self {
+            uses_existing: true,
+            ..Self::new(base_cell_id, true, clone_limit)
+        }
+    }
+
+    /// Constructor for a role whose base cell will be provisioned later.
+    pub fn new_deferred(base_cell_id: CellId, clone_limit: u32) -> Self {
+        Self {
+            is_deferred: true,
+            ..Self::new(base_cell_id, false, clone_limit)
+        }
+    }
+
     /// Accessor
     pub fn cell_id(&self) -> &CellId {
         &self.base_cell_id
@@ -1052,6 +1216,16 @@ impl AppRoleAssignment {
         }
     }
 
+    /// Accessor
+    pub fn is_deferred(&self) -> bool {
+        self.is_deferred
+    }
+
+    /// Accessor
+    pub fn uses_existing(&self) -> bool {
+        self.uses_existing
+    }
+
     /// Accessor
     pub fn clone_ids(&self) -> impl Iterator<Item = &CloneId> {
         self.clones.keys()
@@ -1,6 +1,10 @@
 //! An App Bundle is an AppManifest bundled together with DNA bundles.
 
-use std::{collections::HashMap, path::PathBuf, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    sync::Arc,
+};
 
 use super::{AppManifest, AppManifestValidated};
 use crate::prelude::*;
@@ -57,12 +61,16 @@ impl AppBundle {
     }
 
     /// Given a partial list of already available DnaFiles, fetch the missing others via
-    /// mr_bundle::Location resolution
+    /// mr_bundle::Location resolution.
+    ///
+    /// Roles which use existing cells are matched against `existing_cells`,
+    /// the cells already installed on the conductor.
     pub async fn resolve_cells(
         self,
         dna_store: &impl DnaStore,
         agent: AgentPubKey,
         membrane_proofs: HashMap<RoleName, MembraneProof>,
+        existing_cells: &HashSet<CellId>,
     ) -> AppBundleResult<AppRoleResolution> {
         let AppManifestValidated { name: _, roles } = self.manifest().clone().validate()?;
         let bundle = Arc::new(self);
@@ -70,7 +78,9 @@ impl AppBundle {
             let bundle = bundle.clone();
             Ok((
                 role_name.clone(),
-                bundle.resolve_cell(dna_store, role_name, role).await?,
+                bundle
+                    .resolve_cell(dna_store, role_name, role, &agent, existing_cells)
+                    .await?,
             ))
         });
         let resolution = futures::future::join_all(tasks)
@@ -94,9 +104,21 @@ impl AppBundle {
                         }
 
                         CellProvisioningOp::Existing(cell_id, clone_limit) => {
-                            let role = AppRoleAssignment::new(cell_id, true, clone_limit);
+                            let role = AppRoleAssignment::new_existing(cell_id, clone_limit);
                             resolution.role_assignments.push((role_name, role));
                         }
+                        CellProvisioningOp::Deferred(dna, clone_limit) => {
+                            let agent = resolution.agent.clone();
+                            let dna_hash = dna.dna_hash().clone();
+                            let cell_id = CellId::new(dna_hash, agent);
+
+                            // The membrane proof is supplied when the cell is provisioned.
+                            resolution.dnas_to_register.push((dna, None));
+                            resolution.role_assignments.push((
+                                role_name,
+                                AppRoleAssignment::new_deferred(cell_id, clone_limit),
+                            ));
+                        }
                         CellProvisioningOp::ProvisionOnly(dna, clone_limit) => {
                             let agent = resolution.agent.clone();
                             let dna_hash = dna.dna_hash().clone();
@@ -110,14 +132,6 @@ impl AppBundle {
                                 AppRoleAssignment::new(cell_id, false, clone_limit),
                             ));
                         }
-                        other @ (CellProvisioningOp::HashMismatch(_, _)
-                        | CellProvisioningOp::Conflict(_)) => {
-                            tracing::error!(
-                                "Encountered unexpected CellProvisioningOp: {:?}",
-                                other
-                            );
-                            unimplemented!()
-                        }
                     }
 
                     resolution
@@ -133,6 +147,8 @@ impl AppBundle {
         dna_store: &impl DnaStore,
         role_name: RoleName,
         role: AppRoleManifestValidated,
+        agent: &AgentPubKey,
+        existing_cells: &HashSet<CellId>,
     ) -> AppBundleResult<CellProvisioningOp> {
         Ok(match role {
             AppRoleManifestValidated::Create {
@@ -140,7 +156,7 @@ impl AppBundle {
                 installed_hash,
                 clone_limit,
                 modifiers,
-                deferred: _,
+                deferred,
             } => {
                 let dna = self
                     .resolve_dna(
@@ -151,23 +167,39 @@ impl AppBundle {
                         modifiers,
                     )
                     .await?;
-                CellProvisioningOp::CreateFromDnaFile(dna, clone_limit)
+                if deferred {
+                    CellProvisioningOp::Deferred(dna, clone_limit)
+                } else {
+                    CellProvisioningOp::CreateFromDnaFile(dna, clone_limit)
+                }
             }
 
+            // An existing cell needs no genesis, so there is nothing to defer.
             AppRoleManifestValidated::UseExisting {
                 installed_hash,
                 clone_limit,
                 deferred: _,
-            } => self.resolve_cell_existing(&installed_hash, clone_limit),
+            } => match Self::resolve_cell_existing(&installed_hash, agent, existing_cells) {
+                Some(cell_id) => CellProvisioningOp::Existing(cell_id, clone_limit),
+                None => {
+                    return Err(AppBundleError::CellResolutionFailure(
+                        role_name,
+                        format!(
+                            "No existing cell with DNA hash {} for agent {}",
+                            installed_hash, agent
+                        ),
+                    ))
+                }
+            },
             AppRoleManifestValidated::CreateIfNotExists {
                 location,
                 installed_hash,
                 clone_limit,
                 modifiers,
-                deferred: _,
-            } => match self.resolve_cell_existing(&installed_hash, clone_limit) {
-                op @ CellProvisioningOp::Existing(_, _) => op,
-                CellProvisioningOp::HashMismatch(_, _) => {
+                deferred,
+            } => match Self::resolve_cell_existing(&installed_hash, agent, existing_cells) {
+                Some(cell_id) => CellProvisioningOp::Existing(cell_id, clone_limit),
+                None => {
                     let dna = self
                         .resolve_dna(
                             role_name,
@@ -177,16 +209,11 @@ impl AppBundle {
                             modifiers,
                         )
                         .await?;
-                    CellProvisioningOp::CreateFromDnaFile(dna, clone_limit)
-                }
-                CellProvisioningOp::Conflict(_) => {
-                    unimplemented!("conflicts are not handled, or even possible yet")
-                }
-                CellProvisioningOp::CreateFromDnaFile(_, _) => {
-                    unreachable!("resolve_cell_existing will never return a Create op")
-                }
-                CellProvisioningOp::ProvisionOnly(_, _) => {
-                    unreachable!("resolve_cell_existing will never return a ProvisionOnly")
+                    if deferred {
+                        CellProvisioningOp::Deferred(dna, clone_limit)
+                    } else {
+                        CellProvisioningOp::CreateFromDnaFile(dna, clone_limit)
+                    }
                 }
             },
             AppRoleManifestValidated::CloneOnly {
@@ -240,12 +267,14 @@ impl AppBundle {
         Ok(dna_file)
     }
 
+    /// Find the existing cell of the agent for the DNA with the given hash
     fn resolve_cell_existing(
-        &self,
-        _version: &DnaHashB64,
-        _clone_limit: u32,
-    ) -> CellProvisioningOp {
-        unimplemented!("Reusing existing cells is not yet implemented")
+        installed_hash: &DnaHashB64,
+        agent: &AgentPubKey,
+        existing_cells: &HashSet<CellId>,
+    ) -> Option<CellId> {
+        let cell_id = CellId::new(installed_hash.clone().into(), agent.clone());
+        existing_cells.contains(&cell_id).then_some(cell_id)
     }
 
     async fn resolve_location(
@@ -260,12 +289,6 @@ impl AppBundle {
     }
 }
 
-/// This function is called in places where it will be necessary to rework that
-/// area after use_existing has been implemented
-pub fn we_must_remember_to_rework_cell_panic_handling_after_implementing_use_existing_cell_resolution(
-) {
-}
-
 /// The answer to the question:
 /// "how do we concretely assign DNAs to the open roles of this App?"
 /// Includes the DNAs selected to fill the roles and the details of the role assignments.
@@ -328,12 +351,7 @@ pub enum CellProvisioningOp {
     /// No creation needed, but there might be a clone_limit, and so we need
     /// to know which DNA to use for making clones
     ProvisionOnly(DnaFile, u32),
-    /// The specified installed_hash does not match the actual hash of the DNA selected for provisioning. Expected: {0}, Actual: {1}
-    HashMismatch(DnaHash, DnaHash),
-    /// Ambiguous result, needs manual resolution; can't provision (should this be an Err?)
-    Conflict(CellProvisioningConflict),
+    /// Register the given DNA file, but don't create the Cell until it is
+    /// explicitly provisioned later
+    Deferred(DnaFile, u32),
 }
-
-/// Uninhabitable placeholder
-#[derive(Debug)]
-pub enum CellProvisioningConflict {}
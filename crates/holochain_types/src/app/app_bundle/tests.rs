@@ -61,6 +61,7 @@ async fn provisioning_1_create() {
             &std::collections::HashMap::new(),
             agent.clone(),
             Default::default(),
+            &Default::default(),
         )
         .await
         .unwrap();
@@ -76,3 +77,102 @@ async fn provisioning_1_create() {
     };
     assert_eq!(resolution, expected);
 }
+
+/// Test that a deferred role registers its DNA without creating a cell
+#[tokio::test]
+async fn provisioning_1_create_deferred() {
+    holochain_trace::test_run().ok();
+    let agent = fixt!(AgentPubKey);
+    let (bundle, dna) = app_bundle_fixture(DnaModifiersOpt::none()).await;
+
+    let mut manifest = bundle.manifest().clone();
+    match &mut manifest {
+        AppManifest::V1(manifest) => {
+            manifest.roles[0].provisioning = Some(CellProvisioning::Create { deferred: true });
+        }
+    }
+    let bundle: AppBundle = bundle
+        .into_inner()
+        .update_manifest(manifest)
+        .unwrap()
+        .into();
+
+    let resolution = bundle
+        .resolve_cells(
+            &std::collections::HashMap::new(),
+            agent.clone(),
+            Default::default(),
+            &Default::default(),
+        )
+        .await
+        .unwrap();
+
+    let cell_id = CellId::new(dna.dna_hash().to_owned(), agent.clone());
+    let role = AppRoleAssignment::new_deferred(cell_id, 50);
+    assert!(resolution.cells_to_create().is_empty());
+    assert_eq!(
+        resolution.role_assignments,
+        vec![("role_name".into(), role)]
+    );
+    assert_eq!(resolution.dnas_to_register.len(), 1);
+    assert_eq!(resolution.dnas_to_register[0].1, None);
+}
+
+/// Test that a role using an existing cell binds to the agent's cell of that DNA
+#[tokio::test]
+async fn provisioning_1_use_existing() {
+    holochain_trace::test_run().ok();
+    let agent = fixt!(AgentPubKey);
+    let (bundle, dna) = app_bundle_fixture(DnaModifiersOpt::none()).await;
+
+    let mut manifest = bundle.manifest().clone();
+    match &mut manifest {
+        AppManifest::V1(manifest) => {
+            manifest.roles[0].provisioning =
+                Some(CellProvisioning::UseExisting { deferred: false });
+        }
+    }
+    let bundle: AppBundle = bundle
+        .into_inner()
+        .update_manifest(manifest)
+        .unwrap()
+        .into();
+
+    // Without an existing cell of this agent, the resolution fails.
+    let other_cell_id = CellId::new(dna.dna_hash().to_owned(), fixt!(AgentPubKey));
+    let result = AppBundle::decode(&bundle.encode().unwrap())
+        .unwrap()
+        .resolve_cells(
+            &std::collections::HashMap::new(),
+            agent.clone(),
+            Default::default(),
+            &[other_cell_id].into_iter().collect(),
+        )
+        .await;
+    assert!(matches!(
+        result,
+        Err(AppBundleError::CellResolutionFailure(_, _))
+    ));
+
+    let cell_id = CellId::new(dna.dna_hash().to_owned(), agent.clone());
+    let resolution = bundle
+        .resolve_cells(
+            &std::collections::HashMap::new(),
+            agent.clone(),
+            Default::default(),
+            &[cell_id.clone()].into_iter().collect(),
+        )
+        .await
+        .unwrap();
+
+    let expected = AppRoleResolution {
+        agent,
+        dnas_to_register: vec![],
+        role_assignments: vec![(
+            "role_name".into(),
+            AppRoleAssignment::new_existing(cell_id, 50),
+        )],
+    };
+    assert_eq!(resolution, expected);
+    assert!(resolution.cells_to_create().is_empty());
+}
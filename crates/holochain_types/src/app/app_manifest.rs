@@ -37,8 +37,8 @@
 //!   the acceptable range of versions, and the cloning limitations.
 
 use holochain_zome_types::prelude::*;
-use mr_bundle::{Location, Manifest};
-use std::path::PathBuf;
+use mr_bundle::{Location, Manifest, ResourceHash};
+use std::{collections::HashMap, path::PathBuf};
 
 pub(crate) mod app_manifest_v1;
 pub mod app_manifest_validated;
@@ -75,6 +75,16 @@ impl Manifest for AppManifest {
         }
     }
 
+    fn resource_hashes(&self) -> HashMap<Location, ResourceHash> {
+        match self {
+            AppManifest::V1(m) => m
+                .roles
+                .iter()
+                .filter_map(|role| Some((role.dna.location.clone()?, role.dna.sha256?)))
+                .collect(),
+        }
+    }
+
     fn path() -> PathBuf {
         "happ.yaml".into()
     }
@@ -125,6 +135,7 @@ impl AppManifest {
                     provisioning: None,
                     dna: AppRoleDnaManifest {
                         location: Some(mr_bundle::Location::Bundled(path)),
+                        sha256: None,
                         modifiers: Default::default(),
                         installed_hash: Some(cell_id.dna_hash().clone().into()),
                         clone_limit: 256,
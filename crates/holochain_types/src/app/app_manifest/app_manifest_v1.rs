@@ -90,6 +90,11 @@ pub struct AppRoleDnaManifest {
     #[serde(flatten)]
     pub location: Option<mr_bundle::Location>,
 
+    /// The SHA-256 hash of the DNA bundle file at the location.
+    /// If specified, resolving the DNA bundle fails if it does not match this.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<mr_bundle::ResourceHash>,
+
     /// Optional default modifier values.
     ///
     /// Overrides any default modifiers specified in the DNA file,
@@ -122,6 +127,7 @@ impl AppRoleDnaManifest {
             location: Some(mr_bundle::Location::Bundled(
                 "./path/to/my/dnabundle.dna".into(),
             )),
+            sha256: None,
             modifiers: DnaModifiersOpt::none(),
             installed_hash: None,
             clone_limit: 0,
@@ -145,18 +151,17 @@ pub enum CellProvisioning {
     /// Always create a new Cell when installing this App
     Create { deferred: bool },
 
-    /*
-        TODO: implement
-
-        /// Require that a Cell is already installed which matches the DNA installed_hash
-        /// spec, and which has an Agent that's associated with this App's agent
-        /// via DPKI. If no such Cell exists, *app installation fails*.
-        UseExisting { deferred: bool },
+    /// Require that a Cell is already installed by another app which matches
+    /// the DNA installed_hash spec and this App's agent.
+    /// If no such Cell exists, *app installation fails*.
+    ///
+    /// The app which created the Cell can't be uninstalled while this App is
+    /// installed, unless the uninstallation explicitly cascades to this App.
+    UseExisting { deferred: bool },
 
-        /// Try `UseExisting`, and if that fails, fallback to `Create`
-        CreateIfNotExists { deferred: bool },
+    /// Try `UseExisting`, and if that fails, fallback to `Create`
+    CreateIfNotExists { deferred: bool },
 
-    */
     /// Install or locate the DNA, but never create a Cell for this DNA.
     /// Only allow clones to be created from the DNA specified.
     /// This case requires `clone_limit > 0`, otherwise no Cells will ever be created.
@@ -184,6 +189,9 @@ impl AppManifestV1 {
                 CellProvisioning::Create { .. } | CellProvisioning::CloneOnly => {
                     role.dna.modifiers.network_seed = Some(network_seed.clone());
                 }
+                // Existing cells are matched by their hash, which a new network seed would change
+                CellProvisioning::UseExisting { .. }
+                | CellProvisioning::CreateIfNotExists { .. } => {}
             }
         }
     }
@@ -205,6 +213,7 @@ impl AppManifestV1 {
                  }| {
                     let AppRoleDnaManifest {
                         location,
+                        sha256: _,
                         installed_hash,
                         clone_limit,
                         modifiers,
@@ -220,28 +229,28 @@ impl AppManifestV1 {
                             modifiers,
                             installed_hash,
                         },
-                        // CellProvisioning::UseExisting { deferred } => {
-                        //     AppRoleManifestValidated::UseExisting {
-                        //         deferred,
-                        //         clone_limit,
-                        //         installed_hash: Self::require(
-                        //             installed_hash,
-                        //             "roles.dna.installed_hash",
-                        //         )?,
-                        //     }
-                        // }
-                        // CellProvisioning::CreateIfNotExists { deferred } => {
-                        //     AppRoleManifestValidated::CreateIfNotExists {
-                        //         deferred,
-                        //         clone_limit,
-                        //         location: Self::require(location, "roles.dna.(path|url)")?,
-                        //         installed_hash: Self::require(
-                        //             installed_hash,
-                        //             "roles.dna.installed_hash",
-                        //         )?,
-                        //         modifiers,
-                        //     }
-                        // }
+                        CellProvisioning::UseExisting { deferred } => {
+                            AppRoleManifestValidated::UseExisting {
+                                deferred,
+                                clone_limit,
+                                installed_hash: Self::require(
+                                    installed_hash,
+                                    "roles.dna.installed_hash",
+                                )?,
+                            }
+                        }
+                        CellProvisioning::CreateIfNotExists { deferred } => {
+                            AppRoleManifestValidated::CreateIfNotExists {
+                                deferred,
+                                clone_limit,
+                                location: Self::require(location, "roles.dna.(path|url)")?,
+                                installed_hash: Self::require(
+                                    installed_hash,
+                                    "roles.dna.installed_hash",
+                                )?,
+                                modifiers,
+                            }
+                        }
                         CellProvisioning::CloneOnly => AppRoleManifestValidated::CloneOnly {
                             clone_limit,
                             location: Self::require(location, "roles.dna.(path|url)")?,
@@ -295,6 +304,7 @@ pub mod tests {
             name: "role_name".into(),
             dna: AppRoleDnaManifest {
                 location,
+                sha256: None,
                 modifiers,
                 installed_hash: Some(installed_hash.into()),
                 clone_limit: 50,
@@ -374,14 +384,14 @@ roles:
         manifest.roles = vec![
             AppRoleManifest::arbitrary(&mut u).unwrap(),
             AppRoleManifest::arbitrary(&mut u).unwrap(),
-            // AppRoleManifest::arbitrary(&mut u).unwrap(),
-            // AppRoleManifest::arbitrary(&mut u).unwrap(),
+            AppRoleManifest::arbitrary(&mut u).unwrap(),
+            AppRoleManifest::arbitrary(&mut u).unwrap(),
         ];
         manifest.roles[0].provisioning = Some(CellProvisioning::Create { deferred: false });
         manifest.roles[1].provisioning = Some(CellProvisioning::Create { deferred: false });
-        // manifest.roles[2].provisioning = Some(CellProvisioning::UseExisting { deferred: false });
-        // manifest.roles[3].provisioning =
-        //     Some(CellProvisioning::CreateIfNotExists { deferred: false });
+        manifest.roles[2].provisioning = Some(CellProvisioning::UseExisting { deferred: false });
+        manifest.roles[3].provisioning =
+            Some(CellProvisioning::CreateIfNotExists { deferred: false });
 
         let network_seed = NetworkSeed::from("blabla");
         manifest.set_network_seed(network_seed.clone());
@@ -395,15 +405,15 @@ roles:
             manifest.roles[1].dna.modifiers.network_seed.as_ref(),
             Some(&network_seed)
         );
-        // assert_eq!(
-        //     manifest.roles[3].dna.modifiers.network_seed.as_ref(),
-        //     Some(&network_seed)
-        // );
-
-        // // - The others do not.
-        // assert_ne!(
-        //     manifest.roles[2].dna.modifiers.network_seed.as_ref(),
-        //     Some(&network_seed)
-        // );
+
+        // - The roles which may use existing cells do not.
+        assert_ne!(
+            manifest.roles[2].dna.modifiers.network_seed.as_ref(),
+            Some(&network_seed)
+        );
+        assert_ne!(
+            manifest.roles[3].dna.modifiers.network_seed.as_ref(),
+            Some(&network_seed)
+        );
     }
 }
@@ -0,0 +1,84 @@
+//! Archives of the source chain of a cell, for backing up a chain and
+//! restoring it onto another conductor.
+
+use crate::prelude::*;
+use holochain_keystore::KeystoreError;
+use holochain_keystore::LairResult;
+use holochain_keystore::MetaLairClient;
+
+/// The version of the archive format written by this version of Holochain.
+/// Archives of any other version are refused on import.
+pub const CHAIN_ARCHIVE_VERSION: u32 = 1;
+
+/// Everything the authored database of a cell holds about the cell's own chain.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, SerializedBytes)]
+pub struct ChainArchive {
+    /// The version of the archive format, see [`CHAIN_ARCHIVE_VERSION`].
+    pub version: u32,
+    /// The cell whose chain was exported.
+    pub cell_id: CellId,
+    /// When the chain was exported.
+    pub exported_at: Timestamp,
+    /// The records of the chain in chain order, starting from genesis.
+    /// Private entries, including capability grants and claims, are included.
+    pub records: Vec<Record>,
+    /// The locks held on the chain by countersigning sessions which had not
+    /// expired when the chain was exported.
+    pub chain_locks: Vec<ArchivedChainLock>,
+}
+
+/// A lock held on a chain by a countersigning session.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchivedChainLock {
+    /// The lock, which is the hash of the preflight request of the session.
+    pub lock: Vec<u8>,
+    /// When the lock expires.
+    pub expires_at: Timestamp,
+}
+
+/// A [`ChainArchive`] signed by the agent of the cell it was exported from.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, SerializedBytes)]
+pub struct SignedChainArchive {
+    /// The archive.
+    pub archive: ChainArchive,
+    /// The signature of the agent over the archive.
+    pub signature: Signature,
+}
+
+/// Extension trait for signing and checking chain archives.
+#[async_trait::async_trait]
+pub trait SignedChainArchiveExt {
+    /// Sign an archive with the keypair of the agent of its cell
+    async fn sign(
+        keystore: &MetaLairClient,
+        archive: ChainArchive,
+    ) -> LairResult<SignedChainArchive>;
+    /// Validate that the archive was signed by the agent of its cell
+    async fn validate(&self) -> Result<(), KeystoreError>;
+}
+
+#[async_trait::async_trait]
+impl SignedChainArchiveExt for SignedChainArchive {
+    async fn sign(keystore: &MetaLairClient, archive: ChainArchive) -> LairResult<Self> {
+        let signature = archive
+            .cell_id
+            .agent_pubkey()
+            .sign(keystore, &archive)
+            .await?;
+        Ok(Self { archive, signature })
+    }
+
+    async fn validate(&self) -> Result<(), KeystoreError> {
+        let agent = self.archive.cell_id.agent_pubkey();
+        if !agent
+            .verify_signature(&self.signature, &self.archive)
+            .await?
+        {
+            return Err(KeystoreError::InvalidSignature(
+                self.signature.clone(),
+                format!("chain archive of {:?}", self.archive.cell_id),
+            ));
+        }
+        Ok(())
+    }
+}
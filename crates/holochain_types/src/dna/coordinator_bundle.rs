@@ -29,6 +29,15 @@ impl Manifest for CoordinatorManifest {
             .collect()
     }
 
+    fn resource_hashes(
+        &self,
+    ) -> std::collections::HashMap<mr_bundle::Location, mr_bundle::ResourceHash> {
+        self.zomes
+            .iter()
+            .filter_map(|zome| Some((zome.location.clone(), zome.sha256?)))
+            .collect()
+    }
+
     fn path() -> std::path::PathBuf {
         "coordinators.yaml".into()
     }
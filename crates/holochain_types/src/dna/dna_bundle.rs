@@ -127,6 +127,7 @@ impl DnaBundle {
                     },
                     integrity_zomes,
                     coordinator_zomes,
+                    rate_limits: manifest.integrity.rate_limits.clone(),
                 };
 
                 let original_hash = DnaHash::with_data_sync(&dna_def);
@@ -168,6 +169,7 @@ impl DnaBundle {
                         name,
                         hash: Some(hash),
                         location: Location::Bundled(PathBuf::from(filename)),
+                        sha256: None,
                         dylib: None,
                         dependencies: Some(dependencies),
                     }
@@ -192,6 +194,7 @@ impl DnaBundle {
                         name,
                         hash: Some(hash),
                         location: Location::Bundled(PathBuf::from(filename)),
+                        sha256: None,
                         dylib: None,
                         dependencies: Some(dependencies),
                     }
@@ -210,6 +213,7 @@ impl DnaBundle {
                 })?),
                 origin_time: dna_def.modifiers.origin_time.into(),
                 zomes: integrity,
+                rate_limits: dna_def.rate_limits,
             },
             coordinator: CoordinatorManifest { zomes: coordinator },
         }
@@ -275,6 +279,7 @@ mod tests {
                         name: "zome1".into(),
                         hash: None,
                         location: mr_bundle::Location::Bundled(path1.clone()),
+                        sha256: None,
                         dylib: None,
                         dependencies: Default::default(),
                     },
@@ -283,10 +288,12 @@ mod tests {
                         // Intentional wrong hash
                         hash: Some(hash1.clone().into()),
                         location: mr_bundle::Location::Bundled(path2.clone()),
+                        sha256: None,
                         dylib: None,
                         dependencies: Default::default(),
                     },
                 ],
+                rate_limits: Default::default(),
             },
             coordinator: CoordinatorManifest { zomes: vec![] },
         };
@@ -347,4 +354,67 @@ mod tests {
             SerializedBytes::try_from(properties).unwrap()
         );
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn dna_bundle_rate_limits() {
+        let path = PathBuf::from("1");
+        let wasm = vec![1, 2, 3];
+        let mut manifest = DnaManifestCurrent {
+            name: "name".into(),
+            integrity: IntegrityManifest {
+                network_seed: None,
+                properties: None,
+                origin_time: Timestamp::HOLOCHAIN_EPOCH.into(),
+                zomes: vec![ZomeManifest {
+                    name: "zome1".into(),
+                    hash: None,
+                    location: mr_bundle::Location::Bundled(path.clone()),
+                    sha256: None,
+                    dylib: None,
+                    dependencies: Default::default(),
+                }],
+                rate_limits: Default::default(),
+            },
+            coordinator: CoordinatorManifest { zomes: vec![] },
+        };
+        let resources = vec![(path, wasm.into())];
+        let to_dna_file = |manifest: DnaManifestCurrent| {
+            let bundle: DnaBundle =
+                mr_bundle::Bundle::new_unchecked(manifest.try_into().unwrap(), resources.clone())
+                    .unwrap()
+                    .into();
+            async move {
+                bundle
+                    .into_dna_file(DnaModifiersOpt::none())
+                    .await
+                    .unwrap()
+                    .0
+            }
+        };
+
+        let unlimited = to_dna_file(manifest.clone()).await;
+        assert!(unlimited.dna_def().rate_limits.is_empty());
+
+        let limit = RateLimit {
+            capacity: 100,
+            drain_amount: 10,
+            drain_interval_ms: 1000,
+        };
+        manifest.integrity.rate_limits = [(1, limit)].into();
+        let limited = to_dna_file(manifest.clone()).await;
+        assert_eq!(
+            limited.dna_def().rate_limits,
+            manifest.integrity.rate_limits
+        );
+        // The rate limits are part of the DNA's identity
+        assert_ne!(unlimited.dna_hash(), limited.dna_hash());
+
+        // and they survive the round trip through a bundle
+        let bundle = DnaBundle::from_dna_file(limited.clone()).unwrap();
+        match &bundle.manifest().0 {
+            DnaManifest::V1(m) => {
+                assert_eq!(m.integrity.rate_limits, manifest.integrity.rate_limits)
+            }
+        }
+    }
 }
@@ -1,5 +1,8 @@
 use crate::prelude::*;
-use std::{collections::HashSet, path::PathBuf};
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+};
 mod dna_manifest_v1;
 
 #[cfg(test)]
@@ -34,6 +37,15 @@ impl mr_bundle::Manifest for ValidatedDnaManifest {
         }
     }
 
+    fn resource_hashes(&self) -> HashMap<mr_bundle::Location, mr_bundle::ResourceHash> {
+        match &self.0 {
+            DnaManifest::V1(m) => m
+                .all_zomes()
+                .filter_map(|zome| Some((zome.location.clone(), zome.sha256?)))
+                .collect(),
+        }
+    }
+
     fn path() -> PathBuf {
         "dna.yaml".into()
     }
@@ -56,7 +68,13 @@ impl DnaManifest {
     ) -> Self {
         DnaManifestCurrent::new(
             name,
-            IntegrityManifest::new(network_seed, properties, origin_time, integrity_zomes),
+            IntegrityManifest::new(
+                network_seed,
+                properties,
+                origin_time,
+                integrity_zomes,
+                Default::default(),
+            ),
             CoordinatorManifest {
                 zomes: coordinator_zomes,
             },
@@ -58,6 +58,27 @@ use serde_with::serde_as;
 ///     - name: zome4
 ///       bundled: ../dna2/zomes/zome2.wasm
 /// ```
+///
+/// Rate limits are declared per bucket. Here, the actions which the `weigh`
+/// callback puts in bucket 1 may fill it with at most 100 units, and it drains
+/// by 10 units every second.
+///
+/// ```yaml
+/// manifest_version: "1"
+/// name: rate limited dna
+/// integrity:
+///   network_seed: 00000000-0000-0000-0000-000000000000
+///   properties: ~
+///   origin_time: 2022-02-11T23:05:19.470323Z
+///   zomes:
+///     - name: zome1
+///       bundled: ../dna1/zomes/zome1.wasm
+///   rate_limits:
+///     1:
+///       capacity: 100
+///       drain_amount: 10
+///       drain_interval_ms: 1000
+/// ```
 
 #[serde_as]
 #[derive(
@@ -127,6 +148,13 @@ pub struct IntegrityManifest {
     /// The order is significant: it determines initialization order.
     /// The integrity zome manifests.
     pub zomes: Vec<ZomeManifest>,
+
+    /// The rate limits which every agent's actions are held to, keyed by the
+    /// bucket which the `weigh` callback of an integrity zome puts an action in.
+    /// Actions weighed into a bucket without a limit are not limited.
+    #[serde(default, skip_serializing_if = "RateLimits::is_empty")]
+    #[builder(default)]
+    pub rate_limits: RateLimits,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Default)]
@@ -151,6 +179,11 @@ pub struct ZomeManifest {
     #[serde(flatten)]
     pub location: ZomeLocation,
 
+    /// The SHA-256 hash of the wasm file at the location.
+    /// If specified, resolving the wasm fails if it does not match this.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<mr_bundle::ResourceHash>,
+
     /// The integrity zomes this zome depends on.
     /// The order of these must match the order the types
     /// are used in the zome.
@@ -50,6 +50,61 @@ coordinator:
     let _manifest: DnaManifest = serde_yaml::from_str(&manifest_yaml).unwrap();
 }
 
+#[test]
+fn can_deserialize_dna_manifest_with_resource_hashes() {
+    let manifest_yaml = r#"
+---
+manifest_version: "1"
+name: test_dna
+integrity:
+  network_seed: blablabla
+  origin_time: 2022-02-11T23:29:00.789576Z
+  properties: ~
+  zomes:
+    - name: zome1
+      url: https://example.com/zome-1.wasm
+      sha256: ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad
+    - name: zome2
+      bundled: nested/zome-2.wasm
+coordinator:
+  zomes:
+    - name: zome3
+      path: ../zome-3.wasm
+      sha256: BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD
+        "#;
+
+    let manifest: DnaManifest = serde_yaml::from_str(&manifest_yaml).unwrap();
+    let manifest = ValidatedDnaManifest::try_from(manifest).unwrap();
+    let hash = mr_bundle::ResourceHash::of(b"abc");
+    assert_eq!(
+        mr_bundle::Manifest::resource_hashes(&manifest),
+        maplit::hashmap! {
+            mr_bundle::Location::Url("https://example.com/zome-1.wasm".into()) => hash,
+            mr_bundle::Location::Path("../zome-3.wasm".into()) => hash,
+        }
+    );
+}
+
+#[test]
+fn rejects_manifest_with_invalid_resource_hash() {
+    let manifest_yaml = r#"
+---
+manifest_version: "1"
+name: test_dna
+integrity:
+  network_seed: blablabla
+  origin_time: 2022-02-11T23:29:00.789576Z
+  properties: ~
+  zomes:
+    - name: zome1
+      url: https://example.com/zome-1.wasm
+      sha256: not-a-hash
+        "#;
+
+    let result: Result<DnaManifest, _> = serde_yaml::from_str(&manifest_yaml);
+    assert!(result.is_err());
+}
+
 #[test]
 fn deserialize_dna_manifest_coordinator_only() {
     let manifest_yaml = r#"
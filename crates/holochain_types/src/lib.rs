@@ -18,6 +18,7 @@ pub mod activity;
 pub mod app;
 pub mod autonomic;
 pub mod chain;
+pub mod chain_archive;
 pub mod chc;
 pub mod combinators;
 pub mod db;
@@ -36,6 +37,7 @@ pub mod signal;
 #[warn(missing_docs)]
 pub mod sql;
 pub mod validation_receipt;
+pub mod warrant;
 pub mod web_app;
 pub mod zome_types;
 
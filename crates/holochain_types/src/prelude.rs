@@ -13,6 +13,7 @@ pub use crate::activity::*;
 pub use crate::app::*;
 pub use crate::autonomic::*;
 pub use crate::chain::*;
+pub use crate::chain_archive::*;
 pub use crate::chc::*;
 pub use crate::combinators::*;
 pub use crate::db::*;
@@ -27,6 +28,7 @@ pub use crate::metadata::*;
 pub use crate::record::*;
 pub use crate::signal::*;
 pub use crate::validation_receipt::*;
+pub use crate::warrant::*;
 
 #[cfg(feature = "fixturators")]
 pub use crate::fixt::TimestampFixturator;
@@ -29,6 +29,58 @@ impl Signal {
     pub fn try_from_vec(v: Vec<u8>) -> Result<Self, SerializedBytesError> {
         Self::try_from(SerializedBytes::from(UnsafeBytes::from(v)))
     }
+
+    /// The Cell which emitted this signal, if it can be attributed to one.
+    pub fn cell_id(&self) -> Option<&CellId> {
+        match self {
+            Self::App { cell_id, .. } => Some(cell_id),
+            Self::System(_) => None,
+        }
+    }
+
+    /// The name of the variant of this signal.
+    ///
+    /// For system signals this is the name of the [`SystemSignal`] variant.
+    /// For app signals this is the value of the `type` field, which is present
+    /// when the zome serializes its signal enum with `#[serde(tag = "type")]`.
+    /// App signals without such a field have no variant name.
+    pub fn variant_name(&self) -> Option<String> {
+        match self {
+            Self::App { signal, .. } => {
+                #[derive(Deserialize)]
+                struct Tagged {
+                    #[serde(rename = "type")]
+                    ty: String,
+                }
+                signal
+                    .as_inner()
+                    .decode::<Tagged>()
+                    .ok()
+                    .map(|tagged| tagged.ty)
+            }
+            Self::System(system_signal) => Some(system_signal.variant_name().to_string()),
+        }
+    }
+}
+
+/// A [`Signal`] together with its sequence number, as it is sent to the
+/// clients of an app interface.
+///
+/// Sequence numbers increase by one for every signal of an app, so clients can
+/// detect missed signals and resume after the last signal they received.
+#[derive(Clone, Debug, Serialize, Deserialize, SerializedBytes, PartialEq, Eq)]
+pub struct SequencedSignal {
+    /// The sequence number of the signal within its app
+    pub seq: u64,
+    /// The signal
+    pub signal: Signal,
+}
+
+impl SequencedSignal {
+    /// Parse from vec.
+    pub fn try_from_vec(v: Vec<u8>) -> Result<Self, SerializedBytesError> {
+        Self::try_from(SerializedBytes::from(UnsafeBytes::from(v)))
+    }
 }
 
 /// A Signal which originates from within the Holochain system, as opposed to
@@ -45,6 +97,16 @@ pub enum SystemSignal {
     SuccessfulCountersigning(holo_hash::EntryHash),
 }
 
+impl SystemSignal {
+    /// The name of this variant
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            Self::Test(_) => "Test",
+            Self::SuccessfulCountersigning(_) => "SuccessfulCountersigning",
+        }
+    }
+}
+
 /// Create a test signal
 pub fn test_signal(s: &str) -> Signal {
     SystemSignal::Test(s.to_string()).into()
@@ -0,0 +1,38 @@
+//! Signing and checking of warrants.
+
+use crate::prelude::*;
+use holochain_keystore::KeystoreError;
+use holochain_keystore::LairResult;
+use holochain_keystore::MetaLairClient;
+
+/// Extension trait to keep zome types minimal
+#[async_trait::async_trait]
+pub trait SignedWarrantExt {
+    /// Sign a warrant with the keypair of its author
+    async fn sign(keystore: &MetaLairClient, warrant: Warrant) -> LairResult<SignedWarrant>;
+    /// Validate that the warrant was signed by its author
+    async fn validate(&self) -> Result<(), KeystoreError>;
+}
+
+#[async_trait::async_trait]
+impl SignedWarrantExt for SignedWarrant {
+    async fn sign(keystore: &MetaLairClient, warrant: Warrant) -> LairResult<Self> {
+        let signature = warrant.author.sign(keystore, &warrant).await?;
+        Ok(Self::new(warrant, signature))
+    }
+
+    async fn validate(&self) -> Result<(), KeystoreError> {
+        if !self
+            .warrant
+            .author
+            .verify_signature(&self.signature, &self.warrant)
+            .await?
+        {
+            return Err(KeystoreError::InvalidSignature(
+                self.signature.clone(),
+                format!("warrant by {:?}", self.warrant.author),
+            ));
+        }
+        Ok(())
+    }
+}
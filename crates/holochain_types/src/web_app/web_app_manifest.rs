@@ -2,8 +2,8 @@
 
 //! Defines the hApp Manifest YAML format, including validation.
 
-use mr_bundle::{Location, Manifest};
-use std::path::PathBuf;
+use mr_bundle::{Location, Manifest, ResourceHash};
+use std::{collections::HashMap, path::PathBuf};
 
 mod current;
 pub(crate) mod web_app_manifest_v1;
@@ -27,6 +27,18 @@ impl Manifest for WebAppManifest {
         }
     }
 
+    fn resource_hashes(&self) -> HashMap<Location, ResourceHash> {
+        match self {
+            WebAppManifest::V1(m) => [
+                (m.ui.location.clone(), m.ui.sha256),
+                (m.happ_manifest.location.clone(), m.happ_manifest.sha256),
+            ]
+            .into_iter()
+            .filter_map(|(location, hash)| Some((location, hash?)))
+            .collect(),
+        }
+    }
+
     fn path() -> PathBuf {
         "web-happ.yaml".into()
     }
@@ -43,9 +55,11 @@ impl WebAppManifest {
             name,
             ui: WebUI {
                 location: Location::Bundled("./path/to/my/ui.zip".into()),
+                sha256: None,
             },
             happ_manifest: AppManifestLocation {
                 location: Location::Bundled("./path/to/my/happ-bundle.happ".into()),
+                sha256: None,
             },
         })
     }
@@ -90,9 +104,11 @@ mod tests {
             name: app_name.clone(),
             ui: WebUI {
                 location: ui_location.clone(),
+                sha256: None,
             },
             happ_manifest: AppManifestLocation {
                 location: happ_location.clone(),
+                sha256: None,
             },
         });
 
@@ -31,6 +31,11 @@ pub struct WebUI {
     /// there is no actual "location" key in the manifest.
     #[serde(flatten)]
     pub location: mr_bundle::Location,
+
+    /// The SHA-256 hash of the file at the location.
+    /// If specified, resolving the file fails if it does not match this.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<mr_bundle::ResourceHash>,
 }
 
 /// Location of the happ bundle to bind with the Web UI
@@ -44,4 +49,9 @@ pub struct AppManifestLocation {
     /// there is no actual "location" key in the manifest.
     #[serde(flatten)]
     pub location: mr_bundle::Location,
+
+    /// The SHA-256 hash of the file at the location.
+    /// If specified, resolving the file fails if it does not match this.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<mr_bundle::ResourceHash>,
 }
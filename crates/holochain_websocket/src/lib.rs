@@ -2,6 +2,9 @@
 //! Holochain websocket support library.
 //! This is currently a thin wrapper around tokio-tungstenite that
 //! provides rpc-style request/responses via u64 message ids.
+//!
+//! Connections are made either over TCP, optionally secured with TLS, or,
+//! on unix platforms, over a unix domain socket. All use the same websocket framing.
 
 use holochain_serialized_bytes::prelude::*;
 use holochain_types::websocket::AllowedOrigins;
@@ -13,6 +16,9 @@ use tokio_tungstenite::tungstenite::handshake::server::{Callback, ErrorResponse,
 use tokio_tungstenite::tungstenite::http::{HeaderMap, HeaderValue, StatusCode};
 use tokio_tungstenite::tungstenite::protocol::Message;
 
+mod tls;
+pub use tls::TlsServerConfig;
+
 #[derive(Debug, serde::Serialize, serde::Deserialize, SerializedBytes)]
 #[serde(rename_all = "snake_case", tag = "type")]
 /// The messages actually sent over the wire by this library.
@@ -43,6 +49,19 @@ pub enum WireMessage {
         /// Actual bytes of the message serialized as [message pack](https://msgpack.org/).
         data: Option<Vec<u8>>,
     },
+
+    /// A message which authenticates the connection, and requires a response.
+    ///
+    /// Servers which require authentication expect this to be the first
+    /// message sent on a new connection, and respond to it with whether the
+    /// connection is authenticated.
+    Authenticate {
+        /// The id of this authentication request.
+        id: u64,
+        #[serde(with = "serde_bytes")]
+        /// Actual bytes of the message serialized as [message pack](https://msgpack.org/).
+        data: Vec<u8>,
+    },
 }
 
 impl WireMessage {
@@ -54,14 +73,19 @@ impl WireMessage {
         Ok(b)
     }
 
+    /// Get a new unique msg id for a message which requires a response.
+    fn next_id() -> u64 {
+        static ID: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(1);
+        ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed)
+    }
+
     /// Create a new request message (with new unique msg id).
     fn request<S>(s: S) -> Result<(Message, u64)>
     where
         S: std::fmt::Debug,
         SerializedBytes: TryFrom<S, Error = SerializedBytesError>,
     {
-        static ID: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(1);
-        let id = ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        let id = Self::next_id();
         tracing::trace!(?s, %id, "OutRequest");
         let s1 = SerializedBytes::try_from(s).map_err(Error::other)?;
         let s2 = Self::Request {
@@ -101,6 +125,24 @@ impl WireMessage {
         let s3: SerializedBytes = s2.try_into().map_err(Error::other)?;
         Ok(Message::Binary(UnsafeBytes::from(s3).into()))
     }
+
+    /// Create a new authenticate message (with new unique msg id).
+    ///
+    /// The content is not traced, since it carries the credentials.
+    fn authenticate<S>(s: S) -> Result<(Message, u64)>
+    where
+        SerializedBytes: TryFrom<S, Error = SerializedBytesError>,
+    {
+        let id = Self::next_id();
+        tracing::trace!(%id, "SendAuthenticate");
+        let s1 = SerializedBytes::try_from(s).map_err(Error::other)?;
+        let s2 = Self::Authenticate {
+            id,
+            data: UnsafeBytes::from(s1).into(),
+        };
+        let s3: SerializedBytes = s2.try_into().map_err(Error::other)?;
+        Ok((Message::Binary(UnsafeBytes::from(s3).into()), id))
+    }
 }
 
 /// Websocket configuration struct.
@@ -119,6 +161,16 @@ pub struct WebsocketConfig {
     /// Allowed origins access control for a [WebsocketListener].
     /// Not used by the [WebsocketSender].
     pub allowed_origins: Option<AllowedOrigins>,
+
+    /// If set, a [WebsocketListener] bound to a TCP address serves `wss://`
+    /// with this certificate. Not used by the [WebsocketSender], see
+    /// [ConnectRequest::try_set_root_certificate] instead.
+    pub tls: Option<TlsServerConfig>,
+
+    /// Time within which a new connection to a [WebsocketListener] must
+    /// complete its TLS and websocket handshakes. Not used by the
+    /// [WebsocketSender]. [default = 10 seconds]
+    pub handshake_timeout: std::time::Duration,
 }
 
 impl WebsocketConfig {
@@ -128,6 +180,8 @@ impl WebsocketConfig {
         max_message_size: 64 << 20,
         max_frame_size: 16 << 20,
         allowed_origins: None,
+        tls: None,
+        handshake_timeout: std::time::Duration::from_secs(10),
     };
 
     /// The default listener WebsocketConfig.
@@ -136,6 +190,8 @@ impl WebsocketConfig {
         max_message_size: 64 << 20,
         max_frame_size: 16 << 20,
         allowed_origins: Some(AllowedOrigins::Any),
+        tls: None,
+        handshake_timeout: std::time::Duration::from_secs(10),
     };
 
     /// Internal convert to tungstenite config.
@@ -195,7 +251,12 @@ impl RMap {
     }
 }
 
-type WsStream = tokio_tungstenite::WebSocketStream<tokio::net::TcpStream>;
+/// The byte stream underlying a websocket connection,
+/// either a TCP stream or a unix domain socket stream.
+trait RawStream: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin + Send {}
+impl<T: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin + Send> RawStream for T {}
+
+type WsStream = tokio_tungstenite::WebSocketStream<Box<dyn RawStream>>;
 type WsSend =
     futures::stream::SplitSink<WsStream, tokio_tungstenite::tungstenite::protocol::Message>;
 type WsSendSync = Arc<tokio::sync::Mutex<WsSend>>;
@@ -303,6 +364,10 @@ where
 
     /// Received a request from the remote.
     Request(D, WebsocketRespond),
+
+    /// Received an authentication message from the remote, which must be
+    /// responded to with whether the connection is authenticated.
+    Authenticate(Vec<u8>, WebsocketRespond),
 }
 
 /// Receive signals and requests from a websocket connection.
@@ -311,7 +376,7 @@ where
 /// If this receiver is dropped, the sender side will also be closed.
 pub struct WebsocketReceiver(
     WsCoreSync,
-    std::net::SocketAddr,
+    Option<std::net::SocketAddr>,
     tokio::task::JoinHandle<()>,
 );
 
@@ -323,7 +388,7 @@ impl Drop for WebsocketReceiver {
 }
 
 impl WebsocketReceiver {
-    fn new(core: WsCoreSync, addr: std::net::SocketAddr) -> Self {
+    fn new(core: WsCoreSync, addr: Option<std::net::SocketAddr>) -> Self {
         let core2 = core.clone();
         let ping_task = tokio::task::spawn(async move {
             loop {
@@ -349,8 +414,8 @@ impl WebsocketReceiver {
         Self(core, addr, ping_task)
     }
 
-    /// Peer address.
-    pub fn peer_addr(&self) -> std::net::SocketAddr {
+    /// Peer address, or `None` if connected over a unix domain socket.
+    pub fn peer_addr(&self) -> Option<std::net::SocketAddr> {
         self.1
     }
 
@@ -429,6 +494,14 @@ impl WebsocketReceiver {
                             Ok(None)
                         }
                         WireMessage::Signal { data } => Ok(Some(ReceiveMessage::Signal(data))),
+                        WireMessage::Authenticate { id, data } => {
+                            let resp = WebsocketRespond {
+                                id,
+                                core: core_sync,
+                            };
+                            tracing::trace!(%id, "InAuthenticate");
+                            Ok(Some(ReceiveMessage::Authenticate(data, resp)))
+                        }
                     }
                 })
                 .await?
@@ -549,12 +622,103 @@ impl WebsocketSender {
             })
             .await
     }
+
+    /// Authenticate the connection, using the default configured timeout,
+    /// and return the server's response, which tells whether the connection
+    /// is authenticated.
+    ///
+    /// Servers which require authentication expect this to be sent before
+    /// any other message.
+    pub async fn authenticate<S, R>(&self, s: S) -> Result<R>
+    where
+        SerializedBytes: TryFrom<S, Error = SerializedBytesError>,
+        R: serde::de::DeserializeOwned + std::fmt::Debug,
+    {
+        use futures::sink::SinkExt;
+        use futures::stream::StreamExt;
+
+        let (s, id) = WireMessage::authenticate(s)?;
+        let (resp_s, resp_r) = tokio::sync::oneshot::channel();
+
+        let resp = self
+            .0
+            .exec(move |_, core| async move {
+                core.rmap.insert(id, resp_s);
+
+                let resp = tokio::time::timeout(core.timeout, async {
+                    core.send.lock().await.send(s).await.map_err(Error::other)?;
+
+                    // If the receiver is being polled, it hands the response
+                    // over. Otherwise read it here, since the remote sends
+                    // nothing else before the connection is authenticated.
+                    let read_response = async {
+                        let mut recv = core.recv.lock().await;
+                        loop {
+                            let msg = recv
+                                .next()
+                                .await
+                                .ok_or(Error::other("ReceiverClosed"))?
+                                .map_err(Error::other)?;
+                            let msg = match msg {
+                                Message::Text(s) => s.into_bytes(),
+                                Message::Binary(b) => b,
+                                Message::Ping(b) => {
+                                    core.send
+                                        .lock()
+                                        .await
+                                        .send(Message::Pong(b))
+                                        .await
+                                        .map_err(Error::other)?;
+                                    continue;
+                                }
+                                Message::Pong(_) => continue,
+                                Message::Close(frame) => {
+                                    return Err(Error::other(format!(
+                                        "ReceivedCloseFrame: {frame:?}"
+                                    )));
+                                }
+                                Message::Frame(_) => {
+                                    return Err(Error::other("UnexpectedRawFrame"))
+                                }
+                            };
+                            match WireMessage::try_from_bytes(msg)? {
+                                WireMessage::Response {
+                                    id: resp_id,
+                                    data: Some(data),
+                                } if resp_id == id => {
+                                    return Ok(SerializedBytes::from(UnsafeBytes::from(data)));
+                                }
+                                _ => {
+                                    return Err(Error::other(
+                                        "UnexpectedMessageBeforeAuthenticated",
+                                    ))
+                                }
+                            }
+                        }
+                    };
+
+                    tokio::select! {
+                        resp = resp_r => resp.map_err(|_| Error::other("ResponderDropped"))?,
+                        resp = read_response => resp,
+                    }
+                })
+                .await
+                .map_err(Error::other)?;
+                core.rmap.remove(id);
+                resp
+            })
+            .await?;
+
+        let res = decode(&Vec::from(UnsafeBytes::from(resp))).map_err(Error::other)?;
+        tracing::trace!(?res, %id, "OutAuthenticateResponse");
+        Ok(res)
+    }
 }
 
 fn split(
     stream: WsStream,
     timeout: std::time::Duration,
-    peer_addr: std::net::SocketAddr,
+    peer_addr: Option<std::net::SocketAddr>,
 ) -> Result<(WebsocketSender, WebsocketReceiver)> {
     let (sink, stream) = futures::stream::StreamExt::split(stream);
 
@@ -587,6 +751,16 @@ pub async fn connect(
     let request = request.into();
     let stream = tokio::net::TcpStream::connect(request.addr).await?;
     let peer_addr = stream.peer_addr()?;
+    let stream: Box<dyn RawStream> = match request.root_certificates.clone() {
+        Some(roots) => {
+            let server_name = request
+                .server_name
+                .clone()
+                .unwrap_or(tokio_rustls::rustls::ServerName::IpAddress(peer_addr.ip()));
+            Box::new(tls::connect(stream, roots, server_name).await?)
+        }
+        None => Box::new(stream),
+    };
     let (stream, _addr) = tokio_tungstenite::client_async_with_config(
         request.into_client_request()?,
         stream,
@@ -594,13 +768,79 @@ pub async fn connect(
     )
     .await
     .map_err(Error::other)?;
-    split(stream, config.default_request_timeout, peer_addr)
+    split(stream, config.default_request_timeout, Some(peer_addr))
+}
+
+/// Bind a unix domain socket at `path` with the permissions `mode`.
+///
+/// The socket is created in a new directory only the current user can enter,
+/// so nobody else can connect to it before its permissions are set, and is
+/// then moved to `path`.
+#[cfg(unix)]
+fn bind_unix_socket_private(path: &std::path::Path, mode: u32) -> Result<tokio::net::UnixListener> {
+    use std::os::unix::fs::DirBuilderExt;
+    use std::os::unix::fs::PermissionsExt;
+
+    static COUNT: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| Error::other(format!("{} is not a file path", path.display())))?;
+    let private_dir = path.with_file_name(format!(
+        ".{}.{}.{}",
+        file_name.to_string_lossy(),
+        std::process::id(),
+        COUNT.fetch_add(1, std::sync::atomic::Ordering::Relaxed)
+    ));
+    std::fs::DirBuilder::new()
+        .mode(0o700)
+        .create(&private_dir)?;
+
+    let private_path = private_dir.join(file_name);
+    let result = tokio::net::UnixListener::bind(&private_path).and_then(|listener| {
+        std::fs::set_permissions(&private_path, std::fs::Permissions::from_mode(mode))?;
+        std::fs::rename(&private_path, path)?;
+        Ok(listener)
+    });
+    let _ = std::fs::remove_file(&private_path);
+    let _ = std::fs::remove_dir(&private_dir);
+    result
+}
+
+/// Establish a new outgoing websocket connection over the unix domain socket
+/// at `path`.
+#[cfg(unix)]
+pub async fn connect_unix_socket(
+    config: Arc<WebsocketConfig>,
+    path: impl AsRef<std::path::Path>,
+) -> Result<(WebsocketSender, WebsocketReceiver)> {
+    use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+
+    let stream = tokio::net::UnixStream::connect(path).await?;
+    let stream: Box<dyn RawStream> = Box::new(stream);
+
+    // The host is never resolved, the request only needs to be well formed.
+    // A unix socket listener doesn't check the origin, but still requires one.
+    let mut request = "ws://localhost"
+        .into_client_request()
+        .map_err(Error::other)?;
+    request
+        .headers_mut()
+        .insert("Origin", HeaderValue::from_static("holochain_websocket"));
+
+    let (stream, _addr) =
+        tokio_tungstenite::client_async_with_config(request, stream, Some(config.as_tungstenite()))
+            .await
+            .map_err(Error::other)?;
+    split(stream, config.default_request_timeout, None)
 }
 
 /// A request to connect to a websocket server.
 pub struct ConnectRequest {
     addr: std::net::SocketAddr,
     headers: HeaderMap<HeaderValue>,
+    root_certificates: Option<tokio_rustls::rustls::RootCertStore>,
+    server_name: Option<tokio_rustls::rustls::ServerName>,
 }
 
 impl From<std::net::SocketAddr> for ConnectRequest {
@@ -615,6 +855,8 @@ impl ConnectRequest {
         let mut cr = ConnectRequest {
             addr,
             headers: HeaderMap::new(),
+            root_certificates: None,
+            server_name: None,
         };
 
         // Set a default Origin so that the connection request will be allowed by default when the listener is
@@ -636,12 +878,38 @@ impl ConnectRequest {
         Ok(self)
     }
 
+    /// Try to connect over TLS (`wss://`), trusting only the PEM encoded
+    /// root certificates given here.
+    ///
+    /// The server's certificate must be valid for the IP address connected to,
+    /// unless another name is set with [ConnectRequest::try_set_server_name].
+    pub fn try_set_root_certificate(mut self, pem: &[u8]) -> Result<Self> {
+        self.root_certificates = Some(tls::root_certificates(pem)?);
+        Ok(self)
+    }
+
+    /// Try to set the name the server's TLS certificate must be valid for.
+    ///
+    /// Errors if the name is not a valid DNS name or IP address.
+    pub fn try_set_server_name(mut self, name: &str) -> Result<Self> {
+        self.server_name = Some(
+            tokio_rustls::rustls::ServerName::try_from(name)
+                .map_err(|e| Error::new(std::io::ErrorKind::InvalidInput, e))?,
+        );
+        Ok(self)
+    }
+
     fn into_client_request(
         self,
     ) -> Result<impl tokio_tungstenite::tungstenite::client::IntoClientRequest + Unpin> {
         use tokio_tungstenite::tungstenite::client::IntoClientRequest;
-        let mut req =
-            String::into_client_request(format!("ws://{}", self.addr)).map_err(Error::other)?;
+        let scheme = if self.root_certificates.is_some() {
+            "wss"
+        } else {
+            "ws"
+        };
+        let mut req = String::into_client_request(format!("{}://{}", scheme, self.addr))
+            .map_err(Error::other)?;
         for (name, value) in self.headers {
             if let Some(name) = name {
                 req.headers_mut().insert(name, value);
@@ -660,21 +928,55 @@ impl ConnectRequest {
     }
 }
 
+enum Listener {
+    Tcp(tokio::net::TcpListener),
+    #[cfg(unix)]
+    Unix(tokio::net::UnixListener, std::path::PathBuf),
+}
+
 /// A Holochain websocket listener.
 pub struct WebsocketListener {
     config: Arc<WebsocketConfig>,
     access_control: Arc<AllowedOrigins>,
-    listener: tokio::net::TcpListener,
+    listener: Listener,
+    handshakes: Handshakes,
+}
+
+type HandshakeResult = Result<(WebsocketSender, WebsocketReceiver)>;
+
+/// The connections whose handshakes completed, or failed, in their own tasks,
+/// waiting to be returned by [WebsocketListener::accept].
+struct Handshakes {
+    send: tokio::sync::mpsc::UnboundedSender<HandshakeResult>,
+    recv: tokio::sync::Mutex<tokio::sync::mpsc::UnboundedReceiver<HandshakeResult>>,
+}
+
+impl Default for Handshakes {
+    fn default() -> Self {
+        let (send, recv) = tokio::sync::mpsc::unbounded_channel();
+        Self {
+            send,
+            recv: tokio::sync::Mutex::new(recv),
+        }
+    }
 }
 
 impl Drop for WebsocketListener {
     fn drop(&mut self) {
         tracing::info!("WebsocketListenerDrop");
+        #[cfg(unix)]
+        if let Listener::Unix(_, path) = &self.listener {
+            if let Err(err) = std::fs::remove_file(path) {
+                tracing::warn!(?err, ?path, "Failed to remove unix socket file");
+            }
+        }
     }
 }
 
 impl WebsocketListener {
     /// Bind a new websocket listener.
+    ///
+    /// If TLS is set in the config, the listener serves `wss://`.
     pub async fn bind<A: ToSocketAddrs>(config: Arc<WebsocketConfig>, addr: A) -> Result<Self> {
         let access_control = Arc::new(config.allowed_origins.clone().ok_or_else(|| {
             Error::other("WebsocketListener requires access control to be set in the config")
@@ -683,35 +985,168 @@ impl WebsocketListener {
         let listener = tokio::net::TcpListener::bind(addr).await?;
 
         let addr = listener.local_addr()?;
-        tracing::info!(?addr, "WebsocketListener Listening");
+        let tls = config.tls.is_some();
+        tracing::info!(?addr, tls, "WebsocketListener Listening");
 
         Ok(Self {
             config,
             access_control,
-            listener,
+            listener: Listener::Tcp(listener),
+            handshakes: Handshakes::default(),
+        })
+    }
+
+    /// Bind a new websocket listener on a unix domain socket at `path`.
+    ///
+    /// A stale socket file left at `path` by a listener which is no longer
+    /// running is replaced, but binding fails if another listener is still
+    /// accepting connections there. The permissions of the socket file are set
+    /// to `mode`, or to `0o600` if none is given, so that file system
+    /// permissions govern who can connect. The socket is bound in a private
+    /// directory next to `path` and only moved to `path` once its permissions
+    /// are set, so it can't be connected to with looser permissions meanwhile.
+    ///
+    /// Origins are not checked, since browsers can't connect to unix domain sockets.
+    /// The socket file is removed when the listener is dropped.
+    #[cfg(unix)]
+    pub async fn bind_unix_socket(
+        config: Arc<WebsocketConfig>,
+        path: impl AsRef<std::path::Path>,
+        mode: Option<u32>,
+    ) -> Result<Self> {
+        use std::os::unix::fs::FileTypeExt;
+
+        let path = path.as_ref().to_path_buf();
+
+        if let Ok(metadata) = std::fs::symlink_metadata(&path) {
+            if !metadata.file_type().is_socket() {
+                return Err(Error::new(
+                    std::io::ErrorKind::AlreadyExists,
+                    format!("{} exists and is not a unix socket", path.display()),
+                ));
+            }
+            if tokio::net::UnixStream::connect(&path).await.is_ok() {
+                return Err(Error::new(
+                    std::io::ErrorKind::AddrInUse,
+                    format!("{} is already in use by another listener", path.display()),
+                ));
+            }
+            std::fs::remove_file(&path)?;
+        }
+
+        let listener = bind_unix_socket_private(&path, mode.unwrap_or(0o600))?;
+        tracing::info!(?path, "WebsocketListener Listening");
+
+        Ok(Self {
+            config,
+            access_control: Arc::new(AllowedOrigins::Any),
+            listener: Listener::Unix(listener, path),
+            handshakes: Handshakes::default(),
         })
     }
 
     /// Get the bound local address of this listener.
+    ///
+    /// Errors if the listener is bound to a unix domain socket.
     pub fn local_addr(&self) -> Result<std::net::SocketAddr> {
-        self.listener.local_addr()
+        match &self.listener {
+            Listener::Tcp(listener) => listener.local_addr(),
+            #[cfg(unix)]
+            Listener::Unix(..) => Err(Error::other(
+                "A unix domain socket listener has no socket address",
+            )),
+        }
+    }
+
+    /// Get the path of the socket file, if the listener is bound to a unix domain socket.
+    pub fn local_path(&self) -> Option<&std::path::Path> {
+        match &self.listener {
+            Listener::Tcp(_) => None,
+            #[cfg(unix)]
+            Listener::Unix(_, path) => Some(path),
+        }
     }
 
     /// Accept an incoming connection.
+    ///
+    /// The handshakes of new connections run in their own tasks, so a client
+    /// which is slow to complete them doesn't hold up other clients, and fail
+    /// if they don't complete within the configured `handshake_timeout`.
+    /// Connections are returned in the order their handshakes complete.
     pub async fn accept(&self) -> Result<(WebsocketSender, WebsocketReceiver)> {
-        let (stream, addr) = self.listener.accept().await?;
-        tracing::debug!(?addr, "Accept Incoming Websocket Connection");
-        let stream = tokio_tungstenite::accept_hdr_async_with_config(
-            stream,
-            ConnectCallback {
-                allowed_origin: self.access_control.clone(),
-            },
-            Some(self.config.as_tungstenite()),
-        )
-        .await
-        .map_err(Error::other)?;
-        split(stream, self.config.default_request_timeout, addr)
+        let mut handshakes = self.handshakes.recv.lock().await;
+        loop {
+            tokio::select! {
+                Some(result) = handshakes.recv() => return result,
+                accepted = self.accept_stream() => {
+                    let (stream, addr, tls) = accepted?;
+                    let config = self.config.clone();
+                    let access_control = self.access_control.clone();
+                    let send = self.handshakes.send.clone();
+                    tokio::task::spawn(async move {
+                        let result = tokio::time::timeout(
+                            config.handshake_timeout,
+                            handshake(config.clone(), access_control, stream, addr, tls),
+                        )
+                        .await
+                        .unwrap_or_else(|_| {
+                            Err(Error::new(
+                                std::io::ErrorKind::TimedOut,
+                                format!("Handshake with {addr:?} timed out"),
+                            ))
+                        });
+                        let _ = send.send(result);
+                    });
+                }
+            }
+        }
     }
+
+    /// Accept the next raw connection, along with the TLS config to secure it with.
+    async fn accept_stream(
+        &self,
+    ) -> Result<(
+        Box<dyn RawStream>,
+        Option<std::net::SocketAddr>,
+        Option<TlsServerConfig>,
+    )> {
+        match &self.listener {
+            Listener::Tcp(listener) => {
+                let (stream, addr) = listener.accept().await?;
+                Ok((Box::new(stream), Some(addr), self.config.tls.clone()))
+            }
+            #[cfg(unix)]
+            Listener::Unix(listener, _) => {
+                let (stream, _) = listener.accept().await?;
+                Ok((Box::new(stream), None, None))
+            }
+        }
+    }
+}
+
+/// Run the TLS, if any, and websocket handshakes of a new incoming connection.
+async fn handshake(
+    config: Arc<WebsocketConfig>,
+    access_control: Arc<AllowedOrigins>,
+    stream: Box<dyn RawStream>,
+    addr: Option<std::net::SocketAddr>,
+    tls: Option<TlsServerConfig>,
+) -> Result<(WebsocketSender, WebsocketReceiver)> {
+    let stream: Box<dyn RawStream> = match tls {
+        Some(tls) => Box::new(tls.acceptor().accept(stream).await?),
+        None => stream,
+    };
+    tracing::debug!(?addr, "Accept Incoming Websocket Connection");
+    let stream = tokio_tungstenite::accept_hdr_async_with_config(
+        stream,
+        ConnectCallback {
+            allowed_origin: access_control,
+        },
+        Some(config.as_tungstenite()),
+    )
+    .await
+    .map_err(Error::other)?;
+    split(stream, config.default_request_timeout, addr)
 }
 
 struct ConnectCallback {
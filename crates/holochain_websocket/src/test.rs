@@ -68,6 +68,219 @@ async fn sanity() {
     r_task.await.unwrap();
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn authenticate() {
+    holochain_trace::test_run().ok();
+
+    #[derive(Debug, serde::Serialize, serde::Deserialize, SerializedBytes, PartialEq)]
+    struct TestAuth {
+        token: Vec<u8>,
+    }
+
+    #[derive(Debug, serde::Serialize, serde::Deserialize, SerializedBytes, PartialEq)]
+    enum TestAuthResponse {
+        Authenticated,
+        Failed,
+    }
+
+    let l = WebsocketListener::bind(Arc::new(WebsocketConfig::LISTENER_DEFAULT), "localhost:0")
+        .await
+        .unwrap();
+    let addr = l.local_addr().unwrap();
+
+    let l_task = tokio::task::spawn(async move {
+        for _ in 0..2 {
+            let (_send, mut recv) = l.accept().await.unwrap();
+
+            match recv.recv::<TestAuth>().await.unwrap() {
+                ReceiveMessage::Authenticate(data, respond) => {
+                    let auth: TestAuth = decode(&data).unwrap();
+                    let response = if auth.token == vec![1, 2, 3] {
+                        TestAuthResponse::Authenticated
+                    } else {
+                        TestAuthResponse::Failed
+                    };
+                    respond.respond(response).await.unwrap();
+                }
+                other => panic!("expected Authenticate, got {other:?}"),
+            }
+        }
+    });
+
+    // The response arrives whether or not the receiver is being polled
+    for (token, expected, poll_receiver) in [
+        (vec![1, 2, 3], TestAuthResponse::Authenticated, true),
+        (vec![4, 5, 6], TestAuthResponse::Failed, false),
+    ] {
+        let (send, mut recv) = connect(Arc::new(WebsocketConfig::CLIENT_DEFAULT), addr)
+            .await
+            .unwrap();
+        let r_task = tokio::task::spawn(async move {
+            if poll_receiver {
+                while recv.recv::<TestAuth>().await.is_ok() {}
+            }
+            futures::future::pending::<()>().await
+        });
+        let response: TestAuthResponse = send.authenticate(TestAuth { token }).await.unwrap();
+        assert_eq!(expected, response);
+        r_task.abort();
+    }
+
+    l_task.await.unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn tls() {
+    holochain_trace::test_run().ok();
+
+    #[derive(Debug, serde::Serialize, serde::Deserialize, SerializedBytes, PartialEq)]
+    enum TestMsg {
+        Hello,
+    }
+
+    let cert = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+    let cert_pem = cert.serialize_pem().unwrap();
+    let key_pem = cert.serialize_private_key_pem();
+
+    let mut config = WebsocketConfig::LISTENER_DEFAULT;
+    config.tls = Some(TlsServerConfig::from_pem(cert_pem.as_bytes(), key_pem.as_bytes()).unwrap());
+    let l = WebsocketListener::bind(Arc::new(config), "localhost:0")
+        .await
+        .unwrap();
+    let addr = l.local_addr().unwrap();
+
+    let l_task = tokio::task::spawn(async move {
+        // The first client doesn't use TLS, so its handshake fails
+        assert!(l.accept().await.is_err());
+
+        let (_send, mut recv) = l.accept().await.unwrap();
+        match recv.recv::<TestMsg>().await.unwrap() {
+            ReceiveMessage::Request(data, res) => {
+                assert_eq!(TestMsg::Hello, data);
+                res.respond(TestMsg::Hello).await.unwrap();
+            }
+            oth => panic!("unexpected: {oth:?}"),
+        }
+        // Keep the connection open until the client has received the response
+        let _ = recv.recv::<TestMsg>().await;
+    });
+
+    // Without a root certificate, the client doesn't use TLS
+    assert!(connect(Arc::new(WebsocketConfig::CLIENT_DEFAULT), addr)
+        .await
+        .is_err());
+
+    let request = ConnectRequest::new(addr)
+        .try_set_root_certificate(cert_pem.as_bytes())
+        .unwrap()
+        .try_set_server_name("localhost")
+        .unwrap();
+    let (send, mut recv) = connect(Arc::new(WebsocketConfig::CLIENT_DEFAULT), request)
+        .await
+        .unwrap();
+    let r_task =
+        tokio::task::spawn(async move { while let Ok(_r) = recv.recv::<TestMsg>().await {} });
+
+    let res: TestMsg = send
+        .request_timeout(TestMsg::Hello, std::time::Duration::from_secs(5))
+        .await
+        .unwrap();
+    assert_eq!(TestMsg::Hello, res);
+
+    drop(send);
+    r_task.abort();
+    l_task.await.unwrap();
+}
+
+#[cfg(unix)]
+#[tokio::test(flavor = "multi_thread")]
+async fn unix_socket() {
+    use std::os::unix::fs::PermissionsExt;
+
+    holochain_trace::test_run().ok();
+
+    #[derive(Debug, serde::Serialize, serde::Deserialize, SerializedBytes, PartialEq)]
+    enum TestMsg {
+        Hello,
+    }
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("test.sock");
+
+    let l = WebsocketListener::bind_unix_socket(
+        Arc::new(WebsocketConfig::LISTENER_DEFAULT),
+        &path,
+        Some(0o600),
+    )
+    .await
+    .unwrap();
+    assert_eq!(Some(path.as_path()), l.local_path());
+    assert!(l.local_addr().is_err());
+    assert_eq!(
+        0o600,
+        std::fs::metadata(&path).unwrap().permissions().mode() & 0o777
+    );
+
+    // The socket is still in use, so a second listener can't take it over
+    assert!(WebsocketListener::bind_unix_socket(
+        Arc::new(WebsocketConfig::LISTENER_DEFAULT),
+        &path,
+        None,
+    )
+    .await
+    .is_err());
+
+    // Without a mode, only the owner can connect, and no private directory is left behind
+    let other_path = dir.path().join("other.sock");
+    let other = WebsocketListener::bind_unix_socket(
+        Arc::new(WebsocketConfig::LISTENER_DEFAULT),
+        &other_path,
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        0o600,
+        std::fs::metadata(&other_path).unwrap().permissions().mode() & 0o777
+    );
+    assert_eq!(2, std::fs::read_dir(dir.path()).unwrap().count());
+    drop(other);
+
+    let l_task = tokio::task::spawn(async move {
+        let (_send, mut recv) = l.accept().await.unwrap();
+        assert_eq!(None, recv.peer_addr());
+
+        match recv.recv::<TestMsg>().await.unwrap() {
+            ReceiveMessage::Request(data, res) => {
+                assert_eq!(TestMsg::Hello, data);
+                res.respond(TestMsg::Hello).await.unwrap();
+            }
+            oth => panic!("unexpected: {oth:?}"),
+        }
+        // Keep the connection open until the client has received the response
+        let _ = recv.recv::<TestMsg>().await;
+    });
+
+    let (send, mut recv) = connect_unix_socket(Arc::new(WebsocketConfig::CLIENT_DEFAULT), &path)
+        .await
+        .unwrap();
+    let r_task =
+        tokio::task::spawn(async move { while let Ok(_r) = recv.recv::<TestMsg>().await {} });
+
+    let res: TestMsg = send
+        .request_timeout(TestMsg::Hello, std::time::Duration::from_secs(5))
+        .await
+        .unwrap();
+    assert_eq!(TestMsg::Hello, res);
+
+    drop(send);
+    r_task.abort();
+    l_task.await.unwrap();
+
+    // The socket file is cleaned up with the listener
+    assert!(!path.exists());
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn blocks_connect_with_mismatched_origin() {
     holochain_trace::test_run().unwrap();
@@ -181,3 +394,37 @@ async fn origin_is_required_on_listener() {
         }
     }
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn slow_handshake_does_not_block_accept() {
+    holochain_trace::test_run().ok();
+
+    let mut config = WebsocketConfig::LISTENER_DEFAULT;
+    config.handshake_timeout = std::time::Duration::from_secs(1);
+    let l = WebsocketListener::bind(Arc::new(config), "localhost:0")
+        .await
+        .unwrap();
+    let addr = l.local_addr().unwrap();
+
+    // This client connects but never starts its handshake
+    let _idle = tokio::net::TcpStream::connect(addr).await.unwrap();
+
+    let l_task = tokio::task::spawn(async move {
+        // The client which completes its handshake is accepted first
+        let (_send, recv) = l.accept().await.unwrap();
+        assert!(recv.peer_addr().is_some());
+
+        // Then the idle client's handshake times out
+        let err = l.accept().await.err().unwrap();
+        assert_eq!(std::io::ErrorKind::TimedOut, err.kind());
+    });
+
+    let (_send, _recv) = connect(Arc::new(WebsocketConfig::CLIENT_DEFAULT), addr)
+        .await
+        .unwrap();
+
+    tokio::time::timeout(std::time::Duration::from_secs(10), l_task)
+        .await
+        .unwrap()
+        .unwrap();
+}
@@ -0,0 +1,90 @@
+//! TLS support, so that a [WebsocketListener](crate::WebsocketListener)
+//! can serve `wss://` and clients can connect to it.
+
+use crate::{Error, Result};
+use std::sync::Arc;
+use tokio_rustls::rustls;
+
+/// The certificate chain and private key with which a
+/// [WebsocketListener](crate::WebsocketListener) serves `wss://`.
+#[derive(Clone)]
+pub struct TlsServerConfig(Arc<rustls::ServerConfig>);
+
+impl std::fmt::Debug for TlsServerConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TlsServerConfig").finish_non_exhaustive()
+    }
+}
+
+impl TlsServerConfig {
+    /// Create a config from a PEM encoded certificate chain and private key.
+    pub fn from_pem(cert_chain: &[u8], private_key: &[u8]) -> Result<Self> {
+        let cert_chain = read_certificates(cert_chain)?;
+        let private_key = read_private_key(private_key)?;
+        let config = rustls::ServerConfig::builder()
+            .with_safe_defaults()
+            .with_no_client_auth()
+            .with_single_cert(cert_chain, private_key)
+            .map_err(Error::other)?;
+        Ok(Self(Arc::new(config)))
+    }
+
+    /// Create a config from files containing a PEM encoded certificate chain
+    /// and private key.
+    pub fn from_pem_files(
+        cert_path: impl AsRef<std::path::Path>,
+        key_path: impl AsRef<std::path::Path>,
+    ) -> Result<Self> {
+        Self::from_pem(&std::fs::read(cert_path)?, &std::fs::read(key_path)?)
+    }
+
+    pub(crate) fn acceptor(&self) -> tokio_rustls::TlsAcceptor {
+        self.0.clone().into()
+    }
+}
+
+/// Build a store of the root certificates a client trusts from PEM.
+pub(crate) fn root_certificates(pem: &[u8]) -> Result<rustls::RootCertStore> {
+    let mut roots = rustls::RootCertStore::empty();
+    for certificate in read_certificates(pem)? {
+        roots.add(&certificate).map_err(Error::other)?;
+    }
+    Ok(roots)
+}
+
+/// Connect a client to a TLS server which presents a certificate
+/// signed by one of the `roots`.
+pub(crate) async fn connect(
+    stream: tokio::net::TcpStream,
+    roots: rustls::RootCertStore,
+    server_name: rustls::ServerName,
+) -> Result<tokio_rustls::client::TlsStream<tokio::net::TcpStream>> {
+    let config = rustls::ClientConfig::builder()
+        .with_safe_defaults()
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+    tokio_rustls::TlsConnector::from(Arc::new(config))
+        .connect(server_name, stream)
+        .await
+}
+
+fn read_certificates(pem: &[u8]) -> Result<Vec<rustls::Certificate>> {
+    let certificates = rustls_pemfile::certs(&mut std::io::Cursor::new(pem))?;
+    if certificates.is_empty() {
+        return Err(Error::other("No certificates found in PEM"));
+    }
+    Ok(certificates.into_iter().map(rustls::Certificate).collect())
+}
+
+fn read_private_key(pem: &[u8]) -> Result<rustls::PrivateKey> {
+    let mut reader = std::io::Cursor::new(pem);
+    while let Some(item) = rustls_pemfile::read_one(&mut reader)? {
+        match item {
+            rustls_pemfile::Item::RSAKey(key)
+            | rustls_pemfile::Item::PKCS8Key(key)
+            | rustls_pemfile::Item::ECKey(key) => return Ok(rustls::PrivateKey(key)),
+            _ => continue,
+        }
+    }
+    Err(Error::other("No private key found in PEM"))
+}
@@ -29,6 +29,8 @@ pub enum CellBlockReason {
     App(Vec<u8>),
     /// Invalid validation result.
     InvalidOp(DhtOpHash),
+    /// Some validator issued a warrant against this cell.
+    Warrant(WarrantProof),
     /// Some bad cryptography.
     BadCrypto,
 }
@@ -42,7 +44,7 @@ impl From<kitsune_p2p_block::AgentSpaceBlockReason> for CellBlockReason {
 }
 
 /// Reason why we might want to block a node.
-#[derive(Clone, serde::Serialize, Debug)]
+#[derive(Clone, serde::Serialize, serde::Deserialize, Debug, Eq, PartialEq)]
 pub enum NodeBlockReason {
     Kitsune(kitsune_p2p_block::NodeBlockReason),
 }
@@ -54,7 +56,7 @@ impl From<kitsune_p2p_block::NodeBlockReason> for NodeBlockReason {
 }
 
 /// Reason why we might want to block an IP.
-#[derive(Clone, serde::Serialize, Debug)]
+#[derive(Clone, serde::Serialize, serde::Deserialize, Debug, Eq, PartialEq)]
 pub enum IpBlockReason {
     Kitsune(kitsune_p2p_block::IpBlockReason),
 }
@@ -65,12 +67,12 @@ impl From<kitsune_p2p_block::IpBlockReason> for IpBlockReason {
     }
 }
 
-/// The type to use for identifying blocking ipv4 addresses.
-type IpV4 = std::net::Ipv4Addr;
+/// The type to use for identifying blocking IP addresses, both v4 and v6.
+type Ip = std::net::IpAddr;
 
 /// Target of a block.
 /// Each target type has an ID and associated reason.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize, Eq, PartialEq)]
 pub enum BlockTarget {
     /// Some cell did bad at the happ level.
     Cell(CellId, CellBlockReason),
@@ -78,7 +80,7 @@ pub enum BlockTarget {
     /// Some node is playing silly buggers.
     Node(kitsune_p2p_block::NodeId, NodeBlockReason),
     /// An entire college campus has it out for us.
-    Ip(IpV4, IpBlockReason),
+    Ip(Ip, IpBlockReason),
 }
 
 impl From<kitsune_p2p_block::BlockTarget> for BlockTarget {
@@ -95,12 +97,12 @@ impl From<kitsune_p2p_block::BlockTarget> for BlockTarget {
     }
 }
 
-#[derive(Debug, serde::Serialize, Clone)]
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Eq, PartialEq)]
 pub enum BlockTargetId {
     Cell(CellId),
     NodeDna(kitsune_p2p_block::NodeId, DnaHash),
     Node(kitsune_p2p_block::NodeId),
-    Ip(IpV4),
+    Ip(Ip),
 }
 
 impl From<kitsune_p2p_block::BlockTargetId> for BlockTargetId {
@@ -137,7 +139,7 @@ impl ToSql for BlockTargetId {
     }
 }
 
-#[derive(Debug, serde::Serialize, Clone)]
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Eq, PartialEq)]
 pub enum BlockTargetReason {
     Cell(CellBlockReason),
     NodeDna(NodeSpaceBlockReason),
@@ -167,6 +169,57 @@ impl From<BlockTarget> for BlockTargetReason {
     }
 }
 
+impl BlockTarget {
+    /// Put a target back together from its ID and reason, as they are stored.
+    /// Returns `None` if the ID and reason are for different kinds of target.
+    pub fn from_parts(id: BlockTargetId, reason: BlockTargetReason) -> Option<Self> {
+        match (id, reason) {
+            (BlockTargetId::Cell(id), BlockTargetReason::Cell(reason)) => {
+                Some(Self::Cell(id, reason))
+            }
+            (BlockTargetId::NodeDna(node_id, dna), BlockTargetReason::NodeDna(reason)) => {
+                Some(Self::NodeDna(node_id, dna, reason))
+            }
+            (BlockTargetId::Node(id), BlockTargetReason::Node(reason)) => {
+                Some(Self::Node(id, reason))
+            }
+            (BlockTargetId::Ip(id), BlockTargetReason::Ip(reason)) => Some(Self::Ip(id, reason)),
+            _ => None,
+        }
+    }
+}
+
+/// A block of a target for an interval, as it is stored and listed.
+///
+/// Unlike [`Block`] this can be serialized, so the interval it carries has not
+/// necessarily been checked. Use [`BlockSpan::try_into_block`] to check it.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize, Eq, PartialEq)]
+pub struct BlockSpan {
+    /// Target of the block.
+    pub target: BlockTarget,
+    /// The interval during which the target is blocked.
+    pub interval: InclusiveTimestampInterval,
+}
+
+impl BlockSpan {
+    /// Check the interval and turn this into a [`Block`].
+    pub fn try_into_block(self) -> Result<Block, kitsune_p2p_timestamp::TimestampError> {
+        Ok(Block::new(
+            self.target,
+            InclusiveTimestampInterval::try_new(self.interval.start(), self.interval.end())?,
+        ))
+    }
+}
+
+impl From<Block> for BlockSpan {
+    fn from(block: Block) -> Self {
+        Self {
+            target: block.target,
+            interval: block.interval,
+        }
+    }
+}
+
 /// Represents a block.
 /// Also can represent an unblock.
 /// NOT serializable and NOT pub fields by design. `try_new` MUST be the only
@@ -24,6 +24,9 @@
 //! information needed to refer to the capability as well as the secret needed
 //! to send to the Grantor.
 
+use holo_hash::ActionHash;
+use holo_hash::AgentPubKey;
+use holochain_serialized_bytes::prelude::*;
 use serde::{Deserialize, Serialize};
 
 use crate::prelude::*;
@@ -42,3 +45,109 @@ pub struct GrantZomeCallCapabilityPayload {
     /// signing for as well as access level, secret and assignees.
     pub cap_grant: ZomeCallCapGrant,
 }
+
+/// Filter for the capability grants returned by the `capability_grants` host function.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, SerializedBytes)]
+pub struct CapGrantQuery {
+    /// Only return grants with exactly this tag.
+    pub tag: Option<String>,
+    /// Only return grants which are assigned to this agent.
+    /// Unrestricted and transferable grants are not assigned to any agent
+    /// and are never returned when this is set.
+    pub grantee: Option<AgentPubKey>,
+}
+
+impl CapGrantQuery {
+    /// Only return grants with exactly this tag.
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.tag = Some(tag.into());
+        self
+    }
+
+    /// Only return grants which are assigned to this agent.
+    pub fn grantee(mut self, grantee: AgentPubKey) -> Self {
+        self.grantee = Some(grantee);
+        self
+    }
+
+    /// Check whether a grant matches this query.
+    pub fn matches(&self, grant: &ZomeCallCapGrant) -> bool {
+        if let Some(tag) = &self.tag {
+            if *tag != grant.tag {
+                return false;
+            }
+        }
+        if let Some(grantee) = &self.grantee {
+            match &grant.access {
+                CapAccess::Assigned { assignees, .. } if assignees.contains(grantee) => (),
+                _ => return false,
+            }
+        }
+        true
+    }
+}
+
+/// Filter for the capability claims returned by the `capability_claims` host function.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, SerializedBytes)]
+pub struct CapClaimQuery {
+    /// Only return claims with exactly this tag.
+    pub tag: Option<String>,
+    /// Only return claims on grants authored by this agent.
+    pub grantor: Option<AgentPubKey>,
+}
+
+impl CapClaimQuery {
+    /// Only return claims with exactly this tag.
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.tag = Some(tag.into());
+        self
+    }
+
+    /// Only return claims on grants authored by this agent.
+    pub fn grantor(mut self, grantor: AgentPubKey) -> Self {
+        self.grantor = Some(grantor);
+        self
+    }
+
+    /// Check whether a claim matches this query.
+    pub fn matches(&self, claim: &CapClaim) -> bool {
+        self.tag.as_ref().map_or(true, |tag| *tag == claim.tag)
+            && self
+                .grantor
+                .as_ref()
+                .map_or(true, |grantor| *grantor == claim.grantor)
+    }
+}
+
+/// A capability grant on the local source chain
+/// which has neither been updated nor deleted.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, SerializedBytes)]
+pub struct LiveCapGrant {
+    /// The hash of the action which created or updated the grant.
+    /// Use this to update or delete the grant.
+    pub action_hash: ActionHash,
+    /// The grant.
+    pub grant: ZomeCallCapGrant,
+}
+
+/// A capability claim on the local source chain which has not been deleted.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, SerializedBytes)]
+pub struct LiveCapClaim {
+    /// The hash of the action which created the claim.
+    pub action_hash: ActionHash,
+    /// The claim.
+    pub claim: CapClaim,
+}
+
+/// The capability which authorized the current zome call,
+/// as returned by the `capability_info` host function.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, SerializedBytes)]
+pub struct CapabilityInfo {
+    /// The agent who made the call.
+    pub provenance: AgentPubKey,
+    /// The secret the caller provided, if any.
+    pub cap_secret: Option<CapSecret>,
+    /// The grant which authorized the call.
+    /// This is [`CapGrant::ChainAuthor`] for calls made by the author of the chain.
+    pub cap_grant: CapGrant,
+}
@@ -49,6 +49,13 @@ pub struct DnaDef {
     /// A vector of zomes that do not affect
     /// the [`DnaHash`].
     pub coordinator_zomes: CoordinatorZomes,
+
+    /// The rate limits which every agent's actions are held to,
+    /// keyed by the bucket which the `weigh` callback puts an action in.
+    /// The rate limits are included in the DNA hash computation.
+    #[serde(default)]
+    #[cfg_attr(feature = "full-dna-def", builder(default))]
+    pub rate_limits: RateLimits,
 }
 
 #[derive(Serialize, Debug, PartialEq, Eq)]
@@ -56,6 +63,9 @@ pub struct DnaDef {
 struct DnaDefHash<'a> {
     modifiers: &'a DnaModifiers,
     integrity_zomes: &'a IntegrityZomes,
+    // Skipped when empty so that DNAs without rate limits keep their hash.
+    #[serde(skip_serializing_if = "RateLimits::is_empty")]
+    rate_limits: &'a RateLimits,
 }
 
 #[cfg(feature = "test_utils")]
@@ -234,6 +244,7 @@ impl HashableContent for DnaDef {
         let hash = DnaDefHash {
             modifiers: &self.modifiers,
             integrity_zomes: &self.integrity_zomes,
+            rate_limits: &self.rate_limits,
         };
         HashableContentBytes::Content(
             holochain_serialized_bytes::UnsafeBytes::from(
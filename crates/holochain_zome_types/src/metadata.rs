@@ -1,9 +1,16 @@
 //! Metadata types for use in wasm
+use crate::action::ActionHashed;
 use crate::record::Record;
 use crate::record::SignedActionHashed;
+use crate::request::MetadataRequest;
+use crate::timestamp::Timestamp;
 use crate::validate::ValidationStatus;
 use crate::Entry;
+use holo_hash::ActionHash;
+use holo_hash::AnyDhtHash;
 use holochain_serialized_bytes::prelude::*;
+use kitsune_p2p_dht_arc::DhtArcRange;
+use std::collections::BTreeSet;
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, SerializedBytes)]
 #[serde(tag = "type", content = "content")]
@@ -91,3 +98,84 @@ pub enum EntryDhtStatus {
     /// **not implemented** We have agreed to drop this [Entry] content from the system. Action can stay with no entry
     Purged,
 }
+
+/// Timestamp of when the action was created with the actions hash.
+#[derive(Debug, Hash, PartialOrd, Ord, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct TimedActionHash {
+    /// Time when this action was created
+    pub timestamp: Timestamp,
+    /// Hash of the action
+    pub action_hash: ActionHash,
+}
+
+/// Metadata returned from a GetMeta request.
+/// The Ord derive on TimedActionHash means each set is ordered by time.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, SerializedBytes)]
+pub struct MetadataSet {
+    /// Actions that created or updated an entry.
+    /// These are the actions that show the entry exists.
+    pub actions: BTreeSet<TimedActionHash>,
+    /// Actions that created or updated an entry
+    /// but did not pass validation.
+    pub invalid_actions: BTreeSet<TimedActionHash>,
+    /// Deletes on an action
+    pub deletes: BTreeSet<TimedActionHash>,
+    /// Updates on an action or entry
+    pub updates: BTreeSet<TimedActionHash>,
+    /// The status of an entry from an authority.
+    /// This is simply a faster way of determining if
+    /// there are any live actions on an entry.
+    pub entry_dht_status: Option<EntryDhtStatus>,
+    /// The validation status of the record, if the hash is of an
+    /// action that the authority has integrated.
+    pub validation_status: Option<ValidationStatus>,
+    /// The number of links on the hash as a base which
+    /// are valid and have not been deleted.
+    pub link_count: usize,
+    /// Whether the hash is within the storage arc of the agent that sent
+    /// this metadata. Metadata from an agent which is not an authority
+    /// for the hash may be incomplete.
+    pub is_authority: bool,
+    /// The range of the DHT covered by the storage arc of the agent that
+    /// sent this metadata, if it is known.
+    pub storage_arc: Option<DhtArcRange>,
+}
+
+/// Zome input for get_meta calls.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct GetMetaInput {
+    /// Any DHT hash to get the metadata of.
+    pub any_dht_hash: AnyDhtHash,
+    /// Which metadata to get.
+    pub metadata_request: MetadataRequest,
+}
+
+impl GetMetaInput {
+    /// Constructor.
+    pub fn new(any_dht_hash: AnyDhtHash, metadata_request: MetadataRequest) -> Self {
+        Self {
+            any_dht_hash,
+            metadata_request,
+        }
+    }
+}
+
+impl From<ActionHashed> for TimedActionHash {
+    fn from(h: ActionHashed) -> Self {
+        let (action, hash) = h.into_inner();
+        TimedActionHash {
+            timestamp: action.timestamp(),
+            action_hash: hash,
+        }
+    }
+}
+
+#[cfg(feature = "full")]
+impl From<ActionHash> for TimedActionHash {
+    fn from(h: ActionHash) -> Self {
+        TimedActionHash {
+            timestamp: Timestamp::now(),
+            action_hash: h,
+        }
+    }
+}
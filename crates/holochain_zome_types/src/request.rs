@@ -8,8 +8,8 @@ pub struct MetadataRequest {
     /// Get all the actions on an entry.
     /// Invalid request on an action.
     pub all_valid_actions: bool,
-    // TODO: Implement after validation
-    /// Placeholder
+    /// Get all the actions on an entry or action
+    /// which did not pass validation.
     pub all_invalid_actions: bool,
     /// Get all the deletes on an action
     pub all_deletes: bool,
@@ -19,6 +19,11 @@ impl AppSignal {
     pub fn into_inner(self) -> ExternIO {
         self.0
     }
+
+    /// Borrow the inner type
+    pub fn as_inner(&self) -> &ExternIO {
+        &self.0
+    }
 }
 
 /// Remote signal many agents without waiting for responses.
@@ -1,6 +1,142 @@
 //! Types for warrants
+//!
+//! A warrant is a signed claim, made by an agent who validated some data,
+//! that another agent has broken the integrity of their source chain.
+//! Warrants are sent to the agent activity authorities of the agent they
+//! are about (the "warrantee"), who store them and return them as part of
+//! the [`AgentActivity`](crate::query::AgentActivity) of that agent.
+
+use holo_hash::ActionHash;
+use holo_hash::AgentPubKey;
+use holochain_integrity_types::Signature;
+use holochain_integrity_types::Timestamp;
 pub use holochain_serialized_bytes::prelude::*;
 
-#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
-/// Placeholder for warrant type
-pub struct Warrant;
+/// The hash of an action and the signature its author made of it.
+pub type ActionHashAndSig = (ActionHash, Signature);
+
+#[derive(
+    Clone, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize, SerializedBytes,
+)]
+/// A claim, made by its author, that the warrantee broke the integrity of
+/// their source chain.
+pub struct Warrant {
+    /// The proof that the warrantee broke the integrity of their chain.
+    pub proof: WarrantProof,
+    /// The agent who issued this warrant.
+    pub author: AgentPubKey,
+    /// The time at which this warrant was issued.
+    pub timestamp: Timestamp,
+}
+
+impl Warrant {
+    /// Constructor
+    pub fn new(proof: WarrantProof, author: AgentPubKey, timestamp: Timestamp) -> Self {
+        Self {
+            proof,
+            author,
+            timestamp,
+        }
+    }
+
+    /// The agent this warrant is about.
+    pub fn warrantee(&self) -> &AgentPubKey {
+        self.proof.warrantee()
+    }
+}
+
+#[derive(
+    Clone, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize, SerializedBytes,
+)]
+/// The proof contained in a [`Warrant`].
+pub enum WarrantProof {
+    /// The warrantee authored data which broke the integrity of their chain.
+    ChainIntegrity(ChainIntegrityWarrant),
+}
+
+impl WarrantProof {
+    /// The agent this proof is about.
+    pub fn warrantee(&self) -> &AgentPubKey {
+        match self {
+            Self::ChainIntegrity(warrant) => warrant.warrantee(),
+        }
+    }
+}
+
+#[derive(
+    Clone, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize, SerializedBytes,
+)]
+/// Proof that an agent broke the integrity of their source chain.
+///
+/// Each proof references the signed actions which can be fetched to check it.
+pub enum ChainIntegrityWarrant {
+    /// Something invalid was authored on a chain.
+    /// When we receive this warrant, we fetch the action and validate it
+    /// as every other op is validated.
+    InvalidChainOp {
+        /// The author of the action.
+        action_author: AgentPubKey,
+        /// The hash and signature of the invalid action.
+        action: ActionHashAndSig,
+        /// The kind of validation which the action failed.
+        validation_type: ValidationType,
+    },
+    /// Proof that the chain was forked, in the form of two different actions
+    /// by the same author at the same sequence number.
+    ChainFork {
+        /// The author of the forked chain.
+        chain_author: AgentPubKey,
+        /// The two actions at the same position in the chain.
+        action_pair: (ActionHashAndSig, ActionHashAndSig),
+    },
+}
+
+impl ChainIntegrityWarrant {
+    /// The agent this proof is about.
+    pub fn warrantee(&self) -> &AgentPubKey {
+        match self {
+            Self::InvalidChainOp { action_author, .. } => action_author,
+            Self::ChainFork { chain_author, .. } => chain_author,
+        }
+    }
+}
+
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize, SerializedBytes,
+)]
+/// The kind of validation which an op failed.
+pub enum ValidationType {
+    /// The op failed the system validation done by Holochain.
+    Sys,
+    /// The op failed the validation callbacks of the DNA's integrity zomes.
+    App,
+}
+
+#[derive(
+    Clone, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize, SerializedBytes,
+)]
+/// A [`Warrant`] with the signature of its author.
+pub struct SignedWarrant {
+    /// The warrant.
+    pub warrant: Warrant,
+    /// The signature of the warrant's author.
+    pub signature: Signature,
+}
+
+impl SignedWarrant {
+    /// Constructor
+    pub fn new(warrant: Warrant, signature: Signature) -> Self {
+        Self { warrant, signature }
+    }
+
+    /// The agent this warrant is about.
+    pub fn warrantee(&self) -> &AgentPubKey {
+        self.warrant.warrantee()
+    }
+}
+
+impl AsRef<Warrant> for SignedWarrant {
+    fn as_ref(&self) -> &Warrant {
+        &self.warrant
+    }
+}
@@ -53,14 +53,14 @@ wasm_io_types! {
 
     fn call (Vec<zt::call::Call>) -> Vec<zt::prelude::ZomeCallResponse>;
 
-    // @todo List all the local capability claims.
-    fn capability_claims (()) -> ();
+    // List the live capability claims on the local chain.
+    fn capability_claims (zt::capability::CapClaimQuery) -> Vec<zt::capability::LiveCapClaim>;
 
-    // @todo List all the local capability grants.
-    fn capability_grants (()) -> ();
+    // List the live capability grants on the local chain.
+    fn capability_grants (zt::capability::CapGrantQuery) -> Vec<zt::capability::LiveCapGrant>;
 
-    // @todo Get the capability for the current zome call.
-    fn capability_info (()) -> ();
+    // Get the capability which authorized the current zome call.
+    fn capability_info (()) -> zt::capability::CapabilityInfo;
 
     // Returns ActionHash of the newly created record.
     fn create (zt::entry::CreateInput) -> holo_hash::ActionHash;
@@ -88,6 +88,9 @@ wasm_io_types! {
 
     fn get_details (Vec<zt::entry::GetInput>) -> Vec<Option<zt::metadata::Details>>;
 
+    // Get the metadata the authorities for a hash hold about it.
+    fn get_meta (zt::metadata::GetMetaInput) -> Vec<zt::metadata::MetadataSet>;
+
     fn get_link_details (Vec<zt::link::GetLinksInput>) -> Vec<zt::link::LinkDetails>;
 
     // Get links by entry hash from the cascade.
@@ -115,6 +118,9 @@ wasm_io_types! {
     // Query the source chain for data.
     fn query (zt::query::ChainQueryFilter) -> Vec<crate::prelude::Record>;
 
+    // Query the source chain which the agent migrated from.
+    fn query_previous_chain (zt::query::ChainQueryFilter) -> Vec<crate::prelude::Record>;
+
     // the length of random bytes to create
     fn random_bytes (u32) -> zt::bytes::Bytes;
 
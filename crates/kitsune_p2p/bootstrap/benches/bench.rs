@@ -6,9 +6,12 @@ use criterion::BenchmarkId;
 use criterion::Criterion;
 
 use ::fixt::prelude::*;
+use ed25519_dalek::{Keypair, Signer};
 use kitsune_p2p::agent_store::AgentInfoSigned;
 use kitsune_p2p::dependencies::url2::url2;
 use kitsune_p2p::fixt::*;
+use kitsune_p2p::KitsuneAgent;
+use kitsune_p2p::KitsuneSignature;
 use kitsune_p2p::KitsuneSpace;
 use kitsune_p2p_bootstrap::error::BootstrapClientError;
 use kitsune_p2p_bootstrap::error::BootstrapClientResult;
@@ -58,14 +61,18 @@ fn bootstrap(bench: &mut Criterion) {
     group.bench_function(BenchmarkId::new("test", "put".to_string()), |b| {
         b.iter(|| {
             runtime.block_on(async {
+                let keypair = Keypair::generate(&mut rand_dalek::thread_rng());
                 let info = AgentInfoSigned::sign(
                     space.clone(),
-                    Arc::new(fixt!(KitsuneAgent, Unpredictable)),
+                    Arc::new(KitsuneAgent::new(keypair.public.as_bytes().to_vec())),
                     u32::MAX / 4,
                     fixt!(UrlList, Empty),
                     0,
                     std::time::UNIX_EPOCH.elapsed().unwrap().as_millis() as u64 + 60_000_000,
-                    |_| async move { Ok(Arc::new(fixt!(KitsuneSignature, Unpredictable))) },
+                    |data| {
+                        let signature = keypair.sign(data).to_bytes().to_vec();
+                        async move { Ok(Arc::new(KitsuneSignature(signature))) }
+                    },
                 )
                 .await
                 .unwrap();
@@ -6,6 +6,7 @@ use std::sync::atomic::AtomicUsize;
 
 use kitsune_p2p_types::codec::rmp_decode;
 use kitsune_p2p_types::codec::rmp_encode;
+use rate_limit::PutRateLimits;
 use store::Store;
 use warp::{hyper::body::Bytes, Filter};
 
@@ -18,8 +19,12 @@ mod now;
 mod proxy_list;
 mod put;
 mod random;
+mod rate_limit;
 mod store;
 
+#[cfg(test)]
+mod test_utils;
+
 pub mod error;
 
 /// No reason to accept a peer data bigger then 1KB.
@@ -33,6 +38,38 @@ pub type BootstrapDriver = futures::future::BoxFuture<'static, ()>;
 
 pub type BootstrapShutdown = Box<dyn FnOnce() + 'static + Send + Sync>;
 
+/// Configuration of a bootstrap server.
+#[derive(Clone, Debug)]
+pub struct BootstrapConfig {
+    /// The proxy server addresses returned by the `proxy_list` op.
+    pub proxy_list: Vec<String>,
+    /// How often expired entries are pruned.
+    pub prune_frequency: std::time::Duration,
+    /// The most agent infos one IP address may put per minute,
+    /// or `None` for no limit.
+    pub max_puts_per_ip_per_minute: Option<u32>,
+    /// The most agent infos which may be put into one space per minute,
+    /// or `None` for no limit.
+    pub max_puts_per_space_per_minute: Option<u32>,
+    /// The SQLite database the peers are kept in, so that they survive a
+    /// restart. If `None`, the peers are only kept in memory.
+    #[cfg(feature = "sqlite_store")]
+    pub db_path: Option<std::path::PathBuf>,
+}
+
+impl Default for BootstrapConfig {
+    fn default() -> Self {
+        Self {
+            proxy_list: Vec::new(),
+            prune_frequency: PRUNE_EXPIRED_FREQ,
+            max_puts_per_ip_per_minute: None,
+            max_puts_per_space_per_minute: None,
+            #[cfg(feature = "sqlite_store")]
+            db_path: None,
+        }
+    }
+}
+
 /// Run a bootstrap with the default prune frequency [`PRUNE_EXPIRED_FREQ`].
 pub async fn run(
     addr: impl Into<SocketAddr> + 'static,
@@ -47,20 +84,51 @@ pub async fn run_with_prune_freq(
     proxy_list: Vec<String>,
     prune_frequency: std::time::Duration,
 ) -> Result<(BootstrapDriver, SocketAddr, BootstrapShutdown), String> {
-    let store = Store::new(proxy_list);
+    run_with_config(
+        addr,
+        BootstrapConfig {
+            proxy_list,
+            prune_frequency,
+            ..Default::default()
+        },
+    )
+    .await
+}
+
+/// Run a bootstrap server with the given configuration.
+pub async fn run_with_config(
+    addr: impl Into<SocketAddr> + 'static,
+    config: BootstrapConfig,
+) -> Result<(BootstrapDriver, SocketAddr, BootstrapShutdown), String> {
+    #[cfg(feature = "sqlite_store")]
+    let store = match &config.db_path {
+        Some(path) => Store::open(config.proxy_list, path)
+            .map_err(|e| format!("Failed to open the peer database: {:?}", e))?,
+        None => Store::new(config.proxy_list),
+    };
+    #[cfg(not(feature = "sqlite_store"))]
+    let store = Store::new(config.proxy_list);
+
+    let limits = PutRateLimits::new(
+        config.max_puts_per_ip_per_minute,
+        config.max_puts_per_space_per_minute,
+    );
 
     {
         let store = store.clone();
+        let limits = limits.clone();
+        let prune_frequency = config.prune_frequency;
         tokio::task::spawn(async move {
             loop {
                 tokio::time::sleep(prune_frequency).await;
                 store.prune();
+                limits.prune();
             }
         });
     }
 
     let boot = now::now()
-        .or(put::put(store.clone()))
+        .or(put::put(store.clone(), limits))
         .or(random::random(store.clone()))
         .or(proxy_list::proxy_list(store.clone()))
         .or(clear::clear(store));
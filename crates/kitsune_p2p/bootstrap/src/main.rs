@@ -12,6 +12,22 @@ struct Args {
     /// multiple times
     #[clap(short, long, verbatim_doc_comment)]
     proxy: Vec<String>,
+
+    /// the most agent infos one IP address
+    /// may put per minute
+    #[clap(long, verbatim_doc_comment)]
+    max_puts_per_ip: Option<u32>,
+
+    /// the most agent infos which may be put
+    /// into one space per minute
+    #[clap(long, verbatim_doc_comment)]
+    max_puts_per_space: Option<u32>,
+
+    /// keep the peers in this SQLite database,
+    /// so that they survive a restart
+    #[cfg(feature = "sqlite_store")]
+    #[clap(long, verbatim_doc_comment)]
+    db: Option<std::path::PathBuf>,
 }
 
 #[tokio::main(flavor = "multi_thread")]
@@ -27,7 +43,16 @@ async fn main() {
         .next()
         .unwrap();
 
-    match kitsune_p2p_bootstrap::run(addr, args.proxy).await {
+    let config = kitsune_p2p_bootstrap::BootstrapConfig {
+        proxy_list: args.proxy,
+        max_puts_per_ip_per_minute: args.max_puts_per_ip,
+        max_puts_per_space_per_minute: args.max_puts_per_space,
+        #[cfg(feature = "sqlite_store")]
+        db_path: args.db,
+        ..Default::default()
+    };
+
+    match kitsune_p2p_bootstrap::run_with_config(addr, config).await {
         Ok((driver, addr, _shutdown)) => {
             println!("http://{}", addr);
             driver.await;
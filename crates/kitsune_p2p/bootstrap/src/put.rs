@@ -1,50 +1,85 @@
+use crate::rate_limit::PutRateLimits;
 use crate::store::Store;
 
 use super::*;
 use kitsune_p2p_types::agent_info::AgentInfoSigned;
+use std::net::SocketAddr;
+use warp::http::StatusCode;
 use warp::Filter;
 
 pub(crate) fn put(
     store: Store,
+    limits: PutRateLimits,
 ) -> impl Filter<Extract = impl warp::Reply + Sized, Error = warp::Rejection> + Clone {
     warp::post()
         .and(warp::header::exact("X-Op", "put"))
         .and(warp::body::content_length_limit(SIZE_LIMIT))
         .and(warp::body::bytes())
+        .and(warp::addr::remote())
         .and(with_store(store))
+        .and(warp::any().map(move || limits.clone()))
         .and_then(put_info)
 }
 
-async fn put_info(peer: Bytes, store: Store) -> Result<impl warp::Reply, warp::Rejection> {
+async fn put_info(
+    peer: Bytes,
+    remote: Option<SocketAddr>,
+    store: Store,
+    limits: PutRateLimits,
+) -> Result<impl warp::Reply, warp::Rejection> {
     #[derive(Debug)]
     struct BadDecode(String);
     impl warp::reject::Reject for BadDecode {}
     let peer: AgentInfoSigned =
         rmp_decode(&mut AsRef::<[u8]>::as_ref(&peer)).map_err(|e| BadDecode(format!("{e:?}")))?;
+    // Only valid puts count against the limits, so that forged agent infos
+    // can't use up the limit of a space.
     if !valid(&peer) {
         #[derive(Debug)]
         struct Invalid;
         impl warp::reject::Reject for Invalid {}
         return Err(Invalid.into());
     }
+    if !limits.check(remote.map(|addr| addr.ip()), &peer.space) {
+        return Ok(warp::reply::with_status(
+            Vec::new(),
+            StatusCode::TOO_MANY_REQUESTS,
+        ));
+    }
     store.put(peer);
     PUT.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
     let mut buf = Vec::with_capacity(1);
     rmp_encode(&mut buf, ()).map_err(|_| warp::reject())?;
-    Ok(buf)
+    Ok(warp::reply::with_status(buf, StatusCode::OK))
 }
 
 fn valid(peer: &AgentInfoSigned) -> bool {
-    // TODO: actually verify signature... just checking size for now
-    if peer.signature.len() != 64 {
-        return false;
-    }
     // Verify time
-    peer.expires_at_ms as u128
-        > std::time::UNIX_EPOCH
+    let expired = peer.expires_at_ms as u128
+        <= std::time::UNIX_EPOCH
             .elapsed()
             .expect("Bootstrap system clock is set before the epoch")
-            .as_millis()
+            .as_millis();
+    !expired && verify_signature(peer)
+}
+
+/// Check that the agent info was signed by the agent it is about.
+///
+/// The first 32 bytes of the agent are its ed25519 public key. Holochain
+/// agents have 4 more bytes for their location, which are not part of the key.
+fn verify_signature(peer: &AgentInfoSigned) -> bool {
+    use ed25519_dalek::{PublicKey, Signature};
+
+    let Some(key) = peer.agent.0.get(..32) else {
+        return false;
+    };
+    let Ok(key) = PublicKey::from_bytes(key) else {
+        return false;
+    };
+    let Ok(signature) = Signature::try_from(peer.signature.0.as_slice()) else {
+        return false;
+    };
+    key.verify_strict(&peer.encoded_bytes, &signature).is_ok()
 }
 
 #[cfg(test)]
@@ -52,36 +87,42 @@ mod tests {
     use std::sync::Arc;
 
     use super::*;
+    use crate::test_utils::sign_test_agent_info;
     use ::fixt::prelude::*;
     use kitsune_p2p_bin_data::fixt::*;
+    use kitsune_p2p_bin_data::KitsuneSpace;
     use kitsune_p2p_types::fixt::*;
 
+    async fn request<F>(filter: &F, info: &AgentInfoSigned) -> warp::http::Response<Bytes>
+    where
+        F: Filter + 'static,
+        F::Extract: warp::Reply + Send,
+    {
+        let mut buf = Vec::new();
+        rmp_encode(&mut buf, info.clone()).unwrap();
+
+        warp::test::request()
+            .method("POST")
+            .header("Content-type", "application/octet")
+            .header("X-Op", "put")
+            .remote_addr(([10, 0, 0, 1], 5000).into())
+            .body(buf)
+            .reply(filter)
+            .await
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_put() {
         let store = Store::new(vec![]);
-        let filter = put(store.clone());
+        let filter = put(store.clone(), PutRateLimits::new(None, None));
 
-        let info = AgentInfoSigned::sign(
+        let info = sign_test_agent_info(
             Arc::new(fixt!(KitsuneSpace, Unpredictable)),
-            Arc::new(fixt!(KitsuneAgent, Unpredictable)),
-            u32::MAX / 4,
             fixt!(UrlList, Empty),
-            0,
-            std::time::UNIX_EPOCH.elapsed().unwrap().as_millis() as u64 + 60_000_000,
-            |_| async move { Ok(Arc::new(fixt!(KitsuneSignature, Unpredictable))) },
         )
-        .await
-        .unwrap();
-        let mut buf = Vec::new();
-        rmp_encode(&mut buf, info.clone()).unwrap();
+        .await;
 
-        let res = warp::test::request()
-            .method("POST")
-            .header("Content-type", "application/octet")
-            .header("X-Op", "put")
-            .body(buf)
-            .reply(&filter)
-            .await;
+        let res = request(&filter, &info).await;
         assert_eq!(res.status(), 200);
         assert_eq!(
             *store
@@ -93,4 +134,72 @@ mod tests {
             info
         );
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_put_rejects_bad_signature() {
+        let store = Store::new(vec![]);
+        let filter = put(store.clone(), PutRateLimits::new(None, None));
+
+        let info = AgentInfoSigned::sign(
+            Arc::new(fixt!(KitsuneSpace, Unpredictable)),
+            Arc::new(fixt!(KitsuneAgent, Unpredictable)),
+            u32::MAX / 4,
+            fixt!(UrlList, Empty),
+            0,
+            std::time::UNIX_EPOCH.elapsed().unwrap().as_millis() as u64 + 60_000_000,
+            |_| async move { Ok(Arc::new(fixt!(KitsuneSignature, Unpredictable))) },
+        )
+        .await
+        .unwrap();
+
+        let res = request(&filter, &info).await;
+        assert_ne!(res.status(), 200);
+        assert!(store.all().is_empty());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_put_rate_limited() {
+        let store = Store::new(vec![]);
+        let filter = put(store.clone(), PutRateLimits::new(Some(2), None));
+        let space = Arc::new(fixt!(KitsuneSpace, Unpredictable));
+
+        for _ in 0..2 {
+            let info = sign_test_agent_info(space.clone(), fixt!(UrlList, Empty)).await;
+            assert_eq!(request(&filter, &info).await.status(), 200);
+        }
+        let info = sign_test_agent_info(space.clone(), fixt!(UrlList, Empty)).await;
+        assert_eq!(
+            request(&filter, &info).await.status(),
+            StatusCode::TOO_MANY_REQUESTS
+        );
+        assert_eq!(store.all().get(space.as_ref()).unwrap().len(), 2);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_invalid_puts_are_not_rate_limited() {
+        let store = Store::new(vec![]);
+        let filter = put(store.clone(), PutRateLimits::new(Some(1), Some(1)));
+        let space = Arc::new(fixt!(KitsuneSpace, Unpredictable));
+
+        for _ in 0..2 {
+            let info = AgentInfoSigned::sign(
+                space.clone(),
+                Arc::new(fixt!(KitsuneAgent, Unpredictable)),
+                u32::MAX / 4,
+                fixt!(UrlList, Empty),
+                0,
+                std::time::UNIX_EPOCH.elapsed().unwrap().as_millis() as u64 + 60_000_000,
+                |_| async move { Ok(Arc::new(fixt!(KitsuneSignature, Unpredictable))) },
+            )
+            .await
+            .unwrap();
+            let status = request(&filter, &info).await.status();
+            assert_ne!(status, 200);
+            assert_ne!(status, StatusCode::TOO_MANY_REQUESTS);
+        }
+
+        let info = sign_test_agent_info(space.clone(), fixt!(UrlList, Empty)).await;
+        assert_eq!(request(&filter, &info).await.status(), 200);
+        assert_eq!(store.all().get(space.as_ref()).unwrap().len(), 1);
+    }
 }
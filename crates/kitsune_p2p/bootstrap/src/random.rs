@@ -32,13 +32,14 @@ mod tests {
     use std::sync::Arc;
 
     use super::*;
+    use crate::test_utils::sign_test_agent_info;
     use ::fixt::prelude::*;
     use kitsune_p2p::{agent_store::AgentInfoSigned, KitsuneSpace};
     use kitsune_p2p_bin_data::fixt::*;
     use kitsune_p2p_types::{bootstrap::RandomLimit, tx2::tx2_utils::TxUrl};
 
     async fn put(store: Store, peers: Vec<AgentInfoSigned>) {
-        let filter = crate::put::put(store);
+        let filter = crate::put::put(store, crate::rate_limit::PutRateLimits::new(None, None));
 
         for peer in peers {
             let mut buf = Vec::new();
@@ -60,17 +61,8 @@ mod tests {
         let store = Store::new(vec![]);
         let filter = super::random(store.clone());
         let space: Arc<KitsuneSpace> = Arc::new(fixt!(KitsuneSpace));
-        let offline_peer = AgentInfoSigned::sign(
-            space.clone(),
-            Arc::new(fixt!(KitsuneAgent, Unpredictable)),
-            u32::MAX / 4,
-            vec![], // no url means offline
-            0,
-            std::time::UNIX_EPOCH.elapsed().unwrap().as_millis() as u64 + 60_000_000,
-            |_| async move { Ok(Arc::new(fixt!(KitsuneSignature, Unpredictable))) },
-        )
-        .await
-        .unwrap();
+        // no url means offline
+        let offline_peer = sign_test_agent_info(space.clone(), vec![]).await;
         put(store.clone(), vec![offline_peer.clone()]).await;
 
         let query = RandomQuery {
@@ -111,17 +103,8 @@ mod tests {
         let space: Arc<KitsuneSpace> = Arc::new(fixt!(KitsuneSpace));
         let mut peers = Vec::new();
         for _ in 0..20 {
-            let info = AgentInfoSigned::sign(
-                space.clone(),
-                Arc::new(fixt!(KitsuneAgent, Unpredictable)),
-                u32::MAX / 4,
-                vec![TxUrl::from_str_panicking("fake:")],
-                0,
-                std::time::UNIX_EPOCH.elapsed().unwrap().as_millis() as u64 + 60_000_000,
-                |_| async move { Ok(Arc::new(fixt!(KitsuneSignature, Unpredictable))) },
-            )
-            .await
-            .unwrap();
+            let info =
+                sign_test_agent_info(space.clone(), vec![TxUrl::from_str_panicking("fake:")]).await;
             peers.push(info);
         }
         put(store.clone(), peers.clone()).await;
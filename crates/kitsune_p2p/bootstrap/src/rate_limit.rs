@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use kitsune_p2p_types::bin_types::KitsuneSpace;
+use parking_lot::Mutex;
+
+/// The window the put limits are counted over.
+const WINDOW: Duration = Duration::from_secs(60);
+
+/// Limits on how many agent infos can be put per minute, from one IP address
+/// and into one space.
+#[derive(Clone, Debug)]
+pub(crate) struct PutRateLimits {
+    per_ip: Option<Arc<RateLimiter<IpAddr>>>,
+    per_space: Option<Arc<RateLimiter<Arc<KitsuneSpace>>>>,
+}
+
+impl PutRateLimits {
+    pub fn new(max_puts_per_ip: Option<u32>, max_puts_per_space: Option<u32>) -> Self {
+        Self {
+            per_ip: max_puts_per_ip.map(|max| Arc::new(RateLimiter::new(max))),
+            per_space: max_puts_per_space.map(|max| Arc::new(RateLimiter::new(max))),
+        }
+    }
+
+    /// Count a put from this address into this space, returning false if
+    /// either of them is over its limit.
+    ///
+    /// Puts from an unknown address are only limited per space.
+    pub fn check(&self, ip: Option<IpAddr>, space: &Arc<KitsuneSpace>) -> bool {
+        let ip_ok = match (&self.per_ip, ip) {
+            (Some(limiter), Some(ip)) => limiter.check(ip),
+            _ => true,
+        };
+        // Puts which were already refused for their address don't count
+        // against the space, so one address can't lock everyone out of it.
+        ip_ok
+            && self
+                .per_space
+                .as_ref()
+                .map_or(true, |limiter| limiter.check(space.clone()))
+    }
+
+    /// Forget the counts of windows which are over.
+    pub fn prune(&self) {
+        if let Some(limiter) = &self.per_ip {
+            limiter.prune();
+        }
+        if let Some(limiter) = &self.per_space {
+            limiter.prune();
+        }
+    }
+}
+
+/// Counts events per key in fixed windows.
+#[derive(Debug)]
+struct RateLimiter<K> {
+    max: u32,
+    windows: Mutex<HashMap<K, (Instant, u32)>>,
+}
+
+impl<K: Eq + Hash> RateLimiter<K> {
+    fn new(max: u32) -> Self {
+        Self {
+            max,
+            windows: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn check(&self, key: K) -> bool {
+        let now = Instant::now();
+        let mut windows = self.windows.lock();
+        let (start, count) = windows.entry(key).or_insert((now, 0));
+        if now.duration_since(*start) >= WINDOW {
+            *start = now;
+            *count = 0;
+        }
+        if *count >= self.max {
+            return false;
+        }
+        *count += 1;
+        true
+    }
+
+    fn prune(&self) {
+        let now = Instant::now();
+        self.windows
+            .lock()
+            .retain(|_, (start, _)| now.duration_since(*start) < WINDOW);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::fixt::prelude::*;
+    use kitsune_p2p_bin_data::fixt::*;
+
+    #[test]
+    fn test_put_rate_limits() {
+        let limits = PutRateLimits::new(Some(3), Some(4));
+        let space = Arc::new(fixt!(KitsuneSpace, Unpredictable));
+        let ip_a = IpAddr::from([10, 0, 0, 1]);
+        let ip_b = IpAddr::from([10, 0, 0, 2]);
+
+        for _ in 0..3 {
+            assert!(limits.check(Some(ip_a), &space));
+        }
+        // Over the limit of the address.
+        assert!(!limits.check(Some(ip_a), &space));
+
+        assert!(limits.check(Some(ip_b), &space));
+        // Over the limit of the space.
+        assert!(!limits.check(Some(ip_b), &space));
+
+        let other_space = Arc::new(fixt!(KitsuneSpace, Unpredictable));
+        assert!(limits.check(Some(ip_b), &other_space));
+        assert!(limits.check(None, &other_space));
+    }
+}
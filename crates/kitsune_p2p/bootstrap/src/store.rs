@@ -13,53 +13,168 @@ type AgentMap = HashMap<Arc<KitsuneAgent>, AgentInfoSigned>;
 type SpaceMap = HashMap<Arc<KitsuneSpace>, AgentMap>;
 
 #[derive(Clone, Debug)]
-pub(crate) struct Store(Arc<RwLock<SpaceMap>>, Arc<Vec<String>>);
+pub(crate) struct Store {
+    map: Arc<RwLock<SpaceMap>>,
+    proxy_list: Arc<Vec<String>>,
+    /// Where the peers are persisted, so that they survive a restart.
+    /// The map is the source of truth while the server is running.
+    #[cfg(feature = "sqlite_store")]
+    db: Option<Arc<parking_lot::Mutex<rusqlite::Connection>>>,
+}
 
 impl Store {
     pub fn new(proxy_list: Vec<String>) -> Self {
-        Self(Arc::new(RwLock::new(HashMap::new())), Arc::new(proxy_list))
+        Self {
+            map: Arc::new(RwLock::new(HashMap::new())),
+            proxy_list: Arc::new(proxy_list),
+            #[cfg(feature = "sqlite_store")]
+            db: None,
+        }
+    }
+
+    /// Open a store which persists its peers in the SQLite database at
+    /// this path, loading the peers which have not expired yet.
+    #[cfg(feature = "sqlite_store")]
+    pub fn open(proxy_list: Vec<String>, path: &std::path::Path) -> rusqlite::Result<Self> {
+        let conn = rusqlite::Connection::open(path)?;
+        conn.pragma_update(None, "journal_mode", "WAL")?;
+        conn.pragma_update(None, "synchronous", "NORMAL")?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS Peer (
+                space BLOB NOT NULL,
+                agent BLOB NOT NULL,
+                signed_at_ms INTEGER NOT NULL,
+                expires_at_ms INTEGER NOT NULL,
+                info BLOB NOT NULL,
+                PRIMARY KEY (space, agent)
+            )",
+            [],
+        )?;
+
+        let store = Self {
+            db: Some(Arc::new(parking_lot::Mutex::new(conn))),
+            ..Self::new(proxy_list)
+        };
+        store.load()?;
+        Ok(store)
+    }
+
+    #[cfg(feature = "sqlite_store")]
+    fn load(&self) -> rusqlite::Result<()> {
+        let Some(db) = &self.db else {
+            return Ok(());
+        };
+        let conn = db.lock();
+        let mut stmt = conn.prepare("SELECT info FROM Peer WHERE expires_at_ms > ?")?;
+        let infos = stmt
+            .query_map([now() as i64], |row| row.get::<_, Vec<u8>>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        let mut map = self.map.write();
+        for info in infos {
+            match kitsune_p2p_types::codec::rmp_decode::<_, AgentInfoSigned>(&mut info.as_slice()) {
+                Ok(info) => {
+                    map.entry(info.space.clone())
+                        .or_default()
+                        .insert(info.agent.clone(), info);
+                }
+                Err(err) => {
+                    tracing::warn!(?err, "Skipping a stored peer which can't be decoded")
+                }
+            }
+        }
+        Ok(())
     }
 
     pub fn proxy_list(&self) -> Arc<Vec<String>> {
-        self.1.clone()
+        self.proxy_list.clone()
     }
 
     pub fn prune(&self) {
-        let now = std::time::UNIX_EPOCH
-            .elapsed()
-            .expect("Bootstrap server time set before epoch")
-            .as_millis() as u64;
+        let now = now();
 
-        self.0.write().retain(|_, map| {
+        self.map.write().retain(|_, map| {
             map.retain(|_, info| info.expires_at_ms >= now);
             !map.is_empty()
         });
+
+        #[cfg(feature = "sqlite_store")]
+        if let Some(db) = &self.db {
+            if let Err(err) = db
+                .lock()
+                .execute("DELETE FROM Peer WHERE expires_at_ms < ?", [now as i64])
+            {
+                tracing::error!(?err, "Failed to prune the stored peers");
+            }
+        }
     }
 
     pub fn put(&self, info: AgentInfoSigned) {
-        let mut lock = self.0.write();
-        let space_map = lock.entry(info.space.clone()).or_default();
-        match space_map.entry(info.agent.clone()) {
-            std::collections::hash_map::Entry::Occupied(mut e) => {
-                if info.signed_at_ms > e.get().signed_at_ms {
-                    e.insert(info);
+        {
+            let mut lock = self.map.write();
+            let space_map = lock.entry(info.space.clone()).or_default();
+            match space_map.entry(info.agent.clone()) {
+                std::collections::hash_map::Entry::Occupied(mut e) => {
+                    if info.signed_at_ms <= e.get().signed_at_ms {
+                        return;
+                    }
+                    e.insert(info.clone());
+                }
+                std::collections::hash_map::Entry::Vacant(e) => {
+                    e.insert(info.clone());
                 }
-            }
-            std::collections::hash_map::Entry::Vacant(e) => {
-                e.insert(info);
             }
         }
+        // Written once the map is unlocked, so that other requests don't
+        // wait on the database.
+        self.persist(&info);
     }
 
+    /// Concurrent puts for the same agent may be persisted in any order,
+    /// so a row is only replaced by an agent info which was signed later.
+    #[cfg(feature = "sqlite_store")]
+    fn persist(&self, info: &AgentInfoSigned) {
+        let Some(db) = &self.db else {
+            return;
+        };
+        let mut buf = Vec::new();
+        let r = rmp_encode(&mut buf, info)
+            .map_err(|err| format!("{err:?}"))
+            .and_then(|_| {
+                db.lock()
+                    .execute(
+                        "INSERT INTO Peer (space, agent, signed_at_ms, expires_at_ms, info)
+                        VALUES (?, ?, ?, ?, ?)
+                        ON CONFLICT (space, agent) DO UPDATE SET
+                            signed_at_ms = excluded.signed_at_ms,
+                            expires_at_ms = excluded.expires_at_ms,
+                            info = excluded.info
+                        WHERE excluded.signed_at_ms > Peer.signed_at_ms",
+                        rusqlite::params![
+                            info.space.0,
+                            info.agent.0,
+                            info.signed_at_ms as i64,
+                            info.expires_at_ms as i64,
+                            buf
+                        ],
+                    )
+                    .map_err(|err| format!("{err:?}"))
+            });
+        // The peer is still served from memory, it is only lost on restart.
+        if let Err(err) = r {
+            tracing::error!(%err, "Failed to store a peer");
+        }
+    }
+
+    #[cfg(not(feature = "sqlite_store"))]
+    fn persist(&self, _info: &AgentInfoSigned) {}
+
     pub fn random(&self, query: RandomQuery) -> Vec<Vec<u8>> {
         // TODO: Max this limit
         let limit = query.limit.0 as usize;
         let mut rng = rand::thread_rng();
-        let now = std::time::UNIX_EPOCH
-            .elapsed()
-            .expect("Bootstrap server time set before epoch")
-            .as_millis() as u64;
-        self.0
+        let now = now();
+        self.map
             .read()
             .get(query.space.as_ref())
             .map(|space| {
@@ -81,11 +196,55 @@ impl Store {
     }
 
     pub fn clear(&self) {
-        self.0.write().clear()
+        self.map.write().clear();
+
+        #[cfg(feature = "sqlite_store")]
+        if let Some(db) = &self.db {
+            if let Err(err) = db.lock().execute("DELETE FROM Peer", []) {
+                tracing::error!(?err, "Failed to clear the stored peers");
+            }
+        }
     }
 
     #[cfg(test)]
     pub fn all(&self) -> HashMap<Arc<KitsuneSpace>, HashMap<Arc<KitsuneAgent>, AgentInfoSigned>> {
-        self.0.read().clone()
+        self.map.read().clone()
+    }
+}
+
+fn now() -> u64 {
+    std::time::UNIX_EPOCH
+        .elapsed()
+        .expect("Bootstrap server time set before epoch")
+        .as_millis() as u64
+}
+
+#[cfg(all(test, feature = "sqlite_store"))]
+mod tests {
+    use super::*;
+    use crate::test_utils::sign_test_agent_info;
+    use ::fixt::prelude::*;
+    use kitsune_p2p_bin_data::fixt::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_store_survives_restart() {
+        let tmp_dir = tempfile::TempDir::new().unwrap();
+        let path = tmp_dir.path().join("bootstrap.sqlite3");
+        let space = Arc::new(fixt!(KitsuneSpace, Unpredictable));
+        let info = sign_test_agent_info(space.clone(), vec![]).await;
+
+        let store = Store::open(vec![], &path).unwrap();
+        store.put(info.clone());
+        drop(store);
+
+        let store = Store::open(vec![], &path).unwrap();
+        assert_eq!(
+            store.all().get(&space).unwrap().get(&info.agent).unwrap(),
+            &info
+        );
+
+        store.clear();
+        drop(store);
+        assert!(Store::open(vec![], &path).unwrap().all().is_empty());
     }
 }
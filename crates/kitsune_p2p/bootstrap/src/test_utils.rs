@@ -0,0 +1,29 @@
+use std::sync::Arc;
+
+use ed25519_dalek::{Keypair, Signer};
+use kitsune_p2p_bin_data::{KitsuneAgent, KitsuneSignature, KitsuneSpace};
+use kitsune_p2p_types::agent_info::AgentInfoSigned;
+use kitsune_p2p_types::tx2::tx2_utils::TxUrl;
+
+/// Sign agent info for a new agent, which expires in a long time.
+pub(crate) async fn sign_test_agent_info(
+    space: Arc<KitsuneSpace>,
+    url_list: Vec<TxUrl>,
+) -> AgentInfoSigned {
+    let keypair = Keypair::generate(&mut rand_dalek::thread_rng());
+    let agent = KitsuneAgent::new(keypair.public.as_bytes().to_vec());
+    AgentInfoSigned::sign(
+        space,
+        Arc::new(agent),
+        u32::MAX / 4,
+        url_list,
+        0,
+        std::time::UNIX_EPOCH.elapsed().unwrap().as_millis() as u64 + 60_000_000,
+        |data| {
+            let signature = keypair.sign(data).to_bytes().to_vec();
+            async move { Ok(Arc::new(KitsuneSignature(signature))) }
+        },
+    )
+    .await
+    .unwrap()
+}
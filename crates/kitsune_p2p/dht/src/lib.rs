@@ -152,6 +152,7 @@ pub mod hash;
 pub mod op;
 pub mod region;
 pub mod region_set;
+pub mod region_tree;
 pub mod spacetime;
 
 pub use arq::{actual_coverage, Arq, ArqBounds, ArqStrat, PeerStrat, PeerView, PeerViewQ};
@@ -175,6 +176,7 @@ pub mod prelude {
     pub use super::op::*;
     pub use super::region::*;
     pub use super::region_set::*;
+    pub use super::region_tree::*;
     pub use super::spacetime::*;
 
     #[cfg(feature = "test_utils")]
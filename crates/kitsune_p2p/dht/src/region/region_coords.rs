@@ -105,6 +105,84 @@ impl RegionBounds {
     }
 }
 
+/// The inclusive ranges of space and time quanta covered by a region.
+///
+/// The space range wraps around the end of the DHT if `space.0 > space.1`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct QuantumBounds {
+    pub(crate) space: (SpaceQuantum, SpaceQuantum),
+    pub(crate) time: (TimeQuantum, TimeQuantum),
+}
+
+impl QuantumBounds {
+    /// The quanta covered by the region with these coords
+    pub(crate) fn from_coords(topo: &Topology, coords: &RegionCoords) -> Self {
+        Self {
+            space: coords.space.quantum_bounds(topo.space),
+            time: coords.time.quantum_bounds(topo.time),
+        }
+    }
+
+    /// The quanta which overlap these bounds. If the bounds are not aligned
+    /// to quantum boundaries, this covers more than the bounds themselves.
+    ///
+    /// Returns None if the bounds contain no time at or after the time origin.
+    pub(crate) fn from_bounds(topo: &Topology, bounds: &RegionBounds) -> Option<Self> {
+        let (x0, x1) = bounds.x;
+        let (t0, t1) = bounds.t;
+        if t0 > t1 || t1 < topo.time_origin {
+            return None;
+        }
+        let (s0, s1) = (topo.space_quantum(x0), topo.space_quantum(x1));
+        // Bounds which wrap around the DHT but begin and end in the same or
+        // adjacent quanta cover the whole DHT.
+        let space = if x0 > x1 && (s0 <= s1 || s0.inner() == s1.inner() + 1) {
+            (SpaceQuantum::from(0), SpaceQuantum::max_value(topo.space))
+        } else {
+            (s0, s1)
+        };
+        Some(Self {
+            space,
+            time: (topo.time_quantum(t0), topo.time_quantum(t1)),
+        })
+    }
+
+    /// The non-wrapping ranges of space quanta which make up the space range
+    pub(crate) fn space_ranges(&self, topo: &Topology) -> Vec<(SpaceQuantum, SpaceQuantum)> {
+        let (s0, s1) = self.space;
+        if s0 <= s1 {
+            vec![(s0, s1)]
+        } else {
+            vec![
+                (s0, SpaceQuantum::max_value(topo.space)),
+                (SpaceQuantum::from(0), s1),
+            ]
+        }
+    }
+
+    /// The number of spacetime quanta covered
+    pub(crate) fn num_quanta(&self, topo: &Topology) -> u128 {
+        let nx: u128 = self
+            .space_ranges(topo)
+            .into_iter()
+            .map(|(a, b)| (b.inner() - a.inner()) as u128 + 1)
+            .sum();
+        let nt = (self.time.1.inner() - self.time.0.inner()) as u128 + 1;
+        nx * nt
+    }
+
+    /// Are all the quanta covered by `other` also covered by these bounds?
+    pub(crate) fn contains(&self, topo: &Topology, other: &Self) -> bool {
+        let ranges = self.space_ranges(topo);
+        self.time.0 <= other.time.0
+            && other.time.1 <= self.time.1
+            && other
+                .space_ranges(topo)
+                .into_iter()
+                .all(|(a, b)| ranges.iter().any(|(lo, hi)| *lo <= a && b <= *hi))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
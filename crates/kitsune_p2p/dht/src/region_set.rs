@@ -14,7 +14,9 @@ pub use ltcs::*;
 
 use crate::{error::GossipResult, spacetime::*};
 
-use crate::region::{Region, RegionBounds, RegionCoords, RegionData, RegionDataConstraints};
+use crate::region::{
+    QuantumBounds, Region, RegionBounds, RegionCoords, RegionData, RegionDataConstraints,
+};
 
 /// The generic definition of a set of Regions.
 /// The current representation is very specific to our current algorithm,
@@ -44,20 +46,26 @@ impl<D: RegionDataConstraints> RegionSet<D> {
 
     /// The RegionSet can be used to answer questions about more regions than
     /// just the ones specified: If a larger region is queried, and this set contains
-    /// a set of regions which over that larger region, then the larger region
+    /// a set of regions which cover that larger region, then the larger region
     /// can be dynamically constructed.
     ///
     /// This allows agents with differently computed RegionSets to still engage
     /// in gossip without needing to recompute regions.
-    pub fn query(&self, _bounds: &RegionBounds) -> ! {
-        unimplemented!("only implement after trying naive database-only approach")
+    ///
+    /// Returns None if the regions in this set do not exactly cover the bounds.
+    pub fn query(&self, topo: &Topology, bounds: &RegionBounds) -> Option<D> {
+        match self {
+            Self::Ltcs(set) => set.query(topo, bounds),
+        }
     }
 
     /// In order for this RegionSet to be queryable, new data needs to be
     /// integrated into it to avoid needing to recompute it from the database
     /// on each query.
-    pub fn update(&self, _c: SpacetimeQuantumCoords, _d: D) -> ! {
-        unimplemented!("only implement after trying naive database-only approach")
+    pub fn update(&mut self, topo: &Topology, c: &SpacetimeQuantumCoords, d: D) {
+        match self {
+            Self::Ltcs(set) => set.update(topo, c, d),
+        }
     }
 
     /// Find a set of Regions which represents the intersection of the two
@@ -232,6 +240,57 @@ mod tests {
         assert_eq!(tt_b.segments()[0..nt], rset_b.coords.times.segments());
     }
 
+    #[test]
+    fn test_query_and_update() {
+        let topo = Topology::unit_zero();
+        let arq = Arq::new(8, 0u32.into(), 4.into()).to_bounds(&topo);
+        let mut store = OpStore::new(topo.clone(), GossipParams::zero());
+        store.integrate_ops(op_grid(&topo, &arq, 10..20).into_iter());
+
+        let times = TelescopingTimes::new(TimeQuantum::from(20));
+        let t1 = times
+            .segments()
+            .iter()
+            .map(|t| t.timestamp_bounds(&topo).1)
+            .max()
+            .unwrap();
+        let coords = RegionCoordSetLtcs::new(times, ArqSet::single(arq));
+        let mut rset: RegionSet = RegionSetLtcs::from_store(&store, coords.clone()).into();
+
+        // The regions exactly cover the whole arq over all of the times
+        let whole = RegionBounds::new(arq.to_edge_locs(&topo), (Timestamp::from_micros(0), t1));
+        let total: RegionData = rset.regions().map(|r| r.data).sum();
+        assert_eq!(rset.query(&topo, &whole), Some(total.clone()));
+
+        // Any single region can be queried by its bounds
+        let region = rset.regions().nth(3).unwrap();
+        assert_eq!(
+            rset.query(&topo, &region.coords.to_bounds(&topo)),
+            Some(region.data)
+        );
+
+        // Bounds which extend beyond the regions can't be answered
+        let beyond = RegionBounds::new(
+            arq.to_edge_locs(&topo),
+            (
+                Timestamp::from_micros(0),
+                Timestamp::from_micros(t1.as_micros() + 1),
+            ),
+        );
+        assert_eq!(rset.query(&topo, &beyond), None);
+
+        let op = OpData::fake(Loc::from(12u32), Timestamp::from_micros(12), 4);
+        rset.update(&topo, &op.coords(&topo), op.region_data());
+        assert_eq!(
+            rset.query(&topo, &whole),
+            Some(total.clone() + op.region_data())
+        );
+
+        store.integrate_op(op);
+        let expected: RegionSet = RegionSetLtcs::from_store(&store, coords).into();
+        assert!(rset.diff(expected).unwrap().is_empty());
+    }
+
     #[test]
     fn test_diff() {
         let topo = Topology::unit_zero();
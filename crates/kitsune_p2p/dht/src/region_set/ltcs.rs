@@ -4,8 +4,9 @@ use crate::{
     spacetime::*,
 };
 use derivative::Derivative;
+use num_traits::Zero;
 
-use super::{Region, RegionCoords, RegionData, RegionDataConstraints};
+use super::{QuantumBounds, Region, RegionBounds, RegionCoords, RegionData, RegionDataConstraints};
 
 /// A compact representation of a set of [`RegionCoords`].
 /// The [`TelescopingTimes`] generates all relevant [`TimeSegment`]s, and the
@@ -208,6 +209,47 @@ impl<D: RegionDataConstraints> RegionSetLtcs<D> {
         Ok(regions)
     }
 
+    /// Construct the data for the given bounds out of the regions in this set
+    /// which lie within those bounds. Returns None if these regions do not
+    /// exactly cover the bounds.
+    pub fn query(&self, topo: &Topology, bounds: &RegionBounds) -> Option<D> {
+        let target = match QuantumBounds::from_bounds(topo, bounds) {
+            Some(target) => target,
+            None => return Some(D::zero()),
+        };
+        let mut covered = 0;
+        let mut data = D::zero();
+        // The regions of an LTCS set never overlap, so each quantum is
+        // counted at most once.
+        for region in self.regions() {
+            let quanta = QuantumBounds::from_coords(topo, &region.coords);
+            if target.contains(topo, &quanta) {
+                covered += quanta.num_quanta(topo);
+                data += region.data;
+            }
+        }
+        (covered == target.num_quanta(topo)).then_some(data)
+    }
+
+    /// Add data to the region which contains the given spacetime quantum, if any.
+    pub fn update(&mut self, topo: &Topology, c: &SpacetimeQuantumCoords, d: D) {
+        #[allow(deprecated)]
+        let found = self
+            .coords
+            .region_coords_flat()
+            .find(|(_, coords)| coords.contains(topo, c));
+        if let Some(((ia, ix, it), _)) = found {
+            if let Some(data) = self
+                .data
+                .get_mut(ia)
+                .and_then(|d| d.get_mut(ix))
+                .and_then(|d| d.get_mut(it))
+            {
+                *data += d;
+            }
+        }
+    }
+
     /// Return only the regions which have ops in them. Useful for testing
     /// sparse scenarios.
     pub fn nonzero_regions(
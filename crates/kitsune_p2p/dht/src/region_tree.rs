@@ -0,0 +1,300 @@
+//! A RegionTree holds the [`RegionData`] of every spacetime quantum which
+//! contains ops, so that the data of any region can be computed in memory
+//! rather than being recomputed from the host's database on every gossip round.
+//!
+//! The tree is kept up to date by adding the data of each new op as it is
+//! stored. Since region data can only be added to, any ops which are removed
+//! by the host will remain in the tree until it is reconciled with a fresh tree
+//! built from the host's database.
+//!
+//! The data of the segment-aligned regions used during gossip are cached, and
+//! the cached regions are updated along with the quanta, so repeated gossip
+//! rounds over the same regions do not need to sum over the quanta again.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use crate::{
+    op::OpRegion,
+    region::{QuantumBounds, RegionBounds, RegionCoords, RegionData, RegionDataConstraints},
+    region_set::{RegionCoordSetLtcs, RegionSetLtcs},
+    spacetime::*,
+};
+
+/// The maximum number of regions to cache. The cache is cleared if it grows
+/// beyond this size, which can only happen if the regions being queried shift
+/// over a long period without the tree being reconciled.
+pub const MAX_CACHED_REGIONS: usize = 100_000;
+
+/// An in-memory index of the [`RegionData`] of each spacetime quantum, which
+/// can answer queries about arbitrary regions.
+#[derive(Clone, Debug)]
+pub struct RegionTree<D: RegionDataConstraints = RegionData> {
+    topo: Topology,
+    /// The data of each nonempty quantum, indexed by space and then time
+    quanta: BTreeMap<SpaceQuantum, BTreeMap<TimeQuantum, D>>,
+    /// The data of regions which have been queried by coords
+    cache: HashMap<RegionCoords, D>,
+    /// The space and time powers of all regions in the cache
+    cached_powers: BTreeSet<(u8, u8)>,
+}
+
+impl<D: RegionDataConstraints> RegionTree<D> {
+    /// An empty tree
+    pub fn new(topo: Topology) -> Self {
+        Self {
+            topo,
+            quanta: Default::default(),
+            cache: Default::default(),
+            cached_powers: Default::default(),
+        }
+    }
+
+    /// Build a tree from the data of some ops
+    pub fn from_ops<'a, O: OpRegion<D> + 'a>(
+        topo: Topology,
+        ops: impl IntoIterator<Item = &'a O>,
+    ) -> Self {
+        let mut tree = Self::new(topo);
+        for op in ops {
+            tree.integrate_op(op);
+        }
+        tree
+    }
+
+    /// The topology used to quantize the data
+    pub fn topo(&self) -> &Topology {
+        &self.topo
+    }
+
+    /// The number of quanta which have had data added
+    pub fn num_quanta(&self) -> usize {
+        self.quanta.values().map(|column| column.len()).sum()
+    }
+
+    /// Add the data of an op to the tree
+    pub fn integrate_op<O: OpRegion<D>>(&mut self, op: &O) {
+        let coords = op.coords(&self.topo);
+        self.update(&coords, op.region_data());
+    }
+
+    /// Add data to the given spacetime quantum, and to all cached regions
+    /// which contain it.
+    pub fn update(&mut self, c: &SpacetimeQuantumCoords, d: D) {
+        let space = c.space.normalized(self.topo.space);
+        for &(space_power, time_power) in self.cached_powers.iter() {
+            let coords = RegionCoords::new(
+                SpaceSegment::new(space_power, shr(space.inner(), space_power)),
+                TimeSegment::new(time_power, shr(c.time.inner(), time_power)),
+            );
+            if let Some(data) = self.cache.get_mut(&coords) {
+                *data += d.clone();
+            }
+        }
+        *self
+            .quanta
+            .entry(space)
+            .or_default()
+            .entry(c.time)
+            .or_insert_with(D::zero) += d;
+    }
+
+    /// Sum the data of all quanta which overlap the given bounds.
+    ///
+    /// The tree only knows about whole quanta, so if the bounds are not aligned
+    /// to quantum boundaries, the result includes all ops in the quanta at the
+    /// edges of the bounds. Bounds produced by [`RegionCoords::to_bounds`] are
+    /// always aligned.
+    pub fn query(&self, bounds: &RegionBounds) -> D {
+        QuantumBounds::from_bounds(&self.topo, bounds)
+            .map(|quanta| self.sum_quanta(&quanta))
+            .unwrap_or_else(D::zero)
+    }
+
+    /// The data of the region with the given coords
+    pub fn query_coords(&self, coords: &RegionCoords) -> D {
+        match self.cache.get(&self.cache_key(coords)) {
+            Some(data) => data.clone(),
+            None => self.sum_quanta(&QuantumBounds::from_coords(&self.topo, coords)),
+        }
+    }
+
+    /// The data of the region with the given coords, caching the result so
+    /// that the region is kept up to date as data is added.
+    pub fn query_coords_cached(&mut self, coords: &RegionCoords) -> D {
+        let key = self.cache_key(coords);
+        if let Some(data) = self.cache.get(&key) {
+            return data.clone();
+        }
+        if self.cache.len() >= MAX_CACHED_REGIONS {
+            self.clear_cache();
+        }
+        let data = self.sum_quanta(&QuantumBounds::from_coords(&self.topo, coords));
+        self.cached_powers.insert((key.space.power, key.time.power));
+        self.cache.insert(key, data.clone());
+        data
+    }
+
+    /// Generate the data for each coord in the set, creating the corresponding
+    /// [`RegionSetLtcs`]. The regions are cached.
+    pub fn region_set(&mut self, coords: RegionCoordSetLtcs) -> RegionSetLtcs<D> {
+        coords.into_region_set_infallible(|(_, coords)| self.query_coords_cached(&coords))
+    }
+
+    /// Replace the contents of this tree with those of a tree freshly built
+    /// from the host's database, returning the number of quanta whose data
+    /// differed between the two.
+    pub fn reconcile(&mut self, fresh: Self) -> usize {
+        debug_assert_eq!(self.topo, fresh.topo);
+        let stale = self
+            .iter_quanta()
+            .filter(|(s, t, d)| fresh.get(s, t) != Some(*d))
+            .count();
+        let missing = fresh
+            .iter_quanta()
+            .filter(|(s, t, _)| self.get(s, t).is_none())
+            .count();
+        self.quanta = fresh.quanta;
+        self.clear_cache();
+        stale + missing
+    }
+
+    fn get(&self, space: &SpaceQuantum, time: &TimeQuantum) -> Option<&D> {
+        self.quanta.get(space).and_then(|column| column.get(time))
+    }
+
+    fn iter_quanta(&self) -> impl Iterator<Item = (&SpaceQuantum, &TimeQuantum, &D)> {
+        self.quanta
+            .iter()
+            .flat_map(|(s, column)| column.iter().map(move |(t, d)| (s, t, d)))
+    }
+
+    fn sum_quanta(&self, quanta: &QuantumBounds) -> D {
+        let mut sum = D::zero();
+        for (s0, s1) in quanta.space_ranges(&self.topo) {
+            for column in self.quanta.range(s0..=s1).map(|(_, column)| column) {
+                for d in column.range(quanta.time.0..=quanta.time.1).map(|(_, d)| d) {
+                    sum += d.clone();
+                }
+            }
+        }
+        sum
+    }
+
+    /// Segments which cover the same quanta can have different offsets if they
+    /// wrap around the DHT, so the cache is keyed by the normalized offset.
+    fn cache_key(&self, coords: &RegionCoords) -> RegionCoords {
+        let (lo, _) = coords.space.quantum_bounds(self.topo.space);
+        RegionCoords::new(
+            SpaceSegment::new(coords.space.power, shr(lo.inner(), coords.space.power)),
+            coords.time,
+        )
+    }
+
+    fn clear_cache(&mut self) {
+        self.cache.clear();
+        self.cached_powers.clear();
+    }
+}
+
+/// The offset of the segment of the given power which contains the quantum
+fn shr(quantum: u32, power: u8) -> u32 {
+    quantum.checked_shr(power.into()).unwrap_or(0)
+}
+
+#[cfg(test)]
+#[cfg(feature = "test_utils")]
+mod tests {
+    use kitsune_p2p_timestamp::Timestamp;
+
+    use crate::{
+        persistence::AccessOpStore,
+        prelude::ArqSet,
+        test_utils::{Op, OpData, OpStore},
+        Arq, Loc,
+    };
+
+    use super::*;
+
+    fn ops(topo: &Topology) -> Vec<Op> {
+        (0..100u32)
+            .map(|i| {
+                let x = SpaceQuantum::from(i * 37 % 64).to_loc_bounds(topo).0;
+                let t = TimeQuantum::from(i % 20).to_timestamp_bounds(topo).0;
+                OpData::fake(x, t, i + 1)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn tree_matches_store() {
+        let topo = Topology::unit_zero();
+        let ops = ops(&topo);
+        let mut store = OpStore::new(topo.clone(), GossipParams::zero());
+        store.integrate_ops(ops.iter().cloned());
+        let tree = RegionTree::from_ops(topo.clone(), ops.iter().map(|o| &**o));
+
+        for coords in [
+            RegionCoords::new(SpaceSegment::new(4, 0), TimeSegment::new(2, 1)),
+            RegionCoords::new(SpaceSegment::new(6, 0), TimeSegment::new(5, 0)),
+            RegionCoords::new(SpaceSegment::new(0, 37), TimeSegment::new(0, 1)),
+        ] {
+            assert_eq!(tree.query_coords(&coords), store.query_region_data(&coords));
+            assert_eq!(
+                tree.query(&coords.to_bounds(&topo)),
+                store.query_region_data(&coords)
+            );
+        }
+
+        let everything = RegionBounds::new(
+            (Loc::from(0u32), Loc::from(u32::MAX)),
+            (Timestamp::from_micros(0), Timestamp::from_micros(1000)),
+        );
+        assert_eq!(tree.query(&everything).count, 100);
+
+        // A region which wraps around the DHT
+        let wrapping = RegionBounds::new(
+            (Loc::from(50u32), Loc::from(9u32)),
+            (Timestamp::from_micros(0), Timestamp::from_micros(1000)),
+        );
+        let expected = ops
+            .iter()
+            .filter(|o| o.loc.as_u32() >= 50 || o.loc.as_u32() <= 9)
+            .count() as u32;
+        assert_eq!(tree.query(&wrapping).count, expected);
+    }
+
+    #[test]
+    fn cached_regions_stay_up_to_date() {
+        let topo = Topology::unit_zero();
+        let ops = ops(&topo);
+        let (old, new) = ops.split_at(60);
+        let arq = Arq::new(3, 0u32.into(), 8.into()).to_bounds(&topo);
+        let coords = RegionCoordSetLtcs::new(
+            TelescopingTimes::new(TimeQuantum::from(20)),
+            ArqSet::single(arq),
+        );
+
+        let mut tree = RegionTree::from_ops(topo.clone(), old.iter().map(|o| &**o));
+        let before = tree.region_set(coords.clone());
+        for op in new {
+            tree.integrate_op(op);
+        }
+        let after = tree.region_set(coords.clone());
+        assert_ne!(before, after);
+        let mut fresh = RegionTree::from_ops(topo, ops.iter().map(|o| &**o));
+        assert_eq!(after, fresh.region_set(coords));
+    }
+
+    #[test]
+    fn reconcile_counts_mismatched_quanta() {
+        let topo = Topology::unit_zero();
+        let ops = ops(&topo);
+        let mut tree = RegionTree::from_ops(topo.clone(), ops[..99].iter().map(|o| &**o));
+        let fresh = RegionTree::from_ops(topo, ops[1..].iter().map(|o| &**o));
+        let n = tree.num_quanta();
+
+        assert_eq!(tree.reconcile(fresh.clone()), 2);
+        assert_eq!(tree.num_quanta(), n);
+        assert_eq!(tree.reconcile(fresh), 0);
+    }
+}
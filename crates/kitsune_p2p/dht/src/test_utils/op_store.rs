@@ -1,7 +1,7 @@
 use crate::{
     op::OpRegion,
     persistence::AccessOpStore,
-    prelude::{RegionCoords, RegionSet, RegionSetLtcs},
+    prelude::{RegionCoords, RegionTree},
     region::{RegionData, RegionDataConstraints},
     spacetime::{GossipParams, Topology},
 };
@@ -15,7 +15,7 @@ use super::op_data::OpData;
 pub struct OpStore<O: OpRegion<D> = OpData, D: RegionDataConstraints = RegionData> {
     pub(crate) topo: Topology,
     pub(crate) ops: BTreeSet<Arc<O>>,
-    pub(crate) _region_set: RegionSet<D>,
+    pub(crate) region_tree: RegionTree<D>,
     pub(crate) gossip_params: GossipParams,
 }
 
@@ -23,9 +23,9 @@ impl<D: RegionDataConstraints, O: OpRegion<D>> OpStore<O, D> {
     /// Construct an empty store
     pub fn new(topo: Topology, gossip_params: GossipParams) -> Self {
         Self {
+            region_tree: RegionTree::new(topo.clone()),
             topo,
             ops: Default::default(),
-            _region_set: RegionSetLtcs::empty().into(),
             gossip_params,
         }
     }
@@ -56,16 +56,19 @@ impl<D: RegionDataConstraints, O: OpRegion<D>> AccessOpStore<O, D> for OpStore<O
         &self,
         coords: crate::prelude::RegionCoordSetLtcs,
     ) -> must_future::MustBoxFuture<Result<crate::prelude::RegionSetLtcs<D>, ()>> {
-        async move { coords.into_region_set(|(_, coords)| Ok(self.query_region_data(&coords))) }
-            .boxed()
-            .into()
+        async move {
+            coords.into_region_set(|(_, coords)| Ok(self.region_tree.query_coords(&coords)))
+        }
+        .boxed()
+        .into()
     }
 
     fn integrate_ops<Ops: Clone + Iterator<Item = Arc<O>>>(&mut self, ops: Ops) {
-        // for op in ops.clone() {
-        //     self.region_set.add(op.region_tuple(self.region_set.topo()));
-        // }
-        self.ops.extend(ops);
+        for op in ops {
+            if self.ops.insert(op.clone()) {
+                self.region_tree.integrate_op(op.as_ref());
+            }
+        }
     }
 
     fn topo(&self) -> &Topology {
@@ -77,15 +80,6 @@ impl<D: RegionDataConstraints, O: OpRegion<D>> AccessOpStore<O, D> for OpStore<O
     }
 }
 
-// impl OpStore<RegionData> {
-//     fn integrate_ops<O: Iterator<Item = Op>>(&mut self, ops: O) {
-//         for op in ops {
-//             self.tree.add_op(op);
-//         }
-//         self.ops.extend(ops);
-//     }
-// }
-
 // fn op_bound(timestamp: Timestamp, loc: Loc) -> OpData {
 //     OpData {
 //         loc,
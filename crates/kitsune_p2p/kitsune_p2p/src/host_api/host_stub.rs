@@ -128,6 +128,18 @@ impl KitsuneHost for HostStub {
                     false
                 }
             }
+            BlockTargetId::Ip(check_ip) => {
+                let maybe_matched_block = blocks.iter().find(|b| match b.target() {
+                    BlockTarget::Ip(ip, _) => ip == check_ip,
+                    _ => false,
+                });
+
+                if let Some(block) = maybe_matched_block {
+                    timestamp.0 > block.start().0 && timestamp.0 < block.end().0
+                } else {
+                    false
+                }
+            }
             _ => false,
         };
 
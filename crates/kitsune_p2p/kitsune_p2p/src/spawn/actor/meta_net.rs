@@ -266,6 +266,67 @@ pub async fn nodespace_is_authorized(
     }
 }
 
+/// The IP address of the remote end of a connection, if its url addresses
+/// the remote directly by IP.
+///
+/// tx2 proxy urls address the proxy through which the remote is reached,
+/// which may be a relay shared by many nodes, and tx5 urls address the
+/// signal server rather than the remote, so neither carries the address
+/// the remote connects from.
+pub fn remote_ip(remote_url: &str) -> Option<std::net::IpAddr> {
+    let url = url2::Url2::try_parse(remote_url).ok()?;
+    if matches!(url.scheme(), "kitsune-proxy" | "ws" | "wss") {
+        return None;
+    }
+    url.host_str()?
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .parse()
+        .ok()
+}
+
+async fn ip_is_authorized(host: &HostApi, remote_url: &str, now: Timestamp) -> MetaNetAuth {
+    match remote_ip(remote_url) {
+        Some(ip) => match host.is_blocked(BlockTargetId::Ip(ip), now).await {
+            Ok(true) => MetaNetAuth::UnauthorizedDisconnect,
+            Ok(false) => MetaNetAuth::Authorized,
+            Err(_) => MetaNetAuth::UnauthorizedIgnore,
+        },
+        None => MetaNetAuth::Authorized,
+    }
+}
+
+/// Check whether a new connection may be kept, i.e. whether neither the
+/// remote node nor the IP address it connects from is blocked.
+pub async fn connection_is_authorized(
+    host: &HostApi,
+    remote_url: &str,
+    node_id: NodeCert,
+    now: Timestamp,
+) -> MetaNetAuth {
+    match ip_is_authorized(host, remote_url, now).await {
+        MetaNetAuth::Authorized => node_is_authorized(host, node_id, now).await,
+        unauthorized => unauthorized,
+    }
+}
+
+/// Check whether a message received on a connection may be handled.
+///
+/// The IP address is checked again, since the messages of a new connection
+/// may be handled before the connection itself is checked.
+pub async fn remote_is_authorized(
+    host: &HostApi,
+    remote_url: &str,
+    node_id: NodeCert,
+    maybe_space: Option<Arc<KitsuneSpace>>,
+    now: Timestamp,
+) -> MetaNetAuth {
+    match ip_is_authorized(host, remote_url, now).await {
+        MetaNetAuth::Authorized => nodespace_is_authorized(host, node_id, maybe_space, now).await,
+        unauthorized => unauthorized,
+    }
+}
+
 pub type MetaNetEvtRecv = futures::channel::mpsc::Receiver<MetaNetEvt>;
 
 type ResStore = Arc<Mutex<HashMap<u64, tokio::sync::oneshot::Sender<wire::Wire>>>>;
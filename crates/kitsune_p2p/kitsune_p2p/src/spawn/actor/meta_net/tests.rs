@@ -985,3 +985,28 @@ async fn request_timeout() {
 
     nodes.shutdown().await;
 }
+
+#[test]
+fn remote_ip_of_urls() {
+    use std::net::IpAddr;
+
+    assert_eq!(
+        Some("192.0.2.1".parse::<IpAddr>().unwrap()),
+        remote_ip("kitsune-quic://192.0.2.1:5778")
+    );
+    assert_eq!(
+        Some("2001:db8::1".parse::<IpAddr>().unwrap()),
+        remote_ip("kitsune-quic://[2001:db8::1]:5778")
+    );
+    // proxy urls may address a relay, and tx5 urls the signal server,
+    // rather than the remote
+    assert_eq!(
+        None,
+        remote_ip("kitsune-proxy://CEgoNlOcQSL23yOSwWVmd8fqWeIU5mSEBjvO7yoGAqE/kitsune-quic/h/192.0.2.1/p/5778/--")
+    );
+    assert_eq!(
+        None,
+        remote_ip("wss://192.0.2.1:8443/tx5-ws/CEgoNlOcQSL23yOSwWVmd8fqWeIU5mSEBjvO7yoGAqE")
+    );
+    assert_eq!(None, remote_ip("kitsune-mem://test"));
+}
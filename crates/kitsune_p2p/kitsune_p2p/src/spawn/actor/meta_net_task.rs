@@ -8,7 +8,8 @@ use crate::spawn::actor::{
     Internal, InternalSender, UNAUTHORIZED_DISCONNECT_CODE, UNAUTHORIZED_DISCONNECT_REASON,
 };
 use crate::spawn::meta_net::{
-    nodespace_is_authorized, MetaNetAuth, MetaNetCon, MetaNetEvt, MetaNetEvtRecv, Respond,
+    connection_is_authorized, remote_is_authorized, MetaNetAuth, MetaNetCon, MetaNetEvt,
+    MetaNetEvtRecv, Respond,
 };
 use crate::wire::WireData;
 use crate::{wire, HostApiLegacy, KitsuneAgent, KitsuneP2pError, KitsuneSpace};
@@ -109,11 +110,11 @@ impl MetaNetTask {
                                     this.handle_disconnect(remote_url).await
                                 }
                                 MetaNetEvt::Request {
-                                    remote_url: _,
+                                    remote_url,
                                     con,
                                     data,
                                     respond,
-                                } => this.handle_request(con, data, respond).await,
+                                } => this.handle_request(remote_url, con, data, respond).await,
                                 MetaNetEvt::Notify {
                                     remote_url: url,
                                     con,
@@ -148,6 +149,15 @@ impl MetaNetTask {
     }
 
     async fn handle_connect(&self, remote_url: String, con: MetaNetCon) -> MetaNetTaskResult<()> {
+        if let MetaNetAuth::UnauthorizedDisconnect =
+            connection_is_authorized(&self.host, &remote_url, con.peer_id(), Timestamp::now()).await
+        {
+            tracing::info!(%remote_url, "Closing connection from a blocked node or IP address");
+            con.close(UNAUTHORIZED_DISCONNECT_CODE, UNAUTHORIZED_DISCONNECT_REASON)
+                .await;
+            return Ok(());
+        }
+
         match self.i_s.new_con(remote_url, con.clone()).await {
             Err(e) => Err(e.into()),
             Ok(_) => Ok(()),
@@ -163,12 +173,14 @@ impl MetaNetTask {
 
     async fn handle_request(
         &self,
+        remote_url: String,
         con: MetaNetCon,
         data: wire::Wire,
         respond: Respond,
     ) -> MetaNetTaskResult<()> {
-        match nodespace_is_authorized(
+        match remote_is_authorized(
             &self.host,
+            &remote_url,
             con.peer_id(),
             data.maybe_space(),
             Timestamp::now(),
@@ -273,8 +285,9 @@ impl MetaNetTask {
         con: MetaNetCon,
         data: wire::Wire,
     ) -> MetaNetTaskResult<()> {
-        match nodespace_is_authorized(
+        match remote_is_authorized(
             &self.host,
+            &url,
             con.peer_id(),
             data.maybe_space(),
             Timestamp::now(),
@@ -606,7 +619,7 @@ mod tests {
     use ghost_actor::actor_builder::GhostActorBuilder;
     use ghost_actor::{GhostControlSender, GhostSender};
     use kitsune_p2p::KitsuneBinType;
-    use kitsune_p2p_block::{Block, BlockTarget, NodeBlockReason, NodeId};
+    use kitsune_p2p_block::{Block, BlockTarget, IpBlockReason, NodeBlockReason, NodeId};
     use kitsune_p2p_fetch::test_utils::{test_key_op, test_req_op, test_source, test_space};
     use kitsune_p2p_fetch::{FetchPool, FetchResponseQueue};
     use kitsune_p2p_timestamp::{InclusiveTimestampInterval, Timestamp};
@@ -636,6 +649,42 @@ mod tests {
         assert_eq!(1, internal_stub.connections.read().len());
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn handle_connect_from_blocked_ip() {
+        let (mut ep_evt_send, internal_stub, _, _, host_stub, _, _, _) = setup().await;
+
+        host_stub
+            .block(Block::new(
+                BlockTarget::Ip("2001:db8::1".parse().unwrap(), IpBlockReason::DOS),
+                InclusiveTimestampInterval::try_new(
+                    Timestamp::now(),
+                    Timestamp::now()
+                        .checked_add(&Duration::from_secs(10))
+                        .unwrap(),
+                )
+                .unwrap(),
+            ))
+            .await
+            .unwrap();
+
+        let con = mk_test_con();
+        let con_state = get_con_state(&con);
+
+        ep_evt_send
+            .send(MetaNetEvt::Connected {
+                remote_url: "kitsune-quic://[2001:db8::1]:5778".to_string(),
+                con,
+            })
+            .await
+            .unwrap();
+
+        wait_for_condition(|| con_state.read().closed)
+            .await
+            .expect("Timed out waiting for the connection to be closed");
+
+        assert!(internal_stub.connections.read().is_empty());
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn handle_connect_stops_task_if_internal_sender_closes() {
         let (mut ep_evt_send, _, internal_sender, _, _, _, _, meta_net_task_finished) =
@@ -446,6 +446,28 @@ impl KitsuneP2pConfig {
         false
     }
 
+    /// Whether the connections of this config are addressed by the IP
+    /// address the remote connects from, so that blocks of IP addresses can
+    /// be enforced.
+    ///
+    /// tx5 urls address the signal server rather than the remote, and tx2
+    /// transports other than the mock network are wrapped in a proxy, whose
+    /// urls address the proxy through which the remote is reached.
+    #[allow(dead_code)] // because of feature flipping
+    pub fn reveals_remote_ip(&self) -> bool {
+        #[cfg(feature = "tx2")]
+        {
+            matches!(
+                self.transport_pool.first(),
+                Some(TransportConfig::Mock { .. })
+            )
+        }
+        #[cfg(not(feature = "tx2"))]
+        {
+            false
+        }
+    }
+
     /// `tx2` is currently designed to use exactly one proxy wrapped transport,
     /// so convert a bunch of the options from the previous transport
     /// paradigm into that pattern.
@@ -129,8 +129,12 @@ where
     }
 
     /// Retrieve the bytes for a resource at a Location, downloading it if
-    /// necessary
+    /// necessary.
+    ///
+    /// If the manifest declares a hash for the Location, the resource must
+    /// match it.
     pub async fn resolve(&self, location: &Location) -> MrBundleResult<Cow<'_, ResourceBytes>> {
+        let expected = self.manifest.resource_hashes().remove(location);
         let bytes = match &location.normalize(self.root_dir.as_ref())? {
             Location::Bundled(path) => Cow::Borrowed(
                 self.resources
@@ -138,8 +142,17 @@ where
                     .ok_or_else(|| BundleError::BundledResourceMissing(path.clone()))?,
             ),
             Location::Path(path) => Cow::Owned(crate::location::resolve_local(path).await?),
-            Location::Url(url) => Cow::Owned(crate::location::resolve_remote(url).await?),
+            Location::Url(url) => {
+                // Remote resources are checked as they are fetched, so that
+                // only matching resources are cached.
+                return Ok(Cow::Owned(
+                    crate::location::resolve_remote(url, expected.as_ref()).await?,
+                ));
+            }
         };
+        if let Some(expected) = expected {
+            crate::location::verify_hash(location, &expected, &bytes)?;
+        }
         Ok(bytes)
     }
 
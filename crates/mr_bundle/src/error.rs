@@ -52,6 +52,12 @@ pub enum BundleError {
         "Cannot use relative paths for local locations. The following local path is relative: {0}"
     )]
     RelativeLocalPath(std::path::PathBuf),
+
+    #[error("The resource at {0:?} does not match the hash declared for it in the manifest. Expected: {1}, actual: {2}")]
+    ResourceHashMismatch(crate::Location, crate::ResourceHash, crate::ResourceHash),
+
+    #[error("Invalid resource hash '{0}'. Resource hashes must be SHA-256 hashes written as 64 hex digits.")]
+    InvalidResourceHash(String),
 }
 pub type BundleResult<T> = Result<T, BundleError>;
 
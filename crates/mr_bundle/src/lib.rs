@@ -27,6 +27,6 @@ mod packing;
 
 pub use bundle::{Bundle, RawBundle};
 pub use encoding::{decode, encode};
-pub use location::Location;
+pub use location::{Location, CACHE_DIR_ENV};
 pub use manifest::Manifest;
-pub use resource::ResourceBytes;
+pub use resource::{ResourceBytes, ResourceHash};
@@ -1,6 +1,6 @@
 use crate::{
     error::{BundleError, MrBundleResult},
-    ResourceBytes,
+    ResourceBytes, ResourceHash,
 };
 use holochain_util::ffs;
 use std::path::{Path, PathBuf};
@@ -64,14 +64,77 @@ pub(crate) async fn resolve_local(path: &Path) -> MrBundleResult<ResourceBytes>
     Ok(ffs::read(path).await?.into())
 }
 
-pub(crate) async fn resolve_remote(url: &str) -> MrBundleResult<ResourceBytes> {
-    Ok(reqwest::get(url)
+/// The environment variable which sets the directory remote resources are
+/// cached in. Defaults to a `mr_bundle` directory in the user's cache directory.
+pub const CACHE_DIR_ENV: &str = "MR_BUNDLE_CACHE_DIR";
+
+/// Fetch a remote resource.
+///
+/// If the hash of the resource is known, the resource is looked up in the
+/// local cache first, and stored there once it has been fetched and checked,
+/// so that it is only downloaded once.
+pub(crate) async fn resolve_remote(
+    url: &str,
+    expected: Option<&ResourceHash>,
+) -> MrBundleResult<ResourceBytes> {
+    let cached_path = expected.and_then(|hash| Some(cache_dir()?.join(hash.to_string())));
+    if let (Some(path), Some(expected)) = (&cached_path, expected) {
+        if let Ok(bytes) = ffs::read(path).await {
+            // A corrupt cache entry is fetched again and overwritten.
+            if ResourceHash::of(&bytes) == *expected {
+                return Ok(bytes.into());
+            }
+        }
+    }
+
+    let bytes: ResourceBytes = reqwest::get(url)
         .await?
         .bytes()
         .await?
         .into_iter()
         .collect::<Vec<_>>()
-        .into())
+        .into();
+
+    if let Some(expected) = expected {
+        verify_hash(&Location::Url(url.to_string()), expected, &bytes)?;
+    }
+    if let Some(path) = cached_path {
+        // The cache is only an optimization, so failing to write to it is
+        // not an error.
+        let _ = store_cached(&path, &bytes).await;
+    }
+    Ok(bytes)
+}
+
+/// Check that a resource has the hash declared for it.
+pub(crate) fn verify_hash(
+    location: &Location,
+    expected: &ResourceHash,
+    bytes: &[u8],
+) -> MrBundleResult<()> {
+    let actual = ResourceHash::of(bytes);
+    if actual != *expected {
+        return Err(BundleError::ResourceHashMismatch(location.clone(), *expected, actual).into());
+    }
+    Ok(())
+}
+
+fn cache_dir() -> Option<PathBuf> {
+    std::env::var_os(CACHE_DIR_ENV)
+        .map(PathBuf::from)
+        .or_else(|| Some(dirs::cache_dir()?.join("mr_bundle")))
+}
+
+async fn store_cached(path: &Path, bytes: &[u8]) -> MrBundleResult<()> {
+    if let Some(dir) = path.parent() {
+        ffs::create_dir_all(dir).await?;
+    }
+    // Write to a temporary file first, so that a partly written resource is
+    // never read from the cache.
+    let tmp_path = path.with_extension("tmp");
+    ffs::write(&tmp_path, bytes).await?;
+    std::fs::rename(&tmp_path, path)?;
+    Ok(())
 }
 
 #[cfg(test)]
@@ -125,4 +188,33 @@ mod tests {
         );
         assert_eq!(val["url"], Value::from("http://r.co"));
     }
+
+    #[tokio::test]
+    async fn remote_resources_are_cached_by_hash() {
+        let cache_dir = tempfile::tempdir().unwrap();
+        std::env::set_var(CACHE_DIR_ENV, cache_dir.path());
+
+        // Nothing listens at this url, so the resource can only be resolved
+        // from the cache.
+        let url = "http://127.0.0.1:1/thing";
+        let bytes = b"thing".to_vec();
+        let hash = ResourceHash::of(&bytes);
+
+        assert!(resolve_remote(url, Some(&hash)).await.is_err());
+
+        store_cached(&cache_dir.path().join(hash.to_string()), &bytes)
+            .await
+            .unwrap();
+        assert_eq!(
+            resolve_remote(url, Some(&hash)).await.unwrap(),
+            bytes.clone().into()
+        );
+
+        // Resources without a declared hash are never read from the cache
+        assert!(resolve_remote(url, None).await.is_err());
+
+        // A cache entry which doesn't match its hash is not used
+        std::fs::write(cache_dir.path().join(hash.to_string()), b"other").unwrap();
+        assert!(resolve_remote(url, Some(&hash)).await.is_err());
+    }
 }
@@ -1,6 +1,6 @@
-use std::path::PathBuf;
+use std::{collections::HashMap, path::PathBuf};
 
-use crate::location::Location;
+use crate::{location::Location, resource::ResourceHash};
 
 /// A Manifest describes the resources in a [`Bundle`](crate::Bundle) and how
 /// to pack and unpack them.
@@ -18,6 +18,13 @@ pub trait Manifest:
     /// correctly implemented to enable resource resolution.
     fn locations(&self) -> Vec<Location>;
 
+    /// The hashes the manifest declares for the resources at some of its
+    /// Locations. A resource with a declared hash fails to resolve if its
+    /// content does not match the hash.
+    fn resource_hashes(&self) -> HashMap<Location, ResourceHash> {
+        HashMap::new()
+    }
+
     /// When unpacking the bundle into a directory structure, this becomes
     /// the relative path of the manifest file.
     #[cfg(feature = "packing")]
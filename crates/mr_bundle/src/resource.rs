@@ -1,3 +1,5 @@
+use crate::error::BundleError;
+
 /// Arbitrary opaque bytes representing a Resource in a [`Bundle`](crate::Bundle)
 #[derive(
     Clone,
@@ -32,3 +34,107 @@ impl std::fmt::Debug for ResourceBytes {
         ))
     }
 }
+
+/// The SHA-256 hash of the bytes of a Resource.
+///
+/// A [`Manifest`](crate::Manifest) can declare the hash of any resource it
+/// does not bundle, to pin the content of that resource. The resource is then
+/// checked against the hash whenever it is resolved. In manifests, the hash is
+/// written as a lowercase hex string.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(try_from = "String", into = "String")]
+#[cfg_attr(
+    feature = "fuzzing",
+    derive(arbitrary::Arbitrary, proptest_derive::Arbitrary)
+)]
+pub struct ResourceHash([u8; 32]);
+
+impl ResourceHash {
+    /// Hash these bytes
+    pub fn of(bytes: &[u8]) -> Self {
+        use sha2::Digest;
+        Self(sha2::Sha256::digest(bytes).into())
+    }
+
+    /// Accessor
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for ResourceHash {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for b in self.0 {
+            write!(f, "{:02x}", b)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for ResourceHash {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "mr_bundle::ResourceHash({})", self)
+    }
+}
+
+impl std::str::FromStr for ResourceHash {
+    type Err = BundleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || BundleError::InvalidResourceHash(s.to_string());
+        if s.len() != 64 || !s.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(invalid());
+        }
+        let mut hash = [0; 32];
+        for (i, b) in hash.iter_mut().enumerate() {
+            *b = u8::from_str_radix(&s[i * 2..i * 2 + 2], 16).map_err(|_| invalid())?;
+        }
+        Ok(Self(hash))
+    }
+}
+
+impl TryFrom<String> for ResourceHash {
+    type Error = BundleError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<ResourceHash> for String {
+    fn from(hash: ResourceHash) -> Self {
+        hash.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resource_hash_roundtrip() {
+        let hash = ResourceHash::of(b"abc");
+        assert_eq!(
+            hash.to_string(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(hash.to_string().parse::<ResourceHash>().unwrap(), hash);
+        assert_eq!(
+            hash.to_string()
+                .to_uppercase()
+                .parse::<ResourceHash>()
+                .unwrap(),
+            hash
+        );
+
+        assert_eq!(
+            "abc".parse::<ResourceHash>(),
+            Err(BundleError::InvalidResourceHash("abc".into()))
+        );
+        let not_hex = "g".repeat(64);
+        assert_eq!(
+            not_hex.parse::<ResourceHash>(),
+            Err(BundleError::InvalidResourceHash(not_hex.clone()))
+        );
+    }
+}
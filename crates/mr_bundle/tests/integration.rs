@@ -1,5 +1,11 @@
-use mr_bundle::{Bundle, Location, Manifest, ResourceBytes};
-use std::{collections::HashSet, path::PathBuf};
+use mr_bundle::{
+    error::{BundleError, MrBundleError},
+    Bundle, Location, Manifest, ResourceBytes, ResourceHash,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+};
 
 #[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 #[serde(tag = "manifest_version")]
@@ -17,6 +23,16 @@ impl Manifest for TestManifest {
         }
     }
 
+    fn resource_hashes(&self) -> HashMap<Location, ResourceHash> {
+        match self {
+            Self::V1(mani) => mani
+                .things
+                .iter()
+                .filter_map(|b| Some((b.location.clone(), b.sha256?)))
+                .collect(),
+        }
+    }
+
     #[cfg(feature = "packing")]
     fn path() -> PathBuf {
         "test-manifest.yaml".into()
@@ -38,6 +54,9 @@ struct ManifestV1 {
 struct ThingManifest {
     #[serde(flatten)]
     location: Location,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    sha256: Option<ResourceHash>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
@@ -66,9 +85,11 @@ async fn resource_resolution() {
         things: vec![
             ThingManifest {
                 location: bundled_location.clone(),
+                sha256: None,
             },
             ThingManifest {
                 location: local_location.clone(),
+                sha256: None,
             },
         ],
     });
@@ -120,6 +141,56 @@ async fn resource_resolution() {
     assert_eq!(bundle, bundle_file);
 }
 
+#[tokio::test]
+async fn resource_hash_pinning() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let local_thing_encoded = mr_bundle::encode(&Thing("local".into())).unwrap();
+    let local_path = dir.path().join("local.thing");
+    std::fs::write(&local_path, &local_thing_encoded).unwrap();
+    let local_location = Location::Path(local_path.clone());
+
+    let bundle_pinned_to = |sha256: ResourceHash| {
+        let manifest = TestManifest::V1(ManifestV1 {
+            name: "name".to_string(),
+            things: vec![ThingManifest {
+                location: local_location.clone(),
+                sha256: Some(sha256),
+            }],
+        });
+        Bundle::new_unchecked(manifest, vec![]).unwrap()
+    };
+
+    // The resource resolves if it matches its declared hash
+    let expected = ResourceHash::of(&local_thing_encoded);
+    let bundle = bundle_pinned_to(expected);
+    assert_eq!(
+        bundle.resolve(&local_location).await.unwrap().into_owned(),
+        local_thing_encoded.clone().into()
+    );
+
+    // The declared hash survives a roundtrip through yaml
+    let yaml = serde_yaml::to_string(bundle.manifest()).unwrap();
+    assert!(yaml.contains(&format!("sha256: {}", expected)));
+    assert_eq!(
+        &serde_yaml::from_str::<TestManifest>(&yaml).unwrap(),
+        bundle.manifest()
+    );
+
+    // Changing the resource makes it fail to resolve
+    let changed_thing_encoded = mr_bundle::encode(&Thing("changed".into())).unwrap();
+    std::fs::write(&local_path, &changed_thing_encoded).unwrap();
+    let err = bundle.resolve(&local_location).await.unwrap_err();
+    matches::assert_matches!(
+        err,
+        MrBundleError::BundleError(BundleError::ResourceHashMismatch(location, e, actual))
+            if location == local_location
+                && e == expected
+                && actual == ResourceHash::of(&changed_thing_encoded)
+    );
+    assert!(bundle.resolve_all().await.is_err());
+}
+
 #[cfg(feature = "packing")]
 #[tokio::test]
 async fn unpack_roundtrip() {
@@ -144,9 +215,11 @@ async fn unpack_roundtrip() {
         things: vec![
             ThingManifest {
                 location: bundled_location.clone(),
+                sha256: None,
             },
             ThingManifest {
                 location: local_location.clone(),
+                sha256: None,
             },
         ],
     });
@@ -140,3 +140,18 @@ fn send_assigned_cap_claim(agent: AgentPubKey) -> ExternResult<()> {
     )?;
     Ok(())
 }
+
+#[hdk_extern]
+fn list_cap_grants(query: CapGrantQuery) -> ExternResult<Vec<LiveCapGrant>> {
+    capability_grants(query)
+}
+
+#[hdk_extern]
+fn list_cap_claims(query: CapClaimQuery) -> ExternResult<Vec<LiveCapClaim>> {
+    capability_claims(query)
+}
+
+#[hdk_extern]
+fn get_capability_info(_: ()) -> ExternResult<CapabilityInfo> {
+    capability_info()
+}
@@ -259,6 +259,11 @@ fn get_count(link_query: LinkQuery) -> ExternResult<usize> {
     hdk::prelude::count_links(link_query)
 }
 
+#[hdk_extern]
+fn get_meta_of(hash: AnyDhtHash) -> ExternResult<Vec<MetadataSet>> {
+    hdk::prelude::get_meta(hash, MetadataRequest::default())
+}
+
 #[hdk_extern]
 fn get_links_with_query(input: GetLinksInput) -> ExternResult<Vec<Link>> {
     Ok(hdk::prelude::get_links(input)?)
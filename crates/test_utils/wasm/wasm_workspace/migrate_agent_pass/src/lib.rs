@@ -4,3 +4,8 @@ use hdk::prelude::*;
 fn migrate_agent(_: MigrateAgent) -> ExternResult<MigrateAgentCallbackResult> {
     Ok(MigrateAgentCallbackResult::Pass)
 }
+
+#[hdk_extern]
+fn query_previous_chain(filter: ChainQueryFilter) -> ExternResult<Vec<Record>> {
+    hdk::prelude::query_previous_chain(filter)
+}
@@ -0,0 +1,12 @@
+use crate::integrity::*;
+use hdk::prelude::*;
+
+#[hdk_extern]
+fn create_thing(n: u32) -> ExternResult<ActionHash> {
+    create_entry(EntryTypes::Thing(Thing(n)))
+}
+
+#[hdk_extern]
+fn link_things((base, target): (ActionHash, ActionHash)) -> ExternResult<ActionHash> {
+    create_link(base, target, LinkTypes::Things, ())
+}
@@ -0,0 +1,36 @@
+use hdi::prelude::*;
+
+/// Links fill this bucket quickly.
+pub const LINK_BUCKET: RateBucketId = 1;
+
+/// Entries fill this bucket slowly.
+pub const ENTRY_BUCKET: RateBucketId = 2;
+
+#[hdk_entry_helper]
+pub struct Thing(pub u32);
+
+#[hdk_entry_types]
+#[unit_enum(UnitEntryTypes)]
+pub enum EntryTypes {
+    Thing(Thing),
+}
+
+#[hdk_link_types]
+pub enum LinkTypes {
+    Things,
+}
+
+#[hdk_extern]
+fn weigh(input: WeighInput) -> ExternResult<RateWeight> {
+    Ok(match input {
+        WeighInput::Link(_) => RateWeight {
+            bucket_id: LINK_BUCKET,
+            units: 10,
+        },
+        WeighInput::Create(_, _) | WeighInput::Update(_, _) => RateWeight {
+            bucket_id: ENTRY_BUCKET,
+            units: 1,
+        },
+        WeighInput::Delete(_) => RateWeight::default(),
+    })
+}
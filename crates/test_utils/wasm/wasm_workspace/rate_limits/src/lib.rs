@@ -0,0 +1,4 @@
+pub mod integrity;
+
+#[cfg(not(feature = "integrity"))]
+pub mod coordinator;